@@ -1,5 +1,6 @@
 /// This module provides test tables of various key / value types, for use in API tests
 module TestAccount::TableTestData {
+    use 0x1::signer;
     use 0x1::table::{Self, Table};
     use 0x1::string;
     use 0x1::guid::{Self, ID};
@@ -60,4 +61,17 @@ module TestAccount::TableTestData {
 
         move_to(&account, test_tables);
     }
+
+    public entry fun add_u64_table_items(account: signer, keys: vector<u64>) acquires TestTables {
+        let t = borrow_global_mut<TestTables>(signer::address_of(&account));
+        while (!vector::is_empty(&keys)) {
+            let key = vector::pop_back(&mut keys);
+            table::add(&mut t.u64_table, key, key);
+        };
+    }
+
+    public entry fun remove_u64_table_item(account: signer, key: u64) acquires TestTables {
+        let t = borrow_global_mut<TestTables>(signer::address_of(&account));
+        table::remove(&mut t.u64_table, key);
+    }
 }
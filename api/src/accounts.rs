@@ -13,16 +13,21 @@ use crate::{
     },
     ApiTags,
 };
-use anyhow::Context as AnyhowContext;
+use anyhow::{format_err, Context as AnyhowContext};
 use aptos_api_types::{
-    AccountData, Address, AptosErrorCode, AsConverter, LedgerInfo, MoveModuleBytecode,
-    MoveModuleId, MoveResource, MoveStructTag, StateKeyWrapper, U64,
+    AccountData, AccountStateChange, AccountStateDiff, Address, AptosErrorCode, AsConverter,
+    LedgerInfo, MoveModuleBytecode, MoveModuleId, MoveResource, MoveStructTag, StateChangeType,
+    StateKeyWrapper, U64,
 };
+use aptos_crypto::hash::CryptoHash;
 use aptos_types::{
-    access_path::AccessPath,
+    access_path::{AccessPath, Path as AccessPathPath},
     account_config::{AccountResource, ObjectGroupResource},
     event::{EventHandle, EventKey},
-    state_store::state_key::StateKey,
+    state_store::{
+        state_diff::StateKeyChange,
+        state_key::{StateKey, StateKeyInner},
+    },
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use move_core_types::{
@@ -178,6 +183,63 @@ impl AccountsApi {
         })
         .await
     }
+
+    /// Get account state diff
+    ///
+    /// Retrieves the resources, resource groups and modules of an account which were added,
+    /// modified or deleted between two ledger versions, in the order of their state keys.
+    ///
+    /// Results are paginated with a cursor. The cursor of the next page, if any, is returned in
+    /// the X-Aptos-Cursor header of the response.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If a requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/accounts/:address/state_diff",
+        method = "get",
+        operation_id = "get_account_state_diff",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn get_account_state_diff(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Ledger version to get the changes from
+        from_version: Query<U64>,
+        /// Ledger version to get the changes up to
+        ///
+        /// If not provided, it will be the latest version
+        to_version: Query<Option<U64>>,
+        /// Cursor specifying where to start for pagination
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// call this endpoint once without this query parameter specified, and
+        /// then use the cursor returned in the X-Aptos-Cursor header in the
+        /// response.
+        start: Query<Option<StateKeyWrapper>>,
+        /// Max number of changes to retrieve
+        ///
+        /// If not provided, defaults to default page size.
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<AccountStateDiff> {
+        fail_point_poem("endpoint_get_account_state_diff")?;
+        self.context
+            .check_api_output_enabled("Get account state diff", &accept_type)?;
+
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let account = Account::new(
+                context,
+                address.0,
+                to_version.0,
+                start.0.map(StateKey::from),
+                limit.0,
+            )?;
+            account.state_diff(&accept_type, from_version.0)
+        })
+        .await
+    }
 }
 
 /// A struct representing Account related lookups for resources and modules
@@ -207,7 +269,8 @@ impl Account {
     ) -> Result<Self, BasicErrorWith404> {
         // Use the latest ledger version, or the requested associated version
         let (latest_ledger_info, requested_ledger_version) = context
-            .get_latest_ledger_info_and_verify_lookup_version(
+            .get_latest_ledger_info_and_verify_account_lookup_version(
+                address.into(),
                 requested_ledger_version.map(|inner| inner.0),
             )?;
 
@@ -456,6 +519,104 @@ impl Account {
         }
     }
 
+    /// Retrieves the resources, resource groups and modules of the account which changed between
+    /// `from_version` and the lookup ledger version
+    pub fn state_diff(
+        self,
+        accept_type: &AcceptType,
+        from_version: U64,
+    ) -> BasicResultWith404<AccountStateDiff> {
+        let (_, from_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_account_lookup_version(
+                self.address.into(),
+                Some(from_version.0),
+            )?;
+        if from_version > self.ledger_version {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "'from_version' ({}) is higher than the version to get the changes up to ({})",
+                    from_version, self.ledger_version
+                ),
+                AptosErrorCode::InvalidInput,
+                &self.latest_ledger_info,
+            ));
+        }
+
+        let max_state_diff_page_size = self.context.max_state_diff_page_size();
+        let (diffs, next_state_key) = self
+            .context
+            .get_account_state_diff_by_pagination(
+                self.address.into(),
+                self.start.as_ref(),
+                from_version,
+                self.ledger_version,
+                determine_limit(
+                    self.limit,
+                    max_state_diff_page_size,
+                    max_state_diff_page_size,
+                    &self.latest_ledger_info,
+                )? as u64,
+            )
+            .context("Failed to get account state diff from storage")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &self.latest_ledger_info,
+                )
+            })?;
+
+        let changes = diffs
+            .into_iter()
+            .map(|(state_key, change)| {
+                let (resource, resource_group, module) = match state_key.inner() {
+                    StateKeyInner::AccessPath(access_path) => match access_path.get_path() {
+                        AccessPathPath::Resource(typ) => (Some(typ.into()), None, None),
+                        AccessPathPath::ResourceGroup(typ) => (None, Some(typ.into()), None),
+                        AccessPathPath::Code(module_id) => (None, None, Some(module_id.into())),
+                    },
+                    _ => {
+                        return Err(format_err!(
+                            "storage prefix scan return inconsistent key ({:?})",
+                            state_key
+                        ))
+                    },
+                };
+                Ok(AccountStateChange {
+                    change_type: match change {
+                        StateKeyChange::Added => StateChangeType::Added,
+                        StateKeyChange::Modified => StateChangeType::Modified,
+                        StateKeyChange::Deleted => StateChangeType::Deleted,
+                    },
+                    state_key_hash: state_key.hash().to_hex_literal(),
+                    resource,
+                    resource_group,
+                    module,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &self.latest_ledger_info,
+                )
+            })?;
+
+        BasicResponse::try_from_rust_value((
+            AccountStateDiff {
+                from_version: from_version.into(),
+                to_version: self.ledger_version.into(),
+                changes,
+            },
+            &self.latest_ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+        .map(|v| v.with_cursor(next_state_key))
+    }
+
     /// Retrieves an event key from a [`MoveStructTag`] and a [`Identifier`] field name
     ///
     /// e.g. If there's the `CoinStore` module, it has a field named `withdraw_events` for
@@ -19,7 +19,10 @@ use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::{error, warn};
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    MempoolClientRequest, MempoolClientSender, MempoolStats, PendingTransactionInfo,
+    SubmissionStatus,
+};
 use aptos_state_view::TStateView;
 use aptos_storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
@@ -35,9 +38,11 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::{GasSchedule, GasScheduleV2, OnChainConfig, OnChainExecutionConfig},
     state_store::{
+        state_diff::StateKeyChange,
         state_key::{StateKey, StateKeyInner},
         state_key_prefix::StateKeyPrefix,
         state_value::StateValue,
+        table::TableHandle,
     },
     transaction::{SignedTransaction, TransactionWithProof, Version},
 };
@@ -45,7 +50,7 @@ use aptos_utils::aptos_try;
 use aptos_vm::data_cache::AsMoveResolver;
 use futures::{channel::oneshot, SinkExt};
 use move_core_types::{
-    language_storage::{ModuleId, StructTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     move_resource::MoveResource,
     resolver::ModuleResolver,
 };
@@ -119,6 +124,14 @@ impl Context {
         self.node_config.api.max_account_modules_page_size
     }
 
+    pub fn max_table_items_page_size(&self) -> u16 {
+        self.node_config.api.max_table_items_page_size
+    }
+
+    pub fn max_state_diff_page_size(&self) -> u16 {
+        self.node_config.api.max_state_diff_page_size
+    }
+
     pub fn latest_state_view(&self) -> Result<DbStateView> {
         self.db.latest_state_checkpoint_view()
     }
@@ -149,6 +162,26 @@ impl Context {
         Ok((latest_ledger_info, requested_ledger_version, state_view))
     }
 
+    /// Returns a 410 if the state Merkle tree at `version` has been pruned, as its proofs can't
+    /// be served anymore.
+    pub fn check_state_merkle_not_pruned<E: StdApiError>(
+        &self,
+        version: Version,
+        ledger_info: &LedgerInfo,
+    ) -> Result<(), E> {
+        let pruned = self
+            .db
+            .is_state_merkle_pruned(version)
+            .context("Failed to check whether the state Merkle tree is pruned")
+            .map_err(|err| {
+                E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+            })?;
+        if pruned {
+            return Err(version_pruned(version, ledger_info));
+        }
+        Ok(())
+    }
+
     pub fn state_view_at_version(&self, version: Version) -> Result<DbStateView> {
         self.db.state_view_at_version(Some(version))
     }
@@ -246,6 +279,26 @@ impl Context {
     pub fn get_latest_ledger_info_and_verify_lookup_version<E: StdApiError>(
         &self,
         requested_ledger_version: Option<Version>,
+    ) -> Result<(LedgerInfo, Version), E> {
+        self.verify_lookup_version(requested_ledger_version, |_, _| Ok(false))
+    }
+
+    /// Like [`Self::get_latest_ledger_info_and_verify_lookup_version`], except that the state of
+    /// an account retained by the storage pruners can be looked up beyond the prune window
+    pub fn get_latest_ledger_info_and_verify_account_lookup_version<E: StdApiError>(
+        &self,
+        address: AccountAddress,
+        requested_ledger_version: Option<Version>,
+    ) -> Result<(LedgerInfo, Version), E> {
+        self.verify_lookup_version(requested_ledger_version, |version, ledger_info| {
+            self.is_account_state_retained(address, version, ledger_info)
+        })
+    }
+
+    fn verify_lookup_version<E: StdApiError>(
+        &self,
+        requested_ledger_version: Option<Version>,
+        is_retained: impl FnOnce(Version, &LedgerInfo) -> Result<bool, E>,
     ) -> Result<(LedgerInfo, Version), E> {
         let latest_ledger_info = self.get_latest_ledger_info()?;
 
@@ -258,7 +311,9 @@ impl Context {
                 requested_ledger_version,
                 &latest_ledger_info,
             ));
-        } else if requested_ledger_version < latest_ledger_info.oldest_ledger_version.0 {
+        } else if requested_ledger_version < latest_ledger_info.oldest_ledger_version.0
+            && !is_retained(requested_ledger_version, &latest_ledger_info)?
+        {
             return Err(version_pruned(
                 requested_ledger_version,
                 &latest_ledger_info,
@@ -268,6 +323,30 @@ impl Context {
         Ok((latest_ledger_info, requested_ledger_version))
     }
 
+    /// Whether the state of an account at `version` is kept by the storage pruners beyond the
+    /// prune window
+    pub fn is_account_state_retained<E: StdApiError>(
+        &self,
+        address: AccountAddress,
+        version: Version,
+        ledger_info: &LedgerInfo,
+    ) -> Result<bool, E> {
+        self.db
+            .is_account_state_retained(address, version)
+            .context("Failed to check whether the account state is retained")
+            .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
+    }
+
+    /// Accounts whose history is kept by the storage pruners beyond the prune window
+    pub fn retained_accounts(&self) -> &[AccountAddress] {
+        &self
+            .node_config
+            .storage
+            .storage_pruner_config
+            .ledger_pruner_config
+            .retained_accounts
+    }
+
     pub fn get_latest_ledger_info_with_signatures(&self) -> Result<LedgerInfoWithSignatures> {
         self.db.get_latest_ledger_info()
     }
@@ -788,6 +867,35 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    pub async fn get_pending_transactions_by_sender(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Vec<PendingTransactionInfo>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetPendingTransactionsBySender(
+                address, req_sender,
+            ))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    pub async fn get_mempool_stats(&self) -> Result<MempoolStats> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetStats(req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
@@ -848,6 +956,114 @@ impl Context {
         }
     }
 
+    /// Returns a page of events of the given type starting at the `start` position, and the
+    /// position of the event the next page starts at, if any.
+    pub fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start: (u64, u64),
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<(Vec<EventWithVersion>, Option<(u64, u64)>)> {
+        self.db
+            .get_events_by_type(type_tag, start, limit as u64, ledger_version)
+    }
+
+    /// Returns a page of the state keys of an account whose values differ between `old_version`
+    /// and `new_version`, starting at `prev_state_key` or at the first key if it's `None`, and
+    /// the state key of the change the next page starts at, if any.
+    pub fn get_account_state_diff_by_pagination(
+        &self,
+        address: AccountAddress,
+        prev_state_key: Option<&StateKey>,
+        old_version: u64,
+        new_version: u64,
+        limit: u64,
+    ) -> Result<(Vec<(StateKey, StateKeyChange)>, Option<StateKey>)> {
+        type Item = (Vec<u8>, StateKey, StateValue);
+        // Both iterators are ordered by encoded state key, so they are merged on it.
+        fn next_item(
+            iter: &mut dyn Iterator<Item = Result<(StateKey, StateValue)>>,
+        ) -> Result<Option<Item>> {
+            match iter.next().transpose()? {
+                Some((k, v)) => Ok(Some((k.encode()?, k, v))),
+                None => Ok(None),
+            }
+        }
+
+        let prefix = StateKeyPrefix::from(address);
+        let mut old_iter =
+            self.db
+                .get_prefixed_state_value_iterator(&prefix, prev_state_key, old_version)?;
+        let mut new_iter =
+            self.db
+                .get_prefixed_state_value_iterator(&prefix, prev_state_key, new_version)?;
+        let mut old_item = next_item(&mut old_iter)?;
+        let mut new_item = next_item(&mut new_iter)?;
+        let mut changes = vec![];
+        loop {
+            let (state_key, change) = match (old_item.take(), new_item.take()) {
+                (None, None) => break,
+                (Some(old), Some(new)) if old.0 == new.0 => {
+                    old_item = next_item(&mut old_iter)?;
+                    new_item = next_item(&mut new_iter)?;
+                    if old.2 == new.2 {
+                        continue;
+                    }
+                    (new.1, StateKeyChange::Modified)
+                },
+                (Some(old), None) => {
+                    old_item = next_item(&mut old_iter)?;
+                    (old.1, StateKeyChange::Deleted)
+                },
+                (Some(old), Some(new)) if old.0 < new.0 => {
+                    old_item = next_item(&mut old_iter)?;
+                    new_item = Some(new);
+                    (old.1, StateKeyChange::Deleted)
+                },
+                (old, Some(new)) => {
+                    old_item = old;
+                    new_item = next_item(&mut new_iter)?;
+                    (new.1, StateKeyChange::Added)
+                },
+            };
+            if changes.len() as u64 == limit {
+                return Ok((changes, Some(state_key)));
+            }
+            changes.push((state_key, change));
+        }
+        Ok((changes, None))
+    }
+
+    /// Returns a page of the items of a table at `version`, starting at `prev_state_key` or at the
+    /// first item if it's `None`, and the state key of the item the next page starts at, if any.
+    pub fn get_table_items_by_pagination(
+        &self,
+        handle: TableHandle,
+        prev_state_key: Option<&StateKey>,
+        version: u64,
+        limit: u64,
+    ) -> Result<(Vec<(Vec<u8>, StateValue)>, Option<StateKey>)> {
+        let mut table_iter = self.db.get_prefixed_state_value_iterator(
+            &StateKeyPrefix::from(handle),
+            prev_state_key,
+            version,
+        )?;
+        let items = table_iter
+            .by_ref()
+            .take(limit as usize)
+            .map(|res| {
+                let (k, v) = res?;
+                match k.into_inner() {
+                    StateKeyInner::TableItem { key, .. } => Ok((key, v)),
+                    k => bail!("storage prefix scan return inconsistent key ({:?})", k),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let next_key = table_iter.next().transpose()?.map(|(k, _v)| k);
+        Ok((items, next_key))
+    }
+
     fn next_bucket(&self, gas_unit_price: u64) -> u64 {
         match self
             .node_config
@@ -9,18 +9,19 @@ use crate::{
     failpoint::fail_point_poem,
     page::Page,
     response::{
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        api_forbidden, version_pruned, BadRequestError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404, InternalError,
     },
     ApiTags,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
-    MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
+    verify_field_identifier, Address, AptosErrorCode, AsConverter, EventCursor, IdentifierWrapper,
+    LedgerInfo, MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
 };
-use aptos_types::event::EventKey;
+use aptos_types::{contract_event::EventWithVersion, event::EventKey};
 use aptos_vm::data_cache::AsMoveResolver;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::{
     param::{Path, Query},
     OpenApi,
@@ -151,6 +152,90 @@ impl EventsApi {
         })
         .await
     }
+
+    /// Get events by type
+    ///
+    /// This API returns the events of the given Move type emitted by any
+    /// transaction, e.g. every `0x1::coin::DepositEvent`, in the order in which
+    /// they were committed.
+    ///
+    /// Results are paginated with a cursor. The cursor of the next page, if
+    /// any, is returned in the X-Aptos-Cursor header of the response.
+    ///
+    /// This requires the internal indexer to be enabled on the node. Otherwise
+    /// a 403 will be returned.
+    #[oai(
+        path = "/events/by_type/:event_type",
+        method = "get",
+        operation_id = "get_events_by_type",
+        tag = "ApiTags::Events"
+    )]
+    async fn get_events_by_type(
+        &self,
+        accept_type: AcceptType,
+        /// Type of the events e.g. `0x1::coin::DepositEvent`
+        event_type: Path<MoveStructTag>,
+        /// Ledger version to start looking for events at
+        ///
+        /// If unspecified, defaults to the oldest version available. Ignored when
+        /// a cursor is given.
+        start_version: Query<Option<U64>>,
+        /// Cursor specifying where to start for pagination
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// call this endpoint once without this query parameter specified, and
+        /// then use the cursor returned in the X-Aptos-Cursor header in the
+        /// response.
+        start: Query<Option<EventCursor>>,
+        /// Max number of events to retrieve.
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        event_type
+            .0
+            .verify(0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let type_tag = StructTag::try_from(event_type.0)
+            .map(|struct_tag| TypeTag::Struct(Box::new(struct_tag)))
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_events_by_type")?;
+        self.context
+            .check_api_output_enabled("Get events by type", &accept_type)?;
+        if !self.context.db.indexer_enabled() {
+            return Err(api_forbidden(
+                "Get events by type",
+                "The internal indexer is not enabled on this node.",
+            ));
+        }
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            let latest_ledger_info = api.context.get_latest_ledger_info()?;
+            let start = match start.0 {
+                Some(cursor) => cursor,
+                None => EventCursor {
+                    version: start_version
+                        .0
+                        .map_or(latest_ledger_info.oldest_ledger_version.0, |v| v.0),
+                    index: 0,
+                },
+            };
+            if start.version < latest_ledger_info.oldest_ledger_version.0 {
+                return Err(version_pruned(start.version, &latest_ledger_info));
+            }
+            let limit = Page::new(None, limit.0, api.context.max_events_page_size())
+                .limit(&latest_ledger_info)?;
+            api.list_by_type(latest_ledger_info, accept_type, type_tag, start, limit)
+        })
+        .await
+    }
 }
 
 impl EventsApi {
@@ -180,11 +265,45 @@ impl EventsApi {
                 )
             })?;
 
+        self.render_events(&latest_ledger_info, &accept_type, events)
+    }
+
+    /// List events of the given type, starting at the `start` cursor
+    fn list_by_type(
+        &self,
+        latest_ledger_info: LedgerInfo,
+        accept_type: AcceptType,
+        type_tag: TypeTag,
+        start: EventCursor,
+        limit: u16,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        let (events, next) = self
+            .context
+            .get_events_by_type(&type_tag, start.into(), limit, latest_ledger_info.version())
+            .context(format!("Failed to find events by type {}", type_tag))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?;
+
+        self.render_events(&latest_ledger_info, &accept_type, events)
+            .map(|v| v.with_event_cursor(next.map(EventCursor::from)))
+    }
+
+    fn render_events(
+        &self,
+        latest_ledger_info: &LedgerInfo,
+        accept_type: &AcceptType,
+        events: Vec<EventWithVersion>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
         match accept_type {
             AcceptType::Json => {
                 let events = self
                     .context
-                    .latest_state_view_poem(&latest_ledger_info)?
+                    .latest_state_view_poem(latest_ledger_info)?
                     .as_move_resolver()
                     .as_converter(self.context.db.clone())
                     .try_into_versioned_events(&events)
@@ -193,14 +312,14 @@ impl EventsApi {
                         BasicErrorWith404::internal_with_code(
                             err,
                             AptosErrorCode::InternalError,
-                            &latest_ledger_info,
+                            latest_ledger_info,
                         )
                     })?;
 
-                BasicResponse::try_from_json((events, &latest_ledger_info, BasicResponseStatus::Ok))
+                BasicResponse::try_from_json((events, latest_ledger_info, BasicResponseStatus::Ok))
             },
             AcceptType::Bcs => {
-                BasicResponse::try_from_bcs((events, &latest_ledger_info, BasicResponseStatus::Ok))
+                BasicResponse::try_from_bcs((events, latest_ledger_info, BasicResponseStatus::Ok))
             },
         }
     }
@@ -24,6 +24,7 @@ mod set_failpoints;
 mod state;
 #[cfg(test)]
 pub mod tests;
+mod transaction_stream;
 mod transactions;
 mod view_function;
 
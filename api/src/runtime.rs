@@ -5,8 +5,8 @@
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
     context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    log::middleware_log, set_failpoints, state::StateApi, transaction_stream::TransactionStreamApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::X_APTOS_CLIENT;
@@ -64,6 +64,7 @@ pub fn get_api_service(
         EventsApi,
         IndexApi,
         StateApi,
+        TransactionStreamApi,
         TransactionsApi,
         ViewFunctionApi,
     ),
@@ -89,6 +90,7 @@ pub fn get_api_service(
         StateApi {
             context: context.clone(),
         },
+        TransactionStreamApi::new(context.clone()),
         TransactionsApi {
             context: context.clone(),
         },
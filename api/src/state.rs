@@ -5,6 +5,7 @@ use crate::{
     accept_type::AcceptType,
     context::api_spawn_blocking,
     failpoint::fail_point_poem,
+    page::Page,
     response::{
         api_forbidden, build_not_found, module_not_found, resource_not_found, table_item_not_found,
         BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
@@ -16,12 +17,17 @@ use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_module_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper,
     MoveModuleBytecode, MoveResource, MoveStructTag, MoveValue, RawStateValueRequest,
-    RawTableItemRequest, TableItemRequest, VerifyInput, VerifyInputWithRecursion, U64,
+    RawStateValuesRequest, RawTableItemRequest, StateKeyWrapper, TableItem, TableItemRequest,
+    VerifyInput, VerifyInputWithRecursion, U64,
 };
 use aptos_state_view::TStateView;
 use aptos_types::{
     access_path::AccessPath,
-    state_store::{state_key::StateKey, table::TableHandle},
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_value::StateValuesWithMultiProof,
+        table::TableHandle,
+    },
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use move_core_types::{
@@ -234,6 +240,74 @@ impl StateApi {
         .await
     }
 
+    /// Get table items
+    ///
+    /// Get the items of the table identified by {table_handle} in the path at a specific ledger
+    /// version, in order of their BCS serialized keys. The keys and values are also decoded with
+    /// the types of the table, if the node knows them.
+    ///
+    /// Results are paginated with a cursor. The cursor of the next page, if any, is returned in
+    /// the X-Aptos-Cursor header of the response.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/tables/:table_handle/items",
+        method = "get",
+        operation_id = "get_table_items",
+        tag = "ApiTags::Tables"
+    )]
+    async fn get_table_items(
+        &self,
+        accept_type: AcceptType,
+        /// Table handle hex encoded 32-byte string
+        table_handle: Path<Address>,
+        /// Ledger version to get state of account
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+        /// Cursor specifying where to start for pagination
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// call this endpoint once without this query parameter specified, and
+        /// then use the cursor returned in the X-Aptos-Cursor header in the
+        /// response.
+        start: Query<Option<StateKeyWrapper>>,
+        /// Max number of table items to retrieve
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<TableItem>> {
+        fail_point_poem("endpoint_get_table_items")?;
+        self.context
+            .check_api_output_enabled("Get table items", &accept_type)?;
+
+        let handle = TableHandle(table_handle.0.into());
+        let start = match start.0.map(|wrapper| wrapper.0) {
+            None => None,
+            Some(state_key)
+                if matches!(
+                    state_key.inner(),
+                    StateKeyInner::TableItem { handle: start_handle, .. } if *start_handle == handle
+                ) =>
+            {
+                Some(state_key)
+            },
+            Some(_) => {
+                return Err(BasicErrorWith404::bad_request_with_code_no_info(
+                    "'start' is not a cursor of this table",
+                    AptosErrorCode::InvalidInput,
+                ))
+            },
+        };
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.table_items(&accept_type, handle, start, limit.0, ledger_version.0)
+        })
+        .await
+    }
+
     /// Get raw state value.
     ///
     /// Get a state value at a specific ledger version, identified by the key provided
@@ -272,6 +346,53 @@ impl StateApi {
         let api = self.clone();
         api_spawn_blocking(move || api.raw_value(&accept_type, request.0, ledger_version.0)).await
     }
+
+    /// Get raw state values with a multiproof
+    ///
+    /// Get the state values of several state keys, identified by the BCS encoded keys provided in
+    /// the request body, with a single Merkle proof of all of them. The values and the proof are
+    /// at the latest state snapshot at or before the ledger version, as the state Merkle tree is
+    /// only persisted at snapshots.
+    ///
+    /// The response is a BCS encoded `StateValuesWithMultiProof`, holding the snapshot version
+    /// and its state root hash, which should be checked against the state checkpoint hash of the
+    /// transaction at that version before trusting the values.
+    ///
+    /// The Aptos nodes prune state Merkle tree history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/experimental/state_values/raw_with_multiproof",
+        method = "post",
+        operation_id = "get_raw_state_values_with_multiproof",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_raw_state_values_with_multiproof(
+        &self,
+        accept_type: AcceptType,
+        /// Request that carries the state keys.
+        request: Json<RawStateValuesRequest>,
+        /// Ledger version at or before which the values are got.
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_raw_state_values_with_multiproof")?;
+
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get raw state values with multiproof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get raw state values with multiproof", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.raw_values_with_multiproof(&accept_type, request.0, ledger_version.0)
+        })
+        .await
+    }
 }
 
 impl StateApi {
@@ -539,6 +660,78 @@ impl StateApi {
         }
     }
 
+    /// Retrieve a page of the items of a table at the ledger version
+    ///
+    /// JSON: Return the raw keys and values, decoded if the table info is known
+    /// BCS: Return the raw keys and values as [`Vec<(Vec<u8>, Vec<u8>)>`]
+    pub fn table_items(
+        &self,
+        accept_type: &AcceptType,
+        handle: TableHandle,
+        start: Option<StateKey>,
+        limit: Option<u16>,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<Vec<TableItem>> {
+        let (ledger_info, ledger_version, state_view) = self
+            .context
+            .state_view(ledger_version.map(|inner| inner.0))?;
+        let limit =
+            Page::new(None, limit, self.context.max_table_items_page_size()).limit(&ledger_info)?;
+
+        let (items, next_state_key) = self
+            .context
+            .get_table_items_by_pagination(handle, start.as_ref(), ledger_version, limit as u64)
+            .context(format!("Failed to get items of table {}", handle.0))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        match accept_type {
+            AcceptType::Json => {
+                let resolver = state_view.as_move_resolver();
+                let converter = resolver.as_converter(self.context.db.clone());
+                let items = items
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let data = converter.try_write_table_item_into_decoded_table_data(
+                            handle,
+                            &key,
+                            value.bytes(),
+                        )?;
+                        Ok(TableItem {
+                            key: key.into(),
+                            value: value.bytes().to_vec().into(),
+                            data,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to decode table items retrieved from DB")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+
+                BasicResponse::try_from_json((items, &ledger_info, BasicResponseStatus::Ok))
+                    .map(|v| v.with_cursor(next_state_key))
+            },
+            AcceptType::Bcs => {
+                let items: Vec<(Vec<u8>, Vec<u8>)> = items
+                    .into_iter()
+                    .map(|(key, value)| (key, value.bytes().to_vec()))
+                    .collect();
+                BasicResponse::try_from_bcs((items, &ledger_info, BasicResponseStatus::Ok))
+                    .map(|v| v.with_cursor(next_state_key))
+            },
+        }
+    }
+
     /// Retrieve state value for a specific ledger version
     pub fn raw_value(
         &self,
@@ -607,4 +800,112 @@ impl StateApi {
             },
         }
     }
+
+    /// Retrieve state values with a single proof at the latest state snapshot at or before a
+    /// specific ledger version
+    pub fn raw_values_with_multiproof(
+        &self,
+        accept_type: &AcceptType,
+        request: RawStateValuesRequest,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        let (ledger_info, ledger_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_lookup_version(
+                ledger_version.map(|inner| inner.0),
+            )?;
+
+        if request.keys.is_empty() {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                "No state keys were provided",
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        let max_state_values = self.context.node_config.api.max_state_values_per_multiproof;
+        if request.keys.len() > max_state_values {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "Number of state keys ({}) is higher than the maximum allowed ({})",
+                    request.keys.len(),
+                    max_state_values
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        let state_keys = request
+            .keys
+            .iter()
+            .map(|key| {
+                bcs::from_bytes::<StateKey>(&key.0)
+                    .context(format!("Failed deserializing state key: {}", key))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+
+        self.context
+            .check_state_merkle_not_pruned(ledger_version, &ledger_info)?;
+        let (version, root_hash) = self
+            .context
+            .db
+            .get_state_snapshot_before(ledger_version + 1)
+            .context("Failed fetching state snapshot")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?
+            .ok_or_else(|| {
+                build_not_found(
+                    "State snapshot",
+                    format!("Ledger version({}) or before", ledger_version),
+                    AptosErrorCode::VersionNotFound,
+                    &ledger_info,
+                )
+            })?;
+        // The snapshot can be older than the prune window even if the ledger version isn't.
+        self.context
+            .check_state_merkle_not_pruned(version, &ledger_info)?;
+        let (values, proof) = self
+            .context
+            .db
+            .get_state_values_with_multiproof_by_version(&state_keys, version)
+            .context("Failed fetching state values with multiproof")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let bytes = bcs::to_bytes(&StateValuesWithMultiProof {
+            version,
+            root_hash,
+            values,
+            proof,
+        })
+        .context("Failed serializing state values with multiproof")
+        .map_err(|err| {
+            BasicErrorWith404::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+        })?;
+
+        match accept_type {
+            AcceptType::Json => Err(api_forbidden(
+                "Get raw state values with multiproof",
+                "This serves only bytes. Use other APIs for Json.",
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
 }
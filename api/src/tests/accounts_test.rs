@@ -363,6 +363,121 @@ async fn test_get_account_items_limit_params() {
     assert_eq!(resp.status(), 400);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_state_diff() {
+    let context = new_test_context(current_function_name!());
+
+    // Nothing changes between a version and itself.
+    let resp = context
+        .get("/accounts/0x1/state_diff?from_version=0&to_version=0")
+        .await;
+    assert_eq!(
+        resp,
+        json!({
+            "from_version": "0",
+            "to_version": "0",
+            "changes": [],
+        })
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_state_diff_with_changes() {
+    let mut context = new_test_context(current_function_name!());
+    let root_address = context.root_account().await.address();
+    let account = context.gen_account();
+    let from_version = context.get_latest_ledger_info().version();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+    let to_version = context.get_latest_ledger_info().version();
+
+    // The resources of the new account were all added.
+    let resp = context
+        .get(&account_state_diff(
+            &account.address().to_hex_literal(),
+            from_version,
+        ))
+        .await;
+    assert_eq!(resp["from_version"], from_version.to_string());
+    assert_eq!(resp["to_version"], to_version.to_string());
+    let changes = resp["changes"].as_array().unwrap();
+    assert!(!changes.is_empty());
+    assert!(changes
+        .iter()
+        .all(|change| change["change_type"] == "added" && change["module"].is_null()));
+    assert!(changes
+        .iter()
+        .any(|change| change["resource"] == "0x1::account::Account"));
+
+    // The sequence number of the sender changed.
+    let resp = context
+        .get(&account_state_diff(
+            &root_address.to_hex_literal(),
+            from_version,
+        ))
+        .await;
+    assert!(resp["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|change| change["change_type"] == "modified"
+            && change["resource"] == "0x1::account::Account"));
+
+    // Walk through the same changes one at a time following the cursor.
+    let path = account_state_diff(&account.address().to_hex_literal(), from_version);
+    let mut paged_changes = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let page_path = match cursor {
+            Some(cursor) => format!("/v1{}&limit=1&start={}", path, cursor),
+            None => format!("/v1{}&limit=1", path),
+        };
+        let resp = context
+            .reply(warp::test::request().method("GET").path(&page_path))
+            .await;
+        assert_eq!(resp.status(), 200);
+        let page: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        let page_changes = page["changes"].as_array().unwrap();
+        assert_eq!(page_changes.len(), 1);
+        paged_changes.extend(page_changes.clone());
+
+        match resp.headers().get("X-Aptos-Cursor") {
+            Some(header) => cursor = Some(header.to_str().unwrap().to_owned()),
+            None => break,
+        }
+    }
+    assert_eq!(&paged_changes, changes);
+
+    // The diff can't go backwards.
+    context
+        .expect_status_code(400)
+        .get(&format!(
+            "/accounts/{}/state_diff?from_version={}&to_version={}",
+            account.address().to_hex_literal(),
+            to_version,
+            from_version
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_state_diff_from_version_not_found() {
+    let mut context = new_test_context(current_function_name!());
+
+    let resp = context
+        .expect_status_code(404)
+        .get("/accounts/0x1/state_diff?from_version=1000000000")
+        .await;
+    assert_eq!(resp["error_code"], "version_not_found");
+}
+
+fn account_state_diff(address: &str, from_version: u64) -> String {
+    format!(
+        "/accounts/{}/state_diff?from_version={}",
+        address, from_version
+    )
+}
+
 fn account_resources(address: &str) -> String {
     format!("/accounts/{}/resources", address)
 }
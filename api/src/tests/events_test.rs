@@ -2,11 +2,12 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_db_with_indexer};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::EventCursor;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
-use serde_json::json;
-use std::path::PathBuf;
+use serde_json::{json, Value};
+use std::{path::PathBuf, str::FromStr};
 
 static ACCOUNT_ADDRESS: &str = "0xa550c18";
 static CREATION_NUMBER: &str = "0";
@@ -199,3 +200,89 @@ async fn test_get_events_by_struct_type_has_generic_type_parameter() {
     let resp = context.expect_status_code(404).get(path.as_str()).await;
     context.check_golden_output(resp);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type_without_indexer() {
+    let context = new_test_context(current_function_name!());
+
+    context
+        .expect_status_code(403)
+        .get(&events_by_type("0x1::coin::DepositEvent"))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_invalid_type() {
+    let context = new_test_context_with_db_with_indexer(current_function_name!());
+
+    context
+        .expect_status_code(400)
+        .get(&events_by_type("0x1::coin"))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type_with_pagination() {
+    let mut context = new_test_context_with_db_with_indexer(current_function_name!());
+    for _ in 0..3 {
+        context.create_account().await;
+    }
+    let path = events_by_type("0x1::coin::DepositEvent");
+
+    // Without a limit, all the events fit in one page and there is no cursor.
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("/v1{}", path));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("X-Aptos-Cursor"));
+    let all_events: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+    // Every account creation deposits coins into the new account.
+    assert!(all_events.len() >= 3);
+    assert!(all_events
+        .iter()
+        .all(|event| event["type"] == "0x1::coin::DepositEvent"));
+
+    // Walk through the same events one at a time following the cursor.
+    let mut events = vec![];
+    let mut cursor: Option<EventCursor> = None;
+    loop {
+        let page_path = match cursor {
+            Some(cursor) => format!("/v1{}?limit=1&start={}", path, cursor),
+            None => format!("/v1{}?limit=1", path),
+        };
+        let resp = context
+            .reply(warp::test::request().method("GET").path(&page_path))
+            .await;
+        assert_eq!(resp.status(), 200);
+        let page: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(page.len(), 1);
+        events.extend(page);
+
+        match resp.headers().get("X-Aptos-Cursor") {
+            Some(header) => {
+                cursor = Some(EventCursor::from_str(header.to_str().unwrap()).unwrap());
+            },
+            None => break,
+        }
+    }
+    assert_eq!(events, all_events);
+
+    // Starting past the last event returns nothing.
+    let last_version: u64 = all_events.last().unwrap()["version"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let resp = context
+        .get(&format!("{}?start_version={}", path, last_version + 1))
+        .await;
+    assert_eq!(resp, json!([]));
+}
+
+fn events_by_type(event_type: &str) -> String {
+    format!(
+        "/events/by_type/{}",
+        utf8_percent_encode(event_type, NON_ALPHANUMERIC)
+    )
+}
@@ -15,6 +15,7 @@ mod resource_groups;
 mod secp256k1_ecdsa;
 mod state_test;
 mod string_resource_test;
+mod transaction_stream_test;
 mod transaction_vector_test;
 mod transactions_test;
 mod view_function;
@@ -29,3 +30,7 @@ fn new_test_context(test_name: String) -> TestContext {
 fn new_test_context_with_config(test_name: String, node_config: NodeConfig) -> TestContext {
     super_new_test_context(test_name, node_config, false)
 }
+
+fn new_test_context_with_db_with_indexer(test_name: String) -> TestContext {
+    super_new_test_context(test_name, NodeConfig::default(), true)
+}
@@ -3,9 +3,15 @@
 
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::{mime_types, HexEncodedBytes};
 use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
 use aptos_storage_interface::DbReader;
-use move_core_types::account_address::AccountAddress;
+use aptos_types::{
+    access_path::AccessPath,
+    account_config::AccountResource,
+    state_store::{state_key::StateKey, state_value::StateValuesWithMultiProof},
+};
+use move_core_types::{account_address::AccountAddress, move_resource::MoveStructType};
 use move_package::BuildConfig;
 use serde::Serialize;
 use serde_json::{json, Value};
@@ -298,6 +304,210 @@ async fn test_get_table_item() {
     assert_table_item(ctx, &nested_table, "u8", "u8", 2, 3).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_table_items_with_pagination() {
+    let mut context = new_test_context(current_function_name!());
+    let ctx = &mut context;
+    let creator = &mut ctx.gen_account();
+    let txn = ctx.mint_user_account(creator).await;
+    let mut txns = vec![txn];
+    for collection_name in ["collection b", "collection a"] {
+        let collection_builder =
+            ctx.transaction_factory()
+                .payload(aptos_token_stdlib::token_create_collection_script(
+                    collection_name.to_owned().into_bytes(),
+                    "description".to_owned().into_bytes(),
+                    "uri".to_owned().into_bytes(),
+                    20_000_000,
+                    vec![false, false, false],
+                ));
+        txns.push(creator.sign_with_transaction_builder(collection_builder));
+    }
+    ctx.commit_block(&txns).await;
+
+    // The collections of an account are in a table keyed by name.
+    let collections = ctx
+        .get(&get_account_resource(
+            &creator.address().to_hex_literal(),
+            "0x3::token::Collections",
+        ))
+        .await;
+    let handle: AccountAddress = collections["data"]["collection_data"]["handle"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let path = get_table_items(handle);
+
+    // Without a limit, all the items fit in one page, ordered by key, and there is no cursor.
+    let resp = ctx
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&format!("/v1{}", path)),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("X-Aptos-Cursor"));
+    let all_items: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+    let keys: Vec<_> = all_items.iter().map(|item| &item["data"]["key"]).collect();
+    assert_eq!(keys, vec!["collection a", "collection b"]);
+    assert!(all_items
+        .iter()
+        .all(|item| item["data"]["key_type"] == "0x1::string::String"
+            && item["data"]["value_type"] == "0x3::token::CollectionData"));
+
+    // Walk through the same items one at a time following the cursor.
+    let mut items = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let page_path = match cursor {
+            Some(cursor) => format!("/v1{}?limit=1&start={}", path, cursor),
+            None => format!("/v1{}?limit=1", path),
+        };
+        let resp = ctx
+            .reply(warp::test::request().method("GET").path(&page_path))
+            .await;
+        assert_eq!(resp.status(), 200);
+        let page: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(page.len(), 1);
+        items.extend(page);
+
+        match resp.headers().get("X-Aptos-Cursor") {
+            Some(header) => cursor = Some(header.to_str().unwrap().to_owned()),
+            None => break,
+        }
+    }
+    assert_eq!(items, all_items);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_table_items_with_removed_items() {
+    let mut context = new_test_context(current_function_name!());
+    let ctx = &mut context;
+    let mut account = ctx.gen_account();
+    let acc = &mut account;
+    let txn = ctx.create_user_account(acc).await;
+    ctx.commit_block(&vec![txn.clone()]).await;
+    make_test_tables(ctx, acc).await;
+    let module = format!("0x{}::TableTestData", acc.address().to_hex());
+    ctx.api_execute_entry_function(
+        acc,
+        &format!("{}::add_u64_table_items", module),
+        json!([]),
+        json!([["2", "3"]]),
+    )
+    .await;
+    let version = ctx.get_latest_ledger_info().version();
+    for key in ["1", "2"] {
+        ctx.api_execute_entry_function(
+            acc,
+            &format!("{}::remove_u64_table_item", module),
+            json!([]),
+            json!([key]),
+        )
+        .await;
+    }
+
+    let tt = ctx
+        .api_get_account_resource(
+            acc.address(),
+            &acc.address().to_hex_literal(),
+            "TableTestData",
+            "TestTables",
+        )
+        .await["data"]
+        .to_owned();
+    let handle: AccountAddress = tt["u64_table"]["handle"].as_str().unwrap().parse().unwrap();
+    let path = get_table_items(handle);
+
+    // The removed items are skipped, the page is filled with the next item and there is no cursor
+    // left pointing past the end of the table.
+    let resp = ctx
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&format!("/v1{}?limit=1", path)),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("X-Aptos-Cursor"));
+    let items: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+    let keys: Vec<_> = items.iter().map(|item| &item["data"]["key"]).collect();
+    assert_eq!(keys, vec!["3"]);
+
+    // The removed items are still there at a version before their removal.
+    let resp = ctx
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&format!("/v1{}?ledger_version={}", path, version)),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let items: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+    let keys: Vec<_> = items.iter().map(|item| &item["data"]["key"]).collect();
+    assert_eq!(keys, vec!["1", "2", "3"]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_raw_state_values_with_multiproof() {
+    let mut context = new_test_context(current_function_name!());
+    let ctx = &mut context;
+    let account = ctx.gen_account();
+    let txn = ctx.create_user_account(&account).await;
+    ctx.commit_block(&vec![txn]).await;
+
+    // The values are at the latest state snapshot, which may not include the latest transactions,
+    // so look up a resource of the genesis and one of an account that never existed.
+    let existing_key = StateKey::access_path(
+        AccessPath::resource_access_path(AccountAddress::ONE, AccountResource::struct_tag())
+            .unwrap(),
+    );
+    let missing_key = StateKey::access_path(
+        AccessPath::resource_access_path(
+            ctx.gen_account().address(),
+            AccountResource::struct_tag(),
+        )
+        .unwrap(),
+    );
+    let resp = ctx
+        .reply(raw_state_values_with_multiproof_request(&[
+            existing_key.clone(),
+            missing_key.clone(),
+        ]))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let values: StateValuesWithMultiProof = bcs::from_bytes(resp.body()).unwrap();
+
+    // The values are proven against the state checkpoint of their snapshot version.
+    let state_checkpoint_hash = ctx
+        .db
+        .get_transaction_by_version(values.version, values.version, false)
+        .unwrap()
+        .proof
+        .transaction_info
+        .state_checkpoint_hash()
+        .unwrap();
+    values.verify(state_checkpoint_hash).unwrap();
+    let value_of = |key: &StateKey| {
+        values
+            .values
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+            .unwrap()
+    };
+    assert!(value_of(&existing_key).is_some());
+    assert!(value_of(&missing_key).is_none());
+
+    // At least one key is required.
+    let resp = ctx
+        .reply(raw_state_values_with_multiproof_request(&[]))
+        .await;
+    assert_eq!(resp.status(), 400);
+}
+
 fn get_account_resource(address: &str, struct_tag: &str) -> String {
     format!("/accounts/{}/resource/{}", address, struct_tag)
 }
@@ -317,6 +527,22 @@ fn get_table_item(handle: AccountAddress) -> String {
     format!("/tables/{}/item", handle)
 }
 
+fn get_table_items(handle: AccountAddress) -> String {
+    format!("/tables/{}/items", handle)
+}
+
+fn raw_state_values_with_multiproof_request(keys: &[StateKey]) -> warp::test::RequestBuilder {
+    let keys: Vec<_> = keys
+        .iter()
+        .map(|key| HexEncodedBytes::from(bcs::to_bytes(key).unwrap()))
+        .collect();
+    warp::test::request()
+        .method("POST")
+        .path("/v1/experimental/state_values/raw_with_multiproof")
+        .header("Accept", mime_types::BCS)
+        .json(&json!({ "keys": keys }))
+}
+
 async fn make_test_tables(ctx: &mut TestContext, account: &mut LocalAccount) {
     let module = build_test_module(account.address()).await;
 
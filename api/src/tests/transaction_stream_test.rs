@@ -0,0 +1,94 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use crate::transaction_stream::{ActiveStream, TransactionStreamFilter};
+use aptos_api_test_context::current_function_name;
+use aptos_api_types::{Address, EntryFunctionId, MoveStructTag};
+use aptos_config::config::NodeConfig;
+use aptos_types::account_config::aptos_test_root_address;
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_disabled() {
+    // Streams are disabled by default
+    let context = new_test_context(current_function_name!());
+
+    context
+        .expect_status_code(403)
+        .get("/transactions/stream")
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_with_invalid_function() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.transaction_stream_enabled = true;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+
+    context
+        .expect_status_code(400)
+        .get("/transactions/stream?function=0x1::aptos_account")
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_transaction_stream_filter() {
+    let mut context = new_test_context(current_function_name!());
+    context.create_account().await;
+    let ledger_version = context.get_latest_ledger_info().version();
+    let txns = context.get_transactions(0, ledger_version as u16 + 1);
+
+    let count_matches =
+        |filter: TransactionStreamFilter| txns.iter().filter(|txn| filter.matches(txn)).count();
+    let root = Some(Address::from(aptos_test_root_address()));
+    let transfer = Some(EntryFunctionId::from_str("0x1::aptos_account::transfer").unwrap());
+    let deposit = Some(MoveStructTag::from_str("0x1::coin::DepositEvent").unwrap());
+
+    // Without any criteria, everything matches
+    let filter = TransactionStreamFilter::new(None, None, None).unwrap();
+    assert!(filter.is_empty());
+    assert_eq!(count_matches(filter), txns.len());
+
+    // The transfer from the root account to the new account is the only user transaction
+    let filter = TransactionStreamFilter::new(root, None, None).unwrap();
+    assert_eq!(count_matches(filter), 1);
+    let filter = TransactionStreamFilter::new(root, transfer.clone(), deposit.clone()).unwrap();
+    assert_eq!(count_matches(filter), 1);
+    let filter = TransactionStreamFilter::new(None, transfer, None).unwrap();
+    assert_eq!(count_matches(filter), 1);
+
+    // All criteria have to match
+    let other_function = Some(EntryFunctionId::from_str("0x1::coin::transfer").unwrap());
+    let filter = TransactionStreamFilter::new(root, other_function, deposit).unwrap();
+    assert_eq!(count_matches(filter), 0);
+    let filter =
+        TransactionStreamFilter::new(Some(Address::from_str("0xdead").unwrap()), None, None)
+            .unwrap();
+    assert_eq!(count_matches(filter), 0);
+}
+
+#[test]
+fn test_active_stream_limit() {
+    let active_streams = Arc::new(AtomicUsize::new(0));
+
+    let first = ActiveStream::try_new(active_streams.clone(), 2).unwrap();
+    let second = ActiveStream::try_new(active_streams.clone(), 2).unwrap();
+    assert_eq!(active_streams.load(Ordering::SeqCst), 2);
+
+    // No more streams can be opened until one of them is closed
+    assert!(ActiveStream::try_new(active_streams.clone(), 2).is_none());
+    assert_eq!(active_streams.load(Ordering::SeqCst), 2);
+
+    drop(first);
+    assert_eq!(active_streams.load(Ordering::SeqCst), 1);
+    let _third = ActiveStream::try_new(active_streams.clone(), 2).unwrap();
+    drop(second);
+    assert_eq!(active_streams.load(Ordering::SeqCst), 1);
+}
@@ -5,7 +5,9 @@
 use super::new_test_context;
 use crate::tests::new_test_context_with_config;
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
-use aptos_config::config::{GasEstimationStaticOverride, NodeConfig};
+use aptos_config::config::{
+    transaction_filter_type::Filter, GasEstimationStaticOverride, NodeConfig,
+};
 use aptos_crypto::{
     ed25519::Ed25519PrivateKey,
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
@@ -186,6 +188,22 @@ async fn test_post_transaction_rejected_by_mempool() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_rejected_by_filter() {
+    let mut node_config = NodeConfig::default();
+    node_config.execution.transaction_filter =
+        Filter::empty().add_deny_module_address(AccountAddress::ONE);
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    let resp = context
+        .expect_status_code(400)
+        .post_bcs_txn("/transactions", &bcs::to_bytes(&txn).unwrap())
+        .await;
+    assert_eq!(resp["error_code"], json!("rejected_by_filter"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_agent_signed_transaction() {
     let mut context = new_test_context(current_function_name!());
@@ -567,6 +585,40 @@ async fn test_get_pending_transaction_by_hash() {
     context.check_golden_output(not_found);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_pending_transactions() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let resp = context
+        .get(&format!(
+            "/accounts/{}/pending_transactions",
+            txn.sender().to_hex_literal()
+        ))
+        .await;
+    let txns = resp["transactions"].as_array().unwrap();
+    assert_eq!(txns.len(), 1);
+    assert_json(txns[0]["transaction"].clone(), pending_txn);
+    assert_eq!(txns[0]["parked"], json!(false));
+    assert_eq!(resp["mempool_stats"]["num_transactions"], json!("1"));
+    assert_eq!(resp["mempool_stats"]["num_accounts"], json!("1"));
+
+    // Accounts without pending transactions have none
+    let resp = context
+        .get(&format!(
+            "/accounts/{}/pending_transactions",
+            account.address().to_hex_literal()
+        ))
+        .await;
+    assert!(resp["transactions"].as_array().unwrap().is_empty());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_signing_message_with_entry_function_payload() {
     let mut context = new_test_context(current_function_name!());
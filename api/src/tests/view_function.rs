@@ -1,8 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::current_function_name;
+use aptos_config::config::NodeConfig;
 use serde_json::json;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -111,3 +112,84 @@ async fn test_view_tuple() {
         .await;
     context.check_golden_output_no_prune(resp);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_batch_view() {
+    let mut context = new_test_context(current_function_name!());
+    let creator = &mut context.gen_account();
+    let owner = &mut context.gen_account();
+    let txn1 = context.mint_user_account(creator).await;
+    let txn2 = context.account_transfer(creator, owner, 100_000);
+
+    context.commit_block(&vec![txn1, txn2]).await;
+
+    let resp = context
+        .post(
+            "/view/batch",
+            json!([
+                {
+                    "function":"0x1::coin::balance",
+                    "arguments": vec![owner.address().to_string()],
+                    "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+                },
+                {
+                    "function":"0x1::aptos_account::assert_account_exists",
+                    "arguments": vec![owner.address().to_string()],
+                    "type_arguments": [],
+                },
+            ]),
+        )
+        .await;
+
+    // A failing view function doesn't fail the rest of the batch
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["values"], json!(["100000"]));
+    assert!(results[0]["error"].is_null());
+    assert!(results[1]["values"].is_null());
+    assert!(results[1]["error"]["message"].is_string());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_batch_view_too_many_requests() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_view_function_batch_size = 1;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+
+    let request = json!({
+        "function":"0x1::coin::balance",
+        "arguments": vec![context.root_account().await.address().to_string()],
+        "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+    });
+    context
+        .expect_status_code(400)
+        .post("/view/batch", json!([request.clone(), request]))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_batch_view_shares_gas_budget() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_gas_view_function_batch = 1;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+
+    let request = json!({
+        "function":"0x1::coin::balance",
+        "arguments": vec![context.root_account().await.address().to_string()],
+        "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+    });
+    let resp = context
+        .post("/view/batch", json!([request.clone(), request]))
+        .await;
+
+    // The first view function runs out of gas, which uses up the budget of the batch, so the
+    // second one isn't executed
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results[0]["values"].is_null());
+    assert!(results[0]["error"]["message"].is_string());
+    assert_eq!(
+        results[1]["error"]["message"],
+        json!("The gas budget of the batch has been used up")
+    );
+}
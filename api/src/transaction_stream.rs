@@ -0,0 +1,371 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    context::{api_spawn_blocking, Context},
+    failpoint::fail_point_poem,
+    response::{
+        api_disabled, json_api_disabled, version_pruned, BadRequestError, BasicError,
+        BasicErrorWith404, ServiceUnavailableError,
+    },
+    ApiTags,
+};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    Address, AptosError, AptosErrorCode, EntryFunctionId, MoveStructTag, Transaction,
+    TransactionOnChainData, TransactionStreamEvent, VerifyInput, VerifyInputWithRecursion, U64,
+};
+use aptos_logger::warn;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{MultisigTransactionPayload, TransactionPayload},
+};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
+use poem_openapi::{param::Query, payload::EventStream, OpenApi};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Interval at which keep-alive comments are sent on idle streams, so that
+/// proxies and load balancers don't close the connection.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+type TransactionStreamResult =
+    poem::Result<EventStream<BoxStream<'static, TransactionStreamEvent>>, BasicErrorWith404>;
+
+/// API for streaming committed transactions
+#[derive(Clone)]
+pub struct TransactionStreamApi {
+    pub context: Arc<Context>,
+    /// Number of streams currently open
+    pub active_streams: Arc<AtomicUsize>,
+}
+
+impl TransactionStreamApi {
+    pub fn new(context: Arc<Context>) -> Self {
+        Self {
+            context,
+            active_streams: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+#[OpenApi]
+impl TransactionStreamApi {
+    /// Stream transactions
+    ///
+    /// Opens a server-sent event stream of on-chain committed transactions,
+    /// starting at the given ledger version. Each event is a JSON encoded
+    /// transaction, in the same format as returned by /transactions. The
+    /// stream stays open and pushes newly committed transactions as they
+    /// are committed.
+    ///
+    /// The stream can optionally be filtered by the sender of the transaction,
+    /// the entry function it invokes and the type of the events it emits.
+    /// When several filters are given, a transaction must match all of them.
+    ///
+    /// If the start version has been pruned, then a 410 will be returned.
+    /// If the node already serves its maximum number of streams, then a 503
+    /// will be returned.
+    ///
+    /// If the node fails to read transactions, it sends an error event, in
+    /// the same format as error responses, and closes the stream.
+    #[oai(
+        path = "/transactions/stream",
+        method = "get",
+        operation_id = "stream_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn stream_transactions(
+        &self,
+        /// Ledger version to start streaming transactions from
+        ///
+        /// If not provided, defaults to the next transaction to be committed
+        start: Query<Option<U64>>,
+        /// Only stream user transactions sent by this account
+        sender: Query<Option<Address>>,
+        /// Only stream user transactions that invoke this entry function
+        /// e.g. `0x1::aptos_account::transfer`, either directly or through a
+        /// multisig payload
+        function: Query<Option<EntryFunctionId>>,
+        /// Only stream transactions that emitted at least one event of this
+        /// type e.g. `0x1::coin::DepositEvent`
+        event_type: Query<Option<MoveStructTag>>,
+    ) -> TransactionStreamResult {
+        fail_point_poem("endpoint_stream_transactions")?;
+        if !self.context.node_config.api.transaction_stream_enabled {
+            return Err(api_disabled("Stream transactions"));
+        }
+        if !self.context.node_config.api.json_output_enabled {
+            return Err(json_api_disabled("Stream transactions"));
+        }
+
+        let filter =
+            TransactionStreamFilter::new(sender.0, function.0, event_type.0).map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+
+        let context = self.context.clone();
+        let latest_ledger_info =
+            api_spawn_blocking(move || context.get_latest_ledger_info()).await?;
+        let start_version = match start.0 {
+            Some(start) => start.0,
+            None => latest_ledger_info.version().saturating_add(1),
+        };
+        if start_version < latest_ledger_info.oldest_ledger_version.0 {
+            return Err(version_pruned(start_version, &latest_ledger_info));
+        }
+
+        let active_stream = ActiveStream::try_new(
+            self.active_streams.clone(),
+            self.context.node_config.api.max_concurrent_streams,
+        )
+        .ok_or_else(|| {
+            BasicErrorWith404::service_unavailable_with_code(
+                "Too many transaction streams are open, try again later",
+                AptosErrorCode::TooManyStreams,
+                &latest_ledger_info,
+            )
+        })?;
+
+        let state = TransactionStreamState {
+            context: self.context.clone(),
+            _active_stream: active_stream,
+            filter,
+            next_version: start_version,
+            poll_interval: Duration::from_millis(
+                self.context
+                    .node_config
+                    .api
+                    .transaction_stream_poll_interval_ms,
+            ),
+        };
+        // The state is dropped, and the stream closed, after an error has been sent
+        let events = stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            match state.next_batch().await {
+                Ok(transactions) => Some((
+                    stream::iter(
+                        transactions
+                            .into_iter()
+                            .map(TransactionStreamEvent::Transaction)
+                            .collect::<Vec<_>>(),
+                    ),
+                    Some(state),
+                )),
+                Err(error) => {
+                    warn!(
+                        error = ?error,
+                        next_version = state.next_version,
+                        "Failed to read transactions for stream, closing it"
+                    );
+                    let error = AptosError::new_with_error_code(
+                        format!(
+                            "Failed to read transactions at version {}: {:#}",
+                            state.next_version, error
+                        ),
+                        AptosErrorCode::InternalError,
+                    );
+                    Some((
+                        stream::iter(vec![TransactionStreamEvent::Error(error)]),
+                        None,
+                    ))
+                },
+            }
+        })
+        .flatten()
+        .boxed();
+
+        Ok(EventStream::new(events).keep_alive(KEEP_ALIVE_INTERVAL))
+    }
+}
+
+/// Counts a stream as open for as long as it's alive
+pub(crate) struct ActiveStream {
+    active_streams: Arc<AtomicUsize>,
+}
+
+impl ActiveStream {
+    /// Returns `None` if `max_streams` streams are already open
+    pub fn try_new(active_streams: Arc<AtomicUsize>, max_streams: usize) -> Option<Self> {
+        active_streams
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < max_streams).then_some(count + 1)
+            })
+            .ok()?;
+        Some(Self { active_streams })
+    }
+}
+
+impl Drop for ActiveStream {
+    fn drop(&mut self) {
+        self.active_streams.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Tracks the progress of a single transaction stream
+struct TransactionStreamState {
+    context: Arc<Context>,
+    _active_stream: ActiveStream,
+    filter: TransactionStreamFilter,
+    next_version: u64,
+    poll_interval: Duration,
+}
+
+impl TransactionStreamState {
+    /// Waits until there are transactions past `next_version` and returns the
+    /// next page of them that match the filter. Returns an empty page if none
+    /// of the transactions in the page matched.
+    async fn next_batch(&mut self) -> anyhow::Result<Vec<Transaction>> {
+        loop {
+            let context = self.context.clone();
+            let filter = self.filter.clone();
+            let start_version = self.next_version;
+            let page = tokio::task::spawn_blocking(move || {
+                read_transactions(&context, &filter, start_version)
+            })
+            .await
+            .context("Failed to join task to read transactions")??;
+
+            match page {
+                Some((next_version, transactions)) => {
+                    self.next_version = next_version;
+                    return Ok(transactions);
+                },
+                None => tokio::time::sleep(self.poll_interval).await,
+            }
+        }
+    }
+}
+
+/// Reads a page of committed transactions starting at `start_version`, filters
+/// them, and converts the remaining ones into their JSON representation.
+///
+/// Returns the version after the last transaction read, or `None` if there are
+/// no transactions committed at `start_version` yet.
+fn read_transactions(
+    context: &Context,
+    filter: &TransactionStreamFilter,
+    start_version: u64,
+) -> anyhow::Result<Option<(u64, Vec<Transaction>)>> {
+    let ledger_info = context.get_latest_ledger_info_wrapped()?;
+    let ledger_version = ledger_info.version();
+    if start_version > ledger_version {
+        return Ok(None);
+    }
+
+    let limit = std::cmp::min(
+        context.max_transactions_page_size() as u64,
+        ledger_version - start_version + 1,
+    ) as u16;
+    let data = context
+        .get_transactions(start_version, limit, ledger_version)
+        .context("Failed to read raw transactions from storage")?;
+    let next_version = start_version + data.len() as u64;
+
+    let transactions = if filter.is_empty() {
+        let timestamp = context.get_block_timestamp::<BasicError>(&ledger_info, start_version)?;
+        context.render_transactions_sequential::<BasicError>(&ledger_info, data, timestamp)?
+    } else {
+        let data = data.into_iter().filter(|txn| filter.matches(txn)).collect();
+        context.render_transactions_non_sequential::<BasicError>(&ledger_info, data)?
+    };
+
+    Ok(Some((next_version, transactions)))
+}
+
+/// Filter applied to the transactions of a stream. Every criteria that is set
+/// must match for a transaction to be included.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TransactionStreamFilter {
+    sender: Option<AccountAddress>,
+    function: Option<(ModuleId, Identifier)>,
+    event_type: Option<TypeTag>,
+}
+
+impl TransactionStreamFilter {
+    pub fn new(
+        sender: Option<Address>,
+        function: Option<EntryFunctionId>,
+        event_type: Option<MoveStructTag>,
+    ) -> anyhow::Result<Self> {
+        let function = function
+            .map(|function| {
+                function.verify().context("'function' invalid")?;
+                Ok::<_, anyhow::Error>((function.module.into(), function.name.into()))
+            })
+            .transpose()?;
+        let event_type = event_type
+            .map(|event_type| {
+                event_type.verify(0).context("'event_type' invalid")?;
+                let struct_tag = StructTag::try_from(event_type).context("'event_type' invalid")?;
+                Ok::<_, anyhow::Error>(TypeTag::Struct(Box::new(struct_tag)))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            sender: sender.map(|sender| sender.into()),
+            function,
+            event_type,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sender.is_none() && self.function.is_none() && self.event_type.is_none()
+    }
+
+    pub fn matches(&self, txn: &TransactionOnChainData) -> bool {
+        if self.sender.is_some() || self.function.is_some() {
+            let signed_txn = match txn.transaction.try_as_signed_user_txn() {
+                Some(signed_txn) => signed_txn,
+                None => return false,
+            };
+            if let Some(sender) = self.sender {
+                if signed_txn.sender() != sender {
+                    return false;
+                }
+            }
+            if let Some((module, name)) = &self.function {
+                let entry_function = match signed_txn.payload() {
+                    TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+                    TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+                        Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                            Some(entry_function)
+                        },
+                        None => None,
+                    },
+                    TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => None,
+                };
+                match entry_function {
+                    Some(entry_function)
+                        if entry_function.module() == module
+                            && entry_function.function() == name.as_ident_str() => {},
+                    _ => return false,
+                }
+            }
+        }
+
+        if let Some(event_type) = &self.event_type {
+            if !txn
+                .events
+                .iter()
+                .any(|event| event.type_tag() == event_type)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
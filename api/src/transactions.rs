@@ -20,14 +20,17 @@ use crate::{
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
-    AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
-    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
+    verify_function_identifier, verify_module_identifier, AccountPendingTransactions, Address,
+    AptosError, AptosErrorCode, AsConverter, EncodeSubmissionRequest, GasEstimation,
+    GasEstimationBcs, HashValue, HexEncodedBytes, LedgerInfo, MempoolTransaction, MoveType,
+    PendingTransaction, SubmitTransactionRequest, Transaction, TransactionData,
+    TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
     TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
     MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_mempool::{MempoolStats, PendingTransactionInfo};
+use aptos_storage_interface::Error as StorageError;
 use aptos_types::{
     account_config::CoinStoreResource,
     mempool_status::MempoolStatusCode,
@@ -43,7 +46,7 @@ use poem_openapi::{
     payload::Json,
     ApiRequest, OpenApi,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::UNIX_EPOCH};
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 
@@ -274,6 +277,35 @@ impl TransactionsApi {
         api_spawn_blocking(move || api.list_by_account(&accept_type, page, address.0)).await
     }
 
+    /// Get account pending transactions
+    ///
+    /// Retrieves the transactions from an account that are waiting in the mempool
+    /// of the node, ordered by sequence number, along with their status in mempool
+    /// and aggregate statistics of the mempool.
+    ///
+    /// A transaction is parked if it can't be committed until the transactions
+    /// from the account with lower sequence numbers are submitted.
+    ///
+    /// This endpoint only supports JSON.
+    #[oai(
+        path = "/accounts/:address/pending_transactions",
+        method = "get",
+        operation_id = "get_account_pending_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_account_pending_transactions(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+    ) -> BasicResultWith404<AccountPendingTransactions> {
+        fail_point_poem("endpoint_get_account_pending_transactions")?;
+        self.context
+            .check_api_output_enabled("Get account pending transactions", &accept_type)?;
+        self.list_pending_by_account_inner(&accept_type, address.0)
+            .await
+    }
+
     /// Submit transaction
     ///
     /// This endpoint accepts transaction submissions in two formats.
@@ -773,7 +805,25 @@ impl TransactionsApi {
             return Ok(GetByVersionResponse::VersionTooNew);
         }
         if version < ledger_info.oldest_version() {
-            return Ok(GetByVersionResponse::VersionTooOld);
+            // Transactions of retained accounts are kept beyond the prune window
+            if self.context.retained_accounts().is_empty() {
+                return Ok(GetByVersionResponse::VersionTooOld);
+            }
+            return match self
+                .context
+                .get_transaction_by_version(version, ledger_info.version())
+            {
+                Ok(txn) => Ok(GetByVersionResponse::Found(txn.into())),
+                Err(err)
+                    if matches!(
+                        err.downcast_ref::<StorageError>(),
+                        Some(StorageError::Pruned { .. })
+                    ) =>
+                {
+                    Ok(GetByVersionResponse::VersionTooOld)
+                },
+                Err(err) => Err(err),
+            };
         }
         Ok(GetByVersionResponse::Found(
             self.context
@@ -808,6 +858,109 @@ impl TransactionsApi {
         })
     }
 
+    /// Lists the transactions of an account waiting in mempool
+    async fn list_pending_by_account_inner(
+        &self,
+        accept_type: &AcceptType,
+        address: Address,
+    ) -> BasicResultWith404<AccountPendingTransactions> {
+        if accept_type == &AcceptType::Bcs {
+            return Err(api_forbidden(
+                "Get account pending transactions",
+                "Please use JSON instead.",
+            ));
+        }
+        let context = self.context.clone();
+        let ledger_info = api_spawn_blocking(move || context.get_latest_ledger_info()).await?;
+
+        let pending_txns = self
+            .context
+            .get_pending_transactions_by_sender(address.into())
+            .await
+            .context(format!(
+                "Failed to get pending transactions of account {}",
+                address
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let stats = self
+            .context
+            .get_mempool_stats()
+            .await
+            .context("Failed to get mempool stats")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.render_pending_transactions(pending_txns, stats, &ledger_info)
+        })
+        .await
+    }
+
+    /// Converts the transactions of an account waiting in mempool into the outgoing type
+    fn render_pending_transactions(
+        &self,
+        pending_txns: Vec<PendingTransactionInfo>,
+        stats: MempoolStats,
+        ledger_info: &LedgerInfo,
+    ) -> BasicResultWith404<AccountPendingTransactions> {
+        let state_view = self.context.latest_state_view_poem(ledger_info)?;
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
+        let transactions = pending_txns
+            .into_iter()
+            .map(|info| {
+                let transaction = converter.try_into_pending_transaction_poem(info.txn)?;
+                let insertion_time = info
+                    .insertion_time
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                Ok(MempoolTransaction {
+                    transaction,
+                    parked: info.parked,
+                    ranking_bucket: info.bucket,
+                    insertion_timestamp_usecs: (insertion_time.as_micros() as u64).into(),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .context("Failed to convert pending transactions")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            })?;
+
+        let mempool_stats = aptos_api_types::MempoolStats {
+            num_transactions: (stats.num_transactions as u64).into(),
+            num_parked_transactions: (stats.num_parked_transactions as u64).into(),
+            num_accounts: (stats.num_accounts as u64).into(),
+            size_bytes: (stats.size_bytes as u64).into(),
+            capacity: (stats.capacity as u64).into(),
+            capacity_bytes: (stats.capacity_bytes as u64).into(),
+        };
+        BasicResponse::try_from_json((
+            AccountPendingTransactions {
+                transactions,
+                mempool_stats,
+            },
+            ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+    }
+
     /// List all transactions for an account
     fn list_by_account(
         &self,
@@ -1009,6 +1162,18 @@ impl TransactionsApi {
 
     /// Submits a single transaction, and converts mempool codes to errors
     async fn create_internal(&self, txn: SignedTransaction) -> Result<(), AptosError> {
+        if !self
+            .context
+            .node_config
+            .execution
+            .transaction_filter
+            .allows_transaction(&txn)
+        {
+            return Err(AptosError::new_with_error_code(
+                "Transaction is denied by the transaction filter",
+                AptosErrorCode::RejectedByFilter,
+            ));
+        }
         let (mempool_status, vm_status_opt) = self
             .context
             .submit_transaction(txn)
@@ -1052,6 +1217,10 @@ impl TransactionsApi {
                 mempool_status.message,
                 AptosErrorCode::InvalidTransactionUpdate,
             )),
+            MempoolStatusCode::RejectedByFilter => Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::RejectedByFilter,
+            )),
             MempoolStatusCode::UnknownStatus => Err(AptosError::new_with_error_code(
                 format!("Transaction was rejected with status {}", mempool_status,),
                 AptosErrorCode::InternalError,
@@ -1113,7 +1282,8 @@ impl TransactionsApi {
                 ),
                 AptosErrorCode::VmError
                 | AptosErrorCode::SequenceNumberTooOld
-                | AptosErrorCode::InvalidTransactionUpdate => Err(
+                | AptosErrorCode::InvalidTransactionUpdate
+                | AptosErrorCode::RejectedByFilter => Err(
                     SubmitTransactionError::bad_request_from_aptos_error(error, ledger_info),
                 ),
                 AptosErrorCode::MempoolIsFull => Err(
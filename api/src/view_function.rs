@@ -14,15 +14,16 @@ use crate::{
 };
 use anyhow::Context as anyhowContext;
 use aptos_api_types::{
-    AptosErrorCode, AsConverter, MoveValue, ViewFunction, ViewRequest, MAX_RECURSIVE_TYPES_ALLOWED,
-    U64,
+    AptosError, AptosErrorCode, AsConverter, MoveConverter, MoveValue, ViewFunction,
+    ViewFunctionResult, ViewFunctionResultBcs, ViewRequest, MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
-use aptos_bcs_utils::serialize_uleb128;
+use aptos_bcs_utils::{deserialize_uleb128, serialize_uleb128};
+use aptos_storage_interface::state_view::DbStateView;
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use itertools::Itertools;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::{language_storage::TypeTag, resolver::MoveResolver};
 use poem_openapi::{param::Query, payload::Json, ApiRequest, OpenApi};
-use std::sync::Arc;
+use std::{cmp::min, sync::Arc};
 
 /// API for executing Move view function.
 #[derive(Clone)]
@@ -39,6 +40,15 @@ pub enum ViewFunctionRequest {
     Bcs(Bcs),
 }
 
+#[derive(ApiRequest, Debug)]
+pub enum ViewFunctionBatchRequest {
+    #[oai(content_type = "application/json")]
+    Json(Json<Vec<ViewRequest>>),
+
+    #[oai(content_type = "application/x.aptos.view_function+bcs")]
+    Bcs(Bcs),
+}
+
 #[OpenApi]
 impl ViewFunctionApi {
     /// Execute view function of a module
@@ -71,6 +81,50 @@ impl ViewFunctionApi {
         api_spawn_blocking(move || view_request(context, accept_type, request, ledger_version))
             .await
     }
+
+    /// Execute a batch of view functions
+    ///
+    /// Execute several Move view functions against the same ledger version and
+    /// return their results in the same order as the requests. A failing view
+    /// function doesn't fail the whole batch, instead its error is returned in
+    /// place of its result.
+    ///
+    /// When requesting BCS, the body must be a BCS encoded vector of view functions,
+    /// and the response is a BCS encoded vector of results, where each successful
+    /// result holds the BCS encoded return values of the view function.
+    ///
+    /// All view functions of the batch share one gas budget, set by the
+    /// `max_gas_view_function_batch` API config. Once it is used up, the
+    /// remaining view functions fail without being executed.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/view/batch",
+        method = "post",
+        operation_id = "view_batch",
+        tag = "ApiTags::View"
+    )]
+    async fn view_function_batch(
+        &self,
+        accept_type: AcceptType,
+        /// View function requests with type and position arguments
+        request: ViewFunctionBatchRequest,
+        /// Ledger version to get state of account
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<ViewFunctionResult>> {
+        fail_point_poem("endpoint_view_function_batch")?;
+        self.context
+            .check_api_output_enabled("View function batch", &accept_type)?;
+
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            view_batch_request(context, accept_type, request, ledger_version)
+        })
+        .await
+    }
 }
 
 fn view_request(
@@ -120,15 +174,12 @@ fn view_request(
         },
     };
 
-    let return_vals = AptosVM::execute_view_function(
+    let (return_vals, _) = execute_view_function(
         &state_view,
-        view_function.module.clone(),
-        view_function.function.clone(),
-        view_function.ty_args.clone(),
-        view_function.args.clone(),
+        &view_function,
         context.node_config.api.max_gas_view_function,
-    )
-    .map_err(|err| {
+    );
+    let return_vals = return_vals.map_err(|err| {
         BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
     })?;
     match accept_type {
@@ -155,14 +206,8 @@ fn view_request(
         },
         AcceptType::Json => {
             let resolver = state_view.as_move_resolver();
-            let return_types = resolver
-                .as_converter(context.db.clone())
-                .function_return_types(&view_function)
-                .and_then(|tys| {
-                    tys.into_iter()
-                        .map(TypeTag::try_from)
-                        .collect::<anyhow::Result<Vec<_>>>()
-                })
+            let converter = resolver.as_converter(context.db.clone());
+            let move_vals = convert_return_values(&converter, &view_function, return_vals)
                 .map_err(|err| {
                     BasicErrorWith404::bad_request_with_code(
                         err,
@@ -171,24 +216,184 @@ fn view_request(
                     )
                 })?;
 
-            let move_vals = return_vals
-                .into_iter()
-                .zip(return_types.into_iter())
-                .map(|(v, ty)| {
-                    resolver
-                        .as_converter(context.db.clone())
-                        .try_into_move_value(&ty, &v)
-                })
-                .collect::<anyhow::Result<Vec<_>>>()
+            BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
+        },
+    }
+}
+
+fn view_batch_request(
+    context: Arc<Context>,
+    accept_type: AcceptType,
+    request: ViewFunctionBatchRequest,
+    ledger_version: Query<Option<U64>>,
+) -> BasicResultWith404<Vec<ViewFunctionResult>> {
+    // Retrieve the current state of the chain, all view functions are executed against it
+    let (ledger_info, requested_version) = context
+        .get_latest_ledger_info_and_verify_lookup_version(ledger_version.map(|inner| inner.0))?;
+
+    // Check the size of the batch before doing any work on its view functions. A BCS batch
+    // starts with its length, so it's read without deserializing the view functions.
+    let batch_size = match &request {
+        ViewFunctionBatchRequest::Json(data) => data.0.len(),
+        ViewFunctionBatchRequest::Bcs(data) => {
+            deserialize_uleb128(data.0.as_slice())
+                .context("Failed to deserialize the length of the batch")
                 .map_err(|err| {
                     BasicErrorWith404::bad_request_with_code(
                         err,
-                        AptosErrorCode::InternalError,
+                        AptosErrorCode::InvalidInput,
                         &ledger_info,
                     )
-                })?;
+                })?
+                .0 as usize
+        },
+    };
+    let max_batch_size = context.node_config.api.max_view_function_batch_size;
+    if batch_size > max_batch_size {
+        return Err(BasicErrorWith404::bad_request_with_code(
+            format!(
+                "Number of view functions in the batch ({}) is higher than the maximum allowed ({})",
+                batch_size, max_batch_size
+            ),
+            AptosErrorCode::InvalidInput,
+            &ledger_info,
+        ));
+    }
 
-            BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
+    let state_view = context
+        .state_view_at_version(requested_version)
+        .map_err(|err| {
+            BasicErrorWith404::internal_with_code(
+                err,
+                AptosErrorCode::InternalError,
+                &ledger_info,
+            )
+        })?;
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter(context.db.clone());
+
+    // A request that can't be parsed fails on its own, unless the whole batch can't be parsed
+    let view_functions: Vec<Result<ViewFunction, AptosError>> = match request {
+        ViewFunctionBatchRequest::Json(data) => data
+            .0
+            .into_iter()
+            .map(|request| {
+                converter.convert_view_function(request).map_err(|err| {
+                    AptosError::new_with_error_code(err, AptosErrorCode::InvalidInput)
+                })
+            })
+            .collect(),
+        ViewFunctionBatchRequest::Bcs(data) => bcs::from_bytes_with_limit::<Vec<ViewFunction>>(
+            data.0.as_slice(),
+            MAX_RECURSIVE_TYPES_ALLOWED as usize,
+        )
+        .context("Failed to deserialize input into a batch of ViewFunction")
+        .map_err(|err| {
+            BasicErrorWith404::bad_request_with_code(
+                err,
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )
+        })?
+        .into_iter()
+        .map(Ok)
+        .collect(),
+    };
+
+    // Each view function gets at most the gas limit of a single view function, out of the gas
+    // left in the budget of the batch
+    let max_gas_view_function = context.node_config.api.max_gas_view_function;
+    let mut remaining_gas = context.node_config.api.max_gas_view_function_batch;
+    let results: Vec<_> = view_functions
+        .into_iter()
+        .map(|view_function| {
+            view_function.and_then(|view_function| {
+                if remaining_gas == 0 {
+                    return Err(AptosError::new_with_error_code(
+                        "The gas budget of the batch has been used up",
+                        AptosErrorCode::InvalidInput,
+                    ));
+                }
+                let (return_vals, gas_used) = execute_view_function(
+                    &state_view,
+                    &view_function,
+                    min(max_gas_view_function, remaining_gas),
+                );
+                remaining_gas = remaining_gas.saturating_sub(gas_used);
+                return_vals
+                    .map(|return_vals| (view_function, return_vals))
+                    .map_err(|err| {
+                        AptosError::new_with_error_code(err, AptosErrorCode::InvalidInput)
+                    })
+            })
+        })
+        .collect();
+
+    match accept_type {
+        AcceptType::Bcs => {
+            let results: Vec<ViewFunctionResultBcs> = results
+                .into_iter()
+                .map(|result| result.map(|(_, return_vals)| return_vals))
+                .collect();
+            BasicResponse::try_from_bcs((results, &ledger_info, BasicResponseStatus::Ok))
+        },
+        AcceptType::Json => {
+            let results = results
+                .into_iter()
+                .map(|result| {
+                    ViewFunctionResult::from(result.and_then(|(view_function, return_vals)| {
+                        convert_return_values(&converter, &view_function, return_vals)
+                            .and_then(|move_vals| {
+                                move_vals
+                                    .iter()
+                                    .map(MoveValue::json)
+                                    .collect::<anyhow::Result<Vec<_>>>()
+                            })
+                            .map_err(|err| {
+                                AptosError::new_with_error_code(err, AptosErrorCode::InternalError)
+                            })
+                    }))
+                })
+                .collect();
+            BasicResponse::try_from_json((results, &ledger_info, BasicResponseStatus::Ok))
         },
     }
 }
+
+/// Executes the view function against the given state, returning its BCS encoded return values
+/// and the gas it used
+fn execute_view_function(
+    state_view: &DbStateView,
+    view_function: &ViewFunction,
+    gas_budget: u64,
+) -> (anyhow::Result<Vec<Vec<u8>>>, u64) {
+    AptosVM::execute_view_function_with_gas_used(
+        state_view,
+        view_function.module.clone(),
+        view_function.function.clone(),
+        view_function.ty_args.clone(),
+        view_function.args.clone(),
+        gas_budget,
+    )
+}
+
+/// Decodes the BCS encoded return values of a view function using its signature
+fn convert_return_values<R: MoveResolver>(
+    converter: &MoveConverter<'_, R>,
+    view_function: &ViewFunction,
+    return_vals: Vec<Vec<u8>>,
+) -> anyhow::Result<Vec<MoveValue>> {
+    let return_types = converter
+        .function_return_types(view_function)
+        .and_then(|tys| {
+            tys.into_iter()
+                .map(TypeTag::try_from)
+                .collect::<anyhow::Result<Vec<_>>>()
+        })?;
+
+    return_vals
+        .into_iter()
+        .zip(return_types.into_iter())
+        .map(|(v, ty)| converter.try_into_move_value(&ty, &v))
+        .collect()
+}
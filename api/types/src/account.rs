@@ -2,9 +2,9 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{HexEncodedBytes, U64};
+use crate::{HexEncodedBytes, MoveModuleId, MoveStructTag, U64};
 use aptos_types::account_config::AccountResource;
-use poem_openapi::Object;
+use poem_openapi::{Enum, Object};
 use serde::{Deserialize, Serialize};
 
 /// Account data
@@ -25,3 +25,39 @@ impl From<AccountResource> for AccountData {
         }
     }
 }
+
+/// How a resource or module of an account changed between two versions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum StateChangeType {
+    /// Didn't exist at the old version
+    Added,
+    /// Exists at both versions with different values
+    Modified,
+    /// Doesn't exist at the new version
+    Deleted,
+}
+
+/// A resource or module of an account which changed between two versions
+///
+/// Only one of `resource`, `resource_group` and `module` is set, depending on what the state
+/// key refers to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct AccountStateChange {
+    pub change_type: StateChangeType,
+    pub state_key_hash: String,
+    pub resource: Option<MoveStructTag>,
+    pub resource_group: Option<MoveStructTag>,
+    pub module: Option<MoveModuleId>,
+}
+
+/// Changes to the resources and modules of an account between two ledger versions
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct AccountStateDiff {
+    /// Ledger version the diff starts from
+    pub from_version: U64,
+    /// Ledger version the diff ends at
+    pub to_version: U64,
+    pub changes: Vec<AccountStateChange>,
+}
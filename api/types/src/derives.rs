@@ -20,8 +20,8 @@
 
 use crate::{
     move_types::{MoveAbility, MoveStructValue},
-    Address, EntryFunctionId, HashValue, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
-    MoveStructTag, MoveType, StateKeyWrapper, U128, U256, U64,
+    Address, EntryFunctionId, EventCursor, HashValue, HexEncodedBytes, IdentifierWrapper,
+    MoveModuleId, MoveStructTag, MoveType, StateKeyWrapper, U128, U256, U64,
 };
 use aptos_openapi::{impl_poem_parameter, impl_poem_type};
 use indoc::indoc;
@@ -231,6 +231,19 @@ impl_poem_type!(
     )
 );
 
+impl_poem_type!(
+    EventCursor,
+    "string",
+    (
+        example = Some(serde_json::Value::String(
+            "000000000000000a0000000000000002".to_string()
+        )),
+        description = Some(indoc! {"
+          Representation of the position of an event as a hex string. This is used for cursor based pagination.
+        "})
+    )
+);
+
 impl_poem_type!(
     StateKeyWrapper,
     "string",
@@ -293,6 +306,8 @@ impl_poem_type!(
 
 impl_poem_parameter!(
     Address,
+    EntryFunctionId,
+    EventCursor,
     HashValue,
     IdentifierWrapper,
     HexEncodedBytes,
@@ -96,11 +96,15 @@ pub enum AptosErrorCode {
     SequenceNumberTooOld = 402,
     /// The submitted transaction failed VM checks.
     VmError = 403,
+    /// The submitted transaction was denied by the transaction filter of the node.
+    RejectedByFilter = 404,
 
     /// Health check failed.
     HealthCheckFailed = 500,
     /// The mempool is full, no new transactions can be submitted.
     MempoolIsFull = 501,
+    /// The maximum number of concurrent transaction streams has been reached.
+    TooManyStreams = 502,
 
     /// Internal server error
     InternalError = 600,
@@ -15,6 +15,7 @@ mod hash;
 mod headers;
 mod index;
 mod ledger_info;
+mod mempool;
 pub mod mime_types;
 mod move_types;
 mod state;
@@ -23,7 +24,7 @@ pub mod transaction;
 mod view;
 mod wrappers;
 
-pub use account::AccountData;
+pub use account::{AccountData, AccountStateChange, AccountStateDiff, StateChangeType};
 pub use address::Address;
 pub use block::{BcsBlock, Block};
 pub use bytecode::Bytecode;
@@ -33,6 +34,7 @@ pub use hash::HashValue;
 pub use headers::*;
 pub use index::{IndexResponse, IndexResponseBcs};
 pub use ledger_info::LedgerInfo;
+pub use mempool::{AccountPendingTransactions, MempoolStats, MempoolTransaction};
 pub use move_types::{
     verify_field_identifier, verify_function_identifier, verify_module_identifier, EntryFunctionId,
     HexEncodedBytes, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
@@ -41,9 +43,9 @@ pub use move_types::{
     ResourceGroup, MAX_RECURSIVE_TYPES_ALLOWED, U128, U256, U64,
 };
 use serde::{Deserialize, Deserializer};
-pub use state::RawStateValueRequest;
+pub use state::{RawStateValueRequest, RawStateValuesRequest};
 use std::str::FromStr;
-pub use table::{RawTableItemRequest, TableItemRequest};
+pub use table::{RawTableItemRequest, TableItem, TableItemRequest};
 pub use transaction::{
     AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
     DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
@@ -52,13 +54,13 @@ pub use transaction::{
     MultisigPayload, MultisigTransactionPayload, PendingTransaction, PublicKey, ScriptPayload,
     ScriptWriteSet, Signature, SingleKeySignature, SubmitTransactionRequest, Transaction,
     TransactionData, TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
-    TransactionSignature, TransactionSigningMessage, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
-    UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
-    WriteSetPayload, WriteTableItem,
+    TransactionSignature, TransactionSigningMessage, TransactionStreamEvent,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
+    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
+    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
 };
-pub use view::{ViewFunction, ViewRequest};
-pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
+pub use view::{ViewFunction, ViewFunctionResult, ViewFunctionResultBcs, ViewRequest};
+pub use wrappers::{EventCursor, EventGuid, IdentifierWrapper, StateKeyWrapper};
 
 pub fn deserialize_from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
@@ -0,0 +1,41 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{PendingTransaction, U64};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// A transaction waiting in mempool, along with its status in mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolTransaction {
+    pub transaction: PendingTransaction,
+    /// Whether the transaction is parked
+    ///
+    /// A parked transaction can't be committed until the transactions of the account with lower
+    /// sequence numbers are submitted.
+    pub parked: bool,
+    /// Ranking bucket of the transaction in mempool, given by the lowest gas unit price of the
+    /// bucket
+    pub ranking_bucket: String,
+    /// Time the transaction was inserted into mempool, in microseconds since the Unix epoch
+    pub insertion_timestamp_usecs: U64,
+}
+
+/// Aggregate statistics of the transactions in mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolStats {
+    pub num_transactions: U64,
+    pub num_parked_transactions: U64,
+    pub num_accounts: U64,
+    pub size_bytes: U64,
+    pub capacity: U64,
+    pub capacity_bytes: U64,
+}
+
+/// The transactions of an account waiting in mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct AccountPendingTransactions {
+    /// Transactions of the account, ordered by sequence number
+    pub transactions: Vec<MempoolTransaction>,
+    pub mempool_stats: MempoolStats,
+}
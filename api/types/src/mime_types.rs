@@ -12,3 +12,6 @@ pub const JSON: &str = "application/json";
 
 /// MIME type to get BCS output
 pub const BCS: &str = "application/x-bcs";
+
+/// MIME type of server-sent event streams
+pub const EVENT_STREAM: &str = "text/event-stream";
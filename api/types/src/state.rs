@@ -10,3 +10,10 @@ use serde::{Deserialize, Serialize};
 pub struct RawStateValueRequest {
     pub key: HexEncodedBytes,
 }
+
+/// State values request for the GetRawStateValuesWithMultiProof API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct RawStateValuesRequest {
+    /// BCS encoded state keys
+    pub keys: Vec<HexEncodedBytes>,
+}
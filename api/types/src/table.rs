@@ -1,7 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{HexEncodedBytes, MoveType, VerifyInput, VerifyInputWithRecursion};
+use crate::{
+    transaction::DecodedTableData, HexEncodedBytes, MoveType, VerifyInput, VerifyInputWithRecursion,
+};
 use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -27,3 +29,14 @@ impl VerifyInput for TableItemRequest {
 pub struct RawTableItemRequest {
     pub key: HexEncodedBytes,
 }
+
+/// An item of a table, as returned by the GetTableItems API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TableItem {
+    /// The BCS serialized key of the item
+    pub key: HexEncodedBytes,
+    /// The BCS serialized value of the item
+    pub value: HexEncodedBytes,
+    /// The key and value decoded with the types of the table, if the node knows them
+    pub data: Option<DecodedTableData>,
+}
@@ -11,7 +11,7 @@ use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
     ed25519::{self, Ed25519PublicKey, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH},
     multi_ed25519::{self, MultiEd25519PublicKey, BITMAP_NUM_OF_BYTES, MAX_NUM_OF_KEYS},
-    secp256k1_ecdsa,
+    p256_ecdsa, secp256k1_ecdsa,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -22,6 +22,7 @@ use aptos_types::{
             AccountAuthenticator, AnyPublicKey, AnySignature, MultiKey, MultiKeyAuthenticator,
             SingleKeyAuthenticator, TransactionAuthenticator, MAX_NUM_OF_SIGS,
         },
+        webauthn::PartialAuthenticatorAssertionResponse,
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
@@ -242,6 +243,17 @@ impl Transaction {
     }
 }
 
+/// An event of a transaction stream
+///
+/// Either a committed transaction, or an error after which the stream is
+/// closed by the server.
+#[derive(Clone, Debug, Serialize, Deserialize, Union)]
+#[serde(untagged)]
+pub enum TransactionStreamEvent {
+    Transaction(Transaction),
+    Error(AptosError),
+}
+
 // TODO: Remove this when we cut over to the new API fully.
 impl From<(SignedTransaction, TransactionPayload)> for Transaction {
     fn from((txn, payload): (SignedTransaction, TransactionPayload)) -> Self {
@@ -1130,6 +1142,10 @@ impl VerifyInput for Secp256k1EcdsaSignature {
 pub enum Signature {
     Ed25519(HexEncodedBytes),
     Secp256k1Ecdsa(HexEncodedBytes),
+    /// BCS encoded WebAuthn `PartialAuthenticatorAssertionResponse`
+    ///
+    /// The secp256r1 signature in it must be the 64 bytes `r || s` encoding, with `s` in low-S form
+    WebAuthn(HexEncodedBytes),
 }
 
 impl TryFrom<Signature> for AnySignature {
@@ -1139,6 +1155,7 @@ impl TryFrom<Signature> for AnySignature {
         Ok(match signature {
             Signature::Ed25519(s) => AnySignature::ed25519(s.inner().try_into()?),
             Signature::Secp256k1Ecdsa(s) => AnySignature::secp256k1_ecdsa(s.inner().try_into()?),
+            Signature::WebAuthn(s) => AnySignature::webauthn(s.inner().try_into()?),
         })
     }
 }
@@ -1152,6 +1169,9 @@ impl From<AnySignature> for Signature {
             AnySignature::Secp256k1Ecdsa { signature } => {
                 Signature::Secp256k1Ecdsa(signature.to_bytes().to_vec().into())
             },
+            AnySignature::WebAuthn { signature } => {
+                Signature::WebAuthn(signature.to_bytes().into())
+            },
         }
     }
 }
@@ -1162,6 +1182,7 @@ impl From<AnySignature> for Signature {
 pub enum PublicKey {
     Ed25519(HexEncodedBytes),
     Secp256k1Ecdsa(HexEncodedBytes),
+    Secp256r1Ecdsa(HexEncodedBytes),
 }
 
 impl TryFrom<PublicKey> for AnyPublicKey {
@@ -1171,6 +1192,7 @@ impl TryFrom<PublicKey> for AnyPublicKey {
         Ok(match public_key {
             PublicKey::Ed25519(p) => AnyPublicKey::ed25519(p.inner().try_into()?),
             PublicKey::Secp256k1Ecdsa(p) => AnyPublicKey::secp256k1_ecdsa(p.inner().try_into()?),
+            PublicKey::Secp256r1Ecdsa(p) => AnyPublicKey::secp256r1_ecdsa(p.inner().try_into()?),
         })
     }
 }
@@ -1184,6 +1206,9 @@ impl From<AnyPublicKey> for PublicKey {
             AnyPublicKey::Secp256k1Ecdsa { public_key } => {
                 PublicKey::Secp256k1Ecdsa(public_key.to_bytes().to_vec().into())
             },
+            AnyPublicKey::Secp256r1Ecdsa { public_key } => {
+                PublicKey::Secp256r1Ecdsa(public_key.to_bytes().to_vec().into())
+            },
         }
     }
 }
@@ -1210,6 +1235,12 @@ impl VerifyInput for SingleKeySignature {
                 }
                 .verify()
             },
+            (PublicKey::Secp256r1Ecdsa(p), Signature::WebAuthn(s)) => {
+                p256_ecdsa::PublicKey::try_from(p.inner())
+                    .context("Failed to parse given public_key bytes as Secp256r1EcdsaPublicKey")?;
+                PartialAuthenticatorAssertionResponse::try_from(s.inner())?;
+                Ok(())
+            },
             _ => bail!("Invalid public key, signature match."),
         }
     }
@@ -1228,22 +1259,28 @@ impl TryFrom<SingleKeySignature> for AccountAuthenticator {
     type Error = anyhow::Error;
 
     fn try_from(value: SingleKeySignature) -> Result<Self, Self::Error> {
-        let key = match value.public_key {
-            PublicKey::Ed25519(p) => {
-                let key = p
-                    .inner()
-                    .try_into()
-                    .context("Failed to parse given public_key bytes as Ed25519PublicKey")?;
-                AnyPublicKey::ed25519(key)
-            },
-            PublicKey::Secp256k1Ecdsa(p) => {
-                let key = p
-                    .inner()
-                    .try_into()
-                    .context("Failed to parse given public_key bytes as Secp256k1EcdsaPublicKey")?;
-                AnyPublicKey::secp256k1_ecdsa(key)
-            },
-        };
+        let key =
+            match value.public_key {
+                PublicKey::Ed25519(p) => {
+                    let key = p
+                        .inner()
+                        .try_into()
+                        .context("Failed to parse given public_key bytes as Ed25519PublicKey")?;
+                    AnyPublicKey::ed25519(key)
+                },
+                PublicKey::Secp256k1Ecdsa(p) => {
+                    let key = p.inner().try_into().context(
+                        "Failed to parse given public_key bytes as Secp256k1EcdsaPublicKey",
+                    )?;
+                    AnyPublicKey::secp256k1_ecdsa(key)
+                },
+                PublicKey::Secp256r1Ecdsa(p) => {
+                    let key = p.inner().try_into().context(
+                        "Failed to parse given public_key bytes as Secp256r1EcdsaPublicKey",
+                    )?;
+                    AnyPublicKey::secp256r1_ecdsa(key)
+                },
+            };
 
         let signature = match value.signature {
             Signature::Ed25519(s) => {
@@ -1260,6 +1297,13 @@ impl TryFrom<SingleKeySignature> for AccountAuthenticator {
                     .context("Failed to parse given public_key bytes as Secp256k1EcdsaSignature")?;
                 AnySignature::secp256k1_ecdsa(signature)
             },
+            Signature::WebAuthn(s) => {
+                let signature = s
+                    .inner()
+                    .try_into()
+                    .context("Failed to parse given signature as WebAuthn assertion")?;
+                AnySignature::webauthn(signature)
+            },
         };
 
         let auth = SingleKeyAuthenticator::new(key, signature);
@@ -1317,6 +1361,12 @@ impl TryFrom<MultiKeySignature> for AccountAuthenticator {
                     )?;
                     AnyPublicKey::secp256k1_ecdsa(key)
                 },
+                PublicKey::Secp256r1Ecdsa(p) => {
+                    let key = p.inner().try_into().context(
+                        "Failed to parse given public_key bytes as Secp256r1EcdsaPublicKey",
+                    )?;
+                    AnyPublicKey::secp256r1_ecdsa(key)
+                },
             };
             public_keys.push(key);
         }
@@ -1337,6 +1387,13 @@ impl TryFrom<MultiKeySignature> for AccountAuthenticator {
                         )?;
                         AnySignature::secp256k1_ecdsa(signature)
                     },
+                    Signature::WebAuthn(s) => {
+                        let signature = s
+                            .inner()
+                            .try_into()
+                            .context("Failed to parse given signature as WebAuthn assertion")?;
+                        AnySignature::webauthn(signature)
+                    },
                 };
             signatures.push((indexed_signature.index, signature));
         }
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{EntryFunctionId, MoveType};
+use crate::{AptosError, EntryFunctionId, MoveType};
 use aptos_types::serde_helper::vec_bytes;
 use move_core_types::{
     identifier::Identifier,
@@ -28,3 +28,35 @@ pub struct ViewFunction {
     #[serde(with = "vec_bytes")]
     pub args: Vec<Vec<u8>>,
 }
+
+/// Result of a single view function call of a batch
+///
+/// Exactly one of `values` and `error` is set, depending on whether the
+/// view function succeeded.
+#[derive(Clone, Debug, Serialize, Deserialize, Object)]
+pub struct ViewFunctionResult {
+    /// Values returned by the view function
+    pub values: Option<Vec<serde_json::Value>>,
+    /// Reason the view function failed
+    pub error: Option<AptosError>,
+}
+
+impl From<Result<Vec<serde_json::Value>, AptosError>> for ViewFunctionResult {
+    fn from(result: Result<Vec<serde_json::Value>, AptosError>) -> Self {
+        match result {
+            Ok(values) => Self {
+                values: Some(values),
+                error: None,
+            },
+            Err(error) => Self {
+                values: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Result of a single view function call of a batch, as returned with BCS
+///
+/// On success, contains the BCS encoded return values of the view function.
+pub type ViewFunctionResultBcs = Result<Vec<Vec<u8>>, AptosError>;
@@ -15,7 +15,7 @@ use anyhow::{bail, Context};
 use aptos_types::{event::EventKey, state_store::state_key::StateKey};
 use move_core_types::identifier::{IdentStr, Identifier};
 use poem_openapi::Object;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::{convert::From, fmt, ops::Deref, str::FromStr};
 
 /// A wrapper of a Move identifier
@@ -151,3 +151,65 @@ impl From<StateKeyWrapper> for StateKey {
         value.0
     }
 }
+
+/// The position of an event in the ledger, serialized as hex encoded bytes. This is used for
+/// cursor based pagination over events that aren't identified by a sequence number.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EventCursor {
+    /// Version of the transaction that emitted the event
+    pub version: u64,
+    /// Index of the event within the events of that transaction
+    pub index: u64,
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = self.version.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+impl FromStr for EventCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, anyhow::Error> {
+        let bytes = hex::decode(s).context("Failed to decode event cursor as hex string")?;
+        if bytes.len() != 16 {
+            bail!(
+                "Event cursor should be 16 bytes long, got {} bytes",
+                bytes.len()
+            );
+        }
+        let (version, index) = bytes.split_at(8);
+        Ok(EventCursor {
+            version: u64::from_be_bytes(version.try_into()?),
+            index: u64::from_be_bytes(index.try_into()?),
+        })
+    }
+}
+
+impl Serialize for EventCursor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventCursor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+impl From<(u64, u64)> for EventCursor {
+    fn from((version, index): (u64, u64)) -> Self {
+        Self { version, index }
+    }
+}
+
+impl From<EventCursor> for (u64, u64) {
+    fn from(value: EventCursor) -> (u64, u64) {
+        (value.version, value.index)
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::fork_executor::ForkExecutor;
 use anyhow::{format_err, Result};
 use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
 use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
@@ -20,7 +21,8 @@ use aptos_types::{
     vm_status::VMStatus,
 };
 use aptos_validator_interface::{
-    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, ForkStateView,
+    RestDebuggerInterface,
 };
 use aptos_vm::{
     data_cache::AsMoveResolver,
@@ -30,7 +32,10 @@ use aptos_vm::{
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::{change_set::VMChangeSet, output::VMOutput, storage::ChangeSetConfigs};
 use move_binary_format::errors::VMResult;
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 pub struct AptosDebugger {
     debugger: Arc<dyn AptosValidatorInterface + Send>,
@@ -51,6 +56,16 @@ impl AptosDebugger {
         )?)))
     }
 
+    /// Forks the network after the transaction at `version` was committed, to execute
+    /// transactions on top of its state. The state read is cached in `cache_dir`, if it is set.
+    pub fn fork(&self, version: Version, cache_dir: Option<PathBuf>) -> Result<ForkExecutor> {
+        Ok(ForkExecutor::new(ForkStateView::new(
+            self.debugger.clone(),
+            version,
+            cache_dir,
+        )?))
+    }
+
     pub fn execute_transactions_at_version(
         &self,
         version: Version,
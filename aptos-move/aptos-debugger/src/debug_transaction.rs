@@ -0,0 +1,65 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{aptos_debugger::AptosDebugger, common::Opts};
+use anyhow::Result;
+use aptos_framework::{BuildOptions, BuiltPackage};
+use aptos_rest_client::Client;
+use aptos_vm::AptosVM;
+use clap::Parser;
+use move_debug_adapter::{DebugServer, SourceIndex};
+use std::{net::TcpListener, path::PathBuf};
+use url::Url;
+
+/// Replays a committed transaction, letting a Debug Adapter Protocol client, e.g. an editor, step
+/// through its Move code.
+#[derive(Parser)]
+pub struct Command {
+    #[clap(flatten)]
+    opts: Opts,
+
+    /// Version of the transaction to replay.
+    #[clap(long)]
+    version: u64,
+
+    /// Move packages to debug the sources of. Their code must match the code on chain.
+    #[clap(long, num_args = 1..)]
+    package_dir: Vec<PathBuf>,
+
+    /// Local port the debugger client connects to.
+    #[clap(long, default_value_t = 4711)]
+    dap_port: u16,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        // The debugger can only follow a single thread.
+        AptosVM::set_concurrency_level_once(1);
+
+        let debugger = if let Some(rest_endpoint) = self.opts.target.rest_endpoint {
+            AptosDebugger::rest_client(Client::new(Url::parse(&rest_endpoint)?))?
+        } else if let Some(db_path) = self.opts.target.db_path {
+            AptosDebugger::db(db_path)?
+        } else {
+            unreachable!("Must provide one target.");
+        };
+
+        let mut sources = SourceIndex::new();
+        for package_dir in self.package_dir {
+            let package = BuiltPackage::build(package_dir, BuildOptions::default())?;
+            sources.add_compiled_package(package.compiled_package())?;
+        }
+
+        let listener = TcpListener::bind(("127.0.0.1", self.dap_port))?;
+        println!(
+            "Waiting for a debugger to connect on port {}",
+            self.dap_port
+        );
+        let debug_server = DebugServer::accept(&listener, sources)?;
+        let result = debugger.execute_past_transactions(self.version, 1).await;
+        debug_server.finish();
+
+        println!("{:#?}", result?);
+        Ok(())
+    }
+}
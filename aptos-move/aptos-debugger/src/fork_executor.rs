@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use aptos_crypto::ed25519::Ed25519PublicKey;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{AccountResource, ChainIdResource, CoinStoreResource},
+    chain_id::ChainId,
+    transaction::{
+        authenticator::AuthenticationKey,
+        signature_verified_transaction::SignatureVerifiedTransaction, SignedTransaction,
+        Transaction, TransactionOutput, TransactionStatus,
+    },
+};
+use aptos_validator_interface::ForkStateView;
+use aptos_vm::{AptosVM, VMExecutor};
+
+/// Executes transactions on top of the state of a network forked at a version, e.g. to try out a
+/// sequence of transactions against mainnet state locally. The writes of the transactions are
+/// kept in the local state only.
+pub struct ForkExecutor {
+    state_view: ForkStateView,
+}
+
+impl ForkExecutor {
+    pub fn new(state_view: ForkStateView) -> Self {
+        Self { state_view }
+    }
+
+    pub fn state_view(&self) -> &ForkStateView {
+        &self.state_view
+    }
+
+    pub fn chain_id(&self) -> Result<ChainId> {
+        self.state_view
+            .get_resource::<ChainIdResource>(AccountAddress::ONE)?
+            .map(|resource| resource.chain_id())
+            .ok_or_else(|| format_err!("Chain ID resource doesn't exist"))
+    }
+
+    /// Returns the sequence number of the next transaction of an account.
+    pub fn sequence_number(&self, address: AccountAddress) -> Result<u64> {
+        self.state_view
+            .get_resource::<AccountResource>(address)?
+            .map(|account| account.sequence_number())
+            .ok_or_else(|| format_err!("Account {} doesn't exist", address))
+    }
+
+    /// Returns the APT balance of an account.
+    pub fn balance(&self, address: AccountAddress) -> Result<u64> {
+        Ok(self
+            .state_view
+            .get_resource::<CoinStoreResource>(address)?
+            .map_or(0, |coin_store| coin_store.coin()))
+    }
+
+    /// Lets the holder of the private key of `public_key` sign transactions on behalf of account
+    /// `address`.
+    pub fn impersonate(
+        &self,
+        address: AccountAddress,
+        public_key: &Ed25519PublicKey,
+    ) -> Result<()> {
+        self.state_view
+            .impersonate(address, AuthenticationKey::ed25519(public_key))
+    }
+
+    /// Executes a transaction and keeps its writes.
+    pub fn execute_transaction(&self, txn: SignedTransaction) -> Result<TransactionOutput> {
+        let mut outputs = self.execute_transactions(vec![txn])?;
+        Ok(outputs.pop().expect("Expected an output per transaction"))
+    }
+
+    /// Executes a block of transactions and keeps the writes of the ones which weren't discarded.
+    pub fn execute_transactions(
+        &self,
+        txns: Vec<SignedTransaction>,
+    ) -> Result<Vec<TransactionOutput>> {
+        let sig_verified_txns: Vec<SignatureVerifiedTransaction> = txns
+            .into_iter()
+            .map(|txn| Transaction::UserTransaction(txn).into())
+            .collect();
+        let outputs = AptosVM::execute_block(&sig_verified_txns, &self.state_view, None)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+        for output in &outputs {
+            if let TransactionStatus::Keep(_) = output.status() {
+                self.state_view.apply_write_set(output.write_set());
+            }
+        }
+        Ok(outputs)
+    }
+}
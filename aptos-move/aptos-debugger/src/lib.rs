@@ -4,5 +4,8 @@
 pub mod aptos_debugger;
 pub mod bcs_txn_decoder;
 pub mod common;
+#[cfg(feature = "debug-adapter")]
+pub mod debug_transaction;
 pub mod execute_past_transactions;
 pub mod execute_pending_block;
+pub mod fork_executor;
@@ -45,3 +45,8 @@
 /// - V1
 ///   - TBA
 pub const LATEST_GAS_FEATURE_VERSION: u64 = 12;
+
+/// Gas feature version from which the BN254 operations are available. It's ahead of the latest gas
+/// feature version until their gas parameters are calibrated, so they aren't in the on-chain gas
+/// schedule, and the BN254 natives abort even if the `BN254_STRUCTURES` feature is enabled.
+pub const BN254_GAS_FEATURE_VERSION: u64 = 13;
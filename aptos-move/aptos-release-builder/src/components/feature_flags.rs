@@ -92,6 +92,8 @@ pub enum FeatureFlag {
     OperatorBeneficiaryChange,
     VMBinaryFormatV7,
     ResourceGroupsChargeAsSizeSum,
+    WebAuthnSignature,
+    Bn254Structures,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -240,6 +242,8 @@ impl From<FeatureFlag> for AptosFeatureFlag {
             FeatureFlag::ResourceGroupsChargeAsSizeSum => {
                 AptosFeatureFlag::RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM
             },
+            FeatureFlag::WebAuthnSignature => AptosFeatureFlag::WEBAUTHN_SIGNATURE,
+            FeatureFlag::Bn254Structures => AptosFeatureFlag::BN254_STRUCTURES,
         }
     }
 }
@@ -311,6 +315,8 @@ impl From<AptosFeatureFlag> for FeatureFlag {
             AptosFeatureFlag::RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM => {
                 FeatureFlag::ResourceGroupsChargeAsSizeSum
             },
+            AptosFeatureFlag::WEBAUTHN_SIGNATURE => FeatureFlag::WebAuthnSignature,
+            AptosFeatureFlag::BN254_STRUCTURES => FeatureFlag::Bn254Structures,
         }
     }
 }
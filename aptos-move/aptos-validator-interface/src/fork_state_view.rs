@@ -0,0 +1,187 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AptosValidatorInterface, DebuggerStateView};
+use anyhow::{anyhow, Result};
+use aptos_crypto::hash::CryptoHash;
+use aptos_state_view::TStateView;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::{AccountResource, ChainIdResource},
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+    transaction::{authenticator::AuthenticationKey, Version},
+    write_set::{TransactionWrite, WriteSet},
+};
+use move_core_types::move_resource::MoveResource;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+/// A view of the state of a network at a pinned version, which local writes are layered on top
+/// of. The state is read through to a remote node or DB, and the values read are cached on disk,
+/// so the network can be forked locally without running a full node.
+pub struct ForkStateView {
+    remote: DebuggerStateView,
+    version: Version,
+    cache_dir: Option<PathBuf>,
+    /// Local writes, where `None` is a deletion
+    writes: RwLock<HashMap<StateKey, Option<StateValue>>>,
+}
+
+impl ForkStateView {
+    /// Forks the network after the transaction at `version` was committed. The values read from
+    /// the network are cached in `cache_dir`, if it is set, and are reused by later forks of the
+    /// same network at the same version.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn new(
+        db: Arc<dyn AptosValidatorInterface + Send>,
+        version: Version,
+        cache_dir: Option<PathBuf>,
+    ) -> Result<Self> {
+        // The debugger state view reads the state before the given version
+        let remote = DebuggerStateView::new(db, version + 1);
+        let cache_dir = match cache_dir {
+            Some(cache_dir) => {
+                // Versions are only unique within a network, which is told apart by its chain ID
+                let chain_id = remote
+                    .get_state_value_bytes(&resource_state_key::<ChainIdResource>(
+                        AccountAddress::ONE,
+                    )?)?
+                    .map(|bytes| bcs::from_bytes::<ChainIdResource>(&bytes))
+                    .transpose()?
+                    .ok_or_else(|| anyhow!("Chain ID doesn't exist at version {}", version))?
+                    .chain_id();
+                let cache_dir = cache_dir
+                    .join(chain_id.id().to_string())
+                    .join(version.to_string());
+                fs::create_dir_all(&cache_dir)?;
+                Some(cache_dir)
+            },
+            None => None,
+        };
+        Ok(Self {
+            remote,
+            version,
+            cache_dir,
+            writes: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// The version of the network the state was forked at
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Applies the writes of a transaction to the local state.
+    pub fn apply_write_set(&self, write_set: &WriteSet) {
+        let mut writes = self.writes.write().unwrap();
+        for (state_key, write_op) in write_set {
+            writes.insert(state_key.clone(), write_op.as_state_value());
+        }
+    }
+
+    /// Sets a value in the local state, or deletes it if `state_value` is `None`.
+    pub fn set_state_value(&self, state_key: StateKey, state_value: Option<StateValue>) {
+        self.writes.write().unwrap().insert(state_key, state_value);
+    }
+
+    /// Loads local writes saved by [`ForkStateView::save_writes`] on top of the local state.
+    pub fn load_writes(&self, path: &Path) -> Result<()> {
+        let saved: Vec<(StateKey, Option<StateValue>)> = bcs::from_bytes(&fs::read(path)?)?;
+        self.writes.write().unwrap().extend(saved);
+        Ok(())
+    }
+
+    /// Saves the local writes to a file, with the metadata of the values, to continue from the
+    /// local state later.
+    pub fn save_writes(&self, path: &Path) -> Result<()> {
+        let writes = self.writes.read().unwrap();
+        let saved: Vec<(&StateKey, &Option<StateValue>)> = writes.iter().collect();
+        fs::write(path, bcs::to_bytes(&saved)?)?;
+        Ok(())
+    }
+
+    /// Reads a resource of an account.
+    pub fn get_resource<T: MoveResource>(&self, address: AccountAddress) -> Result<Option<T>> {
+        let state_key = resource_state_key::<T>(address)?;
+        self.get_state_value_bytes(&state_key)?
+            .map(|bytes| bcs::from_bytes(&bytes).map_err(Into::into))
+            .transpose()
+    }
+
+    /// Lets the holder of the key with authentication key `authentication_key` sign the
+    /// transactions of account `address`, by rotating the key of the account in the local state.
+    pub fn impersonate(
+        &self,
+        address: AccountAddress,
+        authentication_key: AuthenticationKey,
+    ) -> Result<()> {
+        let state_key = resource_state_key::<AccountResource>(address)?;
+        let state_value = self.get_state_value(&state_key)?.ok_or_else(|| {
+            anyhow!(
+                "Account {} doesn't exist at version {}",
+                address,
+                self.version
+            )
+        })?;
+        // Keep the metadata of the resource, e.g. its storage deposit
+        let state_value = state_value.map_bytes(|bytes| {
+            let account = bcs::from_bytes::<AccountResource>(&bytes)?
+                .with_authentication_key(authentication_key.to_vec());
+            Ok(bcs::to_bytes(&account)?.into())
+        })?;
+        self.set_state_value(state_key, Some(state_value));
+        Ok(())
+    }
+
+    fn cache_path(cache_dir: &Path, state_key: &StateKey) -> PathBuf {
+        cache_dir.join(state_key.hash().to_hex())
+    }
+
+    fn get_remote_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        let cache_path = self
+            .cache_dir
+            .as_ref()
+            .map(|cache_dir| Self::cache_path(cache_dir, state_key));
+        if let Some(bytes) = cache_path.as_ref().and_then(|path| fs::read(path).ok()) {
+            return Ok(Some(bcs::from_bytes(&bytes)?));
+        }
+
+        // Only values which exist are cached, as the remote doesn't tell missing values apart
+        // from failed reads
+        let state_value = self.remote.get_state_value(state_key)?;
+        if let (Some(cache_path), Some(state_value)) = (&cache_path, &state_value) {
+            fs::write(cache_path, bcs::to_bytes(state_value)?)?;
+        }
+        Ok(state_value)
+    }
+}
+
+fn resource_state_key<T: MoveResource>(address: AccountAddress) -> Result<StateKey> {
+    Ok(StateKey::access_path(AccessPath::resource_access_path(
+        address,
+        T::struct_tag(),
+    )?))
+}
+
+impl TStateView for ForkStateView {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        if let Some(state_value) = self.writes.read().unwrap().get(state_key) {
+            return Ok(state_value.clone());
+        }
+        self.get_remote_state_value(state_key)
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        Ok(StateStorageUsage::new_untracked())
+    }
+}
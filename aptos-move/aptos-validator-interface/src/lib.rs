@@ -2,10 +2,14 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod fork_state_view;
 mod rest_interface;
 mod storage_interface;
 
-pub use crate::{rest_interface::RestDebuggerInterface, storage_interface::DBDebuggerInterface};
+pub use crate::{
+    fork_state_view::ForkStateView, rest_interface::RestDebuggerInterface,
+    storage_interface::DBDebuggerInterface,
+};
 use anyhow::{anyhow, Result};
 use aptos_state_view::TStateView;
 use aptos_types::{
@@ -114,8 +118,13 @@ pub trait AptosValidatorInterface: Sync {
 }
 
 pub struct DebuggerStateView {
-    query_sender:
-        Mutex<UnboundedSender<(StateKey, Version, std::sync::mpsc::Sender<Option<Vec<u8>>>)>>,
+    query_sender: Mutex<
+        UnboundedSender<(
+            StateKey,
+            Version,
+            std::sync::mpsc::Sender<Option<StateValue>>,
+        )>,
+    >,
     version: Version,
 }
 
@@ -124,13 +133,14 @@ async fn handler_thread<'a>(
     mut thread_receiver: UnboundedReceiver<(
         StateKey,
         Version,
-        std::sync::mpsc::Sender<Option<Vec<u8>>>,
+        std::sync::mpsc::Sender<Option<StateValue>>,
     )>,
 ) {
     const M: usize = 1024 * 1024;
-    let cache = Arc::new(Mutex::new(
-        LruCache::<(StateKey, Version), Option<Vec<u8>>>::new(M),
-    ));
+    let cache = Arc::new(Mutex::new(LruCache::<
+        (StateKey, Version),
+        Option<StateValue>,
+    >::new(M)));
 
     loop {
         let (key, version, sender) =
@@ -151,7 +161,7 @@ async fn handler_thread<'a>(
                     .get_state_value_by_version(&key, version - 1)
                     .await
                     .ok()
-                    .and_then(|v| v.map(|s| s.bytes().to_vec()));
+                    .flatten();
                 cache.lock().unwrap().put((key, version), val.clone());
                 sender.send(val)
             });
@@ -180,8 +190,8 @@ impl DebuggerStateView {
         query_handler_locked
             .send((state_key.clone(), version, tx))
             .unwrap();
-        let bytes_opt = rx.recv()?;
-        Ok(bytes_opt.map(|bytes| StateValue::new_legacy(bytes.into())))
+        // The metadata of the value is kept, e.g. for the storage refunds of the transactions
+        Ok(rx.recv()?)
     }
 }
 
@@ -1162,6 +1162,16 @@ impl AptosVM {
             ));
         }
 
+        if !self.vm_impl.get_features().is_webauthn_signature_enabled()
+            && transaction
+                .authenticator_ref()
+                .all_signers()
+                .iter()
+                .any(|signer| signer.has_webauthn_signature())
+        {
+            return Err(VMStatus::error(StatusCode::FEATURE_UNDER_GATING, None));
+        }
+
         let txn_data = TransactionMetadata::new(transaction);
         self.run_prologue_with_payload(
             session,
@@ -1554,39 +1564,73 @@ impl AptosVM {
         arguments: Vec<Vec<u8>>,
         gas_budget: u64,
     ) -> Result<Vec<Vec<u8>>> {
+        Self::execute_view_function_with_gas_used(
+            state_view, module_id, func_name, type_args, arguments, gas_budget,
+        )
+        .0
+    }
+
+    /// Executes a view function like `execute_view_function`, and also returns the gas it used,
+    /// including when it fails.
+    pub fn execute_view_function_with_gas_used(
+        state_view: &impl StateView,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> (Result<Vec<Vec<u8>>>, u64) {
         let resolver = state_view.as_move_resolver();
         let vm = AptosVM::new(&resolver);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
-        let mut gas_meter =
-            MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
-                vm.vm_impl.get_gas_feature_version(),
-                vm.vm_impl.get_gas_parameters(&log_context)?.vm.clone(),
-                vm.vm_impl.get_storage_gas_parameters(&log_context)?.clone(),
-                gas_budget,
-            )));
+        let mut gas_meter = match vm.make_standard_gas_meter(gas_budget.into(), &log_context) {
+            Ok(gas_meter) => gas_meter,
+            Err(err) => return (Err(err.into()), 0),
+        };
 
-        let mut session = vm.vm_impl.new_session(&resolver, SessionId::Void);
+        let result = vm.execute_view_function_in_vm(
+            &resolver,
+            &module_id,
+            &func_name,
+            type_args,
+            arguments,
+            &mut gas_meter,
+        );
+        let gas_used = gas_budget.saturating_sub(gas_meter.balance().into());
+        (result, gas_used)
+    }
 
-        let func_inst = session.load_function(&module_id, &func_name, &type_args)?;
-        let metadata = vm.vm_impl.extract_module_metadata(&module_id);
+    fn execute_view_function_in_vm(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        module_id: &ModuleId,
+        func_name: &Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_meter: &mut impl AptosGasMeter,
+    ) -> Result<Vec<Vec<u8>>> {
+        let mut session = self.vm_impl.new_session(resolver, SessionId::Void);
+
+        let func_inst = session.load_function(module_id, func_name, &type_args)?;
+        let metadata = self.vm_impl.extract_module_metadata(module_id);
         let arguments = verifier::view_function::validate_view_function(
             &mut session,
             arguments,
             func_name.as_ident_str(),
             &func_inst,
             metadata.as_ref().map(Arc::as_ref),
-            vm.vm_impl
+            self.vm_impl
                 .get_features()
                 .is_enabled(FeatureFlag::STRUCT_CONSTRUCTORS),
         )?;
 
         Ok(session
             .execute_function_bypass_visibility(
-                &module_id,
+                module_id,
                 func_name.as_ident_str(),
                 type_args,
                 arguments,
-                &mut gas_meter,
+                gas_meter,
             )
             .map_err(|err| anyhow!("Failed to execute function: {:?}", err))?
             .return_values
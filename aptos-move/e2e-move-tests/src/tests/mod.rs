@@ -44,3 +44,4 @@ mod transaction_fee;
 mod type_too_large;
 mod vector_numeric_address;
 mod vote;
+mod webauthn;
@@ -0,0 +1,61 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveHarness;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_crypto::{p256_ecdsa, PrivateKey, Uniform};
+use aptos_types::{
+    chain_id::ChainId,
+    on_chain_config::FeatureFlag,
+    transaction::{
+        authenticator::{
+            AccountAuthenticator, AnyPublicKey, AnySignature, AuthenticationKey,
+            SingleKeyAuthenticator,
+        },
+        webauthn::PartialAuthenticatorAssertionResponse,
+        RawTransaction, SignedTransaction, TransactionStatus,
+    },
+};
+use move_core_types::vm_status::StatusCode;
+
+fn webauthn_transaction() -> SignedTransaction {
+    let private_key = p256_ecdsa::PrivateKey::generate_for_testing();
+    let public_key = private_key.public_key();
+    let sender = AuthenticationKey::any_key(AnyPublicKey::secp256r1_ecdsa(public_key.clone()))
+        .account_address();
+
+    let raw_txn = RawTransaction::new(
+        sender,
+        0,
+        aptos_stdlib::aptos_account_set_allow_direct_coin_transfers(true),
+        1_000_000,
+        1,
+        u64::MAX,
+        ChainId::test(),
+    );
+    let assertion =
+        PartialAuthenticatorAssertionResponse::sign_for_testing(&private_key, &raw_txn).unwrap();
+    SignedTransaction::new_single_sender(
+        raw_txn,
+        AccountAuthenticator::single_key(SingleKeyAuthenticator::new(
+            AnyPublicKey::secp256r1_ecdsa(public_key),
+            AnySignature::webauthn(assertion),
+        )),
+    )
+}
+
+#[test]
+fn test_webauthn_signature_feature_gated() {
+    let mut h = MoveHarness::new_with_features(vec![], vec![FeatureFlag::WEBAUTHN_SIGNATURE]);
+    assert_eq!(
+        h.run(webauthn_transaction()),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+
+    // Once enabled, the transaction goes on to the prologue
+    h.enable_features(vec![FeatureFlag::WEBAUTHN_SIGNATURE], vec![]);
+    assert_ne!(
+        h.run(webauthn_transaction()),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
@@ -23,7 +23,11 @@ use aptos_vm_genesis::{
 use move_core_types::language_storage::ModuleId;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
 
 /// Dummy genesis ChangeSet for testing
 pub static GENESIS_CHANGE_SET_HEAD: Lazy<ChangeSet> =
@@ -42,6 +46,23 @@ pub static GENESIS_CHANGE_SET_MAINNET: Lazy<ChangeSet> =
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct FakeDataStore {
     state_data: HashMap<StateKey, StateValue>,
+    /// The state read for keys which aren't set in this data store, e.g. the state of a forked
+    /// network
+    #[serde(skip)]
+    base_state_view: Option<BaseStateView>,
+    /// Keys removed from the base state view
+    #[serde(skip)]
+    removed: HashSet<StateKey>,
+}
+
+/// A state view a [`FakeDataStore`] is layered on top of
+#[derive(Clone)]
+pub struct BaseStateView(pub Arc<dyn TStateView<Key = StateKey> + Send + Sync>);
+
+impl fmt::Debug for BaseStateView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BaseStateView").finish_non_exhaustive()
+    }
 }
 
 impl FakeDataStore {
@@ -52,6 +73,16 @@ impl FakeDataStore {
                 .into_iter()
                 .map(|(k, v)| (k, StateValue::new_legacy(v.into())))
                 .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new `FakeDataStore` on top of the state of `base_state_view`. Writes to the data
+    /// store are kept locally.
+    pub fn new_with_base_state_view(base_state_view: BaseStateView) -> Self {
+        FakeDataStore {
+            base_state_view: Some(base_state_view),
+            ..Default::default()
         }
     }
 
@@ -69,14 +100,14 @@ impl FakeDataStore {
     ///
     /// Returns the previous data if the key was occupied.
     pub fn set_legacy(&mut self, state_key: StateKey, bytes: Vec<u8>) -> Option<StateValue> {
-        self.state_data
-            .insert(state_key, StateValue::new_legacy(bytes.into()))
+        self.set(state_key, StateValue::new_legacy(bytes.into()))
     }
 
     /// Sets a (key, value) pair within this data store.
     ///
     /// Returns the previous data if the key was occupied.
     pub fn set(&mut self, state_key: StateKey, state_value: StateValue) -> Option<StateValue> {
+        self.removed.remove(&state_key);
         self.state_data.insert(state_key, state_value)
     }
 
@@ -84,6 +115,9 @@ impl FakeDataStore {
     ///
     /// Returns the previous data if the key was occupied.
     pub fn remove(&mut self, state_key: &StateKey) -> Option<StateValue> {
+        if self.base_state_view.is_some() {
+            self.removed.insert(state_key.clone());
+        }
         self.state_data.remove(state_key)
     }
 
@@ -117,7 +151,15 @@ impl TStateView for FakeDataStore {
     type Key = StateKey;
 
     fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
-        Ok(self.state_data.get(state_key).cloned())
+        if let Some(state_value) = self.state_data.get(state_key) {
+            return Ok(Some(state_value.clone()));
+        }
+        match &self.base_state_view {
+            Some(base_state_view) if !self.removed.contains(state_key) => {
+                base_state_view.0.get_state_value(state_key)
+            },
+            _ => Ok(None),
+        }
     }
 
     fn get_usage(&self) -> Result<StateStorageUsage> {
@@ -7,7 +7,7 @@
 use crate::{
     account::{Account, AccountData},
     data_store::{
-        FakeDataStore, GENESIS_CHANGE_SET_HEAD, GENESIS_CHANGE_SET_MAINNET,
+        BaseStateView, FakeDataStore, GENESIS_CHANGE_SET_HEAD, GENESIS_CHANGE_SET_MAINNET,
         GENESIS_CHANGE_SET_TESTNET,
     },
     golden_outputs::GoldenOutputs,
@@ -30,8 +30,8 @@ use aptos_state_view::TStateView;
 use aptos_types::{
     access_path::AccessPath,
     account_config::{
-        new_block_event_key, AccountResource, CoinInfoResource, CoinStoreResource, NewBlockEvent,
-        CORE_CODE_ADDRESS,
+        new_block_event_key, AccountResource, ChainIdResource, CoinInfoResource, CoinStoreResource,
+        NewBlockEvent, CORE_CODE_ADDRESS,
     },
     block_metadata::BlockMetadata,
     chain_id::ChainId,
@@ -41,6 +41,7 @@ use aptos_types::{
     },
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
+        authenticator::AuthenticationKey,
         signature_verified_transaction::{
             into_signature_verified_block, SignatureVerifiedTransaction,
         },
@@ -231,6 +232,39 @@ impl FakeExecutor {
         }
     }
 
+    /// Creates an executor on top of the state of `base_state_view`, e.g. the state of a network
+    /// forked at some version. Transactions only write to the data store of the executor.
+    pub fn from_base_state_view(base_state_view: BaseStateView) -> Self {
+        let mut executor = Self::no_genesis();
+        executor.data_store = FakeDataStore::new_with_base_state_view(base_state_view);
+        let resolver = executor.data_store.as_move_resolver();
+        executor.features = Features::fetch_config(&resolver).unwrap_or_default();
+        executor.chain_id = executor
+            .read_resource::<ChainIdResource>(&CORE_CODE_ADDRESS)
+            .expect("chain id must exist in base state view")
+            .chain_id()
+            .id();
+        aptos_vm::aptos_vm::allow_module_bundle_for_test();
+        executor
+    }
+
+    /// Returns an account which can sign the transactions of the existing account at `address`.
+    /// The authentication key of the account is rotated to a fresh key in the data store, which
+    /// allows impersonating accounts of a forked network, whose keys aren't known.
+    pub fn impersonate(&mut self, address: AccountAddress) -> Account {
+        let (privkey, pubkey) = self.rng.generate_ed25519_keypair();
+        let account_resource = self
+            .read_account_resource_at_address(&address)
+            .expect("account must exist in data store")
+            .with_authentication_key(AuthenticationKey::ed25519(&pubkey).to_vec());
+        let account = Account::new_validator(address, privkey, pubkey);
+        self.data_store.set_legacy(
+            StateKey::access_path(account.make_account_access_path()),
+            bcs::to_bytes(&account_resource).expect("account resource must serialize"),
+        );
+        account
+    }
+
     pub fn set_golden_file(&mut self, test_name: &str) {
         // 'test_name' includes ':' in the names, lets re-write these to be '_'s so that these
         // files can persist on windows machines.
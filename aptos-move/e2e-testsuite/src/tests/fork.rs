@@ -0,0 +1,39 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_language_e2e_tests::{
+    common_transactions::peer_to_peer_txn, data_store::BaseStateView, executor::FakeExecutor,
+};
+use aptos_types::transaction::{ExecutionStatus, TransactionStatus};
+use std::sync::Arc;
+
+#[test]
+fn impersonate_account_of_base_state() {
+    let mut base = FakeExecutor::from_head_genesis();
+    let sender = base.create_raw_account_data(1_000_000, 10);
+    let receiver = base.create_raw_account_data(100_000, 10);
+    base.add_account_data(&sender);
+    base.add_account_data(&receiver);
+
+    let mut executor =
+        FakeExecutor::from_base_state_view(BaseStateView(Arc::new(base.data_store().clone())));
+    // The key of the sender is not used, as if it was unknown
+    let impersonated = executor.impersonate(*sender.address());
+
+    let txn = peer_to_peer_txn(&impersonated, receiver.account(), 10, 1_000, 0);
+    let output = executor.execute_and_apply(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+
+    // The writes are only kept in the executor on top of the base state
+    let balance = |executor: &FakeExecutor| {
+        executor
+            .read_coin_store_resource(receiver.account())
+            .expect("receiver balance must exist")
+            .coin()
+    };
+    assert_eq!(balance(&executor), 101_000);
+    assert_eq!(balance(&base), 100_000);
+}
@@ -17,6 +17,7 @@ mod create_account;
 mod data_store;
 mod execution_strategies;
 mod failed_transaction_tests;
+mod fork;
 mod genesis;
 mod genesis_initializations;
 mod invariant_violation;
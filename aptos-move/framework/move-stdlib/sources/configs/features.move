@@ -306,6 +306,17 @@ module std::features {
         is_enabled(OPERATOR_BENEFICIARY_CHANGE)
     }
 
+    /// Whether the generic algebra implementation for BN254 operations are enabled.
+    ///
+    /// Lifetime: transient
+    const BN254_STRUCTURES: u64 = 43;
+
+    public fun get_bn254_structures_feature(): u64 { BN254_STRUCTURES }
+
+    public fun bn254_structures_enabled(): bool acquires Features {
+        is_enabled(BN254_STRUCTURES)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
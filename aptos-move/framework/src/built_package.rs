@@ -249,6 +249,11 @@ impl BuiltPackage {
         self.package_path.as_path()
     }
 
+    /// Returns the compiled package, including the source maps of its modules.
+    pub fn compiled_package(&self) -> &CompiledPackage {
+        &self.package
+    }
+
     pub fn package_artifacts_path(&self) -> PathBuf {
         self.package_path
             .join(CompiledPackageLayout::Root.path())
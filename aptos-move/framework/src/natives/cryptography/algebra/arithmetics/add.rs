@@ -63,6 +63,40 @@ pub fn add_internal(
             mul,
             ALGEBRA_ARK_BLS12_381_FQ12_MUL
         ),
+        Some(Structure::BN254Fr) => {
+            ark_binary_op_internal!(context, args, ark_bn254::Fr, add, ALGEBRA_ARK_BN254_FR_ADD)
+        },
+        Some(Structure::BN254Fq) => {
+            ark_binary_op_internal!(context, args, ark_bn254::Fq, add, ALGEBRA_ARK_BN254_FQ_ADD)
+        },
+        Some(Structure::BN254Fq12) => ark_binary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            add,
+            ALGEBRA_ARK_BN254_FQ12_ADD
+        ),
+        Some(Structure::BN254G1) => ark_binary_op_internal!(
+            context,
+            args,
+            ark_bn254::G1Projective,
+            add,
+            ALGEBRA_ARK_BN254_G1_PROJ_ADD
+        ),
+        Some(Structure::BN254G2) => ark_binary_op_internal!(
+            context,
+            args,
+            ark_bn254::G2Projective,
+            add,
+            ALGEBRA_ARK_BN254_G2_PROJ_ADD
+        ),
+        Some(Structure::BN254Gt) => ark_binary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            mul,
+            ALGEBRA_ARK_BN254_FQ12_MUL
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -63,6 +63,41 @@ pub fn neg_internal(
             let new_handle = store_element!(context, new_element)?;
             Ok(smallvec![Value::u64(new_handle as u64)])
         },
+        Some(Structure::BN254Fr) => {
+            ark_unary_op_internal!(context, args, ark_bn254::Fr, neg, ALGEBRA_ARK_BN254_FR_NEG)
+        },
+        Some(Structure::BN254Fq) => {
+            ark_unary_op_internal!(context, args, ark_bn254::Fq, neg, ALGEBRA_ARK_BN254_FQ_NEG)
+        },
+        Some(Structure::BN254Fq12) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            neg,
+            ALGEBRA_ARK_BN254_FQ12_NEG
+        ),
+        Some(Structure::BN254G1) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::G1Projective,
+            neg,
+            ALGEBRA_ARK_BN254_G1_PROJ_NEG
+        ),
+        Some(Structure::BN254G2) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::G2Projective,
+            neg,
+            ALGEBRA_ARK_BN254_G2_PROJ_NEG
+        ),
+        Some(Structure::BN254Gt) => {
+            let handle = safely_pop_arg!(args, u64) as usize;
+            safe_borrow_element!(context, handle, ark_bn254::Fq12, element_ptr, element);
+            context.charge(ALGEBRA_ARK_BN254_FQ12_INV)?;
+            let new_element = element.inverse().ok_or_else(abort_invariant_violated)?;
+            let new_handle = store_element!(context, new_element)?;
+            Ok(smallvec![Value::u64(new_handle as u64)])
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
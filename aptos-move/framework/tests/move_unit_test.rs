@@ -3,8 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_framework::{extended_checks, path_in_crate};
-use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
-use aptos_types::on_chain_config::{Features, TimedFeaturesBuilder};
+use aptos_gas_schedule::{
+    MiscGasParameters, NativeGasParameters, BN254_GAS_FEATURE_VERSION, LATEST_GAS_FEATURE_VERSION,
+};
+use aptos_types::on_chain_config::{FeatureFlag, Features, TimedFeaturesBuilder};
 use aptos_vm::natives;
 use move_cli::base::test::{run_move_unit_tests, UnitTestResult};
 use move_package::CompilerConfig;
@@ -43,13 +45,17 @@ pub fn aptos_test_natives() -> NativeFunctionTable {
     // By side effect, configure for unit tests
     natives::configure_for_unit_test();
     extended_checks::configure_extended_checks_for_unit_test();
+    // BN254 structures aren't available until their gas parameters are calibrated, but their
+    // natives are still unit tested
+    let mut features = Features::default();
+    features.enable(FeatureFlag::BN254_STRUCTURES);
     // move_stdlib has the testing feature enabled to include debug native functions
     natives::aptos_natives(
-        LATEST_GAS_FEATURE_VERSION,
+        LATEST_GAS_FEATURE_VERSION.max(BN254_GAS_FEATURE_VERSION),
         NativeGasParameters::zeros(),
         MiscGasParameters::zeros(),
         TimedFeaturesBuilder::enable_all().build(),
-        Features::default(),
+        features,
     )
 }
 
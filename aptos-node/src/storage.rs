@@ -86,7 +86,7 @@ pub(crate) fn bootstrap_db(
     let aptos_db = AptosDB::open(
         &node_config.storage.dir(),
         false, /* readonly */
-        node_config.storage.storage_pruner_config,
+        node_config.storage.storage_pruner_config.clone(),
         node_config.storage.rocksdb_configs,
         node_config.storage.enable_indexer,
         node_config.storage.buffered_state_target_items,
@@ -18,7 +18,17 @@ pub struct AdminServiceConfig {
     pub enabled: Option<bool>,
     pub address: String,
     pub port: u16,
-    // TODO(grao): Add auth support if necessary.
+    /// The ways a request can authenticate itself. If empty, requests are not authenticated.
+    pub authentication_configs: Vec<AuthenticationConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthenticationConfig {
+    /// Authenticates requests carrying a passcode in an `Authorization: Bearer <passcode>` header
+    /// (e.g., `curl -H "Authorization: Bearer abc" .../profilez`), whose sha256 is the given hex
+    /// string (e.g., the output of `printf abc | sha256sum`).
+    PasscodeSha256(String),
 }
 
 impl Default for AdminServiceConfig {
@@ -27,6 +37,7 @@ impl Default for AdminServiceConfig {
             enabled: None,
             address: "0.0.0.0".to_string(),
             port: 9102,
+            authentication_configs: vec![],
         }
     }
 }
@@ -39,10 +50,29 @@ impl AdminServiceConfig {
 
 impl ConfigSanitizer for AdminServiceConfig {
     fn sanitize(
-        _node_config: &NodeConfig,
+        node_config: &NodeConfig,
         _node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let admin_service_config = &node_config.admin_service;
+
+        // Verify that the passcode hashes are valid sha256 hex strings
+        for authentication_config in &admin_service_config.authentication_configs {
+            match authentication_config {
+                AuthenticationConfig::PasscodeSha256(passcode_sha256) => {
+                    if passcode_sha256.len() != 64
+                        || !passcode_sha256.chars().all(|c| c.is_ascii_hexdigit())
+                    {
+                        return Err(Error::ConfigSanitizerFailed(
+                            sanitizer_name,
+                            "The passcode sha256 must be a 64 character hex string!".into(),
+                        ));
+                    }
+                },
+            }
+        }
+
         Ok(())
     }
 }
@@ -76,6 +106,24 @@ impl ConfigOptimizer for AdminServiceConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sanitize_invalid_passcode_sha256() {
+        // Create a node config with a passcode hash that isn't a sha256 hex string
+        let node_config = NodeConfig {
+            admin_service: AdminServiceConfig {
+                enabled: Some(true),
+                authentication_configs: vec![AuthenticationConfig::PasscodeSha256("abc".into())],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            AdminServiceConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_optimize_admin_service_disabled() {
         // Create a node config with the admin service disabled
@@ -48,6 +48,14 @@ pub struct ApiConfig {
     /// Enables transaction simulation
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
+    /// Enables the server-sent event stream of committed transactions
+    #[serde(default = "default_disabled")]
+    pub transaction_stream_enabled: bool,
+    /// Interval in milliseconds at which transaction streams poll storage for newly
+    /// committed transactions once they have caught up with the latest ledger version
+    pub transaction_stream_poll_interval_ms: u64,
+    /// Maximum number of transaction streams that can be open at the same time
+    pub max_concurrent_streams: usize,
     /// Maximum number of transactions that can be sent with the Batch submit API
     pub max_submit_transaction_batch_size: usize,
     /// Maximum page size for transaction paginated APIs
@@ -58,10 +66,20 @@ pub struct ApiConfig {
     pub max_account_resources_page_size: u16,
     /// Maximum page size for module paginated APIs
     pub max_account_modules_page_size: u16,
+    /// Maximum page size for table item paginated APIs
+    pub max_table_items_page_size: u16,
+    /// Maximum page size for the account state diff API
+    pub max_state_diff_page_size: u16,
     /// Maximum gas unit limit for view functions
     ///
     /// This limits the execution length of a view function to the given gas used.
     pub max_gas_view_function: u64,
+    /// Maximum number of view functions that can be executed with the batch view API
+    pub max_view_function_batch_size: usize,
+    /// Maximum gas unit limit shared by all the view functions of a batch
+    pub max_gas_view_function_batch: u64,
+    /// Maximum number of state values that can be proven together with the multiproof API
+    pub max_state_values_per_multiproof: usize,
     /// Optional: Maximum number of worker threads for the API.
     ///
     /// If not set, `runtime_worker_multiplier` will multiply times the number of CPU cores on the machine
@@ -82,6 +100,11 @@ pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
 const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000; // We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
+const DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE: usize = 20;
+const DEFAULT_MAX_VIEW_GAS_BATCH: u64 = 2 * DEFAULT_MAX_VIEW_GAS;
+const DEFAULT_MAX_STATE_VALUES_PER_MULTIPROOF: usize = 100;
+const DEFAULT_TRANSACTION_STREAM_POLL_INTERVAL_MS: u64 = 500;
+const DEFAULT_MAX_CONCURRENT_STREAMS: usize = 32;
 
 fn default_enabled() -> bool {
     true
@@ -107,12 +130,20 @@ impl Default for ApiConfig {
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
             transaction_simulation_enabled: default_enabled(),
+            transaction_stream_enabled: default_disabled(),
+            transaction_stream_poll_interval_ms: DEFAULT_TRANSACTION_STREAM_POLL_INTERVAL_MS,
+            max_concurrent_streams: DEFAULT_MAX_CONCURRENT_STREAMS,
             max_submit_transaction_batch_size: DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE,
             max_transactions_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_events_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_account_resources_page_size: DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE,
             max_account_modules_page_size: DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE,
+            max_table_items_page_size: DEFAULT_MAX_PAGE_SIZE,
+            max_state_diff_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_gas_view_function: DEFAULT_MAX_VIEW_GAS,
+            max_view_function_batch_size: DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE,
+            max_gas_view_function_batch: DEFAULT_MAX_VIEW_GAS_BATCH,
+            max_state_values_per_multiproof: DEFAULT_MAX_STATE_VALUES_PER_MULTIPROOF,
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
@@ -165,6 +196,22 @@ impl ConfigSanitizer for ApiConfig {
             ));
         }
 
+        // Verify that transaction streams don't spin on storage, and can be opened
+        if api_config.transaction_stream_enabled {
+            if api_config.transaction_stream_poll_interval_ms == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "transaction_stream_poll_interval_ms must be greater than 0!".into(),
+                ));
+            }
+            if api_config.max_concurrent_streams == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "max_concurrent_streams must be greater than 0!".into(),
+                ));
+            }
+        }
+
         // Sanitize the gas estimation config
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
 
@@ -238,4 +285,46 @@ mod tests {
                 .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_transaction_stream_poll_interval() {
+        // Create a node config with a zero stream poll interval
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                transaction_stream_enabled: true,
+                transaction_stream_poll_interval_ms: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because
+        // the poll interval is invalid.
+        let error =
+            ApiConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_max_concurrent_streams() {
+        // Create a node config that doesn't allow any stream
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                transaction_stream_enabled: true,
+                max_concurrent_streams: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because
+        // the stream limit is invalid.
+        let error =
+            ApiConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
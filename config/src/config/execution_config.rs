@@ -34,7 +34,8 @@ pub struct ExecutionConfig {
     pub paranoid_hot_potato_verification: bool,
     /// Enables enhanced metrics around processed transactions
     pub processed_transactions_detailed_counters: bool,
-    /// Enables filtering of transactions before they are sent to execution
+    /// Enables filtering of transactions before they are sent to execution. The same filter
+    /// rejects the denied transactions when they are submitted to the API or the Mempool.
     pub transaction_filter: Filter,
 }
 
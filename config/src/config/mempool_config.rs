@@ -10,6 +10,7 @@ use aptos_global_constants::DEFAULT_BUCKETS;
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -53,6 +54,18 @@ pub struct MempoolConfig {
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// Persists the transactions accepted by the Mempool to an on-disk journal, which is replayed
+    /// into the Mempool when the node restarts. The journal is written in the background and
+    /// synced to disk in batches, so the most recently accepted transactions can be lost on a
+    /// crash.
+    pub journal_enabled: bool,
+    /// Path of the journal. Relative paths are relative to the data directory of the node.
+    pub journal_path: PathBuf,
+    /// Maximum number of transactions in the journal. Once the journal is half full, it's compacted
+    /// down to the transactions still in the Mempool (on garbage collection) when at least half of
+    /// them are no longer in the Mempool. Transactions aren't journaled while it's full. Must be
+    /// at least the `capacity` of the Mempool.
+    pub journal_capacity: usize,
 }
 
 impl Default for MempoolConfig {
@@ -77,17 +90,49 @@ impl Default for MempoolConfig {
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            journal_enabled: false,
+            journal_path: PathBuf::from("mempool_journal"),
+            journal_capacity: 2_000_000,
+        }
+    }
+}
+
+impl MempoolConfig {
+    /// Returns the path of the journal, resolving relative paths against the given data directory
+    pub fn journal_path(&self, data_dir: &Path) -> PathBuf {
+        if self.journal_path.is_relative() {
+            data_dir.join(&self.journal_path)
+        } else {
+            self.journal_path.clone()
         }
     }
 }
 
 impl ConfigSanitizer for MempoolConfig {
     fn sanitize(
-        _node_config: &NodeConfig,
+        node_config: &NodeConfig,
         _node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
-        Ok(()) // TODO: add reasonable verifications
+        let sanitizer_name = Self::get_sanitizer_name();
+        let mempool_config = &node_config.mempool;
+
+        // Verify that the journal can hold all the transactions of the Mempool
+        if mempool_config.journal_enabled
+            && (mempool_config.journal_capacity == 0
+                || mempool_config.journal_capacity < mempool_config.capacity)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "journal_capacity must be greater than 0 and at least the mempool capacity! \
+                    Journal capacity: {}, mempool capacity: {}",
+                    mempool_config.journal_capacity, mempool_config.capacity
+                ),
+            ));
+        }
+
+        Ok(()) // TODO: add more reasonable verifications
     }
 }
 
@@ -253,4 +298,68 @@ mod tests {
             default_mempool_config.shared_mempool_tick_interval_ms
         );
     }
+
+    #[test]
+    fn test_sanitize_journal_capacity() {
+        // Create a node config with the journal enabled and a zero capacity
+        let node_config = NodeConfig {
+            mempool: MempoolConfig {
+                journal_enabled: true,
+                journal_capacity: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails
+        let error = MempoolConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a node config with a journal smaller than the mempool
+        let node_config = NodeConfig {
+            mempool: MempoolConfig {
+                journal_enabled: true,
+                capacity: 1_000,
+                journal_capacity: 999,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails
+        let error = MempoolConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the config sanitizer passes with the default capacities
+        let node_config = NodeConfig {
+            mempool: MempoolConfig {
+                journal_enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        MempoolConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+    }
+
+    #[test]
+    fn test_journal_path() {
+        let data_dir = Path::new("/opt/aptos/data");
+
+        // Verify that relative paths are resolved against the data directory
+        let mempool_config = MempoolConfig::default();
+        assert_eq!(
+            mempool_config.journal_path(data_dir),
+            data_dir.join("mempool_journal")
+        );
+
+        // Verify that absolute paths are used as is
+        let mempool_config = MempoolConfig {
+            journal_path: PathBuf::from("/tmp/journal"),
+            ..Default::default()
+        };
+        assert_eq!(
+            mempool_config.journal_path(data_dir),
+            PathBuf::from("/tmp/journal")
+        );
+    }
 }
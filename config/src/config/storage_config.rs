@@ -8,7 +8,7 @@ use crate::{
 };
 use anyhow::{ensure, Result};
 use aptos_logger::warn;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use arr_macro::arr;
 use number_range::NumberRangeOptions;
 use serde::{Deserialize, Serialize};
@@ -182,6 +182,7 @@ pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
         prune_window: 0,
         batch_size: 0,
         user_pruning_window_offset: 0,
+        retained_accounts: Vec::new(),
     },
     state_merkle_pruner_config: StateMerklePrunerConfig {
         enable: false,
@@ -195,7 +196,7 @@ pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
     },
 };
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct LedgerPrunerConfig {
     /// Boolean to enable/disable the ledger pruner. The ledger pruner is responsible for pruning
@@ -211,6 +212,15 @@ pub struct LedgerPrunerConfig {
     pub batch_size: usize,
     /// The offset for user pruning window to adjust
     pub user_pruning_window_offset: u64,
+    /// Accounts whose history is kept regardless of the prune window: the transactions they
+    /// sent, the ones writing state under their address or emitting events to the event handles
+    /// they created (e.g. transfers to them), with the infos, write sets and events of those
+    /// transactions, and every version of the state under their address. The history is only
+    /// kept from when an account is added here on, the history pruned before isn't recovered.
+    /// Table items and objects are kept in the write sets of the retained transactions, but not
+    /// in the state. The transaction accumulator isn't pruned while this is not empty, as the
+    /// proofs of the retained transactions depend on it.
+    pub retained_accounts: Vec<AccountAddress>,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -249,7 +259,7 @@ impl From<EpochSnapshotPrunerConfig> for StateMerklePrunerConfig {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct PrunerConfig {
     pub ledger_pruner_config: LedgerPrunerConfig,
@@ -266,6 +276,7 @@ impl Default for LedgerPrunerConfig {
             prune_window: 150_000_000,
             batch_size: 5_000,
             user_pruning_window_offset: 200_000,
+            retained_accounts: Vec::new(),
         }
     }
 }
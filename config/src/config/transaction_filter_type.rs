@@ -4,12 +4,23 @@
 use aptos_crypto::HashValue;
 use aptos_types::{
     account_address::AccountAddress,
-    transaction::{SignedTransaction, TransactionPayload},
+    transaction::{
+        authenticator::{AccountAuthenticator, AnyPublicKey},
+        SignedTransaction, TransactionPayload,
+    },
 };
 use serde::{Deserialize, Serialize};
 
+/// A condition on a transaction, and optionally on the block proposing it.
+///
+/// Block matchers (`BlockId`, `BlockTimeStampGreaterThan` and `BlockTimeStampLessThan`) are
+/// undecided when the filter is applied outside of consensus, e.g., at mempool admission or API
+/// submission, as the transaction isn't in a block yet. Undecided matchers propagate through
+/// `And`, `Or` and `Not` with three-valued logic. As consensus may allow the transaction, a rule
+/// whose matcher is undecided allows it if it's an `Allow` rule, and is skipped if it's a `Deny`
+/// rule, so that a transaction is only rejected early if consensus would reject it too.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-enum Matcher {
+pub enum Matcher {
     All,
     BlockId(HashValue),
     BlockTimeStampGreaterThan(u64),
@@ -18,15 +29,63 @@ enum Matcher {
     Sender(AccountAddress),
     ModuleAddress(AccountAddress),
     EntryFunction(AccountAddress, String, String),
+    /// Matches transactions whose payload is a script
+    Script,
+    /// Matches multisig transactions executed on behalf of the given multisig account
+    MultisigAddress(AccountAddress),
+    /// Matches fee payer transactions whose gas is paid by the given account
+    FeePayer(AccountAddress),
+    /// Matches multi-agent and fee payer transactions with the given secondary signer
+    SecondarySigner(AccountAddress),
+    GasUnitPriceGreaterThan(u64),
+    GasUnitPriceLessThan(u64),
+    /// Matches transactions signed by the given key, whether by the sender, a secondary signer
+    /// or the fee payer, and whether alone or as part of a multi-key
+    PublicKey(AnyPublicKey),
+    /// Matches if all of the matchers match
+    And(Vec<Matcher>),
+    /// Matches if any of the matchers matches
+    Or(Vec<Matcher>),
+    /// Matches if the matcher doesn't match
+    Not(Box<Matcher>),
 }
 
 impl Matcher {
-    fn matches(&self, block_id: HashValue, timestamp: u64, txn: &SignedTransaction) -> bool {
-        match self {
+    /// Returns whether the transaction matches, or None if this can't be decided because the
+    /// matcher depends on the block and there is none.
+    fn matches(&self, block: Option<(HashValue, u64)>, txn: &SignedTransaction) -> Option<bool> {
+        let matches = match self {
+            Matcher::BlockId(id) => return block.map(|(block_id, _)| block_id == *id),
+            Matcher::BlockTimeStampGreaterThan(ts) => {
+                return block.map(|(_, timestamp)| timestamp > *ts)
+            },
+            Matcher::BlockTimeStampLessThan(ts) => {
+                return block.map(|(_, timestamp)| timestamp < *ts)
+            },
+            Matcher::And(matchers) => {
+                let mut result = Some(true);
+                for matcher in matchers {
+                    match matcher.matches(block, txn) {
+                        Some(false) => return Some(false),
+                        None => result = None,
+                        Some(true) => {},
+                    }
+                }
+                return result;
+            },
+            Matcher::Or(matchers) => {
+                let mut result = Some(false);
+                for matcher in matchers {
+                    match matcher.matches(block, txn) {
+                        Some(true) => return Some(true),
+                        None => result = None,
+                        Some(false) => {},
+                    }
+                }
+                return result;
+            },
+            Matcher::Not(matcher) => return matcher.matches(block, txn).map(|matches| !matches),
             Matcher::All => true,
-            Matcher::BlockId(id) => block_id == *id,
-            Matcher::BlockTimeStampGreaterThan(ts) => timestamp > *ts,
-            Matcher::BlockTimeStampLessThan(ts) => timestamp < *ts,
             Matcher::TransactionId(id) => txn.clone().committed_hash() == *id,
             Matcher::Sender(sender) => txn.sender() == *sender,
             Matcher::ModuleAddress(address) => match txn.payload() {
@@ -43,7 +102,56 @@ impl Matcher {
                 },
                 _ => false,
             },
-        }
+            Matcher::Script => matches!(txn.payload(), TransactionPayload::Script(_)),
+            Matcher::MultisigAddress(address) => match txn.payload() {
+                TransactionPayload::Multisig(multisig) => multisig.multisig_address == *address,
+                _ => false,
+            },
+            Matcher::FeePayer(address) => {
+                txn.authenticator_ref().fee_payer_address() == Some(*address)
+            },
+            Matcher::SecondarySigner(address) => txn
+                .authenticator_ref()
+                .secondary_signer_addresses()
+                .contains(address),
+            Matcher::GasUnitPriceGreaterThan(price) => txn.gas_unit_price() > *price,
+            Matcher::GasUnitPriceLessThan(price) => txn.gas_unit_price() < *price,
+            Matcher::PublicKey(public_key) => txn
+                .authenticator_ref()
+                .all_signers()
+                .iter()
+                .any(|signer| signed_by(signer, public_key)),
+        };
+        Some(matches)
+    }
+}
+
+/// Returns true if one of the public keys of the authenticator is the given key
+fn signed_by(authenticator: &AccountAuthenticator, public_key: &AnyPublicKey) -> bool {
+    match authenticator {
+        AccountAuthenticator::Ed25519 {
+            public_key: ed25519_public_key,
+            ..
+        } => match public_key {
+            AnyPublicKey::Ed25519 { public_key } => public_key == ed25519_public_key,
+            _ => false,
+        },
+        AccountAuthenticator::MultiEd25519 {
+            public_key: multi_ed25519_public_key,
+            ..
+        } => match public_key {
+            AnyPublicKey::Ed25519 { public_key } => {
+                multi_ed25519_public_key.public_keys().contains(public_key)
+            },
+            _ => false,
+        },
+        AccountAuthenticator::SingleKey { authenticator } => {
+            authenticator.public_key() == public_key
+        },
+        AccountAuthenticator::MultiKey { authenticator } => authenticator
+            .public_keys()
+            .public_keys()
+            .contains(public_key),
     }
 }
 
@@ -60,17 +168,17 @@ enum EvalResult {
 }
 
 impl Rule {
-    fn eval(&self, block_id: HashValue, timestamp: u64, txn: &SignedTransaction) -> EvalResult {
+    fn eval(&self, block: Option<(HashValue, u64)>, txn: &SignedTransaction) -> EvalResult {
         match self {
             Rule::Allow(matcher) => {
-                if matcher.matches(block_id, timestamp, txn) {
+                if matcher.matches(block, txn) != Some(false) {
                     EvalResult::Allow
                 } else {
                     EvalResult::NoMatch
                 }
             },
             Rule::Deny(matcher) => {
-                if matcher.matches(block_id, timestamp, txn) {
+                if matcher.matches(block, txn) == Some(true) {
                     EvalResult::Deny
                 } else {
                     EvalResult::NoMatch
@@ -104,6 +212,19 @@ impl Rule {
 /// This filter allows transactions from the sender with address f8871acf2c827d40e23b71f6ff2b9accef8dbb17709b88bd9eb95e6bb748c25a or
 /// from the module with address 0000000000000000000000000000000000000000000000000000000000000001 or entry functions
 /// test::check and test::new from the module 0000000000000000000000000000000000000000000000000000000000000001. All other transactions are denied.
+///
+/// Matchers can be combined with `And`, `Or` and `Not`. For example, the following rules deny
+/// scripts, and transactions with a gas unit price below 100 that aren't paid for by 0x1:
+///             rules:
+///                 - Deny: Script
+///                 - Deny:
+///                     And:
+///                         - GasUnitPriceLessThan: 100
+///                         - Not:
+///                             FeePayer: "0000000000000000000000000000000000000000000000000000000000000001"
+///
+/// The same filter can be applied by consensus at block proposal, and by mempool and the API when
+/// transactions are submitted, so that denied transactions are rejected before reaching a block.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Filter {
     rules: Vec<Rule>,
@@ -118,6 +239,16 @@ impl Filter {
         self.rules.is_empty()
     }
 
+    pub fn add_allow(mut self, matcher: Matcher) -> Self {
+        self.rules.push(Rule::Allow(matcher));
+        self
+    }
+
+    pub fn add_deny(mut self, matcher: Matcher) -> Self {
+        self.rules.push(Rule::Deny(matcher));
+        self
+    }
+
     pub fn add_deny_all(mut self) -> Self {
         self.rules.push(Rule::Deny(Matcher::All));
         self
@@ -188,11 +319,23 @@ impl Filter {
         self
     }
 
+    /// Returns true if the transaction is allowed in the given block
     pub fn allows(&self, block_id: HashValue, timestamp: u64, txn: &SignedTransaction) -> bool {
+        self.allows_internal(Some((block_id, timestamp)), txn)
+    }
+
+    /// Returns true if the transaction may be allowed once it is in a block, i.e., when it is
+    /// submitted to mempool or to the API. Rules depending on the block are undecided, which
+    /// allows the transaction for an `Allow` rule, and skips a `Deny` rule.
+    pub fn allows_transaction(&self, txn: &SignedTransaction) -> bool {
+        self.allows_internal(None, txn)
+    }
+
+    fn allows_internal(&self, block: Option<(HashValue, u64)>, txn: &SignedTransaction) -> bool {
         for rule in &self.rules {
             // Rules are evaluated in the order and the first rule that matches is used. If no rule
             // matches, the transaction is allowed.
-            match rule.eval(block_id, timestamp, txn) {
+            match rule.eval(block, txn) {
                 EvalResult::Allow => return true,
                 EvalResult::Deny => return false,
                 EvalResult::NoMatch => continue,
@@ -201,3 +344,259 @@ impl Filter {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{
+        ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+        PrivateKey, SigningKey, Uniform,
+    };
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{
+            authenticator::{AnySignature, SingleKeyAuthenticator},
+            EntryFunction, Multisig, RawTransaction, Script,
+        },
+    };
+
+    fn entry_function_payload() -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            str::parse("0x1::test::add").unwrap(),
+            str::parse("add").unwrap(),
+            vec![],
+            vec![],
+        ))
+    }
+
+    fn raw_transaction(
+        sender: AccountAddress,
+        payload: TransactionPayload,
+        gas_unit_price: u64,
+    ) -> RawTransaction {
+        RawTransaction::new(sender, 0, payload, 0, gas_unit_price, 0, ChainId::new(10))
+    }
+
+    fn account_authenticator(
+        private_key: &Ed25519PrivateKey,
+        raw_txn: &RawTransaction,
+    ) -> AccountAuthenticator {
+        AccountAuthenticator::ed25519(private_key.public_key(), private_key.sign(raw_txn).unwrap())
+    }
+
+    fn create_signed_transaction(
+        payload: TransactionPayload,
+        gas_unit_price: u64,
+    ) -> (SignedTransaction, Ed25519PublicKey) {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = raw_transaction(AccountAddress::random(), payload, gas_unit_price);
+        let signature = private_key.sign(&raw_txn).unwrap();
+        (
+            SignedTransaction::new(raw_txn, private_key.public_key(), signature),
+            private_key.public_key(),
+        )
+    }
+
+    fn create_fee_payer_transaction(
+        secondary_signer: AccountAddress,
+        fee_payer: AccountAddress,
+    ) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = raw_transaction(AccountAddress::random(), entry_function_payload(), 100);
+        SignedTransaction::new_fee_payer(
+            raw_txn.clone(),
+            account_authenticator(&private_key, &raw_txn),
+            vec![secondary_signer],
+            vec![account_authenticator(&private_key, &raw_txn)],
+            fee_payer,
+            account_authenticator(&private_key, &raw_txn),
+        )
+    }
+
+    #[test]
+    fn test_payload_matchers() {
+        let (script_txn, _) = create_signed_transaction(
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            100,
+        );
+        let multisig_address = AccountAddress::random();
+        let (multisig_txn, _) = create_signed_transaction(
+            TransactionPayload::Multisig(Multisig {
+                multisig_address,
+                transaction_payload: None,
+            }),
+            100,
+        );
+        let (entry_function_txn, _) = create_signed_transaction(entry_function_payload(), 100);
+
+        let filter = Filter::empty()
+            .add_deny(Matcher::Script)
+            .add_deny(Matcher::MultisigAddress(multisig_address));
+        assert!(!filter.allows_transaction(&script_txn));
+        assert!(!filter.allows_transaction(&multisig_txn));
+        assert!(filter.allows_transaction(&entry_function_txn));
+    }
+
+    #[test]
+    fn test_signer_matchers() {
+        let secondary_signer = AccountAddress::random();
+        let fee_payer = AccountAddress::random();
+        let txn = create_fee_payer_transaction(secondary_signer, fee_payer);
+        let (other_txn, _) = create_signed_transaction(entry_function_payload(), 100);
+
+        let filter = Filter::empty().add_deny(Matcher::FeePayer(fee_payer));
+        assert!(!filter.allows_transaction(&txn));
+        assert!(filter.allows_transaction(&other_txn));
+
+        let filter = Filter::empty().add_deny(Matcher::SecondarySigner(secondary_signer));
+        assert!(!filter.allows_transaction(&txn));
+        assert!(filter.allows_transaction(&other_txn));
+        let filter = Filter::empty().add_deny(Matcher::SecondarySigner(fee_payer));
+        assert!(filter.allows_transaction(&txn));
+    }
+
+    #[test]
+    fn test_public_key_matcher() {
+        let (txn, public_key) = create_signed_transaction(entry_function_payload(), 100);
+        let (other_txn, _) = create_signed_transaction(entry_function_payload(), 100);
+        let filter = Filter::empty().add_deny(Matcher::PublicKey(AnyPublicKey::ed25519(
+            public_key.clone(),
+        )));
+        assert!(!filter.allows_transaction(&txn));
+        assert!(filter.allows_transaction(&other_txn));
+
+        // The same key signing through a single key authenticator also matches
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = raw_transaction(AccountAddress::random(), entry_function_payload(), 100);
+        let single_key_txn = SignedTransaction::new_single_sender(
+            raw_txn.clone(),
+            AccountAuthenticator::single_key(SingleKeyAuthenticator::new(
+                AnyPublicKey::ed25519(private_key.public_key()),
+                AnySignature::ed25519(private_key.sign(&raw_txn).unwrap()),
+            )),
+        );
+        let filter = Filter::empty().add_deny(Matcher::PublicKey(AnyPublicKey::ed25519(
+            private_key.public_key(),
+        )));
+        assert!(!filter.allows_transaction(&single_key_txn));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let fee_payer = AccountAddress::random();
+        let sponsored_txn = create_fee_payer_transaction(AccountAddress::random(), fee_payer);
+        let (cheap_txn, _) = create_signed_transaction(entry_function_payload(), 10);
+        let (expensive_txn, _) = create_signed_transaction(entry_function_payload(), 1000);
+
+        // Deny transactions outside of the gas unit price range [100, 500] unless sponsored
+        let filter = Filter::empty().add_deny(Matcher::And(vec![
+            Matcher::Or(vec![
+                Matcher::GasUnitPriceLessThan(100),
+                Matcher::GasUnitPriceGreaterThan(500),
+            ]),
+            Matcher::Not(Box::new(Matcher::FeePayer(fee_payer))),
+        ]));
+        assert!(!filter.allows_transaction(&cheap_txn));
+        assert!(!filter.allows_transaction(&expensive_txn));
+        assert!(filter.allows_transaction(&sponsored_txn));
+    }
+
+    #[test]
+    fn test_block_matchers_outside_of_consensus() {
+        let sender = AccountAddress::random();
+        let (txn, _) = create_signed_transaction(entry_function_payload(), 100);
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = raw_transaction(sender, entry_function_payload(), 100);
+        let signature = private_key.sign(&raw_txn).unwrap();
+        let sender_txn = SignedTransaction::new(raw_txn, private_key.public_key(), signature);
+
+        // Only transactions in blocks after the timestamp are allowed. Outside of consensus, the
+        // transaction may still end up in such a block, so it's allowed.
+        let filter = Filter::empty()
+            .add_allow_block_timestamp_greater_than(1000)
+            .add_deny_all();
+        assert!(filter.allows_transaction(&txn));
+        assert!(filter.allows(HashValue::random(), 1001, &txn));
+        assert!(!filter.allows(HashValue::random(), 999, &txn));
+
+        // An undecided deny rule is skipped, so the following rules decide
+        let block_id = HashValue::random();
+        let filter = Filter::empty()
+            .add_deny_block_id(block_id)
+            .add_allow_sender(sender)
+            .add_deny_all();
+        assert!(filter.allows_transaction(&sender_txn));
+        assert!(!filter.allows_transaction(&txn));
+        assert!(!filter.allows(block_id, 0, &sender_txn));
+
+        // An allow rule that is decided not to match is skipped, even with a block matcher
+        let filter = Filter::empty()
+            .add_allow(Matcher::And(vec![
+                Matcher::BlockTimeStampGreaterThan(1000),
+                Matcher::Sender(sender),
+            ]))
+            .add_deny_all();
+        assert!(filter.allows_transaction(&sender_txn));
+        assert!(!filter.allows_transaction(&txn));
+    }
+
+    #[test]
+    fn test_block_matchers_outside_of_block() {
+        let (txn, _) = create_signed_transaction(entry_function_payload(), 100);
+        let block_id = HashValue::random();
+
+        let filter = Filter::empty().add_deny_block_id(block_id);
+        assert!(!filter.allows(block_id, 0, &txn));
+        assert!(filter.allows_transaction(&txn));
+
+        // Only denies transactions of other blocks once in consensus
+        let filter = Filter::empty().add_deny(Matcher::Not(Box::new(Matcher::BlockId(block_id))));
+        assert!(!filter.allows(HashValue::random(), 0, &txn));
+        assert!(filter.allows(block_id, 0, &txn));
+        assert!(filter.allows_transaction(&txn));
+
+        // Undecided block matchers only decide combinations when the other matchers do
+        let sender = txn.sender();
+        let filter = Filter::empty().add_deny(Matcher::And(vec![
+            Matcher::Not(Box::new(Matcher::BlockTimeStampLessThan(100))),
+            Matcher::Sender(sender),
+        ]));
+        assert!(filter.allows_transaction(&txn));
+        assert!(!filter.allows(block_id, 100, &txn));
+        let filter = Filter::empty().add_deny(Matcher::Or(vec![
+            Matcher::BlockTimeStampLessThan(100),
+            Matcher::Sender(sender),
+        ]));
+        assert!(!filter.allows_transaction(&txn));
+        let filter = Filter::empty().add_deny(Matcher::And(vec![
+            Matcher::BlockTimeStampLessThan(100),
+            Matcher::Sender(AccountAddress::random()),
+        ]));
+        assert!(filter.allows_transaction(&txn));
+    }
+
+    #[test]
+    fn test_deserialize_composed_filter() {
+        let filter: Filter = serde_yaml::from_str(
+            r#"
+            rules:
+                - Deny: Script
+                - Deny:
+                    And:
+                        - GasUnitPriceLessThan: 100
+                        - Not:
+                            FeePayer: "0000000000000000000000000000000000000000000000000000000000000001"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            filter,
+            Filter::empty()
+                .add_deny(Matcher::Script)
+                .add_deny(Matcher::And(vec![
+                    Matcher::GasUnitPriceLessThan(100),
+                    Matcher::Not(Box::new(Matcher::FeePayer(AccountAddress::ONE))),
+                ]))
+        );
+    }
+}
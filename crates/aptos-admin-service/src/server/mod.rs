@@ -2,17 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::server::utils::reply_with_status;
-use aptos_config::config::NodeConfig;
+use aptos_config::config::{AuthenticationConfig, NodeConfig};
 use aptos_consensus::{
-    persistent_liveness_storage::StorageWriteProxy, quorum_store::quorum_store_db::QuorumStoreDB,
+    persistent_liveness_storage::{PersistentLivenessStorage, StorageWriteProxy},
+    quorum_store::quorum_store_db::QuorumStoreDB,
 };
 use aptos_infallible::RwLock;
 use aptos_logger::info;
 use aptos_storage_interface::DbReaderWriter;
 use hyper::{
+    header::AUTHORIZATION,
     service::{make_service_fn, service_fn},
     Body, Request, Response, Server, StatusCode,
 };
+use sha2::{Digest, Sha256};
 use std::{
     convert::Infallible,
     net::{SocketAddr, ToSocketAddrs},
@@ -23,6 +26,9 @@ use tokio::runtime::Runtime;
 mod consensus;
 #[cfg(target_os = "linux")]
 mod profiling;
+mod storage;
+#[cfg(test)]
+mod tests;
 #[cfg(target_os = "linux")]
 mod thread_dump;
 mod utils;
@@ -84,7 +90,11 @@ impl AdminService {
 
         // TODO(grao): Consider support enabling the service through an authenticated request.
         let enabled = node_config.admin_service.enabled.unwrap_or(false);
-        admin_service.start(address, enabled);
+        admin_service.start(
+            address,
+            enabled,
+            node_config.admin_service.authentication_configs.clone(),
+        );
 
         admin_service
     }
@@ -102,14 +112,26 @@ impl AdminService {
             .set_consensus_dbs(consensus_db, quorum_store_db)
     }
 
-    fn start(&self, address: SocketAddr, enabled: bool) {
+    fn start(
+        &self,
+        address: SocketAddr,
+        enabled: bool,
+        authentication_configs: Vec<AuthenticationConfig>,
+    ) {
         let context = self.context.clone();
+        let authentication_configs = Arc::new(authentication_configs);
         self.runtime.spawn(async move {
             let make_service = make_service_fn(move |_conn| {
                 let context = context.clone();
+                let authentication_configs = authentication_configs.clone();
                 async move {
                     Ok::<_, Infallible>(service_fn(move |req| {
-                        Self::serve_requests(context.clone(), req, enabled)
+                        Self::serve_requests(
+                            context.clone(),
+                            req,
+                            enabled,
+                            authentication_configs.clone(),
+                        )
                     }))
                 }
             });
@@ -124,6 +146,7 @@ impl AdminService {
         context: Arc<Context>,
         req: Request<Body>,
         enabled: bool,
+        authentication_configs: Arc<Vec<AuthenticationConfig>>,
     ) -> hyper::Result<Response<Body>> {
         if !enabled {
             return Ok(reply_with_status(
@@ -131,6 +154,14 @@ impl AdminService {
                 "AdminService is not enabled.",
             ));
         }
+
+        if !Self::is_authenticated(&req, &authentication_configs) {
+            return Ok(reply_with_status(
+                StatusCode::UNAUTHORIZED,
+                "Request is not authenticated.",
+            ));
+        }
+
         match (req.method().clone(), req.uri().path()) {
             #[cfg(target_os = "linux")]
             (hyper::Method::GET, "/profilez") => profiling::handle_cpu_profiling_request(req).await,
@@ -175,7 +206,62 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::POST, "/debug/storage/checkpoint") => {
+                // Checkpoints are written to arbitrary paths on the node, so they're only
+                // allowed for authenticated requests.
+                if authentication_configs.is_empty() {
+                    return Ok(reply_with_status(
+                        StatusCode::FORBIDDEN,
+                        "Checkpoints require authentication to be configured.",
+                    ));
+                }
+                let aptos_db = context.aptos_db.read().clone();
+                let consensus_db = context
+                    .consensus_db
+                    .read()
+                    .clone()
+                    .map(|db| db as Arc<dyn PersistentLivenessStorage>);
+                if let Some(aptos_db) = aptos_db {
+                    storage::handle_checkpoint_request(req, aptos_db.writer.clone(), consensus_db)
+                        .await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "AptosDB is not available.",
+                    ))
+                }
+            },
             _ => Ok(reply_with_status(StatusCode::NOT_FOUND, "Not found.")),
         }
     }
+
+    /// Returns true if no authentication is configured, or if the request satisfies any of the
+    /// configured authentication methods.
+    fn is_authenticated(
+        req: &Request<Body>,
+        authentication_configs: &[AuthenticationConfig],
+    ) -> bool {
+        if authentication_configs.is_empty() {
+            return true;
+        }
+
+        // The passcode is sent in a header rather than the query string, so that it doesn't end
+        // up in access logs and shell histories.
+        let passcode = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        authentication_configs
+            .iter()
+            .any(|authentication_config| match authentication_config {
+                AuthenticationConfig::PasscodeSha256(passcode_sha256) => {
+                    passcode.map_or(false, |passcode| {
+                        hex::encode(Sha256::digest(passcode.as_bytes()))
+                            .eq_ignore_ascii_case(passcode_sha256)
+                    })
+                },
+            })
+    }
 }
@@ -0,0 +1,130 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{reply_with, reply_with_status};
+use aptos_consensus::persistent_liveness_storage::PersistentLivenessStorage;
+use aptos_logger::info;
+use aptos_storage_interface::DbWriter;
+use aptos_types::waypoint::Waypoint;
+use futures::{channel::mpsc, StreamExt};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+/// Creates a checkpoint of the AptosDB (and the consensus db, if available) at the `path` query
+/// parameter, which must not exist yet. The progress is streamed in the response body as the
+/// checkpoint is made, and ends with either the checkpointed ledger info or the failure, in which
+/// case the partial checkpoint is removed.
+pub async fn handle_checkpoint_request(
+    req: Request<Body>,
+    aptos_db: Arc<dyn DbWriter>,
+    consensus_db: Option<Arc<dyn PersistentLivenessStorage>>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let checkpoint_path: PathBuf = match query_pairs.get("path") {
+        Some(val) => PathBuf::from(val.as_ref()),
+        None => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Missing checkpoint path.",
+            ))
+        },
+    };
+
+    // Failed checkpoints are removed, so the checkpoint path is created here rather than reused,
+    // to not risk deleting anything that isn't ours.
+    if let Err(e) = std::fs::create_dir(&checkpoint_path) {
+        let status_code = if e.kind() == ErrorKind::AlreadyExists {
+            StatusCode::CONFLICT
+        } else {
+            StatusCode::BAD_REQUEST
+        };
+        return Ok(reply_with_status(
+            status_code,
+            format!("Failed to create checkpoint path {checkpoint_path:?}: {e}"),
+        ));
+    }
+
+    info!("Creating db checkpoint at {checkpoint_path:?}.");
+
+    let (sender, receiver) = mpsc::unbounded();
+    tokio::task::spawn_blocking(move || {
+        // The client may have gone away, which doesn't stop the checkpoint.
+        let progress = |msg: &str| {
+            let _ = sender.unbounded_send(format!("{msg}\n"));
+        };
+        match create_checkpoint(aptos_db.as_ref(), consensus_db, &checkpoint_path, &progress) {
+            Ok(()) => info!("Finished creating db checkpoint."),
+            Err(e) => {
+                info!("Failed to create db checkpoint: {e:?}");
+                progress(&format!("Failed to create checkpoint: {e}"));
+                match std::fs::remove_dir_all(&checkpoint_path) {
+                    Ok(()) => progress(&format!(
+                        "Removed partial checkpoint at {checkpoint_path:?}."
+                    )),
+                    Err(e) => progress(&format!(
+                        "Failed to remove partial checkpoint at {checkpoint_path:?}: {e}"
+                    )),
+                }
+            },
+        }
+    });
+
+    let headers: Vec<(_, HeaderValue)> =
+        vec![(CONTENT_TYPE, HeaderValue::from_static("text/plain"))];
+    Ok(reply_with(
+        headers,
+        Body::wrap_stream(receiver.map(Ok::<_, Infallible>)),
+    ))
+}
+
+fn create_checkpoint(
+    aptos_db: &dyn DbWriter,
+    consensus_db: Option<Arc<dyn PersistentLivenessStorage>>,
+    checkpoint_path: &Path,
+    progress: &dyn Fn(&str),
+) -> anyhow::Result<()> {
+    let start = Instant::now();
+
+    // Consensus saves blocks before they are committed, so checkpointing the consensus db first
+    // makes it more likely to hold the blocks committed in the AptosDB checkpoint.
+    if let Some(consensus_db) = consensus_db {
+        consensus_db
+            .consensus_db()
+            .create_checkpoint(checkpoint_path)?;
+        progress(&format!(
+            "Made ConsensusDB checkpoint in {} ms.",
+            start.elapsed().as_millis()
+        ));
+    } else {
+        progress("Consensus db is not available, skipped.");
+    }
+
+    let ledger_info_with_sigs = aptos_db.create_online_checkpoint(checkpoint_path, progress)?;
+    progress(&format!(
+        "Made AptosDB checkpoint in {} ms.",
+        start.elapsed().as_millis()
+    ));
+
+    let ledger_info = ledger_info_with_sigs.ledger_info();
+    progress(&format!("Checkpoint path: {checkpoint_path:?}"));
+    progress(&format!("Version: {}", ledger_info.version()));
+    progress(&format!("Epoch: {}", ledger_info.epoch()));
+    progress(&format!("Waypoint: {}", Waypoint::new_any(ledger_info)));
+    progress(&format!("{ledger_info_with_sigs}"));
+    progress(&format!(
+        "LedgerInfoWithSignatures (BCS, hex): {}",
+        hex::encode(bcs::to_bytes(&ledger_info_with_sigs)?)
+    ));
+
+    Ok(())
+}
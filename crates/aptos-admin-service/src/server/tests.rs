@@ -0,0 +1,233 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::{AdminService, Context};
+use anyhow::{bail, Result};
+use aptos_config::config::AuthenticationConfig;
+use aptos_crypto::HashValue;
+use aptos_storage_interface::{DbReader, DbReaderWriter, DbWriter};
+use aptos_temppath::TempPath;
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+};
+use hyper::{body, header::AUTHORIZATION, Body, Method, Request, Response, StatusCode};
+use std::{io::read_to_string, path::Path, sync::Arc};
+
+// The passcode and its sha256, as configured for the tests
+const PASSCODE: &str = "abc";
+const PASSCODE_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+#[tokio::test]
+async fn test_authentication() {
+    let context = Arc::new(Context::default());
+    let authentication_configs = authentication_configs();
+
+    // Send requests without a passcode, with a wrong one and with one in the query string
+    for (uri, passcode) in [
+        ("/unknown", None),
+        ("/unknown", Some("abd")),
+        ("/unknown?passcode=abc", None),
+    ] {
+        let response = send_request(
+            context.clone(),
+            authentication_configs.clone(),
+            Method::GET,
+            uri,
+            passcode,
+        )
+        .await;
+
+        // Verify that the requests are not authenticated
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Send a request with the passcode and verify that it gets past the authentication
+    let response = send_request(
+        context.clone(),
+        authentication_configs,
+        Method::GET,
+        "/unknown",
+        Some(PASSCODE),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Send a request without any authentication configured and verify that it's let through
+    let response = send_request(context, vec![], Method::GET, "/unknown", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_checkpoint_requires_authentication() {
+    // Create a context with an AptosDB
+    let context = create_context(MockDb { fail: false });
+
+    // Request a checkpoint without any authentication configured
+    let checkpoint_dir = TempPath::new();
+    let response = send_checkpoint_request(context, vec![], checkpoint_dir.path()).await;
+
+    // Verify that the request is forbidden and that no checkpoint is made
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(!checkpoint_dir.path().exists());
+}
+
+#[tokio::test]
+async fn test_checkpoint_invalid_requests() {
+    // Request a checkpoint without an AptosDB and verify that it's not found
+    let checkpoint_dir = TempPath::new();
+    let response = send_checkpoint_request(
+        Arc::new(Context::default()),
+        authentication_configs(),
+        checkpoint_dir.path(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Request a checkpoint without a path and verify that it's rejected
+    let context = create_context(MockDb { fail: false });
+    let response = send_request(
+        context.clone(),
+        authentication_configs(),
+        Method::POST,
+        "/debug/storage/checkpoint",
+        Some(PASSCODE),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Request a checkpoint at an existing path and verify that it's rejected and left alone
+    checkpoint_dir.create_as_dir().unwrap();
+    std::fs::write(checkpoint_dir.path().join("file"), "content").unwrap();
+    let response =
+        send_checkpoint_request(context, authentication_configs(), checkpoint_dir.path()).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert!(checkpoint_dir.path().join("file").exists());
+}
+
+#[tokio::test]
+async fn test_checkpoint() {
+    // Request a checkpoint
+    let context = create_context(MockDb { fail: false });
+    let checkpoint_dir = TempPath::new();
+    let mut response =
+        send_checkpoint_request(context, authentication_configs(), checkpoint_dir.path()).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the progress and the checkpointed ledger info are streamed back
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("Consensus db is not available, skipped."));
+    assert!(response_body_string.contains("Made ledger db checkpoint."));
+    assert!(response_body_string.contains("Made AptosDB checkpoint"));
+    assert!(response_body_string.contains("Version: 0"));
+    assert!(response_body_string.contains("Waypoint: "));
+
+    // Verify that the checkpoint is kept
+    assert!(checkpoint_dir.path().join("ledger_db").exists());
+}
+
+#[tokio::test]
+async fn test_checkpoint_failure() {
+    // Request a checkpoint that fails after it's partially made
+    let context = create_context(MockDb { fail: true });
+    let checkpoint_dir = TempPath::new();
+    let mut response =
+        send_checkpoint_request(context, authentication_configs(), checkpoint_dir.path()).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the failure is streamed back
+    assert!(response_body_string.contains("Made ledger db checkpoint."));
+    assert!(response_body_string.contains("Failed to create checkpoint: Mock failure."));
+    assert!(response_body_string.contains("Removed partial checkpoint"));
+    assert!(!response_body_string.contains("Waypoint: "));
+
+    // Verify that the partial checkpoint is removed
+    assert!(!checkpoint_dir.path().exists());
+}
+
+/// A db that makes a partial checkpoint, and fails it if `fail` is set
+struct MockDb {
+    fail: bool,
+}
+
+impl DbReader for MockDb {}
+
+impl DbWriter for MockDb {
+    fn create_online_checkpoint(
+        &self,
+        cp_path: &Path,
+        progress: &dyn Fn(&str),
+    ) -> Result<LedgerInfoWithSignatures> {
+        std::fs::create_dir_all(cp_path.join("ledger_db"))?;
+        progress("Made ledger db checkpoint.");
+        if self.fail {
+            bail!("Mock failure.");
+        }
+
+        Ok(LedgerInfoWithSignatures::new(
+            LedgerInfo::new(BlockInfo::empty(), HashValue::zero()),
+            AggregateSignature::empty(),
+        ))
+    }
+}
+
+/// Returns the authentication configs that accept the test passcode
+fn authentication_configs() -> Vec<AuthenticationConfig> {
+    vec![AuthenticationConfig::PasscodeSha256(PASSCODE_SHA256.into())]
+}
+
+/// Creates a context holding the given db as the AptosDB
+fn create_context(db: MockDb) -> Arc<Context> {
+    let context = Context::default();
+    context.set_aptos_db(Arc::new(DbReaderWriter::new(db)));
+    Arc::new(context)
+}
+
+/// Sends an authenticated checkpoint request for the given path
+async fn send_checkpoint_request(
+    context: Arc<Context>,
+    authentication_configs: Vec<AuthenticationConfig>,
+    checkpoint_path: &Path,
+) -> Response<Body> {
+    let uri = format!(
+        "/debug/storage/checkpoint?{}",
+        url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("path", &checkpoint_path.to_string_lossy())
+            .finish()
+    );
+    send_request(
+        context,
+        authentication_configs,
+        Method::POST,
+        &uri,
+        Some(PASSCODE),
+    )
+    .await
+}
+
+/// Sends a request to the admin service, with the passcode (if given)
+async fn send_request(
+    context: Arc<Context>,
+    authentication_configs: Vec<AuthenticationConfig>,
+    method: Method,
+    uri: &str,
+    passcode: Option<&str>,
+) -> Response<Body> {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(passcode) = passcode {
+        request = request.header(AUTHORIZATION, format!("Bearer {passcode}"));
+    }
+    let request = request.body(Body::empty()).unwrap();
+
+    AdminService::serve_requests(
+        context,
+        request,
+        /*enabled=*/ true,
+        Arc::new(authentication_configs),
+    )
+    .await
+    .unwrap()
+}
@@ -0,0 +1,734 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the BN254 operations of the `crypto_algebra` natives, used to calibrate their gas
+//! parameters with `scripts/algebra-gas/update_algebra_gas_params.py`.
+
+#[macro_use]
+extern crate criterion;
+
+use ark_bn254::{Fq, Fq12, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{pairing::Pairing, CurveGroup, Group};
+use ark_ff::{BigInteger256, Field, One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::test_rng;
+use criterion::{BenchmarkId, Criterion};
+use std::ops::{Div, Mul, Neg};
+
+macro_rules! rand {
+    ($typ:ty) => {{
+        <$typ>::rand(&mut test_rng())
+    }};
+}
+
+macro_rules! serialize {
+    ($obj:expr, $method:ident) => {{
+        let mut buf = vec![];
+        $obj.$method(&mut buf).unwrap();
+        buf
+    }};
+}
+
+fn bench_group(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ark_bn254");
+
+    group.bench_function("fq12_add", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fq12), rand!(Fq12)),
+            |(e_1, e_2)| {
+                let _e_3 = e_1 + e_2;
+            },
+        )
+    });
+
+    group.bench_function("fq12_clone", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq12),
+            |e| {
+                let _e_2 = e;
+            },
+        )
+    });
+
+    group.bench_function("fq12_deser", move |b| {
+        b.iter_with_setup(
+            || {
+                let e = rand!(Fq12);
+                serialize!(e, serialize_uncompressed)
+            },
+            |buf| {
+                let _e = Fq12::deserialize_uncompressed(buf.as_slice()).unwrap();
+            },
+        )
+    });
+
+    group.bench_function("fq12_div", move |b| {
+        b.iter_with_setup(
+            || {
+                let e = rand!(Fq12);
+                let f = rand!(Fq12);
+                (e, f)
+            },
+            |(e, f)| {
+                let _g = e.div(f);
+            },
+        )
+    });
+
+    group.bench_function("fq12_eq", move |b| {
+        b.iter_with_setup(
+            || {
+                let e_1 = rand!(Fq12);
+                let e_2 = e_1;
+                (e_1, e_2)
+            },
+            |(e_1, e_2)| {
+                let _res = e_1 == e_2;
+            },
+        )
+    });
+
+    group.bench_function("fq12_from_u64", move |b| {
+        b.iter_with_setup(
+            || rand!(u64),
+            |i| {
+                let _res = Fq12::from(i);
+            },
+        )
+    });
+
+    group.bench_function("fq12_inv", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq12),
+            |e| {
+                let _e_inv = e.inverse();
+            },
+        )
+    });
+
+    group.bench_function("fq12_mul", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fq12), rand!(Fq12)),
+            |(e_1, e_2)| {
+                let _e_3 = e_1 * e_2;
+            },
+        )
+    });
+
+    group.bench_function("fq12_neg", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq12),
+            |e| {
+                let _e_2 = e.neg();
+            },
+        )
+    });
+
+    group.bench_function("fq12_one", move |b| {
+        b.iter(|| {
+            let _e = Fq12::one();
+        })
+    });
+
+    group.bench_function("fq12_pow_u256", move |b| {
+        b.iter_with_setup(
+            || {
+                let base = rand!(Fq12);
+                let exp = rand!(Fr);
+                let exp = BigInteger256::from(exp);
+                (base, exp)
+            },
+            |(base, exp)| {
+                let _res = base.pow(exp);
+            },
+        )
+    });
+
+    group.bench_function("fq12_serialize", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq12),
+            |e| {
+                let mut buf = vec![];
+                e.serialize_uncompressed(&mut buf).unwrap();
+            },
+        )
+    });
+
+    group.bench_function("fq12_square", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq12),
+            |e| {
+                let _res = e.square();
+            },
+        )
+    });
+
+    group.bench_function("fq12_sub", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fq12), rand!(Fq12)),
+            |(e, f)| {
+                let _res = e - f;
+            },
+        )
+    });
+
+    group.bench_function("fq12_zero", move |b| {
+        b.iter_with_setup(
+            || (),
+            |_| {
+                let _res = Fq12::zero();
+            },
+        )
+    });
+
+    group.bench_function("fq_add", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fq), rand!(Fq)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 + k_2;
+            },
+        )
+    });
+
+    group.bench_function("fq_deser", move |b| {
+        b.iter_with_setup(
+            || {
+                let k = rand!(Fq);
+                serialize!(k, serialize_uncompressed)
+            },
+            |buf| {
+                let _k = Fq::deserialize_uncompressed(buf.as_slice()).unwrap();
+            },
+        )
+    });
+
+    group.bench_function("fq_div", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fq), rand!(Fq)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 / k_2;
+            },
+        )
+    });
+
+    group.bench_function("fq_eq", move |b| {
+        b.iter_with_setup(
+            || {
+                let k_1 = rand!(Fq);
+                let k_2 = k_1;
+                (k_1, k_2)
+            },
+            |(k_1, k_2)| {
+                let _res = k_1 == k_2;
+            },
+        )
+    });
+
+    group.bench_function("fq_from_u64", move |b| {
+        b.iter_with_setup(
+            || rand!(u64),
+            |v| {
+                let _res: Fq = BigInteger256::from(v).into();
+            },
+        )
+    });
+
+    group.bench_function("fq_inv", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq),
+            |k| {
+                let _k_inv = k.inverse();
+            },
+        )
+    });
+
+    group.bench_function("fq_mul", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fq), rand!(Fq)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 * k_2;
+            },
+        )
+    });
+
+    group.bench_function("fq_neg", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq),
+            |k| {
+                let _k_inv = k.neg();
+            },
+        )
+    });
+
+    group.bench_function("fq_one", move |b| {
+        b.iter_with_setup(
+            || {},
+            |_| {
+                let _k = Fq::one();
+            },
+        )
+    });
+
+    group.bench_function("fq_serialize", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq),
+            |k| {
+                let _buf = serialize!(k, serialize_uncompressed);
+            },
+        )
+    });
+
+    group.bench_function("fq_square", move |b| {
+        b.iter_with_setup(
+            || rand!(Fq),
+            |k| {
+                let _k2 = k.square();
+            },
+        )
+    });
+
+    group.bench_function("fq_sub", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fq), rand!(Fq)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 - k_2;
+            },
+        )
+    });
+
+    group.bench_function("fq_zero", move |b| {
+        b.iter_with_setup(
+            || {},
+            |_| {
+                let _k = Fq::zero();
+            },
+        )
+    });
+
+    group.bench_function("fr_add", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fr), rand!(Fr)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 + k_2;
+            },
+        )
+    });
+
+    group.bench_function("fr_deser", move |b| {
+        b.iter_with_setup(
+            || {
+                let k = rand!(Fr);
+                serialize!(k, serialize_uncompressed)
+            },
+            |buf| {
+                let _k = Fr::deserialize_uncompressed(buf.as_slice()).unwrap();
+            },
+        )
+    });
+
+    group.bench_function("fr_div", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fr), rand!(Fr)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 / k_2;
+            },
+        )
+    });
+
+    group.bench_function("fr_eq", move |b| {
+        b.iter_with_setup(
+            || {
+                let k_1 = rand!(Fr);
+                let k_2 = k_1;
+                (k_1, k_2)
+            },
+            |(k_1, k_2)| {
+                let _res = k_1 == k_2;
+            },
+        )
+    });
+
+    group.bench_function("fr_from_u64", move |b| {
+        b.iter_with_setup(
+            || rand!(u64),
+            |v| {
+                let _res: Fr = BigInteger256::from(v).into();
+            },
+        )
+    });
+
+    group.bench_function("fr_inv", move |b| {
+        b.iter_with_setup(
+            || rand!(Fr),
+            |k| {
+                let _k_inv = k.inverse();
+            },
+        )
+    });
+
+    group.bench_function("fr_mul", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fr), rand!(Fr)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 * k_2;
+            },
+        )
+    });
+
+    group.bench_function("fr_neg", move |b| {
+        b.iter_with_setup(
+            || rand!(Fr),
+            |k| {
+                let _k_inv = k.neg();
+            },
+        )
+    });
+
+    group.bench_function("fr_one", move |b| {
+        b.iter_with_setup(
+            || {},
+            |_| {
+                let _k = Fr::one();
+            },
+        )
+    });
+
+    group.bench_function("fr_serialize", move |b| {
+        b.iter_with_setup(
+            || rand!(Fr),
+            |k| {
+                let _buf = serialize!(k, serialize_uncompressed);
+            },
+        )
+    });
+
+    group.bench_function("fr_square", move |b| {
+        b.iter_with_setup(
+            || rand!(Fr),
+            |k| {
+                let _k2 = k.square();
+            },
+        )
+    });
+
+    group.bench_function("fr_sub", move |b| {
+        b.iter_with_setup(
+            || (rand!(Fr), rand!(Fr)),
+            |(k_1, k_2)| {
+                let _k_3 = k_1 - k_2;
+            },
+        )
+    });
+
+    group.bench_function("fr_zero", move |b| {
+        b.iter_with_setup(
+            || {},
+            |_| {
+                let _k = Fr::zero();
+            },
+        )
+    });
+
+    group.bench_function("g1_affine_deser_comp", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G1Affine);
+                serialize!(p, serialize_compressed)
+            },
+            |buf| {
+                let _p = G1Affine::deserialize_compressed(buf.as_slice());
+            },
+        )
+    });
+
+    group.bench_function("g1_affine_deser_uncomp", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G1Affine);
+                serialize!(p, serialize_uncompressed)
+            },
+            |buf| {
+                let _p = G1Affine::deserialize_uncompressed(buf.as_slice());
+            },
+        )
+    });
+
+    group.bench_function("g1_affine_serialize_comp", move |b| {
+        b.iter_with_setup(
+            || rand!(G1Affine),
+            |p_affine| {
+                let _buf = serialize!(p_affine, serialize_compressed);
+            },
+        )
+    });
+
+    group.bench_function("g1_affine_serialize_uncomp", move |b| {
+        b.iter_with_setup(
+            || rand!(G1Affine),
+            |p_affine| {
+                let _buf = serialize!(p_affine, serialize_uncompressed);
+            },
+        )
+    });
+
+    group.bench_function("g1_proj_add", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G1Projective);
+                let q = rand!(G1Projective);
+                (p, q)
+            },
+            |(p, q)| {
+                let _res = p + q;
+            },
+        )
+    });
+
+    group.bench_function("g1_proj_double", move |b| {
+        b.iter_with_setup(
+            || rand!(G1Projective),
+            |p| {
+                let _q = p.double();
+            },
+        )
+    });
+
+    group.bench_function("g1_proj_eq", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G1Projective);
+                let q = p;
+                (p, q)
+            },
+            |(p, q)| {
+                let _res = p == q;
+            },
+        )
+    });
+
+    group.bench_function("g1_proj_generator", move |b| {
+        b.iter(|| {
+            let _res = G1Projective::generator();
+        })
+    });
+
+    group.bench_function("g1_proj_infinity", move |b| {
+        b.iter(|| {
+            let _res = G1Projective::zero();
+        })
+    });
+
+    group.bench_function("g1_proj_neg", move |b| {
+        b.iter_with_setup(
+            || rand!(G1Projective),
+            |p| {
+                let _q = p.neg();
+            },
+        )
+    });
+
+    group.bench_function("g1_proj_scalar_mul", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G1Projective);
+                let k = rand!(Fr);
+                (p, k)
+            },
+            |(p, k)| {
+                let _q = p.mul(k);
+            },
+        )
+    });
+
+    group.bench_function("g1_proj_sub", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G1Projective);
+                let q = rand!(G1Projective);
+                (p, q)
+            },
+            |(p, q)| {
+                let _r = p - q;
+            },
+        )
+    });
+
+    group.bench_function("g1_proj_to_affine", move |b| {
+        b.iter_with_setup(
+            || rand!(G1Projective),
+            |p_proj| {
+                let _ = p_proj.into_affine();
+            },
+        )
+    });
+
+    group.bench_function("g2_affine_deser_comp", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G2Affine);
+                serialize!(p, serialize_compressed)
+            },
+            |buf| {
+                let _p = G2Affine::deserialize_compressed(buf.as_slice());
+            },
+        )
+    });
+
+    group.bench_function("g2_affine_deser_uncomp", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G2Affine);
+                serialize!(p, serialize_uncompressed)
+            },
+            |buf| {
+                let _p = G2Affine::deserialize_uncompressed(buf.as_slice());
+            },
+        )
+    });
+
+    group.bench_function("g2_affine_serialize_comp", move |b| {
+        b.iter_with_setup(
+            || rand!(G2Affine),
+            |p_affine| {
+                let _buf = serialize!(p_affine, serialize_compressed);
+            },
+        )
+    });
+
+    group.bench_function("g2_affine_serialize_uncomp", move |b| {
+        b.iter_with_setup(
+            || rand!(G2Affine),
+            |p_affine| {
+                let _buf = serialize!(p_affine, serialize_uncompressed);
+            },
+        )
+    });
+
+    group.bench_function("g2_proj_add", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G2Projective);
+                let q = rand!(G2Projective);
+                (p, q)
+            },
+            |(p, q)| {
+                let _res = p + q;
+            },
+        )
+    });
+
+    group.bench_function("g2_proj_double", move |b| {
+        b.iter_with_setup(
+            || rand!(G2Projective),
+            |p| {
+                let _q = p.double();
+            },
+        )
+    });
+
+    group.bench_function("g2_proj_eq", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G2Projective);
+                let q = p;
+                (p, q)
+            },
+            |(p, q)| {
+                let _res = p == q;
+            },
+        )
+    });
+
+    group.bench_function("g2_proj_generator", move |b| {
+        b.iter(|| {
+            let _res = G2Projective::generator();
+        })
+    });
+
+    group.bench_function("g2_proj_infinity", move |b| {
+        b.iter(|| {
+            let _res = G2Projective::zero();
+        })
+    });
+
+    group.bench_function("g2_proj_neg", move |b| {
+        b.iter_with_setup(
+            || rand!(G2Projective),
+            |p| {
+                let _q = p.neg();
+            },
+        )
+    });
+
+    group.bench_function("g2_proj_scalar_mul", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G2Projective);
+                let k = rand!(Fr);
+                (p, k)
+            },
+            |(p, k)| {
+                let _q = p.mul(k);
+            },
+        )
+    });
+
+    group.bench_function("g2_proj_sub", move |b| {
+        b.iter_with_setup(
+            || {
+                let p = rand!(G2Projective);
+                let q = rand!(G2Projective);
+                (p, q)
+            },
+            |(p, q)| {
+                let _r = p - q;
+            },
+        )
+    });
+
+    group.bench_function("g2_proj_to_affine", move |b| {
+        b.iter_with_setup(
+            || rand!(G2Projective),
+            |p_proj| {
+                let _ = p_proj.into_affine();
+            },
+        )
+    });
+
+    group.bench_function("pairing", move |b| {
+        b.iter_with_setup(
+            || (rand!(G1Affine), rand!(G2Affine)),
+            |(g1e, g2e)| {
+                let _res = ark_bn254::Bn254::pairing(g1e, g2e).0;
+            },
+        )
+    });
+
+    let linear_regression_max_num_datapoints = 20;
+
+    let pairing_product_max_num_pairs = 100;
+    for num_pairs in (0..pairing_product_max_num_pairs)
+        .step_by(pairing_product_max_num_pairs / linear_regression_max_num_datapoints)
+    {
+        group.bench_function(BenchmarkId::new("pairing_product", num_pairs), |b| {
+            b.iter_with_setup(
+                || {
+                    let g1_elements = (0..num_pairs).map(|_i| rand!(G1Affine)).collect::<Vec<_>>();
+                    let g2_elements = (0..num_pairs).map(|_i| rand!(G2Affine)).collect::<Vec<_>>();
+                    (g1_elements, g2_elements)
+                },
+                |(g1_elements, g2_elements)| {
+                    let _product = ark_bn254::Bn254::multi_pairing(g1_elements, g2_elements).0;
+                },
+            );
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    name = ark_bn254_benches;
+    config = Criterion::default();
+    targets = bench_group);
+criterion_main!(ark_bn254_benches);
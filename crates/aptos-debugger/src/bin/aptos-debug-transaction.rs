@@ -0,0 +1,17 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays a committed transaction under a Debug Adapter Protocol client. It is a separate binary
+//! built with the `debug-adapter` feature, as it enables the debugging hook of the VM.
+
+use anyhow::Result;
+use aptos_logger::{Level, Logger};
+use aptos_move_debugger::debug_transaction::Command;
+use clap::Parser;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    Logger::new().level(Level::Info).init();
+
+    Command::parse().run().await
+}
@@ -25,10 +25,11 @@ pub use aptos_api_types::{
 };
 use aptos_api_types::{
     deserialize_from_string,
-    mime_types::{BCS, BCS_SIGNED_TRANSACTION, BCS_VIEW_FUNCTION, JSON},
+    mime_types::{BCS, BCS_SIGNED_TRANSACTION, BCS_VIEW_FUNCTION, EVENT_STREAM, JSON},
     AptosError, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse, MoveModuleId,
-    TransactionData, TransactionOnChainData, TransactionsBatchSubmissionResult, UserTransaction,
-    VersionedEvent, ViewFunction, ViewRequest,
+    TableItem, TransactionData, TransactionOnChainData, TransactionStreamEvent,
+    TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent, ViewFunction,
+    ViewFunctionResult, ViewFunctionResultBcs, ViewRequest,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::{debug, info, sample, sample::SampleRate};
@@ -36,10 +37,14 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, CoinStoreResource, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
-    state_store::state_key::StateKey,
+    state_store::{state_key::StateKey, state_value::StateValuesWithMultiProof},
     transaction::SignedTransaction,
 };
-use move_core_types::language_storage::StructTag;
+use futures::{Stream, StreamExt};
+use move_core_types::{
+    ident_str,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use reqwest::{
     header::{ACCEPT, CONTENT_TYPE},
     Client as ReqwestClient, StatusCode,
@@ -255,6 +260,55 @@ impl Client {
         })
     }
 
+    /// Gets the balance of the primary store of `address` for the fungible asset whose metadata
+    /// object is at `metadata`, which is 0 if the account has no primary store for it.
+    pub async fn get_account_fa_balance(
+        &self,
+        address: AccountAddress,
+        metadata: AccountAddress,
+    ) -> AptosResult<Response<u64>> {
+        self.view_fa_balance(address, metadata, None).await
+    }
+
+    pub async fn get_account_fa_balance_at_version(
+        &self,
+        address: AccountAddress,
+        metadata: AccountAddress,
+        version: u64,
+    ) -> AptosResult<Response<u64>> {
+        self.view_fa_balance(address, metadata, Some(version)).await
+    }
+
+    async fn view_fa_balance(
+        &self,
+        address: AccountAddress,
+        metadata: AccountAddress,
+        version: Option<u64>,
+    ) -> AptosResult<Response<u64>> {
+        let request = ViewFunction {
+            module: ModuleId::new(
+                CORE_CODE_ADDRESS,
+                ident_str!("primary_fungible_store").to_owned(),
+            ),
+            function: ident_str!("balance").to_owned(),
+            ty_args: vec![TypeTag::Struct(Box::new(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: ident_str!("fungible_asset").to_owned(),
+                name: ident_str!("Metadata").to_owned(),
+                type_params: vec![],
+            }))],
+            args: vec![bcs::to_bytes(&address)?, bcs::to_bytes(&metadata)?],
+        };
+        let resp = self.view_bcs::<Vec<u64>>(&request, version).await?;
+        resp.and_then(|values| {
+            if let Some(balance) = values.first() {
+                Ok(*balance)
+            } else {
+                Err(anyhow!("No data returned").into())
+            }
+        })
+    }
+
     pub async fn get_index(&self) -> AptosResult<Response<IndexResponse>> {
         self.get(self.build_path("")?).await
     }
@@ -331,6 +385,56 @@ impl Client {
         Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
     }
 
+    /// Executes a batch of view functions against the same ledger version. A view function
+    /// that fails doesn't fail the whole batch, its error is returned in place of its values.
+    pub async fn view_batch(
+        &self,
+        requests: &[ViewRequest],
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<ViewFunctionResult>>> {
+        let request = serde_json::to_string(requests)?;
+        let mut url = self.build_path("view/batch")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("ledger_version={}", version).as_str()));
+        }
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, JSON)
+            .body(request)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
+    /// Executes a batch of view functions against the same ledger version, returning the
+    /// BCS encoded return values of each successful view function.
+    pub async fn view_batch_bcs(
+        &self,
+        requests: &[ViewFunction],
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<ViewFunctionResultBcs>>> {
+        let txn_payload = bcs::to_bytes(requests)?;
+        let mut url = self.build_path("view/batch")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("ledger_version={}", version).as_str()));
+        }
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_VIEW_FUNCTION)
+            .header(ACCEPT, BCS)
+            .body(txn_payload)
+            .send()
+            .await?;
+
+        let response = self.check_and_parse_bcs_response(response).await?;
+        Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
+    }
+
     pub async fn simulate(
         &self,
         txn: &SignedTransaction,
@@ -838,6 +942,56 @@ impl Client {
         Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
     }
 
+    /// Opens a stream of committed transactions, starting at `start` or at the
+    /// next transaction to be committed if not given. The stream can optionally
+    /// be filtered by sender, entry function (e.g. `0x1::aptos_account::transfer`)
+    /// and emitted event type (e.g. `0x1::coin::DepositEvent`).
+    ///
+    /// The stream ends if the connection is closed by the server. If the server
+    /// fails to read transactions, the last item of the stream is its error.
+    pub async fn stream_transactions(
+        &self,
+        start: Option<u64>,
+        sender: Option<AccountAddress>,
+        function: Option<&str>,
+        event_type: Option<&str>,
+    ) -> AptosResult<impl Stream<Item = AptosResult<Transaction>>> {
+        let url = self.build_path("transactions/stream")?;
+
+        let mut request = self.inner.get(url).header(ACCEPT, EVENT_STREAM);
+        if let Some(start) = start {
+            request = request.query(&[("start", start)])
+        }
+
+        if let Some(sender) = sender {
+            request = request.query(&[("sender", sender.to_hex_literal())])
+        }
+
+        if let Some(function) = function {
+            request = request.query(&[("function", function)])
+        }
+
+        if let Some(event_type) = event_type {
+            request = request.query(&[("event_type", event_type)])
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(parse_error(response).await);
+        }
+
+        Ok(
+            parse_event_stream::<TransactionStreamEvent>(Box::pin(response.bytes_stream())).map(
+                |event| match event? {
+                    TransactionStreamEvent::Transaction(transaction) => Ok(transaction),
+                    TransactionStreamEvent::Error(error) => {
+                        Err((error, None, StatusCode::INTERNAL_SERVER_ERROR).into())
+                    },
+                },
+            ),
+        )
+    }
+
     pub async fn get_transaction_by_hash(
         &self,
         hash: HashValue,
@@ -1193,6 +1347,33 @@ impl Client {
         self.json(response).await
     }
 
+    /// Returns a page of the events of the given type emitted by any transaction. The cursor
+    /// of the next page, if any, is returned in the state of the response.
+    pub async fn get_events_by_type(
+        &self,
+        event_type: &str,
+        start_version: Option<u64>,
+        cursor: Option<String>,
+        limit: Option<u16>,
+    ) -> AptosResult<Response<Vec<VersionedEvent>>> {
+        let url = self.build_path(&format!("events/by_type/{}", event_type))?;
+        let mut request = self.inner.get(url);
+        if let Some(start_version) = start_version {
+            request = request.query(&[("start_version", start_version)])
+        }
+
+        if let Some(cursor) = cursor {
+            request = request.query(&[("start", cursor)])
+        }
+
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+
+        let response = request.send().await?;
+        self.json(response).await
+    }
+
     pub async fn get_account_events_bcs(
         &self,
         address: AccountAddress,
@@ -1361,6 +1542,63 @@ impl Client {
         Ok(response.map(|inner| inner.to_vec()))
     }
 
+    /// Gets a page of the items of a table, with their keys and values decoded if the node knows
+    /// the types of the table. The cursor of the next page, if any, is in the returned state.
+    pub async fn get_table_items(
+        &self,
+        table_handle: AccountAddress,
+        cursor: Option<String>,
+        limit: Option<u16>,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<TableItem>>> {
+        let url = self.build_path(&format!("tables/{}/items", table_handle))?;
+        let request = Self::table_items_query(self.inner.get(url), cursor, limit, version);
+
+        let response = request.send().await?;
+        self.json(response).await
+    }
+
+    /// Gets a page of the items of a table as BCS serialized (key, value) pairs. The cursor of
+    /// the next page, if any, is in the returned state.
+    pub async fn get_table_items_bcs(
+        &self,
+        table_handle: AccountAddress,
+        cursor: Option<String>,
+        limit: Option<u16>,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<(Vec<u8>, Vec<u8>)>>> {
+        let url = self.build_path(&format!("tables/{}/items", table_handle))?;
+        let request = Self::table_items_query(
+            self.inner.get(url).header(ACCEPT, BCS),
+            cursor,
+            limit,
+            version,
+        );
+
+        let response = self
+            .check_and_parse_bcs_response(request.send().await?)
+            .await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
+    fn table_items_query(
+        mut request: reqwest::RequestBuilder,
+        cursor: Option<String>,
+        limit: Option<u16>,
+        version: Option<u64>,
+    ) -> reqwest::RequestBuilder {
+        if let Some(cursor) = cursor {
+            request = request.query(&[("start", cursor)])
+        }
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)])
+        }
+        if let Some(version) = version {
+            request = request.query(&[("ledger_version", version)])
+        }
+        request
+    }
+
     pub async fn get_raw_state_value(
         &self,
         state_key: &StateKey,
@@ -1378,6 +1616,30 @@ impl Client {
         Ok(response.map(|inner| inner.to_vec()))
     }
 
+    /// Gets the values of several state keys with a single proof of all of them, at the latest
+    /// state snapshot at or before `version`. The proof should be verified against the state
+    /// checkpoint hash of the transaction at the version of the snapshot.
+    pub async fn get_raw_state_values_with_multiproof(
+        &self,
+        state_keys: &[StateKey],
+        version: u64,
+    ) -> AptosResult<Response<StateValuesWithMultiProof>> {
+        let url = self.build_path(&format!(
+            "experimental/state_values/raw_with_multiproof?ledger_version={}",
+            version
+        ))?;
+        let keys = state_keys
+            .iter()
+            .map(|state_key| Ok(hex::encode(bcs::to_bytes(state_key)?)))
+            .collect::<AptosResult<Vec<_>>>()?;
+        let data = json!({
+            "keys": keys,
+        });
+
+        let response = self.post_bcs(url, data).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address.to_hex()))?;
         let response = self.inner.get(url).send().await?;
@@ -1721,6 +1983,71 @@ async fn parse_error(response: reqwest::Response) -> RestError {
     }
 }
 
+/// Parses the body of a server-sent event stream, deserializing the JSON data
+/// of each event. Lines may end with `\r\n`, `\n` or `\r`, and comments, e.g.
+/// keep-alives, and fields other than `data` are skipped.
+fn parse_event_stream<T: DeserializeOwned>(
+    body: impl Stream<Item = reqwest::Result<bytes::Bytes>> + Unpin,
+) -> impl Stream<Item = AptosResult<T>> {
+    futures::stream::unfold(
+        (body.fuse(), Vec::new(), None::<String>),
+        |(mut body, mut buffer, mut data)| async move {
+            loop {
+                while let Some((line_len, terminator_len)) = find_event_stream_line(&buffer) {
+                    let line: Vec<u8> = buffer.drain(..line_len + terminator_len).collect();
+                    let line = String::from_utf8_lossy(&line[..line_len]);
+
+                    // An empty line dispatches the event, if it has any data
+                    if line.is_empty() {
+                        if let Some(event_data) = data.take().filter(|data| !data.is_empty()) {
+                            let item = serde_json::from_str(&event_data).map_err(RestError::from);
+                            return Some((item, (body, buffer, data)));
+                        }
+                        continue;
+                    }
+
+                    // A line starting with a colon is a comment, which has an empty field name
+                    let (field, value) = line.split_once(':').unwrap_or((&*line, ""));
+                    let value = value.strip_prefix(' ').unwrap_or(value);
+                    if field == "data" {
+                        match &mut data {
+                            Some(data) => {
+                                data.push('\n');
+                                data.push_str(value);
+                            },
+                            None => data = Some(value.to_string()),
+                        }
+                    }
+                }
+
+                match body.next().await {
+                    Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                    Some(Err(err)) => return Some((Err(err.into()), (body, buffer, data))),
+                    // A trailing `\r` can't be followed by a `\n` anymore, so it ends a line.
+                    // Anything after the last empty line is an incomplete event, and dropped.
+                    None if buffer.last() == Some(&b'\r') => buffer.push(b'\n'),
+                    None => return None,
+                }
+            }
+        },
+    )
+}
+
+/// Returns the length of the first complete line in `buffer`, and the length of
+/// its terminator. A line ending with `\r` at the end of the buffer isn't
+/// complete yet, as it may be followed by a `\n`.
+fn find_event_stream_line(buffer: &[u8]) -> Option<(usize, usize)> {
+    let end = buffer
+        .iter()
+        .position(|byte| *byte == b'\r' || *byte == b'\n')?;
+    match (buffer[end], buffer.get(end + 1)) {
+        (b'\n', _) => Some((end, 1)),
+        (_, Some(b'\n')) => Some((end, 2)),
+        (_, Some(_)) => Some((end, 1)),
+        (_, None) => None,
+    }
+}
+
 pub struct GasEstimationParams {
     pub estimated_gas_used: u64,
     pub estimated_gas_price: u64,
@@ -1732,3 +2059,35 @@ enum WaitForTransactionResult<T> {
     Pending(State),
     Success(Response<T>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_event_stream_line_endings() {
+        // Events with each line ending, split across chunks (including between a `\r` and
+        // its `\n`), with comments, and with data spread over several lines
+        let chunks = [
+            ": keep-alive\n\ndata: {\"a\": 1}\n\n",
+            "data: {\"a\": 2}\r",
+            "\n\r\n: keep-alive\r\rdata: {\"a\":\r",
+            "data: 3}\r\r",
+            "id: 4\r\ndata: {\"a\": 4}\r\n\r\ndata: {\"a\": 5}",
+        ];
+        let body = futures::stream::iter(
+            chunks
+                .into_iter()
+                .map(|chunk| Ok(bytes::Bytes::from_static(chunk.as_bytes()))),
+        );
+        let events: Vec<serde_json::Value> = parse_event_stream(body)
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+
+        // Verify that every complete event is parsed, and the incomplete last one is dropped
+        let expected: Vec<serde_json::Value> =
+            (1..=4).map(|a| serde_json::json!({ "a": a })).collect();
+        assert_eq!(events, expected);
+    }
+}
@@ -170,6 +170,11 @@ impl BlockRetriever {
         }
     }
 
+    /// Maximum number of transactions retrieved in a single call to the REST API
+    pub fn page_size(&self) -> u16 {
+        self.page_size
+    }
+
     pub async fn get_block_info_by_height(
         &self,
         height: u64,
@@ -1363,6 +1363,9 @@ async fn construction_submit(
     let txn: SignedTransaction = decode_bcs(&request.signed_transaction, "SignedTransaction")?;
     let hash = txn.clone().committed_hash();
     rest_client.submit_bcs(&txn).await?;
+    server_context
+        .pending_transactions
+        .insert(hash, txn.expiration_timestamp_secs());
     Ok(ConstructionSubmitResponse {
         transaction_identifier: hash.into(),
     })
@@ -9,6 +9,7 @@ use crate::{
     block::BlockRetriever,
     common::{handle_request, with_context},
     error::{ApiError, ApiResult},
+    mempool::PendingTransactions,
     types::Store,
 };
 use aptos_config::config::ApiConfig;
@@ -25,7 +26,9 @@ use warp::{
 mod account;
 mod block;
 mod construction;
+mod mempool;
 mod network;
+mod search;
 
 pub mod client;
 pub mod common;
@@ -46,6 +49,8 @@ pub struct RosettaContext {
     pub block_cache: Option<Arc<BlockRetriever>>,
     pub owner_addresses: Vec<AccountAddress>,
     pub pool_address_to_owner: BTreeMap<AccountAddress, AccountAddress>,
+    /// Transactions submitted through this server which may still be in mempool
+    pub pending_transactions: Arc<PendingTransactions>,
 }
 
 impl RosettaContext {
@@ -87,6 +92,7 @@ impl RosettaContext {
             block_cache,
             owner_addresses,
             pool_address_to_owner,
+            pending_transactions: Arc::new(PendingTransactions::default()),
         }
     }
 
@@ -181,9 +187,12 @@ pub fn routes(
         .or(construction::payloads_route(context.clone()))
         .or(construction::preprocess_route(context.clone()))
         .or(construction::submit_route(context.clone()))
+        .or(mempool::mempool_route(context.clone()))
+        .or(mempool::mempool_transaction_route(context.clone()))
         .or(network::list_route(context.clone()))
         .or(network::options_route(context.clone()))
         .or(network::status_route(context.clone()))
+        .or(search::search_transactions_route(context.clone()))
         .or(health_check_route(context))
         .with(
             warp::cors()
@@ -0,0 +1,214 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rosetta Mempool API
+//!
+//! See: [Mempool API Spec](https://www.rosetta-api.org/docs/MempoolApi.html)
+//!
+
+use crate::{
+    common::{check_network, handle_request, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        MempoolRequest, MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse,
+        Transaction,
+    },
+    RosettaContext,
+};
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::{debug, trace};
+use aptos_rest_client::{
+    aptos_api_types::{AptosErrorCode, TransactionData},
+    error::RestError,
+};
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use warp::Filter;
+
+pub fn mempool_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("mempool")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool))
+}
+
+pub fn mempool_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("mempool" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool_transaction))
+}
+
+/// Transactions submitted through `/construction/submit` which may not be committed yet
+///
+/// The node only lists the pending transactions of a given account, not the whole contents of its
+/// mempool, so these are the only pending transactions that can be listed. Transactions are
+/// evicted once they expired, even if `/mempool` is never called.
+#[derive(Debug, Default)]
+pub struct PendingTransactions {
+    /// Expiration timestamp in seconds of each transaction, by hash
+    transactions: Mutex<BTreeMap<HashValue, u64>>,
+}
+
+impl PendingTransactions {
+    /// Time after their expiration for which transactions are kept, as the expiration is checked
+    /// against ledger time, which lags behind the clock of this server
+    const EXPIRATION_GRACE_SECS: u64 = 60;
+
+    pub fn insert(&self, hash: HashValue, expiration_timestamp_secs: u64) {
+        let now_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.insert_at(hash, expiration_timestamp_secs, now_secs);
+    }
+
+    fn insert_at(&self, hash: HashValue, expiration_timestamp_secs: u64, now_secs: u64) {
+        let mut transactions = self.transactions.lock();
+        transactions.retain(|_, expiration_timestamp_secs| {
+            expiration_timestamp_secs.saturating_add(Self::EXPIRATION_GRACE_SECS) >= now_secs
+        });
+        transactions.insert(hash, expiration_timestamp_secs);
+    }
+
+    fn remove(&self, hash: &HashValue) {
+        self.transactions.lock().remove(hash);
+    }
+
+    fn all(&self) -> Vec<(HashValue, u64)> {
+        self.transactions
+            .lock()
+            .iter()
+            .map(|(hash, expiration_timestamp_secs)| (*hash, *expiration_timestamp_secs))
+            .collect()
+    }
+}
+
+/// Lists the transactions submitted through this server that are still in mempool
+///
+/// Transactions are forgotten once they're committed, expired, or dropped from mempool.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+async fn mempool(
+    request: MempoolRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolResponse> {
+    debug!("/mempool");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+    let pending_transactions = &server_context.pending_transactions;
+
+    // A transaction can't be committed after it expires, which is checked against ledger time
+    let ledger_timestamp_secs = rest_client
+        .get_ledger_information()
+        .await?
+        .state()
+        .timestamp_usecs
+        / 1_000_000;
+
+    let mut transaction_identifiers = vec![];
+    for (hash, expiration_timestamp_secs) in pending_transactions.all() {
+        if expiration_timestamp_secs < ledger_timestamp_secs {
+            pending_transactions.remove(&hash);
+            continue;
+        }
+
+        match rest_client.get_transaction_by_hash_bcs(hash).await {
+            Ok(response) => match response.into_inner() {
+                TransactionData::Pending(_) => transaction_identifiers.push(hash.into()),
+                TransactionData::OnChain(_) => pending_transactions.remove(&hash),
+            },
+            // The transaction was dropped from mempool without being committed
+            Err(RestError::Api(err))
+                if matches!(err.error.error_code, AptosErrorCode::TransactionNotFound) =>
+            {
+                pending_transactions.remove(&hash)
+            },
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Retrieves a transaction in mempool by hash, with its operations parsed from its payload
+///
+/// Unlike `/mempool`, this works for any transaction in the mempool of the node, not only the
+/// ones submitted through this server.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+async fn mempool_transaction(
+    request: MempoolTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolTransactionResponse> {
+    debug!("/mempool/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+
+    let hash = request.transaction_identifier.hash_value()?;
+    match rest_client
+        .get_transaction_by_hash_bcs(hash)
+        .await?
+        .into_inner()
+    {
+        TransactionData::Pending(txn) => Ok(MempoolTransactionResponse {
+            transaction: Transaction::from_pending_transaction(&txn),
+        }),
+        TransactionData::OnChain(_) => Err(ApiError::TransactionNotFound(Some(format!(
+            "Transaction {} is already committed",
+            hash
+        )))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_transactions_expire() {
+        let pending_transactions = PendingTransactions::default();
+        let expired = HashValue::random();
+        let pending = HashValue::random();
+        pending_transactions.insert_at(expired, 100, 100);
+        pending_transactions.insert_at(pending, 200, 100);
+        assert_eq!(pending_transactions.all().len(), 2);
+
+        // Transactions are kept for a while after they expire
+        pending_transactions.insert_at(HashValue::random(), 300, 160);
+        assert_eq!(pending_transactions.all().len(), 3);
+
+        pending_transactions.insert_at(HashValue::random(), 300, 161);
+        let hashes: Vec<_> = pending_transactions
+            .all()
+            .into_iter()
+            .map(|(hash, _)| hash)
+            .collect();
+        assert_eq!(hashes.len(), 3);
+        assert!(!hashes.contains(&expired));
+        assert!(hashes.contains(&pending));
+    }
+}
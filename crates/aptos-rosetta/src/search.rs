@@ -0,0 +1,364 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rosetta Search API
+//!
+//! See: [Search API Spec](https://www.rosetta-api.org/docs/SearchApi.html)
+//!
+
+use crate::{
+    common::{check_network, handle_request, is_native_coin, native_coin_tag, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        AccountIdentifier, BlockIdentifier, BlockTransaction, SearchOperator,
+        SearchTransactionsRequest, SearchTransactionsResponse, Transaction, COIN_MODULE,
+        COIN_STORE_RESOURCE, DEPOSIT_EVENTS_FIELD, WITHDRAW_EVENTS_FIELD,
+    },
+    RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use aptos_rest_client::aptos_api_types::TransactionData;
+use aptos_types::{account_address::AccountAddress, account_config::CoinStoreResource};
+use std::{collections::BTreeSet, future::Future};
+use warp::Filter;
+
+/// Maximum number of transactions returned by a single search, also used when no limit is given
+pub const MAX_SEARCH_LIMIT: u64 = 100;
+
+pub fn search_transactions_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("search" / "transactions")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(search_transactions))
+}
+
+/// Searches for committed transactions by hash or by account, newest first
+///
+/// The transactions of an account are the ones it sent, from the node's account transaction
+/// index, and the ones which withdrew or deposited the native coin from or to it, from the events
+/// of its `CoinStore`. They are looked up newest first, and only until the requested page is
+/// filled, unless several conditions have to match, so `total_count` only counts the matching
+/// transactions up to the page, plus one if there are more.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+async fn search_transactions(
+    request: SearchTransactionsRequest,
+    server_context: RosettaContext,
+) -> ApiResult<SearchTransactionsResponse> {
+    debug!("/search/transactions");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/search/transactions",
+    );
+
+    check_network(request.network_identifier.clone(), &server_context)?;
+    let rest_client = server_context.rest_client()?;
+
+    if request.status.is_some() || request.operation_type.is_some() || request.success.is_some() {
+        return Err(ApiError::InvalidInput(Some(
+            "Searching by status, type, or success is not supported".to_string(),
+        )));
+    }
+    if let Some(ref currency) = request.currency {
+        is_native_coin(currency)?;
+    }
+
+    let max_version = match request.max_block {
+        Some(max_block) => Some(
+            server_context
+                .block_cache()?
+                .get_block_info_by_height(max_block, server_context.chain_id)
+                .await?
+                .last_version,
+        ),
+        None => None,
+    };
+    let offset = request.offset.unwrap_or_default();
+    let limit = request
+        .limit
+        .unwrap_or(MAX_SEARCH_LIMIT)
+        .min(MAX_SEARCH_LIMIT);
+    let accounts = search_accounts(&request)?;
+    let operator = request.operator.unwrap_or_default();
+
+    // The newest matching versions of each condition fill the page when they're combined with
+    // `or`, and one more tells whether there's a next page. The intersection of conditions can
+    // be anywhere in their history though, so all their versions are needed.
+    let num_conditions = accounts.len() + usize::from(request.transaction_identifier.is_some());
+    let num_versions = (operator == SearchOperator::Or || num_conditions == 1)
+        .then_some(offset.saturating_add(limit + 1) as usize);
+
+    // Every condition is a set of matching versions, which are then combined
+    let mut conditions: Vec<BTreeSet<u64>> = vec![];
+    if let Some(ref transaction_identifier) = request.transaction_identifier {
+        let hash = transaction_identifier.hash_value()?;
+        let version = match rest_client
+            .get_transaction_by_hash_bcs(hash)
+            .await?
+            .into_inner()
+        {
+            TransactionData::OnChain(txn) => txn.version,
+            TransactionData::Pending(_) => return Err(ApiError::TransactionIsPending),
+        };
+        conditions.push(BTreeSet::from([version]));
+    }
+    for account in accounts {
+        conditions
+            .push(get_account_versions(&server_context, account, max_version, num_versions).await?);
+    }
+
+    let mut versions = match operator {
+        SearchOperator::And => conditions
+            .into_iter()
+            .reduce(|first, second| first.intersection(&second).copied().collect()),
+        SearchOperator::Or => conditions.into_iter().reduce(|mut first, mut second| {
+            first.append(&mut second);
+            first
+        }),
+    }
+    .ok_or_else(|| {
+        ApiError::InvalidInput(Some(
+            "A transaction identifier or an account is required to search".to_string(),
+        ))
+    })?;
+
+    if let Some(max_version) = max_version {
+        versions.retain(|version| *version <= max_version);
+    }
+
+    let total_count = versions.len() as u64;
+
+    let mut transactions = vec![];
+    for version in versions
+        .into_iter()
+        .rev()
+        .skip(offset as usize)
+        .take(limit as usize)
+    {
+        let txn = match rest_client
+            .get_transaction_by_version_bcs(version)
+            .await?
+            .into_inner()
+        {
+            TransactionData::OnChain(txn) => txn,
+            TransactionData::Pending(_) => return Err(ApiError::TransactionIsPending),
+        };
+        let block = rest_client
+            .get_block_by_version_bcs(version, false)
+            .await?
+            .into_inner();
+        transactions.push(BlockTransaction {
+            block_identifier: BlockIdentifier::from_block(&block, server_context.chain_id),
+            transaction: Transaction::from_transaction(&server_context, txn).await?,
+        });
+    }
+
+    let next_offset = offset + transactions.len() as u64;
+    Ok(SearchTransactionsResponse {
+        transactions,
+        total_count,
+        next_offset: (next_offset < total_count).then_some(next_offset),
+    })
+}
+
+/// Accounts to search for, which must be base accounts
+fn search_accounts(request: &SearchTransactionsRequest) -> ApiResult<Vec<AccountAddress>> {
+    let mut accounts = vec![];
+    if let Some(ref account_identifier) = request.account_identifier {
+        if !account_identifier.is_base_account() {
+            return Err(ApiError::InvalidInput(Some(
+                "Only base accounts can be searched".to_string(),
+            )));
+        }
+        accounts.push(account_identifier.account_address()?);
+    }
+    if let Some(ref address) = request.address {
+        let account_identifier = AccountIdentifier {
+            address: address.clone(),
+            sub_account: None,
+        };
+        accounts.push(account_identifier.account_address()?);
+    }
+    Ok(accounts)
+}
+
+/// Retrieves the versions of the transactions sent by an account, or which moved the native coin
+/// in or out of it, up to `max_version`
+///
+/// The transactions are looked up newest first, and only the newest `num_versions` of each kind
+/// are retrieved, which contain the newest `num_versions` transactions of the account.
+async fn get_account_versions(
+    server_context: &RosettaContext,
+    account: AccountAddress,
+    max_version: Option<u64>,
+    num_versions: Option<usize>,
+) -> ApiResult<BTreeSet<u64>> {
+    let rest_client = server_context.rest_client()?;
+    let rest_client = rest_client.as_ref();
+    let page_size = server_context.block_cache()?.page_size();
+    let mut versions = BTreeSet::new();
+
+    // An account which doesn't exist can't have any transactions
+    let sequence_number = match rest_client.get_account_bcs(account).await {
+        Ok(response) => response.into_inner().sequence_number(),
+        Err(err) => match ApiError::from(err) {
+            ApiError::AccountNotFound(_) => return Ok(versions),
+            err => return Err(err),
+        },
+    };
+
+    // Transactions sent by the account, from the account transaction index
+    let sent = collect_newest_versions(
+        sequence_number,
+        page_size,
+        max_version,
+        num_versions,
+        |start, limit| async move {
+            Ok::<Vec<u64>, ApiError>(
+                rest_client
+                    .get_account_transactions_bcs(account, Some(start), Some(limit))
+                    .await?
+                    .into_inner()
+                    .into_iter()
+                    .map(|txn| txn.version)
+                    .collect(),
+            )
+        },
+    )
+    .await?;
+    versions.extend(sent);
+
+    // Transactions moving the native coin, from the events of the coin store
+    let coin_store_type = format!(
+        "0x1::{}::{}<{}>",
+        COIN_MODULE,
+        COIN_STORE_RESOURCE,
+        native_coin_tag()
+    );
+    let coin_store = match rest_client
+        .get_account_resource_bcs::<CoinStoreResource>(account, &coin_store_type)
+        .await
+    {
+        Ok(response) => response.into_inner(),
+        Err(err) => match ApiError::from(err) {
+            ApiError::ResourceNotFound(_) => return Ok(versions),
+            err => return Err(err),
+        },
+    };
+    let coin_store_type = coin_store_type.as_str();
+    for (field_name, count) in [
+        (DEPOSIT_EVENTS_FIELD, coin_store.deposit_events().count()),
+        (WITHDRAW_EVENTS_FIELD, coin_store.withdraw_events().count()),
+    ] {
+        let moved = collect_newest_versions(
+            count,
+            page_size,
+            max_version,
+            num_versions,
+            |start, limit| async move {
+                Ok::<Vec<u64>, ApiError>(
+                    rest_client
+                        .get_account_events_bcs(
+                            account,
+                            coin_store_type,
+                            field_name,
+                            Some(start),
+                            Some(limit),
+                        )
+                        .await?
+                        .into_inner()
+                        .into_iter()
+                        .map(|event| event.transaction_version)
+                        .collect(),
+                )
+            },
+        )
+        .await?;
+        versions.extend(moved);
+    }
+
+    Ok(versions)
+}
+
+/// Collects the versions of a list of `count` items indexed by sequence number, e.g. the
+/// transactions sent by an account or the events of an event handle, newest first
+///
+/// Pages of items are fetched with `fetch_page(start, limit)` from the end of the list, until
+/// `num_versions` versions up to `max_version` are collected.
+async fn collect_newest_versions<F, Fut>(
+    count: u64,
+    page_size: u16,
+    max_version: Option<u64>,
+    num_versions: Option<usize>,
+    fetch_page: F,
+) -> ApiResult<Vec<u64>>
+where
+    F: Fn(u64, u16) -> Fut,
+    Fut: Future<Output = ApiResult<Vec<u64>>>,
+{
+    let mut versions = vec![];
+    let mut end = count;
+    while end > 0 && num_versions.map_or(true, |num_versions| versions.len() < num_versions) {
+        let start = end.saturating_sub(page_size as u64);
+        let page = fetch_page(start, (end - start) as u16).await?;
+        if page.is_empty() {
+            break;
+        }
+        end = start;
+        versions.extend(
+            page.into_iter()
+                .rev()
+                .filter(|version| max_version.map_or(true, |max_version| *version <= max_version)),
+        );
+    }
+    if let Some(num_versions) = num_versions {
+        versions.truncate(num_versions);
+    }
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_collect_newest_versions() {
+        // The item with sequence number `i` is at version `10 * i`
+        let pages = Mutex::new(vec![]);
+        let fetch_page = |start: u64, limit: u16| {
+            pages.lock().unwrap().push((start, limit));
+            async move {
+                Ok::<Vec<u64>, ApiError>((start..start + limit as u64).map(|i| 10 * i).collect())
+            }
+        };
+
+        // Pages are fetched from the end, until enough versions are collected
+        let versions = collect_newest_versions(10, 3, None, Some(4), &fetch_page)
+            .await
+            .unwrap();
+        assert_eq!(versions, vec![90, 80, 70, 60]);
+        assert_eq!(pages.lock().unwrap().drain(..).collect::<Vec<_>>(), vec![
+            (7, 3),
+            (4, 3)
+        ]);
+
+        // Versions above the max version don't count
+        let versions = collect_newest_versions(10, 3, Some(55), Some(2), &fetch_page)
+            .await
+            .unwrap();
+        assert_eq!(versions, vec![50, 40]);
+
+        // Without a limit, the whole list is fetched
+        pages.lock().unwrap().clear();
+        let versions = collect_newest_versions(10, 3, None, None, &fetch_page)
+            .await
+            .unwrap();
+        assert_eq!(versions, (0..10).rev().map(|i| 10 * i).collect::<Vec<_>>());
+        assert_eq!(*pages.lock().unwrap(), vec![(7, 3), (4, 3), (1, 3), (0, 1)]);
+    }
+}
@@ -6,9 +6,10 @@
 //! [Spec](https://www.rosetta-api.org/docs/api_identifiers.html)
 
 use crate::{
-    common::{to_hex_lower, BlockHash, BLOCKCHAIN},
+    common::{strip_hex_prefix, to_hex_lower, BlockHash, BLOCKCHAIN},
     error::{ApiError, ApiResult},
 };
+use aptos_crypto::HashValue;
 use aptos_types::{
     account_address::AccountAddress, chain_id::ChainId, transaction::TransactionInfo,
 };
@@ -472,6 +473,14 @@ pub struct TransactionIdentifier {
     pub hash: String,
 }
 
+impl TransactionIdentifier {
+    /// Parse the hash of the transaction
+    pub fn hash_value(&self) -> ApiResult<HashValue> {
+        HashValue::from_hex(strip_hex_prefix(&self.hash))
+            .map_err(|_| ApiError::InvalidInput(Some("Invalid transaction hash".to_string())))
+    }
+}
+
 impl From<&TransactionInfo> for TransactionIdentifier {
     fn from(txn: &TransactionInfo) -> Self {
         TransactionIdentifier {
@@ -555,4 +564,21 @@ mod test {
 
         assert!(stake.operator_address().is_err());
     }
+
+    #[test]
+    fn test_transaction_id() {
+        let hash = HashValue::sha3_256_of(b"transaction");
+        let transaction_id = TransactionIdentifier::from(hash);
+        assert_eq!(Ok(hash), transaction_id.hash_value());
+
+        let prefixed = TransactionIdentifier {
+            hash: hash.to_hex_literal(),
+        };
+        assert_eq!(Ok(hash), prefixed.hash_value());
+
+        let invalid = TransactionIdentifier {
+            hash: "0x1234".to_string(),
+        };
+        assert!(invalid.hash_value().is_err());
+    }
 }
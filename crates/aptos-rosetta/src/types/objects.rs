@@ -34,7 +34,7 @@ use aptos_types::{
     fee_statement::FeeStatement,
     stake_pool::{SetOperatorEvent, StakePool},
     state_store::state_key::{StateKey, StateKeyInner},
-    transaction::{EntryFunction, TransactionPayload},
+    transaction::{EntryFunction, SignedTransaction, TransactionPayload},
     write_set::{WriteOp, WriteSet},
 };
 use itertools::Itertools;
@@ -128,9 +128,9 @@ pub struct Block {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BlockTransaction {
     /// Block associated with transaction
-    block_identifier: BlockIdentifier,
+    pub block_identifier: BlockIdentifier,
     /// Transaction associated with block
-    transaction: Transaction,
+    pub transaction: Transaction,
 }
 
 /// Currency represented as atomic units including decimals
@@ -924,10 +924,11 @@ impl Transaction {
         } else {
             // Parse all failed operations from the payload
             if let Some(user_txn) = maybe_user_txn {
-                let mut ops = parse_failed_operations_from_txn_payload(
+                let mut ops = parse_operations_from_txn_payload(
                     operation_index,
                     user_txn.sender(),
                     user_txn.payload(),
+                    Some(OperationStatusType::Failure),
                 );
                 operation_index += ops.len() as u64;
                 operations.append(&mut ops);
@@ -962,16 +963,47 @@ impl Transaction {
             },
         })
     }
+
+    /// Converts a transaction which is still in mempool
+    ///
+    /// There are no state changes yet, so the operations are parsed from the payload and have
+    /// no status.  The gas fee isn't known until the transaction is committed, so it's left out,
+    /// and the version is 0 as the transaction doesn't have one yet.
+    pub fn from_pending_transaction(txn: &SignedTransaction) -> Transaction {
+        let mut operations =
+            parse_operations_from_txn_payload(0, txn.sender(), txn.payload(), None);
+
+        // Same ordering as committed transactions (Create before transfer) (Withdraw before deposit)
+        operations.sort();
+        for (i, operation) in operations.iter_mut().enumerate() {
+            operation.operation_identifier.index = i as u64;
+        }
+
+        Transaction {
+            transaction_identifier: txn.clone().committed_hash().into(),
+            operations,
+            metadata: TransactionMetadata {
+                transaction_type: TransactionType::User,
+                version: 0.into(),
+                failed: false,
+                vm_status: PENDING_VM_STATUS.to_string(),
+            },
+        }
+    }
 }
 
+/// VM status of a transaction which hasn't been executed yet
+pub const PENDING_VM_STATUS: &str = "Pending";
+
 /// Parses operations from the transaction payload
 ///
-/// This case only occurs if the transaction failed, and that's because it's less accurate
-/// than just following the state changes
-fn parse_failed_operations_from_txn_payload(
+/// This case only occurs if the transaction failed or hasn't been committed yet, and that's
+/// because it's less accurate than just following the state changes
+fn parse_operations_from_txn_payload(
     operation_index: u64,
     sender: AccountAddress,
     payload: &TransactionPayload,
+    status: Option<OperationStatusType>,
 ) -> Vec<Operation> {
     let mut operations = vec![];
     if let TransactionPayload::EntryFunction(inner) = payload {
@@ -992,14 +1024,20 @@ fn parse_failed_operations_from_txn_payload(
                             native_coin(),
                             sender,
                             operation_index,
+                            status,
                         )
                     }
                 }
             },
             (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_FUNCTION) => {
                 // We could add a create here as well, but we don't know if it will actually happen
-                operations =
-                    parse_transfer_from_txn_payload(inner, native_coin(), sender, operation_index)
+                operations = parse_transfer_from_txn_payload(
+                    inner,
+                    native_coin(),
+                    sender,
+                    operation_index,
+                    status,
+                )
             },
             (AccountAddress::ONE, ACCOUNT_MODULE, CREATE_ACCOUNT_FUNCTION) => {
                 if let Some(Ok(address)) = inner
@@ -1009,7 +1047,7 @@ fn parse_failed_operations_from_txn_payload(
                 {
                     operations.push(Operation::create_account(
                         operation_index,
-                        Some(OperationStatusType::Failure),
+                        status,
                         address,
                         sender,
                     ));
@@ -1026,7 +1064,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_set_operator_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse set operator {:?}", inner);
@@ -1037,7 +1075,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_set_voter_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse set voter {:?}", inner);
@@ -1048,7 +1086,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_reset_lockup_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse reset lockup {:?}", inner);
@@ -1059,7 +1097,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_update_commission_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse update commission {:?}", inner);
@@ -1070,7 +1108,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_create_stake_pool_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse create staking pool {:?}", inner);
@@ -1081,7 +1119,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_unlock_stake_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse unlock stake {:?}", inner);
@@ -1094,7 +1132,7 @@ fn parse_failed_operations_from_txn_payload(
                     inner.args(),
                 ) {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse distribute staking rewards {:?}", inner);
@@ -1105,7 +1143,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_delegation_pool_add_stake_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse delegation_pool::add_stake {:?}", inner);
@@ -1116,7 +1154,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_delegation_pool_withdraw_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse delegation_pool::withdraw {:?}", inner);
@@ -1127,7 +1165,7 @@ fn parse_failed_operations_from_txn_payload(
                     parse_delegation_pool_unlock_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
                     }
                 } else {
                     warn!("Failed to parse delegation_pool::unlock {:?}", inner);
@@ -1146,6 +1184,7 @@ fn parse_transfer_from_txn_payload(
     currency: Currency,
     sender: AccountAddress,
     operation_index: u64,
+    status: Option<OperationStatusType>,
 ) -> Vec<Operation> {
     let mut operations = vec![];
 
@@ -1158,14 +1197,14 @@ fn parse_transfer_from_txn_payload(
     if let (Some(Ok(receiver)), Some(Ok(amount))) = (maybe_receiver, maybe_amount) {
         operations.push(Operation::withdraw(
             operation_index,
-            Some(OperationStatusType::Failure),
+            status,
             AccountIdentifier::base_account(sender),
             currency.clone(),
             amount,
         ));
         operations.push(Operation::deposit(
             operation_index + 1,
-            Some(OperationStatusType::Failure),
+            status,
             AccountIdentifier::base_account(receiver),
             currency,
             amount,
@@ -3,9 +3,9 @@
 
 use crate::{
     types::{
-        AccountIdentifier, Allow, Amount, Block, BlockIdentifier, Currency, InternalOperation,
-        NetworkIdentifier, Operation, PartialBlockIdentifier, Peer, PublicKey, Signature,
-        SigningPayload, SyncStatus, Transaction, TransactionIdentifier, Version,
+        AccountIdentifier, Allow, Amount, Block, BlockIdentifier, BlockTransaction, Currency,
+        InternalOperation, NetworkIdentifier, Operation, PartialBlockIdentifier, Peer, PublicKey,
+        Signature, SigningPayload, SyncStatus, Transaction, TransactionIdentifier, Version,
     },
     AccountAddress, ApiError,
 };
@@ -559,6 +559,77 @@ pub struct NetworkStatusResponse {
     pub peers: Vec<Peer>,
 }
 
+/// Request to search for transactions matching a set of conditions
+///
+/// Searches are by transaction hash or by account, anything else is rejected
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Whether transactions must match all conditions or any of them, defaults to `and`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator: Option<SearchOperator>,
+    /// The highest block index to search, defaults to the latest block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_block: Option<u64>,
+    /// Number of matching transactions to skip, from the newest one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Maximum number of transactions to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// Hash of a transaction to look up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    /// Account whose transactions to look up, only base accounts are supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
+    /// Hex encoded address of an account, equivalent to its base [`AccountIdentifier`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Only the native coin is supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    /// Not supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Not supported
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub operation_type: Option<String>,
+    /// Not supported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+/// How the conditions of a [`SearchTransactionsRequest`] are combined
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/Operator.html)
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchOperator {
+    Or,
+    #[default]
+    And,
+}
+
+/// Response with the transactions matching a search, newest first
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsResponse {
+    /// Matching transactions along with their blocks
+    pub transactions: Vec<BlockTransaction>,
+    /// Number of matching transactions found, regardless of the offset and limit. The search
+    /// stops once the page is filled, so this may only count the transactions up to the page,
+    /// plus one if there are more
+    pub total_count: u64,
+    /// Offset to use to get the next page, if there are more matching transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
 /// Response with a transaction that was hashed or submitted
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/TransactionIdentifierResponse.html)
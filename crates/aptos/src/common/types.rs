@@ -21,7 +21,9 @@ use anyhow::Context;
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     encoding_type::{EncodingError, EncodingType},
-    x25519, PrivateKey, ValidCryptoMaterialStringExt,
+    hash::CryptoHash,
+    traits::signing_message,
+    x25519, PrivateKey, SigningKey, ValidCryptoMaterialStringExt,
 };
 use aptos_gas_profiling::FrameName;
 use aptos_global_constants::adjust_gas_headroom;
@@ -40,8 +42,9 @@ use aptos_sdk::{
 use aptos_types::{
     chain_id::ChainId,
     transaction::{
-        authenticator::AuthenticationKey, EntryFunction, MultisigTransactionPayload, Script,
-        SignedTransaction, TransactionArgument, TransactionPayload, TransactionStatus,
+        authenticator::{AccountAuthenticator, AuthenticationKey},
+        EntryFunction, MultisigTransactionPayload, RawTransaction, Script, SignedTransaction,
+        TransactionArgument, TransactionPayload, TransactionStatus,
     },
 };
 use async_trait::async_trait;
@@ -1491,11 +1494,60 @@ pub struct TransactionOptions {
     /// flamegraphs that reflect the gas usage.
     #[clap(long)]
     pub(crate) profile_gas: bool,
+
+    /// Execute the transaction locally on top of the state of a network at a version, given as
+    /// `<url>@<version>`, instead of submitting it
+    ///
+    /// The sender is impersonated, so the transaction is signed with the key of the profile
+    /// whichever key the sender has on the network. Only supported by `aptos move publish`,
+    /// `aptos move run` and `aptos move run-script`, other commands fail without submitting the
+    /// transaction.
+    #[clap(long)]
+    pub(crate) fork: Option<ForkTarget>,
+
+    /// Directory to cache the state read from the forked network in
+    ///
+    /// Defaults to `.aptos/fork-cache`
+    #[clap(long, value_parser, requires = "fork")]
+    pub(crate) fork_cache_dir: Option<PathBuf>,
+
+    /// File to load the local state of the fork from, and save it to after the transaction
+    ///
+    /// This lets a sequence of transactions, e.g. publishing a package and running its
+    /// functions, be executed on the fork.
+    #[clap(long, value_parser, requires = "fork")]
+    pub(crate) fork_state_file: Option<PathBuf>,
+}
+
+/// A network forked at a version, given as `<url>@<version>`
+#[derive(Clone, Debug)]
+pub struct ForkTarget {
+    pub url: reqwest::Url,
+    pub version: u64,
+}
+
+impl FromStr for ForkTarget {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (url, version) = s.rsplit_once('@').ok_or_else(|| {
+            CliError::CommandArgumentError(format!(
+                "Invalid fork '{}', expected <url>@<version>",
+                s
+            ))
+        })?;
+        Ok(ForkTarget {
+            url: reqwest::Url::parse(url)
+                .map_err(|err| CliError::UnableToParse("fork URL", err.to_string()))?,
+            version: u64::from_str(version)
+                .map_err(|err| CliError::UnableToParse("fork version", err.to_string()))?,
+        })
+    }
 }
 
 impl TransactionOptions {
     /// Builds a rest client
-    fn rest_client(&self) -> CliTypedResult<Client> {
+    pub(crate) fn rest_client(&self) -> CliTypedResult<Client> {
         self.rest_options.client(&self.profile_options)
     }
 
@@ -1548,6 +1600,98 @@ impl TransactionOptions {
             .extract_public_key(self.encoding_options.encoding, &self.profile_options)
     }
 
+    /// Builds a transaction without accessing the network, so the sequence number, chain id and
+    /// gas must be given rather than looked up on chain
+    pub fn build_offline_transaction(
+        &self,
+        payload: TransactionPayload,
+        sequence_number: u64,
+        chain_id: ChainId,
+        expiration_timestamp_secs: Option<u64>,
+    ) -> CliTypedResult<RawTransaction> {
+        let sender_address = if let Some(sender_address) = self.sender_account {
+            sender_address
+        } else {
+            self.get_public_key_and_address()?.1
+        };
+        let gas_unit_price = self.gas_options.gas_unit_price.ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "--gas-unit-price must be given to build a transaction offline".to_string(),
+            )
+        })?;
+        let max_gas = self.gas_options.max_gas.ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "--max-gas must be given to build a transaction offline".to_string(),
+            )
+        })?;
+        let expiration_timestamp_secs =
+            if let Some(expiration_timestamp_secs) = expiration_timestamp_secs {
+                expiration_timestamp_secs
+            } else {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                    .as_secs()
+                    + self.gas_options.expiration_secs
+            };
+
+        Ok(RawTransaction::new(
+            sender_address,
+            sequence_number,
+            payload,
+            max_gas,
+            gas_unit_price,
+            expiration_timestamp_secs,
+            chain_id,
+        ))
+    }
+
+    /// Signs `message` with the key of the profile or the given private key, without accessing
+    /// the network.  Profiles without a private key are signed with their Ledger.
+    ///
+    /// Returns the address of the signer along with its authenticator
+    pub fn sign_offline<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+    ) -> CliTypedResult<(AccountAddress, AccountAuthenticator)> {
+        match self.get_transaction_account_type()? {
+            AccountType::Local => {
+                let (private_key, address) = self.get_key_and_address()?;
+                let signature = private_key
+                    .sign(message)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+                Ok((
+                    address,
+                    AccountAuthenticator::ed25519(private_key.public_key(), signature),
+                ))
+            },
+            AccountType::HardwareWallet => {
+                let (public_key, address) = self.get_public_key_and_address()?;
+                let derivation_path = self.profile_options.derivation_path()?.ok_or_else(|| {
+                    CliError::CommandArgumentError(
+                        "Profile has neither a private key nor a derivation path".to_string(),
+                    )
+                })?;
+                let ledger_account = HardwareWalletAccount::new(
+                    address,
+                    public_key.clone(),
+                    derivation_path,
+                    HardwareWalletType::Ledger,
+                    0,
+                );
+                let message = signing_message(message)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+                let signature = ledger_account
+                    .sign_arbitrary_message(&message)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+                Ok((
+                    address,
+                    AccountAuthenticator::ed25519(public_key, signature),
+                ))
+            },
+        }
+    }
+
     /// Gets the auth key by account address. We need to fetch the auth key from Rest API rather than creating an
     /// auth key out of the public key.
     pub(crate) async fn auth_key(
@@ -1573,6 +1717,14 @@ impl TransactionOptions {
         &self,
         payload: TransactionPayload,
     ) -> CliTypedResult<Transaction> {
+        // Executing on a fork returns a summary of the transaction rather than the transaction,
+        // see `profile_or_submit`
+        if self.fork.is_some() {
+            return Err(CliError::CommandArgumentError(
+                "--fork is not supported by this command, the transaction was not submitted"
+                    .to_string(),
+            ));
+        }
         let client = self.rest_client()?;
         let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
 
@@ -1809,6 +1961,83 @@ impl TransactionOptions {
         })
     }
 
+    /// Executes the transaction on top of the state of a forked network instead of submitting it.
+    pub async fn execute_on_fork(
+        &self,
+        fork: &ForkTarget,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<TransactionSummary> {
+        const DEFAULT_GAS_UNIT_PRICE: u64 = 100;
+        const DEFAULT_MAX_GAS: u64 = 2_000_000;
+
+        let cache_dir = self
+            .fork_cache_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(CONFIG_FOLDER).join("fork-cache"));
+        let debugger = AptosDebugger::rest_client(Client::new(fork.url.clone()))?;
+        let executor = debugger.fork(fork.version, Some(cache_dir))?;
+        if let Some(state_file) = &self.fork_state_file {
+            if state_file.exists() {
+                executor.state_view().load_writes(state_file)?;
+            }
+        }
+
+        // Impersonate the sender, which may be any account of the network
+        let (sender_key, sender_address) = self.get_key_and_address()?;
+        executor.impersonate(sender_address, &sender_key.public_key())?;
+        let sequence_number = executor.sequence_number(sender_address)?;
+        let gas_unit_price = self
+            .gas_options
+            .gas_unit_price
+            .unwrap_or(DEFAULT_GAS_UNIT_PRICE);
+        let max_gas = self.gas_options.max_gas.unwrap_or_else(|| {
+            if gas_unit_price == 0 {
+                DEFAULT_MAX_GAS
+            } else {
+                let balance = executor.balance(sender_address).unwrap_or_default();
+                std::cmp::min(balance / gas_unit_price, DEFAULT_MAX_GAS)
+            }
+        });
+
+        let transaction_factory = TransactionFactory::new(executor.chain_id()?)
+            .with_gas_unit_price(gas_unit_price)
+            .with_max_gas_amount(max_gas)
+            .with_transaction_expiration_time(self.gas_options.expiration_secs);
+        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
+        let transaction =
+            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+        let hash = transaction.clone().committed_hash();
+        let output = executor.execute_transaction(transaction)?;
+
+        if let Some(state_file) = &self.fork_state_file {
+            executor.state_view().save_writes(state_file)?;
+        }
+
+        let (success, vm_status) = match output.status() {
+            TransactionStatus::Keep(exec_status) => {
+                (Some(exec_status.is_success()), format!("{:?}", exec_status))
+            },
+            TransactionStatus::Discard(status_code) => {
+                (None, format!("Discarded: {:?}", status_code))
+            },
+            TransactionStatus::Retry => (None, "Retry".to_string()),
+        };
+        Ok(TransactionSummary {
+            transaction_hash: hash.into(),
+            gas_used: Some(output.gas_used()),
+            gas_unit_price: Some(gas_unit_price),
+            pending: None,
+            sender: Some(sender_address),
+            // The transaction is only executed on the fork, so it has no sequence number or
+            // version on the network
+            sequence_number: None,
+            success,
+            timestamp_us: None,
+            version: Some(fork.version),
+            vm_status: Some(vm_status),
+        })
+    }
+
     pub async fn estimate_gas_price(&self) -> CliTypedResult<u64> {
         let client = self.rest_client()?;
         client
@@ -484,6 +484,9 @@ pub async fn profile_or_submit(
     // Profile gas if needed.
     if txn_options_ref.profile_gas {
         txn_options_ref.profile_gas(payload).await
+    } else if let Some(fork) = &txn_options_ref.fork {
+        // Execute the transaction on a fork of the network instead of submitting it
+        txn_options_ref.execute_on_fork(fork, payload).await
     } else {
         // Otherwise submit the transaction.
         txn_options_ref
@@ -4,14 +4,23 @@
 use crate::common::types::{CliCommand, CliError, CliResult, CliTypedResult, MovePackageDir};
 use aptos_framework::extended_checks;
 use async_trait::async_trait;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
 use move_coverage::{
-    coverage_map::CoverageMap, format_csv_summary, format_human_summary,
-    source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
+    coverage_map::CoverageMap,
+    export::{write_cobertura, write_lcov},
+    format_csv_summary, format_human_summary,
+    source_coverage::{FileCoverage, SourceCoverageBuilder},
+    summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig, CompilerConfig};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 /// Display a coverage summary for all modules in a package
 ///
@@ -142,6 +151,89 @@ impl CliCommand<()> for BytecodeCoverage {
     }
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CoverageFormat {
+    Lcov,
+    Cobertura,
+}
+
+/// Export coverage of the package sources for coverage reporting tools
+///
+/// Maps the coverage of instructions back to the source lines and branches of the modules of the
+/// package, and writes it as an LCOV tracefile or a Cobertura XML report.
+#[derive(Debug, Parser)]
+pub struct ExportCoverage {
+    /// Format of the coverage report
+    #[clap(long, value_enum)]
+    pub format: CoverageFormat,
+    /// File to write the coverage report to
+    #[clap(long, value_parser)]
+    pub output_file: PathBuf,
+    #[clap(flatten)]
+    pub move_options: MovePackageDir,
+}
+
+impl ExportCoverage {
+    fn export<W: Write>(
+        &self,
+        package: &CompiledPackage,
+        package_path: &Path,
+        files: &[FileCoverage],
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        match self.format {
+            CoverageFormat::Lcov => write_lcov(files, writer),
+            CoverageFormat::Cobertura => write_cobertura(
+                package.compiled_package_info.package_name.as_str(),
+                package_path,
+                files,
+                writer,
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand<()> for ExportCoverage {
+    fn command_name(&self) -> &'static str {
+        "ExportCoverage"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let (coverage_map, package) = compile_coverage(self.move_options.clone())?;
+        let package_path = self.move_options.get_package_path()?;
+        // Report paths relative to the package, whichever way it was found
+        let package_path = package_path.canonicalize().unwrap_or(package_path);
+
+        let mut files: BTreeMap<PathBuf, FileCoverage> = BTreeMap::new();
+        for unit in package.root_modules() {
+            if let CompiledUnit::Module(NamedCompiledModule {
+                module, source_map, ..
+            }) = &unit.unit
+            {
+                let source_path = unit
+                    .source_path
+                    .canonicalize()
+                    .unwrap_or_else(|_| unit.source_path.clone());
+                let file_coverage = SourceCoverageBuilder::new(module, &coverage_map, source_map)
+                    .compute_file_coverage(&source_path);
+                match files.get_mut(&source_path) {
+                    Some(file) => file.merge(file_coverage),
+                    None => {
+                        files.insert(source_path, file_coverage);
+                    },
+                }
+            }
+        }
+        let files: Vec<_> = files.into_values().collect();
+
+        // The report is written to a file, as the standard output holds the result of the command
+        File::create(&self.output_file)
+            .and_then(|mut file| self.export(&package, &package_path, &files, &mut file))
+            .map_err(|err| CliError::UnexpectedError(format!("Failed to export coverage {}", err)))
+    }
+}
+
 fn compile_coverage(
     move_options: MovePackageDir,
 ) -> CliTypedResult<(CoverageMap, CompiledPackage)> {
@@ -178,6 +270,7 @@ pub enum CoveragePackage {
     Summary(SummaryCoverage),
     Source(SourceCoverage),
     Bytecode(BytecodeCoverage),
+    Export(ExportCoverage),
 }
 
 impl CoveragePackage {
@@ -186,6 +279,7 @@ impl CoveragePackage {
             Self::Summary(tool) => tool.execute_serialized_success().await,
             Self::Source(tool) => tool.execute_serialized_success().await,
             Self::Bytecode(tool) => tool.execute_serialized_success().await,
+            Self::Export(tool) => tool.execute_serialized_success().await,
         }
     }
 }
@@ -50,13 +50,17 @@ use itertools::Itertools;
 use move_cli::{self, base::test::UnitTestResult};
 use move_command_line_common::env::MOVE_HOME;
 use move_core_types::{identifier::Identifier, language_storage::ModuleId, u256::U256};
+#[cfg(feature = "debug-adapter")]
+use move_debug_adapter::{DebugServer, SourceIndex};
 use move_package::{
     source_package::layout::SourcePackageLayout, BuildConfig, CompilerConfig, CompilerVersion,
 };
-use move_unit_test::UnitTestingConfig;
+use move_unit_test::{test_reporter::ReportFormat, UnitTestingConfig};
 pub use package_hooks::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+#[cfg(feature = "debug-adapter")]
+use std::net::TcpListener;
 use std::{
     collections::BTreeMap,
     fmt::{Display, Formatter},
@@ -92,6 +96,7 @@ pub enum MoveTool {
     #[clap(subcommand, hide = true)]
     Show(show::ShowTool),
     Test(TestPackage),
+    UpdateDeps(UpdateDependencies),
     VerifyPackage(VerifyPackage),
     View(ViewFunction),
 }
@@ -118,6 +123,7 @@ impl MoveTool {
             MoveTool::RunScript(tool) => tool.execute_serialized().await,
             MoveTool::Show(tool) => tool.execute_serialized().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
+            MoveTool::UpdateDeps(tool) => tool.execute_serialized().await,
             MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
             MoveTool::View(tool) => tool.execute_serialized().await,
         }
@@ -439,6 +445,22 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    /// Debug the tests from a Debug Adapter Protocol client, e.g. an editor, connecting to this
+    /// local port
+    ///
+    /// The tests wait for the client to connect, and are then run one at a time. Requires the CLI
+    /// to be built with the `debug-adapter` feature.
+    #[clap(long)]
+    pub dap_port: Option<u16>,
+
+    /// Write a machine-readable report of the test results, for CI systems
+    #[clap(long, value_enum, requires = "report_file")]
+    pub report: Option<ReportFormat>,
+
+    /// File to write the report of the test results to
+    #[clap(long, value_parser, requires = "report")]
+    pub report_file: Option<PathBuf>,
 }
 
 #[async_trait]
@@ -466,16 +488,38 @@ impl CliCommand<&'static str> for TestPackage {
         };
 
         let path = self.move_options.get_package_path()?;
+        let unit_test_config = UnitTestingConfig {
+            filter: self.filter.clone(),
+            report_stacktrace_on_abort: true,
+            report_storage_on_error: self.dump_state,
+            ignore_compile_warnings: self.ignore_compile_warnings,
+            report_format: self.report,
+            report_file: self.report_file.clone(),
+            ..UnitTestingConfig::default_with_bound(None)
+        };
+        #[cfg(feature = "debug-adapter")]
+        let (unit_test_config, debug_server) = match self.dap_port {
+            Some(port) => (
+                // The debugger can only follow a single thread
+                UnitTestingConfig {
+                    num_threads: 1,
+                    ..unit_test_config
+                },
+                Some(start_debug_server(path.as_path(), config.clone(), port)?),
+            ),
+            None => (unit_test_config, None),
+        };
+        #[cfg(not(feature = "debug-adapter"))]
+        if self.dap_port.is_some() {
+            return Err(CliError::CommandArgumentError(
+                "--dap-port requires the CLI to be built with the `debug-adapter` feature"
+                    .to_string(),
+            ));
+        }
         let result = move_cli::base::test::run_move_unit_tests(
             path.as_path(),
             config.clone(),
-            UnitTestingConfig {
-                filter: self.filter.clone(),
-                report_stacktrace_on_abort: true,
-                report_storage_on_error: self.dump_state,
-                ignore_compile_warnings: self.ignore_compile_warnings,
-                ..UnitTestingConfig::default_with_bound(None)
-            },
+            unit_test_config,
             // TODO(Gas): we may want to switch to non-zero costs in the future
             aptos_debug_natives::aptos_debug_natives(
                 NativeGasParameters::zeros(),
@@ -486,6 +530,10 @@ impl CliCommand<&'static str> for TestPackage {
             &mut std::io::stdout(),
         )
         .map_err(|err| CliError::UnexpectedError(format!("Failed to run tests: {:#}", err)))?;
+        #[cfg(feature = "debug-adapter")]
+        if let Some(debug_server) = debug_server {
+            debug_server.finish();
+        }
 
         // Print coverage summary if --coverage is set
         if self.compute_coverage {
@@ -509,6 +557,26 @@ impl CliCommand<&'static str> for TestPackage {
     }
 }
 
+/// Compiles the package for its source maps, and waits for a debugger client to connect
+#[cfg(feature = "debug-adapter")]
+fn start_debug_server(
+    package_path: &Path,
+    config: BuildConfig,
+    port: u16,
+) -> CliTypedResult<DebugServer> {
+    let package = config
+        .compile_package(package_path, &mut std::io::sink())
+        .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+    let sources = SourceIndex::from_compiled_package(&package)
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|err| CliError::IO(format!("Port {}", port), err))?;
+    eprintln!("Waiting for a debugger to connect on port {}", port);
+    DebugServer::accept(&listener, sources)
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to start debugger: {:#}", err)))
+}
+
 /// Proves a Move package
 ///
 /// This is a tool for formal verification of a Move package using
@@ -1201,6 +1269,40 @@ impl CliCommand<&'static str> for CleanPackage {
     }
 }
 
+/// Updates the dependencies of a package to their latest code
+///
+/// Resolves the git dependencies of the package to the latest commit of their revision, and its
+/// on-chain dependencies to their latest upgrade, and records them in the package's Move.lock.
+/// Later builds then use the recorded dependencies, until they are updated again.
+#[derive(Parser)]
+pub struct UpdateDependencies {
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+}
+
+#[async_trait]
+impl CliCommand<&'static str> for UpdateDependencies {
+    fn command_name(&self) -> &'static str {
+        "UpdateDependencies"
+    }
+
+    async fn execute(self) -> CliTypedResult<&'static str> {
+        let path = self.move_options.get_package_path()?;
+        let config = BuildConfig {
+            additional_named_addresses: self.move_options.named_addresses(),
+            ..Default::default()
+        };
+        // Resolving on-chain dependencies blocks on requests to the node
+        task::spawn_blocking(move || {
+            config.update_lock_file_for_package(path.as_path(), &mut std::io::stderr())
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+        .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+        Ok("succeeded")
+    }
+}
+
 /// Run a Move function
 #[derive(Parser)]
 pub struct RunFunction {
@@ -2,14 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{common::types::load_account_arg, move_tool::CachedPackageRegistry};
+use anyhow::Context;
 use aptos_framework::UPGRADE_POLICY_CUSTOM_FIELD;
 use futures::executor::block_on;
 use move_package::{
-    compilation::package_layout::CompiledPackageLayout, package_hooks::PackageHooks,
+    package_hooks::{CustomDepVersion, PackageHooks},
     source_package::parsed_manifest::CustomDepInfo,
 };
 use move_symbol_pool::Symbol;
 use reqwest::Url;
+use std::fs;
+
+/// Records the version of the chain a downloaded package was read at, in its download directory
+const DOWNLOADED_VERSION_FILE: &str = "DownloadedVersion";
 
 pub fn register_package_hooks() {
     move_package::package_hooks::register_package_hooks(Box::new(AptosPackageHooks {}))
@@ -31,24 +36,54 @@ impl PackageHooks for AptosPackageHooks {
         _dep_name: Symbol,
         info: &CustomDepInfo,
     ) -> anyhow::Result<()> {
-        block_on(maybe_download_package(info))
+        block_on(download_package(info, None)).map(|_| ())
+    }
+
+    fn resolve_locked_custom_dependency(
+        &self,
+        _dep_name: Symbol,
+        info: &CustomDepInfo,
+        locked: Option<CustomDepVersion>,
+    ) -> anyhow::Result<Option<CustomDepVersion>> {
+        block_on(download_package(info, locked)).map(Some)
     }
 }
 
-async fn maybe_download_package(info: &CustomDepInfo) -> anyhow::Result<()> {
-    if !info
-        .download_to
-        .join(CompiledPackageLayout::BuildInfo.path())
-        .exists()
-    {
-        let registry = CachedPackageRegistry::create(
-            Url::parse(info.node_url.as_str())?,
-            load_account_arg(info.package_address.as_str())?,
-        )
-        .await?;
-        let package = registry.get_package(info.package_name).await?;
-        package.save_package_to_disk(info.download_to.as_path())
-    } else {
-        Ok(())
+/// Downloads a package as it was at the `locked` version of the chain, or else as it is now.
+/// Returns the version of the chain it was read at.
+async fn download_package(
+    info: &CustomDepInfo,
+    locked: Option<CustomDepVersion>,
+) -> anyhow::Result<CustomDepVersion> {
+    let version_path = info.download_to.join(DOWNLOADED_VERSION_FILE);
+    if let Some(locked) = locked {
+        // The package was already downloaded at this version
+        if fs::read_to_string(&version_path).ok() == Some(locked.ledger_version.to_string()) {
+            return Ok(locked);
+        }
     }
+
+    let url = Url::parse(info.node_url.as_str())?;
+    let address = load_account_arg(info.package_address.as_str())?;
+    let registry = match locked {
+        Some(locked) => {
+            CachedPackageRegistry::create_at_version(url, address, locked.ledger_version)
+                .await
+                .with_context(|| {
+                    format!(
+                    "Unable to read package '{}' at version {} locked in Move.lock, the node may \
+                    have pruned it. Update Move.lock to use the latest version of the package",
+                    info.package_name, locked.ledger_version
+                )
+                })?
+        },
+        None => CachedPackageRegistry::create(url, address).await?,
+    };
+    let package = registry.get_package(info.package_name).await?;
+    package.save_package_to_disk(info.download_to.as_path())?;
+    fs::write(&version_path, registry.ledger_version().to_string())?;
+    Ok(CustomDepVersion {
+        upgrade_number: package.upgrade_number(),
+        ledger_version: registry.ledger_version(),
+    })
 }
@@ -18,6 +18,8 @@ use std::{fmt, fs, path::Path};
 /// Represents the package registry at a given account.
 pub struct CachedPackageRegistry {
     inner: PackageRegistry,
+    /// The version of the chain the registry was read at
+    ledger_version: u64,
 }
 
 /// Represents the package metadata found in an registry.
@@ -42,11 +44,40 @@ impl CachedPackageRegistry {
     pub async fn create(url: Url, addr: AccountAddress) -> anyhow::Result<Self> {
         let client = Client::new(url);
         // Need to use a different type to deserialize JSON
-        let inner = client
+        let (inner, state) = client
             .get_account_resource_bcs::<PackageRegistry>(addr, "0x1::code::PackageRegistry")
             .await?
+            .into_parts();
+        Ok(Self {
+            inner,
+            ledger_version: state.version,
+        })
+    }
+
+    /// Creates the registry as it was at the given version of the chain.
+    pub async fn create_at_version(
+        url: Url,
+        addr: AccountAddress,
+        ledger_version: u64,
+    ) -> anyhow::Result<Self> {
+        let client = Client::new(url);
+        let inner = client
+            .get_account_resource_at_version_bcs::<PackageRegistry>(
+                addr,
+                "0x1::code::PackageRegistry",
+                ledger_version,
+            )
+            .await?
             .into_inner();
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            ledger_version,
+        })
+    }
+
+    /// Returns the version of the chain the registry was read at.
+    pub fn ledger_version(&self) -> u64 {
+        self.ledger_version
     }
 
     /// Returns the list of packages in this registry by name.
@@ -42,6 +42,10 @@ use crate::{
         AddStake, IncreaseLockup, InitializeStakeOwner, SetDelegatedVoter, SetOperator,
         UnlockStake, WithdrawStake,
     },
+    transaction::{
+        build::BuildTransaction, sign::SignTransaction, submit::SubmitTransaction,
+        TransactionFileFormat,
+    },
     CliCommand,
 };
 use aptos_config::config::Peer;
@@ -59,7 +63,7 @@ use aptos_rest_client::{
 };
 use aptos_sdk::move_types::{account_address::AccountAddress, language_storage::ModuleId};
 use aptos_temppath::TempPath;
-use aptos_types::on_chain_config::ValidatorSet;
+use aptos_types::{chain_id::ChainId, on_chain_config::ValidatorSet};
 use move_core_types::ident_str;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
@@ -338,6 +342,98 @@ impl CliTestFramework {
         .await
     }
 
+    /// Arguments of an entry function transferring `amount` Octas to the receiver
+    pub fn transfer_entry_function_args(
+        &self,
+        receiver_index: usize,
+        amount: u64,
+    ) -> EntryFunctionArguments {
+        EntryFunctionArguments {
+            function_id: Some(MemberId {
+                module_id: ModuleId::new(AccountAddress::ONE, ident_str!("aptos_account").into()),
+                member_id: ident_str!("transfer").into(),
+            }),
+            arg_vec: ArgWithTypeVec {
+                args: vec![
+                    ArgWithType::address(self.account_id(receiver_index)),
+                    ArgWithType::u64(amount),
+                ],
+            },
+            type_arg_vec: TypeArgVec { type_args: vec![] },
+            json_file: None,
+        }
+    }
+
+    /// Builds an unsigned transaction, running the script at `compiled_script_path` with the
+    /// arguments of `entry_function_args` if given
+    pub async fn build_transaction(
+        &self,
+        sender_index: usize,
+        entry_function_args: EntryFunctionArguments,
+        compiled_script_path: Option<PathBuf>,
+        sequence_number: u64,
+        chain_id: ChainId,
+        fee_payer_index: Option<usize>,
+        output_file: PathBuf,
+    ) -> CliTypedResult<Vec<AccountAddress>> {
+        BuildTransaction {
+            function_id: entry_function_args.function_id,
+            compiled_script_path,
+            type_arg_vec: entry_function_args.type_arg_vec,
+            arg_vec: entry_function_args.arg_vec,
+            json_file: entry_function_args.json_file,
+            sequence_number,
+            chain_id,
+            expiration_timestamp_secs: None,
+            secondary_signer_addresses: vec![],
+            fee_payer_address: fee_payer_index.map(|index| self.account_id(index)),
+            output_file,
+            format: TransactionFileFormat::Json,
+            txn_options: self.transaction_options(
+                sender_index,
+                Some(GasOptions {
+                    gas_unit_price: Some(100),
+                    max_gas: Some(10_000),
+                    ..Default::default()
+                }),
+            ),
+        }
+        .execute()
+        .await
+    }
+
+    pub async fn sign_transaction(
+        &self,
+        index: usize,
+        transaction_file: PathBuf,
+        output_file: PathBuf,
+    ) -> CliTypedResult<AccountAddress> {
+        SignTransaction {
+            transaction_file,
+            output_file,
+            format: TransactionFileFormat::Json,
+            txn_options: self.transaction_options(index, None),
+        }
+        .execute()
+        .await
+    }
+
+    pub async fn submit_transaction(
+        &self,
+        index: usize,
+        transaction_file: PathBuf,
+        signature_files: Vec<PathBuf>,
+    ) -> CliTypedResult<TransactionSummary> {
+        SubmitTransaction {
+            transaction_file,
+            signature_files,
+            format: TransactionFileFormat::Json,
+            txn_options: self.transaction_options(index, None),
+        }
+        .execute()
+        .await
+    }
+
     pub async fn show_validator_config(
         &self,
         pool_index: usize,
@@ -853,6 +949,7 @@ impl CliTestFramework {
             ignore_compile_warnings: false,
             compute_coverage: false,
             dump_state: false,
+            dap_port: None,
         }
         .execute()
         .await
@@ -3,10 +3,14 @@
 
 use crate::{
     move_tool::{ArgWithType, FunctionArgType},
+    test::CliTestFramework,
+    transaction::{TransactionFileFormat, TransactionSignature, UnsignedTransaction},
     CliResult, Tool,
 };
+use aptos_types::{chain_id::ChainId, transaction::TransactionPayload};
 use clap::Parser;
-use std::str::FromStr;
+use std::{io::ErrorKind, net::TcpListener, str::FromStr};
+use tempfile::TempDir;
 
 /// In order to ensure that there aren't duplicate input arguments for untested CLI commands,
 /// we call help on every command to ensure it at least runs
@@ -106,6 +110,169 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "stake", "set-operator", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "stake", "unlock-stake", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "stake", "withdraw-stake", "--help"]).await;
+
+    assert_cmd_not_panic(&["aptos", "transaction"]).await;
+    assert_cmd_not_panic(&["aptos", "transaction", "build", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "transaction", "sign", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "transaction", "submit", "--help"]).await;
+}
+
+/// Ensure a fee payer transaction can be built and signed offline, and assembled from the
+/// signature files of its signers
+#[tokio::test]
+async fn ensure_offline_signatures_assemble() {
+    let cli = CliTestFramework::local_new(3);
+    let dir = TempDir::new().unwrap();
+    let txn_file = dir.path().join("txn.json");
+
+    let signers = cli
+        .build_transaction(
+            0,
+            cli.transfer_entry_function_args(2, 10),
+            None,
+            0,
+            ChainId::test(),
+            Some(1),
+            txn_file.clone(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(signers, vec![cli.account_id(0), cli.account_id(1)]);
+
+    let mut signature_files = vec![];
+    for index in [0, 1] {
+        let signature_file = dir.path().join(format!("signature_{}.json", index));
+        let signer = cli
+            .sign_transaction(index, txn_file.clone(), signature_file.clone())
+            .await
+            .unwrap();
+        assert_eq!(signer, cli.account_id(index));
+        signature_files.push(signature_file);
+    }
+    // Only the sender and the fee payer can sign
+    cli.sign_transaction(2, txn_file.clone(), dir.path().join("signature_2.json"))
+        .await
+        .unwrap_err();
+
+    let format = TransactionFileFormat::Json;
+    let txn: UnsignedTransaction = format.read_file("UnsignedTransaction", &txn_file).unwrap();
+    let signatures: Vec<TransactionSignature> = signature_files
+        .iter()
+        .map(|path| format.read_file("TransactionSignature", path).unwrap())
+        .collect();
+    txn.clone()
+        .into_signed_transaction(signatures.clone())
+        .unwrap()
+        .verify_signature()
+        .unwrap();
+    // The fee payer signature is missing
+    txn.into_signed_transaction(signatures[..1].to_vec())
+        .unwrap_err();
+}
+
+/// Ensure a script transaction can be built offline from a compiled script
+#[tokio::test]
+async fn ensure_offline_script_transaction_builds() {
+    let cli = CliTestFramework::local_new(2);
+    let dir = TempDir::new().unwrap();
+    let script_file = dir.path().join("script.mv");
+    let txn_file = dir.path().join("txn.json");
+    let bytecode = vec![0xA1, 0x1C, 0xEB, 0x0B];
+    std::fs::write(&script_file, &bytecode).unwrap();
+
+    let mut script_args = cli.transfer_entry_function_args(1, 10);
+    script_args.function_id = None;
+    let signers = cli
+        .build_transaction(
+            0,
+            script_args,
+            Some(script_file),
+            0,
+            ChainId::test(),
+            None,
+            txn_file.clone(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(signers, vec![cli.account_id(0)]);
+
+    let txn: UnsignedTransaction = TransactionFileFormat::Json
+        .read_file("UnsignedTransaction", &txn_file)
+        .unwrap();
+    match txn.raw_txn.into_payload() {
+        TransactionPayload::Script(script) => {
+            assert_eq!(script.code(), bytecode.as_slice());
+            assert_eq!(script.args().len(), 2);
+        },
+        payload => panic!("Unexpected payload {:?}", payload),
+    }
+}
+
+/// Ensure transactions are not submitted to the network when executed on a fork
+// The state of the fork is read from a task of the runtime, while the command blocks
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn ensure_fork_does_not_submit() {
+    // The network, which must not be connected to
+    let network = TcpListener::bind("127.0.0.1:0").unwrap();
+    network.set_nonblocking(true).unwrap();
+    let url = format!("http://{}", network.local_addr().unwrap());
+    // The network to fork, which refuses connections
+    let fork_url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let fork = format!("{}@10", fork_url);
+    let dir = TempDir::new().unwrap();
+    let fork_cache_dir = dir.path().to_string_lossy().to_string();
+    let private_key = format!("0x{}", "11".repeat(32));
+    let txn_options = [
+        "--private-key",
+        private_key.as_str(),
+        "--url",
+        url.as_str(),
+        "--fork",
+        fork.as_str(),
+        "--fork-cache-dir",
+        fork_cache_dir.as_str(),
+        "--gas-unit-price",
+        "100",
+        "--max-gas",
+        "1000",
+        "--assume-yes",
+    ];
+
+    // Commands summarizing the transaction execute it on the fork
+    let args = [
+        "aptos",
+        "move",
+        "run",
+        "--function-id",
+        "0x1::aptos_account::transfer",
+        "--args",
+        "address:0x2",
+        "u64:1",
+    ];
+    let err = run_cmd(&[&args[..], &txn_options[..]].concat())
+        .await
+        .unwrap_err();
+    assert!(!err.contains("--fork is not supported"), "{}", err);
+
+    // Other commands reject it
+    let args = [
+        "aptos",
+        "account",
+        "transfer",
+        "--account",
+        "0x2",
+        "--amount",
+        "1",
+    ];
+    let err = run_cmd(&[&args[..], &txn_options[..]].concat())
+        .await
+        .unwrap_err();
+    assert!(err.contains("--fork is not supported"), "{}", err);
+
+    assert_eq!(network.accept().unwrap_err().kind(), ErrorKind::WouldBlock);
 }
 
 /// Ensure we can parse URLs for args
@@ -0,0 +1,137 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{
+            ArgWithTypeVec, CliCommand, CliTypedResult, EntryFunctionArguments,
+            ScriptFunctionArguments, TransactionOptions, TypeArgVec,
+        },
+        utils::{check_if_file_exists, read_from_file, write_to_file},
+    },
+    move_tool::MemberId,
+    transaction::{TransactionFileFormat, UnsignedTransaction},
+};
+use aptos_types::{
+    account_address::AccountAddress, chain_id::ChainId, transaction::TransactionPayload,
+};
+use async_trait::async_trait;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Build an unsigned transaction into a file, without accessing the network
+///
+/// The sequence number, chain id, gas unit price and max gas must be given, as they can't be
+/// looked up on chain.  The transaction can then be signed by each of its signers with
+/// `aptos transaction sign`.
+///
+/// The payload is either an entry function, given by `--function-id`, or a script given by
+/// `--compiled-script-path`.  Scripts must already be compiled, e.g. with
+/// `aptos move compile-script`, as compiling them may need network access to fetch their
+/// dependencies.
+#[derive(Parser)]
+pub struct BuildTransaction {
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
+    ///
+    /// Example: `0x842ed41fad9640a2ad08fdd7d3e4f7f505319aac7d67e1c0dd6a7cce8732c7e3::message::set_message`
+    #[clap(long, required_unless_present_any = &["json_file", "compiled_script_path"])]
+    pub(crate) function_id: Option<MemberId>,
+
+    /// Path to the compiled Move script to run instead of an entry function
+    #[clap(long, value_parser, conflicts_with = "function_id")]
+    pub(crate) compiled_script_path: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) type_arg_vec: TypeArgVec,
+    #[clap(flatten)]
+    pub(crate) arg_vec: ArgWithTypeVec,
+
+    /// JSON file specifying the type arguments and arguments, and the entry function ID
+    /// unless `--compiled-script-path` is given
+    #[clap(long, value_parser, conflicts_with_all = &["function_id", "args", "type_args"])]
+    pub(crate) json_file: Option<PathBuf>,
+
+    /// Sequence number of the sender account
+    #[clap(long)]
+    pub(crate) sequence_number: u64,
+
+    /// Chain id of the network the transaction is for
+    ///
+    /// Either a chain number, e.g. `2`, or a chain name, e.g. `testnet` or `mainnet`
+    #[clap(long)]
+    pub(crate) chain_id: ChainId,
+
+    /// Expiration time of the transaction, in seconds since the Unix epoch
+    ///
+    /// Defaults to `--expiration-secs` from the current local time
+    #[clap(long)]
+    pub(crate) expiration_timestamp_secs: Option<u64>,
+
+    /// Addresses of the other accounts signing a multi-agent transaction, in order
+    #[clap(long, num_args = 0.., value_parser = crate::common::types::load_account_arg)]
+    pub(crate) secondary_signer_addresses: Vec<AccountAddress>,
+
+    /// Address of the account paying the gas fee of the transaction
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) fee_payer_address: Option<AccountAddress>,
+
+    /// Output file to write the unsigned transaction to
+    #[clap(long, value_parser)]
+    pub(crate) output_file: PathBuf,
+
+    /// Encoding of the output file
+    #[clap(long, value_enum, ignore_case = true, default_value_t = TransactionFileFormat::Json)]
+    pub(crate) format: TransactionFileFormat,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<AccountAddress>> for BuildTransaction {
+    fn command_name(&self) -> &'static str {
+        "BuildTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<AccountAddress>> {
+        check_if_file_exists(&self.output_file, self.txn_options.prompt_options)?;
+
+        let payload = if let Some(compiled_script_path) = &self.compiled_script_path {
+            let bytecode = read_from_file(compiled_script_path)?;
+            ScriptFunctionArguments {
+                type_arg_vec: self.type_arg_vec,
+                arg_vec: self.arg_vec,
+                json_file: self.json_file,
+            }
+            .create_script_payload(bytecode)?
+        } else {
+            TransactionPayload::EntryFunction(
+                EntryFunctionArguments {
+                    function_id: self.function_id,
+                    type_arg_vec: self.type_arg_vec,
+                    arg_vec: self.arg_vec,
+                    json_file: self.json_file,
+                }
+                .try_into()?,
+            )
+        };
+        let raw_txn = self.txn_options.build_offline_transaction(
+            payload,
+            self.sequence_number,
+            self.chain_id,
+            self.expiration_timestamp_secs,
+        )?;
+        let txn = UnsignedTransaction {
+            raw_txn,
+            secondary_signer_addresses: self.secondary_signer_addresses,
+            fee_payer_address: self.fee_payer_address,
+        };
+
+        write_to_file(
+            &self.output_file,
+            "Unsigned transaction",
+            &self.format.to_bytes("UnsignedTransaction", &txn)?,
+        )?;
+        Ok(txn.signers())
+    }
+}
@@ -0,0 +1,242 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliError, CliResult, CliTypedResult, TransactionOptions},
+    utils::read_from_file,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{
+        authenticator::AccountAuthenticator, RawTransaction, RawTransactionWithData,
+        SignedTransaction,
+    },
+};
+use clap::{Subcommand, ValueEnum};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::Path,
+    str::FromStr,
+};
+
+pub mod build;
+pub mod sign;
+pub mod submit;
+
+/// Tool for building, signing and submitting transactions separately
+///
+/// This allows signing transactions on a machine without network access: the transaction is
+/// built into a file, signed into signature files by each of its signers, and the signatures are
+/// then submitted together from a machine that has network access.
+#[derive(Subcommand)]
+pub enum TransactionTool {
+    Build(build::BuildTransaction),
+    Sign(sign::SignTransaction),
+    Submit(submit::SubmitTransaction),
+}
+
+impl TransactionTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            TransactionTool::Build(tool) => tool.execute_serialized().await,
+            TransactionTool::Sign(tool) => tool.execute_serialized().await,
+            TransactionTool::Submit(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+/// Encoding of the unsigned transaction and signature files
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum TransactionFileFormat {
+    Json,
+    Bcs,
+}
+
+impl Display for TransactionFileFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            TransactionFileFormat::Json => "json",
+            TransactionFileFormat::Bcs => "bcs",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+impl FromStr for TransactionFileFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(TransactionFileFormat::Json),
+            "bcs" => Ok(TransactionFileFormat::Bcs),
+            _ => Err("Invalid file format. Valid values are json, bcs"),
+        }
+    }
+}
+
+impl TransactionFileFormat {
+    pub(crate) fn to_bytes<T: Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> CliTypedResult<Vec<u8>> {
+        match self {
+            TransactionFileFormat::Json => serde_json::to_vec_pretty(value)
+                .map_err(|err| CliError::UnexpectedError(err.to_string())),
+            TransactionFileFormat::Bcs => {
+                bcs::to_bytes(value).map_err(|err| CliError::BCS(name, err))
+            },
+        }
+    }
+
+    pub(crate) fn read_file<T: DeserializeOwned>(
+        self,
+        name: &'static str,
+        path: &Path,
+    ) -> CliTypedResult<T> {
+        let bytes = read_from_file(path)?;
+        match self {
+            TransactionFileFormat::Json => serde_json::from_slice(&bytes)
+                .map_err(|err| CliError::UnableToParse(name, err.to_string())),
+            TransactionFileFormat::Bcs => {
+                bcs::from_bytes(&bytes).map_err(|err| CliError::BCS(name, err))
+            },
+        }
+    }
+}
+
+/// A transaction waiting to be signed by all of its signers
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub raw_txn: RawTransaction,
+    pub secondary_signer_addresses: Vec<AccountAddress>,
+    pub fee_payer_address: Option<AccountAddress>,
+}
+
+impl UnsignedTransaction {
+    /// All the accounts that must sign the transaction, starting with the sender
+    pub fn signers(&self) -> Vec<AccountAddress> {
+        let mut signers = vec![self.raw_txn.sender()];
+        signers.extend(self.secondary_signer_addresses.iter().copied());
+        signers.extend(self.fee_payer_address);
+        signers
+    }
+
+    /// The message signed by multi-agent and fee payer transactions, which covers every signer
+    /// rather than only the raw transaction
+    fn raw_txn_with_data(&self) -> Option<RawTransactionWithData> {
+        if let Some(fee_payer_address) = self.fee_payer_address {
+            Some(RawTransactionWithData::new_fee_payer(
+                self.raw_txn.clone(),
+                self.secondary_signer_addresses.clone(),
+                fee_payer_address,
+            ))
+        } else if !self.secondary_signer_addresses.is_empty() {
+            Some(RawTransactionWithData::new_multi_agent(
+                self.raw_txn.clone(),
+                self.secondary_signer_addresses.clone(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Signs the transaction with the key selected by `txn_options`
+    pub fn sign(&self, txn_options: &TransactionOptions) -> CliTypedResult<TransactionSignature> {
+        let (signer, authenticator) = match self.raw_txn_with_data() {
+            Some(message) => txn_options.sign_offline(&message)?,
+            None => txn_options.sign_offline(&self.raw_txn)?,
+        };
+        if !self.signers().contains(&signer) {
+            return Err(CliError::CommandArgumentError(format!(
+                "Account {} is not a signer of the transaction, use --sender-account if the \
+                 authentication key of the account was rotated",
+                signer
+            )));
+        }
+
+        Ok(TransactionSignature {
+            signer,
+            authenticator,
+        })
+    }
+
+    fn verify(&self, signature: &TransactionSignature) -> CliTypedResult<()> {
+        match self.raw_txn_with_data() {
+            Some(message) => signature.authenticator.verify(&message),
+            None => signature.authenticator.verify(&self.raw_txn),
+        }
+        .map_err(|err| {
+            CliError::CommandArgumentError(format!(
+                "Invalid signature from {}: {}",
+                signature.signer, err
+            ))
+        })
+    }
+
+    /// Assembles the signatures collected from every signer into a transaction that can be
+    /// submitted
+    pub fn into_signed_transaction(
+        self,
+        signatures: Vec<TransactionSignature>,
+    ) -> CliTypedResult<SignedTransaction> {
+        let signers = self.signers();
+        let mut authenticators = BTreeMap::new();
+        for signature in signatures {
+            if !signers.contains(&signature.signer) {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Account {} is not a signer of the transaction",
+                    signature.signer
+                )));
+            }
+            self.verify(&signature)?;
+            authenticators.insert(signature.signer, signature.authenticator);
+        }
+        let authenticator = |address: AccountAddress| {
+            authenticators.get(&address).cloned().ok_or_else(|| {
+                CliError::CommandArgumentError(format!("Missing signature of account {}", address))
+            })
+        };
+
+        let sender = authenticator(self.raw_txn.sender())?;
+        let secondary_signers = self
+            .secondary_signer_addresses
+            .iter()
+            .map(|address| authenticator(*address))
+            .collect::<CliTypedResult<Vec<_>>>()?;
+        Ok(match self.fee_payer_address {
+            Some(fee_payer_address) => SignedTransaction::new_fee_payer(
+                self.raw_txn,
+                sender,
+                self.secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                authenticator(fee_payer_address)?,
+            ),
+            None if !self.secondary_signer_addresses.is_empty() => {
+                SignedTransaction::new_multi_agent(
+                    self.raw_txn,
+                    sender,
+                    self.secondary_signer_addresses,
+                    secondary_signers,
+                )
+            },
+            None => match sender {
+                AccountAuthenticator::Ed25519 {
+                    public_key,
+                    signature,
+                } => SignedTransaction::new(self.raw_txn, public_key, signature),
+                sender => SignedTransaction::new_single_sender(self.raw_txn, sender),
+            },
+        })
+    }
+}
+
+/// The signature of an `UnsignedTransaction` by one of its signers
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionSignature {
+    pub signer: AccountAddress,
+    pub authenticator: AccountAuthenticator,
+}
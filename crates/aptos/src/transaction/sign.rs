@@ -0,0 +1,60 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{CliCommand, CliTypedResult, TransactionOptions},
+        utils::{check_if_file_exists, write_to_file},
+    },
+    transaction::{TransactionFileFormat, UnsignedTransaction},
+};
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Sign a transaction built by `aptos transaction build`, without accessing the network
+///
+/// The transaction is signed with the given private key, or with the key of the profile.  Profiles
+/// created from a Ledger are signed on the Ledger.  Every signer of a multi-agent or fee payer
+/// transaction writes its own signature file.
+#[derive(Parser)]
+pub struct SignTransaction {
+    /// File containing the unsigned transaction
+    #[clap(long, value_parser)]
+    pub(crate) transaction_file: PathBuf,
+
+    /// Output file to write the signature to
+    #[clap(long, value_parser)]
+    pub(crate) output_file: PathBuf,
+
+    /// Encoding of the transaction and signature files
+    #[clap(long, value_enum, ignore_case = true, default_value_t = TransactionFileFormat::Json)]
+    pub(crate) format: TransactionFileFormat,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<AccountAddress> for SignTransaction {
+    fn command_name(&self) -> &'static str {
+        "SignTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<AccountAddress> {
+        check_if_file_exists(&self.output_file, self.txn_options.prompt_options)?;
+
+        let txn: UnsignedTransaction = self
+            .format
+            .read_file("UnsignedTransaction", &self.transaction_file)?;
+        let signature = txn.sign(&self.txn_options)?;
+
+        write_to_file(
+            &self.output_file,
+            "Transaction signature",
+            &self.format.to_bytes("TransactionSignature", &signature)?,
+        )?;
+        Ok(signature.signer)
+    }
+}
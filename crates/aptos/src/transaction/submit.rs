@@ -0,0 +1,57 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::types::{CliCommand, CliError, CliTypedResult, TransactionOptions, TransactionSummary},
+    transaction::{TransactionFileFormat, TransactionSignature, UnsignedTransaction},
+};
+use async_trait::async_trait;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Submit a transaction built by `aptos transaction build` along with the signatures of all of its
+/// signers
+#[derive(Parser)]
+pub struct SubmitTransaction {
+    /// File containing the unsigned transaction
+    #[clap(long, value_parser)]
+    pub(crate) transaction_file: PathBuf,
+
+    /// Files containing the signatures of the sender, and of the secondary signers and the fee
+    /// payer if any, in any order
+    #[clap(long, num_args = 1.., required = true, value_parser)]
+    pub(crate) signature_files: Vec<PathBuf>,
+
+    /// Encoding of the transaction and signature files
+    #[clap(long, value_enum, ignore_case = true, default_value_t = TransactionFileFormat::Json)]
+    pub(crate) format: TransactionFileFormat,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for SubmitTransaction {
+    fn command_name(&self) -> &'static str {
+        "SubmitTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let txn: UnsignedTransaction = self
+            .format
+            .read_file("UnsignedTransaction", &self.transaction_file)?;
+        let signatures = self
+            .signature_files
+            .iter()
+            .map(|path| self.format.read_file("TransactionSignature", path))
+            .collect::<CliTypedResult<Vec<TransactionSignature>>>()?;
+        let signed_txn = txn.into_signed_transaction(signatures)?;
+
+        self.txn_options
+            .rest_client()?
+            .submit_and_wait(&signed_txn)
+            .await
+            .map(|response| TransactionSummary::from(response.into_inner()))
+            .map_err(|err| CliError::ApiError(err.to_string()))
+    }
+}
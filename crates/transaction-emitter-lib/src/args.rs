@@ -12,7 +12,7 @@ use std::{
     convert::TryFrom,
     fs::File,
     io::{BufRead, BufReader},
-    path::Path,
+    path::{Path, PathBuf},
 };
 use url::Url;
 
@@ -153,6 +153,12 @@ pub struct EmitArgs {
     #[clap(long, num_args = 0..)]
     pub transaction_phases: Vec<usize>,
 
+    /// Replays the user transactions of a recording instead of the `--transaction-type`
+    /// workloads. Either a transaction backup manifest, or a `.json` / `.bcs` dump of
+    /// transactions. The rate of the replay is set by `--target-tps` or `--mempool-backlog`.
+    #[clap(long)]
+    pub replay_traffic: Option<PathBuf>,
+
     #[clap(long)]
     pub gas_price: Option<u64>,
 
@@ -14,9 +14,12 @@ use anyhow::{bail, Context, Result};
 use aptos_config::config::DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE;
 use aptos_logger::{error, info};
 use aptos_sdk::transaction_builder::TransactionFactory;
-use aptos_transaction_generator_lib::args::TransactionTypeArg;
+use aptos_transaction_generator_lib::{args::TransactionTypeArg, RecordedTraffic, TransactionType};
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 pub async fn emit_transactions(
     cluster_args: &ClusterArgs,
@@ -81,13 +84,25 @@ pub async fn emit_transactions_with_cluster(
         StdRng::from_entropy(),
     );
 
-    let transaction_mix_per_phase = TransactionTypeArg::args_to_transaction_mix_per_phase(
-        &args.transaction_type,
-        &args.transaction_weights,
-        &args.transaction_phases,
-        args.module_working_set_size.unwrap_or(1),
-        args.sender_use_account_pool.unwrap_or(false),
-    );
+    let transaction_mix_per_phase = match &args.replay_traffic {
+        Some(replay_traffic) => vec![vec![(
+            TransactionType::Replay {
+                traffic: Arc::new(
+                    RecordedTraffic::load(replay_traffic)
+                        .context("Failed to load recorded traffic")?,
+                ),
+                use_account_pool: args.sender_use_account_pool.unwrap_or(false),
+            },
+            1,
+        )]],
+        None => TransactionTypeArg::args_to_transaction_mix_per_phase(
+            &args.transaction_type,
+            &args.transaction_weights,
+            &args.transaction_phases,
+            args.module_working_set_size.unwrap_or(1),
+            args.sender_use_account_pool.unwrap_or(false),
+        ),
+    };
     let mut emit_job_request =
         EmitJobRequest::new(cluster.all_instances().map(Instance::rest_client).collect())
             .mode(emitter_mode)
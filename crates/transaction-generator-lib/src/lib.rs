@@ -31,6 +31,7 @@ mod entry_points;
 mod p2p_transaction_generator;
 pub mod publish_modules;
 mod publishing;
+mod replay;
 mod transaction_mix_generator;
 use self::{
     account_generator::AccountGeneratorCreator,
@@ -43,12 +44,14 @@ use crate::{
     accounts_pool_wrapper::AccountsPoolWrapperCreator,
     batch_transfer::BatchTransferTransactionGeneratorCreator,
     entry_points::EntryPointTransactionGenerator, p2p_transaction_generator::SamplingMode,
+    replay::ReplayGeneratorCreator,
 };
 pub use publishing::module_simple::EntryPoints;
+pub use replay::RecordedTraffic;
 
 pub const SEND_AMOUNT: u64 = 1;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum TransactionType {
     NonConflictingCoinTransfer {
         invalid_transaction_ratio: usize,
@@ -74,6 +77,10 @@ pub enum TransactionType {
     BatchTransfer {
         batch_size: usize,
     },
+    Replay {
+        traffic: Arc<RecordedTraffic>,
+        use_account_pool: bool,
+    },
 }
 
 impl Default for TransactionType {
@@ -290,6 +297,24 @@ pub async fn create_txn_generator_creator(
                         *batch_size,
                     ))
                 },
+                TransactionType::Replay {
+                    traffic,
+                    use_account_pool,
+                } => wrap_accounts_pool(
+                    Box::new(
+                        ReplayGeneratorCreator::new(
+                            txn_factory.clone(),
+                            init_txn_factory.clone(),
+                            traffic.clone(),
+                            source_accounts,
+                            addresses_pool.clone(),
+                            txn_executor,
+                        )
+                        .await,
+                    ),
+                    *use_account_pool,
+                    accounts_pool.clone(),
+                ),
             };
             txn_generator_creator_mix.push((txn_generator_creator, *weight));
         }
@@ -0,0 +1,566 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    create_account_transaction, ReliableTransactionSubmitter, TransactionGenerator,
+    TransactionGeneratorCreator,
+};
+use anyhow::{bail, ensure, Context, Result};
+use aptos_framework::natives::code::PackageMetadata;
+use aptos_infallible::RwLock;
+use aptos_logger::{info, warn};
+use aptos_sdk::{
+    bcs,
+    move_types::{
+        account_address::AccountAddress,
+        language_storage::{ModuleId, StructTag, TypeTag},
+    },
+    transaction_builder::{aptos_stdlib, TransactionFactory},
+    types::{
+        contract_event::ContractEvent,
+        transaction::{
+            EntryFunction, SignedTransaction, Transaction, TransactionInfo, TransactionPayload,
+        },
+        write_set::WriteSet,
+        LocalAccount,
+    },
+};
+use move_binary_format::{access::ModuleAccess, file_format::SignatureToken, CompiledModule};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    io::{BufReader, ErrorKind, Read},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// A package published by a recorded transaction.
+struct RecordedPackage {
+    publisher: AccountAddress,
+    metadata: PackageMetadata,
+    modules: Vec<CompiledModule>,
+}
+
+/// How an argument of a recorded call is mapped, decided by the type of the parameter it is
+/// passed for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArgKind {
+    Address,
+    AddressVector,
+    Other,
+}
+
+/// An entry function call in the recording.
+struct RecordedCall {
+    sender: AccountAddress,
+    entry_function: EntryFunction,
+    /// Kinds of the arguments, in order
+    arg_kinds: Vec<ArgKind>,
+}
+
+/// User transactions recorded on a network, e.g. by a transaction backup, which are replayed
+/// as a workload. Only the payloads of the transactions are replayed, the senders are mapped
+/// onto the accounts of the workload.
+pub struct RecordedTraffic {
+    /// Packages published in the recording, in the order they were published
+    packages: Vec<RecordedPackage>,
+    /// Entry function calls in the recording, in order
+    calls: Vec<RecordedCall>,
+}
+
+impl fmt::Debug for RecordedTraffic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordedTraffic")
+            .field("packages", &self.packages.len())
+            .field("calls", &self.calls.len())
+            .finish()
+    }
+}
+
+impl RecordedTraffic {
+    /// Loads a recording, which is either the manifest of a transaction backup created by
+    /// `aptos-db-tool backup` into a local directory (`transaction.manifest`), or a dump of a
+    /// list of transactions serialized as JSON (`.json`) or BCS (`.bcs`).
+    pub fn load(path: &Path) -> Result<Self> {
+        let txns: Vec<Transaction> = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_slice(&fs::read(path)?)?,
+            Some("bcs") => bcs::from_bytes(&fs::read(path)?)?,
+            _ => read_transaction_backup(path)?,
+        };
+        Self::from_transactions(txns)
+    }
+
+    /// Collects the replayable user transactions among `txns`. Calls to modules which aren't
+    /// published in the recording or part of the framework can't be replayed, and neither can
+    /// scripts, so they are skipped. Multi-agent transactions are skipped as well, as the
+    /// workload signs each transaction with a single account. A fee payer doesn't change what
+    /// a transaction does, so such transactions are replayed with the sender paying the fee.
+    pub fn from_transactions(txns: Vec<Transaction>) -> Result<Self> {
+        let mut packages = Vec::new();
+        let mut calls = Vec::new();
+        let mut num_skipped = 0;
+        let mut num_multi_agent = 0;
+        for txn in txns {
+            let txn = match txn {
+                Transaction::UserTransaction(txn) => txn,
+                _ => continue,
+            };
+            if !txn
+                .authenticator_ref()
+                .secondary_signer_addresses()
+                .is_empty()
+            {
+                num_multi_agent += 1;
+                continue;
+            }
+            let sender = txn.sender();
+            match txn.into_raw_transaction().into_payload() {
+                TransactionPayload::EntryFunction(entry_function) => {
+                    if is_publish_package(&entry_function) {
+                        match decode_package(sender, &entry_function) {
+                            Ok(package) => packages.push(package),
+                            Err(err) => {
+                                warn!("Skipping package published by {}: {:#}", sender, err);
+                                num_skipped += 1;
+                            },
+                        }
+                    } else {
+                        calls.push((sender, entry_function));
+                    }
+                },
+                _ => num_skipped += 1,
+            }
+        }
+
+        // The argument kinds are looked up in the modules published in the recording and in
+        // the framework, calls to any other module can't be replayed
+        let framework_modules = aptos_cached_packages::head_release_bundle().compiled_modules();
+        let modules: HashMap<ModuleId, &CompiledModule> = framework_modules
+            .iter()
+            .chain(packages.iter().flat_map(|package| package.modules.iter()))
+            .map(|module| (module.self_id(), module))
+            .collect();
+        let num_calls = calls.len();
+        let calls: Vec<_> = calls
+            .into_iter()
+            .filter_map(|(sender, entry_function)| {
+                let arg_kinds = modules
+                    .get(entry_function.module())
+                    .and_then(|module| arg_kinds(module, &entry_function))?;
+                Some(RecordedCall {
+                    sender,
+                    entry_function,
+                    arg_kinds,
+                })
+            })
+            .collect();
+        num_skipped += num_calls - calls.len();
+
+        ensure!(!calls.is_empty(), "No replayable transactions recorded");
+        if num_multi_agent > 0 {
+            warn!(
+                "Skipped {} recorded multi-agent transactions, which can't be replayed",
+                num_multi_agent
+            );
+        }
+        if num_skipped > 0 {
+            warn!(
+                "Skipped {} recorded transactions which can't be replayed",
+                num_skipped
+            );
+        }
+        info!(
+            "Loaded {} recorded calls and {} recorded packages",
+            calls.len(),
+            packages.len()
+        );
+        Ok(Self { packages, calls })
+    }
+
+    /// Number of recorded calls, which are replayed in a loop
+    pub fn num_calls(&self) -> usize {
+        self.calls.len()
+    }
+}
+
+/// Minimal view of the manifest of a transaction backup, see `TransactionBackup` in
+/// `aptos-backup-cli`.
+#[derive(Deserialize)]
+struct TransactionBackupManifest {
+    chunks: Vec<TransactionBackupChunk>,
+}
+
+#[derive(Deserialize)]
+struct TransactionBackupChunk {
+    transactions: String,
+}
+
+/// Reads the transactions of a transaction backup in a local directory, where the file handles
+/// in the manifest are relative to the directory containing all backups.
+fn read_transaction_backup(manifest_path: &Path) -> Result<Vec<Transaction>> {
+    let manifest: TransactionBackupManifest = serde_json::from_slice(
+        &fs::read(manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?,
+    )
+    .with_context(|| {
+        format!(
+            "{} is not a transaction backup manifest",
+            manifest_path.display()
+        )
+    })?;
+    let backup_root = manifest_path
+        .parent()
+        .and_then(Path::parent)
+        .context("Transaction backup manifest is expected within a backup directory")?;
+
+    let mut txns = Vec::new();
+    for chunk in manifest.chunks {
+        let mut file = BufReader::new(fs::File::open(backup_root.join(&chunk.transactions))?);
+        // Records are BCS serialized
+        // `(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)` tuples, each prefixed
+        // with its length
+        while let Some(record) = read_record(&mut file)? {
+            let (txn, _, _, _): (Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet) =
+                bcs::from_bytes(&record)?;
+            txns.push(txn);
+        }
+    }
+    Ok(txns)
+}
+
+fn read_record(file: &mut impl Read) -> Result<Option<Vec<u8>>> {
+    let mut size_buf = [0u8; 4];
+    match file.read_exact(&mut size_buf) {
+        Ok(()) => (),
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+    let mut record = vec![0u8; u32::from_be_bytes(size_buf) as usize];
+    file.read_exact(&mut record)
+        .context("Hit EOF when reading record")?;
+    Ok(Some(record))
+}
+
+fn is_publish_package(entry_function: &EntryFunction) -> bool {
+    let module = entry_function.module();
+    module.address() == &AccountAddress::ONE
+        && module.name().as_str() == "code"
+        && entry_function.function().as_str() == "publish_package_txn"
+}
+
+/// Kinds of the arguments of a call to an entry function of `module`, or `None` if the
+/// function doesn't exist or doesn't take the recorded arguments.
+fn arg_kinds(module: &CompiledModule, entry_function: &EntryFunction) -> Option<Vec<ArgKind>> {
+    let handle = module.function_defs().iter().find_map(|def| {
+        let handle = module.function_handle_at(def.function);
+        (module.identifier_at(handle.name) == entry_function.function()).then_some(handle)
+    })?;
+    // Signers aren't passed as arguments
+    let kinds: Vec<_> = module
+        .signature_at(handle.parameters)
+        .0
+        .iter()
+        .filter(|param| match param {
+            SignatureToken::Signer => false,
+            SignatureToken::Reference(inner) => **inner != SignatureToken::Signer,
+            _ => true,
+        })
+        .map(|param| match param {
+            SignatureToken::Address => ArgKind::Address,
+            SignatureToken::Vector(inner) if **inner == SignatureToken::Address => {
+                ArgKind::AddressVector
+            },
+            _ => ArgKind::Other,
+        })
+        .collect();
+    (kinds.len() == entry_function.args().len()).then_some(kinds)
+}
+
+fn decode_package(
+    publisher: AccountAddress,
+    entry_function: &EntryFunction,
+) -> Result<RecordedPackage> {
+    let args = entry_function.args();
+    if args.len() != 2 {
+        bail!("Unexpected arguments to publish_package_txn");
+    }
+    let metadata_bytes: Vec<u8> = bcs::from_bytes(&args[0])?;
+    let code: Vec<Vec<u8>> = bcs::from_bytes(&args[1])?;
+    Ok(RecordedPackage {
+        publisher,
+        metadata: bcs::from_bytes(&metadata_bytes)?,
+        modules: code
+            .iter()
+            .map(|bytes| CompiledModule::deserialize(bytes))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Maps the addresses of a recording onto the accounts of the replay.
+struct AddressMapping {
+    /// Recorded publishers to the publishers of the replayed packages
+    publishers: HashMap<AccountAddress, AccountAddress>,
+    /// Recorded senders to their index, which picks the account they are mapped onto when
+    /// they are passed as an argument to another sender's call
+    senders: HashMap<AccountAddress, usize>,
+    addresses_pool: Arc<RwLock<Vec<AccountAddress>>>,
+}
+
+impl AddressMapping {
+    fn map_address(&self, address: &AccountAddress) -> Option<AccountAddress> {
+        if let Some(publisher) = self.publishers.get(address) {
+            return Some(*publisher);
+        }
+        let index = self.senders.get(address)?;
+        let addresses_pool = self.addresses_pool.read();
+        if addresses_pool.is_empty() {
+            None
+        } else {
+            Some(addresses_pool[index % addresses_pool.len()])
+        }
+    }
+
+    fn map_type_tag(&self, type_tag: &TypeTag) -> TypeTag {
+        match type_tag {
+            TypeTag::Vector(element) => TypeTag::Vector(Box::new(self.map_type_tag(element))),
+            TypeTag::Struct(struct_tag) => TypeTag::Struct(Box::new(StructTag {
+                address: self
+                    .publishers
+                    .get(&struct_tag.address)
+                    .copied()
+                    .unwrap_or(struct_tag.address),
+                module: struct_tag.module.clone(),
+                name: struct_tag.name.clone(),
+                type_params: struct_tag
+                    .type_params
+                    .iter()
+                    .map(|type_param| self.map_type_tag(type_param))
+                    .collect(),
+            })),
+            _ => type_tag.clone(),
+        }
+    }
+
+    /// Maps a recorded address argument, with the recorded sender becoming `sender`. Addresses
+    /// which aren't recorded publishers or senders are kept.
+    fn map_arg_address(
+        &self,
+        recorded_sender: &AccountAddress,
+        sender: AccountAddress,
+        address: AccountAddress,
+    ) -> AccountAddress {
+        if &address == recorded_sender {
+            sender
+        } else {
+            self.map_address(&address).unwrap_or(address)
+        }
+    }
+
+    /// Maps a recorded call onto a call from `sender`. Arguments passed for address
+    /// parameters are mapped as well, with the recorded sender becoming `sender`.
+    fn map_call(&self, call: &RecordedCall, sender: AccountAddress) -> EntryFunction {
+        let entry_function = &call.entry_function;
+        let module = entry_function.module();
+        let module_address = self
+            .publishers
+            .get(module.address())
+            .copied()
+            .unwrap_or(*module.address());
+        let args = entry_function
+            .args()
+            .iter()
+            .zip(&call.arg_kinds)
+            .map(|(arg, kind)| match kind {
+                ArgKind::Address => match bcs::from_bytes::<AccountAddress>(arg) {
+                    Ok(address) => {
+                        bcs::to_bytes(&self.map_arg_address(&call.sender, sender, address))
+                            .expect("AccountAddress must serialize")
+                    },
+                    Err(_) => arg.clone(),
+                },
+                ArgKind::AddressVector => match bcs::from_bytes::<Vec<AccountAddress>>(arg) {
+                    Ok(addresses) => bcs::to_bytes(
+                        &addresses
+                            .into_iter()
+                            .map(|address| self.map_arg_address(&call.sender, sender, address))
+                            .collect::<Vec<_>>(),
+                    )
+                    .expect("addresses must serialize"),
+                    Err(_) => arg.clone(),
+                },
+                ArgKind::Other => arg.clone(),
+            })
+            .collect();
+        EntryFunction::new(
+            ModuleId::new(module_address, module.name().to_owned()),
+            entry_function.function().to_owned(),
+            entry_function
+                .ty_args()
+                .iter()
+                .map(|type_tag| self.map_type_tag(type_tag))
+                .collect(),
+            args,
+        )
+    }
+
+    /// Rewrites the addresses of recorded publishers in a recorded package, so it can be
+    /// published by the replay publishers.
+    fn map_package(&self, package: &RecordedPackage) -> (PackageMetadata, Vec<Vec<u8>>) {
+        let mut metadata = package.metadata.clone();
+        for dep in &mut metadata.deps {
+            if let Some(publisher) = self.publishers.get(&dep.account) {
+                dep.account = *publisher;
+            }
+        }
+        let code = package
+            .modules
+            .iter()
+            .map(|module| {
+                let mut module = module.clone();
+                for address in &mut module.address_identifiers {
+                    if let Some(publisher) = self.publishers.get(address) {
+                        *address = *publisher;
+                    }
+                }
+                let mut bytes = vec![];
+                module.serialize(&mut bytes).expect("Module must serialize");
+                bytes
+            })
+            .collect();
+        (metadata, code)
+    }
+}
+
+/// Replays the recorded calls in order, looping over them, shared by all generators of a
+/// creator so the replayed mix follows the recording.
+pub struct ReplayGenerator {
+    txn_factory: TransactionFactory,
+    traffic: Arc<RecordedTraffic>,
+    mapping: Arc<AddressMapping>,
+    next_call: Arc<AtomicUsize>,
+}
+
+impl TransactionGenerator for ReplayGenerator {
+    fn generate_transactions(
+        &mut self,
+        account: &LocalAccount,
+        num_to_create: usize,
+    ) -> Vec<SignedTransaction> {
+        let mut requests = Vec::with_capacity(num_to_create);
+        for _ in 0..num_to_create {
+            let index = self.next_call.fetch_add(1, Ordering::Relaxed) % self.traffic.calls.len();
+            let entry_function = self
+                .mapping
+                .map_call(&self.traffic.calls[index], account.address());
+            requests.push(
+                account.sign_with_transaction_builder(
+                    self.txn_factory
+                        .payload(TransactionPayload::EntryFunction(entry_function)),
+                ),
+            );
+        }
+        requests
+    }
+}
+
+pub struct ReplayGeneratorCreator {
+    txn_factory: TransactionFactory,
+    traffic: Arc<RecordedTraffic>,
+    mapping: Arc<AddressMapping>,
+    next_call: Arc<AtomicUsize>,
+}
+
+impl ReplayGeneratorCreator {
+    /// Republishes the packages of the recording under new publisher accounts, funded by
+    /// `accounts`.
+    pub async fn new(
+        txn_factory: TransactionFactory,
+        init_txn_factory: TransactionFactory,
+        traffic: Arc<RecordedTraffic>,
+        accounts: &mut [LocalAccount],
+        addresses_pool: Arc<RwLock<Vec<AccountAddress>>>,
+        txn_executor: &dyn ReliableTransactionSubmitter,
+    ) -> Self {
+        let mut rng = StdRng::from_entropy();
+        let mut publishers = HashMap::new();
+        for package in &traffic.packages {
+            publishers
+                .entry(package.publisher)
+                .or_insert_with(|| LocalAccount::generate(&mut rng));
+        }
+
+        let mut senders = HashMap::new();
+        for call in &traffic.calls {
+            let index = senders.len();
+            senders.entry(call.sender).or_insert(index);
+        }
+        let mapping = AddressMapping {
+            publishers: publishers
+                .iter()
+                .map(|(recorded, publisher)| (*recorded, publisher.address()))
+                .collect(),
+            senders,
+            addresses_pool,
+        };
+
+        if !publishers.is_empty() {
+            assert!(!accounts.is_empty());
+            let requests_create = publishers
+                .values()
+                .zip(accounts.iter().cycle())
+                .map(|(publisher, account)| {
+                    create_account_transaction(
+                        account,
+                        publisher.address(),
+                        &init_txn_factory,
+                        2 * init_txn_factory.get_gas_unit_price()
+                            * init_txn_factory.get_max_gas_amount()
+                            * traffic.packages.len() as u64,
+                    )
+                })
+                .collect::<Vec<_>>();
+            info!("Creating {} publisher accounts", requests_create.len());
+            txn_executor
+                .execute_transactions(&requests_create)
+                .await
+                .unwrap();
+
+            // Packages may depend on packages published before them, so they are published
+            // one at a time
+            info!("Publishing {} packages", traffic.packages.len());
+            for package in &traffic.packages {
+                let (metadata, code) = mapping.map_package(package);
+                let txn = publishers[&package.publisher].sign_with_transaction_builder(
+                    init_txn_factory.payload(aptos_stdlib::code_publish_package_txn(
+                        bcs::to_bytes(&metadata).expect("PackageMetadata must serialize"),
+                        code,
+                    )),
+                );
+                txn_executor.execute_transactions(&[txn]).await.unwrap();
+            }
+        }
+
+        Self {
+            txn_factory,
+            traffic,
+            mapping: Arc::new(mapping),
+            next_call: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl TransactionGeneratorCreator for ReplayGeneratorCreator {
+    fn create_transaction_generator(&self) -> Box<dyn TransactionGenerator> {
+        Box::new(ReplayGenerator {
+            txn_factory: self.txn_factory.clone(),
+            traffic: self.traffic.clone(),
+            mapping: self.mapping.clone(),
+            next_call: self.next_call.clone(),
+        })
+    }
+}
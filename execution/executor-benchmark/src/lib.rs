@@ -61,7 +61,7 @@ where
         AptosDB::open(
             config.storage.get_dir_paths(),
             false, /* readonly */
-            config.storage.storage_pruner_config,
+            config.storage.storage_pruner_config.clone(),
             config.storage.rocksdb_configs,
             false,
             config.storage.buffered_state_target_items,
@@ -572,12 +572,21 @@ mod tests {
     use crate::{native_executor::NativeExecutor, pipeline::PipelineConfig};
     use aptos_config::config::NO_OP_STORAGE_PRUNER_CONFIG;
     use aptos_executor::block_executor::TransactionBlockExecutor;
+    use aptos_sdk::{
+        bcs,
+        transaction_builder::{aptos_stdlib, TransactionFactory},
+        types::{chain_id::ChainId, transaction::Transaction, LocalAccount},
+    };
     use aptos_temppath::TempPath;
-    use aptos_transaction_generator_lib::args::TransactionTypeArg;
+    use aptos_transaction_generator_lib::{
+        args::TransactionTypeArg, RecordedTraffic, TransactionType,
+    };
     use aptos_vm::AptosVM;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::{fs, sync::Arc};
 
     fn test_generic_benchmark<E>(
-        transaction_type: Option<TransactionTypeArg>,
+        transaction_mix: Option<Vec<(TransactionType, usize)>>,
         verify_sequence_numbers: bool,
     ) where
         E: TransactionBlockExecutor + 'static,
@@ -606,7 +615,7 @@ mod tests {
         super::run_benchmark::<E>(
             6, /* block_size */
             5, /* num_blocks */
-            transaction_type.map(|t| vec![(t.materialize(2, false), 1)]),
+            transaction_mix,
             2,     /* transactions per sender */
             0,     /* connected txn groups in a block */
             false, /* shuffle the connected txns in a block */
@@ -630,7 +639,52 @@ mod tests {
     #[test]
     fn test_benchmark_transaction() {
         AptosVM::set_concurrency_level_once(4);
-        test_generic_benchmark::<AptosVM>(Some(TransactionTypeArg::TokenV2AmbassadorMint), true);
+        test_generic_benchmark::<AptosVM>(
+            Some(vec![(
+                TransactionTypeArg::TokenV2AmbassadorMint.materialize(2, false),
+                1,
+            )]),
+            true,
+        );
+    }
+
+    #[test]
+    fn test_benchmark_replay() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let senders = [
+            LocalAccount::generate(&mut rng),
+            LocalAccount::generate(&mut rng),
+        ];
+        let txn_factory = TransactionFactory::new(ChainId::test());
+        // Transfers between the recorded senders, which are replayed between generated accounts
+        let txns = (0..10)
+            .map(|i| {
+                let (sender, receiver) = (&senders[i % 2], &senders[(i + 1) % 2]);
+                Transaction::UserTransaction(
+                    sender.sign_with_transaction_builder(
+                        txn_factory
+                            .payload(aptos_stdlib::aptos_account_transfer(receiver.address(), 1)),
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+        let recording_dir = TempPath::new();
+        recording_dir.create_as_dir().unwrap();
+        let recording = recording_dir.path().join("recording.bcs");
+        fs::write(&recording, bcs::to_bytes(&txns).unwrap()).unwrap();
+
+        let traffic = RecordedTraffic::load(&recording).unwrap();
+        assert_eq!(traffic.num_calls(), 10);
+        test_generic_benchmark::<AptosVM>(
+            Some(vec![(
+                TransactionType::Replay {
+                    traffic: Arc::new(traffic),
+                    use_account_pool: false,
+                },
+                1,
+            )]),
+            true,
+        );
     }
 
     #[test]
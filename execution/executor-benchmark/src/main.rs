@@ -22,13 +22,14 @@ use aptos_experimental_runtimes::thread_manager::{ThreadConfigStrategy, ThreadMa
 use aptos_metrics_core::{register_int_gauge, IntGauge};
 use aptos_profiler::{ProfilerConfig, ProfilerHandler};
 use aptos_push_metrics::MetricsPusher;
-use aptos_transaction_generator_lib::args::TransactionTypeArg;
+use aptos_transaction_generator_lib::{args::TransactionTypeArg, RecordedTraffic, TransactionType};
 use aptos_vm::AptosVM;
 use clap::{ArgGroup, Parser, Subcommand};
 use once_cell::sync::Lazy;
 use std::{
     net::SocketAddr,
     path::PathBuf,
+    sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -89,6 +90,7 @@ impl PrunerOpt {
                 prune_window: self.ledger_prune_window,
                 batch_size: self.ledger_pruning_batch_size,
                 user_pruning_window_offset: 0,
+                retained_accounts: vec![],
             },
         }
     }
@@ -310,6 +312,12 @@ enum Command {
         #[clap(long, default_value_t = 1)]
         module_working_set_size: usize,
 
+        /// Replays the user transactions of a recording instead of the `--transaction-type`
+        /// workloads. Either a transaction backup manifest, or a `.json` / `.bcs` dump of
+        /// transactions.
+        #[clap(long, value_parser)]
+        replay_traffic: Option<PathBuf>,
+
         #[clap(long, value_parser)]
         data_dir: PathBuf,
 
@@ -359,10 +367,21 @@ where
             transaction_type,
             transaction_weights,
             module_working_set_size,
+            replay_traffic,
             data_dir,
             checkpoint_dir,
         } => {
-            let transaction_mix = if transaction_type.is_empty() {
+            let transaction_mix = if let Some(replay_traffic) = replay_traffic {
+                let traffic = RecordedTraffic::load(&replay_traffic)
+                    .expect("Failed to load recorded traffic");
+                Some(vec![(
+                    TransactionType::Replay {
+                        traffic: Arc::new(traffic),
+                        use_account_pool: false,
+                    },
+                    1,
+                )])
+            } else if transaction_type.is_empty() {
                 None
             } else {
                 let mix_per_phase = TransactionTypeArg::args_to_transaction_mix_per_phase(
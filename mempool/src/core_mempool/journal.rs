@@ -0,0 +1,498 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! On-disk journal of the transactions accepted by Mempool.
+//!
+//! Every accepted transaction is appended to the journal, which is replayed into Mempool when the
+//! node restarts. Removals aren't journaled: committed and expired transactions are instead dropped
+//! when the journal is replayed, and when it is compacted down to the transactions still in Mempool.
+//!
+//! The journal is written by a background thread, so that Mempool never waits on disk I/O while
+//! holding its lock. The thread writes the pending entries in batches, and syncs the journal to
+//! disk after each batch. It also does the compactions, given the keys of the transactions still
+//! in Mempool, by rereading the journal. The journal is thus best effort: a transaction is accepted
+//! before its entry is on disk, so the last batch can be lost on a crash, and entries are dropped
+//! when the writer falls more than `JOURNAL_CHANNEL_SIZE` entries behind, or when the journal is
+//! full until its next compaction.
+use crate::{
+    core_mempool::transaction::{MempoolTransaction, SubmittedBy, TimelineState},
+    counters,
+    logging::{LogEntry, LogSchema},
+};
+use anyhow::{bail, format_err, Result};
+use aptos_logger::prelude::*;
+use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    iter,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Maximum number of writes waiting for the journal writer
+const JOURNAL_CHANNEL_SIZE: usize = 10_000;
+
+/// Maximum number of writes made before syncing the journal to disk
+const MAX_BATCH_SIZE: usize = 1_000;
+
+/// Sender and sequence number of a transaction, identifying it in Mempool
+pub type TxnKey = (AccountAddress, u64);
+
+/// A transaction accepted by Mempool, as recorded in the journal
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JournalEntry {
+    pub txn: SignedTransaction,
+    pub ranking_score: u64,
+    /// System expiration time of the transaction, since the Unix epoch
+    pub expiration_time: Duration,
+    /// Time the transaction was inserted into Mempool, since the Unix epoch
+    pub insertion_time: Duration,
+    pub timeline_state: TimelineState,
+    pub client_submitted: bool,
+}
+
+impl From<&MempoolTransaction> for JournalEntry {
+    fn from(txn: &MempoolTransaction) -> Self {
+        // The broadcast position of a transaction isn't kept across restarts
+        let timeline_state = match txn.timeline_state {
+            TimelineState::Ready(_) => TimelineState::NotReady,
+            timeline_state => timeline_state,
+        };
+        Self {
+            txn: txn.txn.clone(),
+            ranking_score: txn.ranking_score,
+            expiration_time: txn.expiration_time,
+            insertion_time: aptos_infallible::duration_since_epoch_at(
+                &txn.insertion_info.insertion_time,
+            ),
+            timeline_state,
+            client_submitted: txn.insertion_info.submitted_by == SubmittedBy::Client,
+        }
+    }
+}
+
+impl JournalEntry {
+    fn key(&self) -> TxnKey {
+        (self.txn.sender(), self.txn.sequence_number())
+    }
+}
+
+/// Append-only file of length prefixed BCS encoded [`JournalEntry`]s
+pub struct MempoolJournal {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    capacity: usize,
+    num_entries: usize,
+}
+
+impl MempoolJournal {
+    /// Opens the journal at `path`, creating it if it doesn't exist, and returns the entries it
+    /// holds.
+    pub fn open(path: &Path, capacity: usize) -> Result<(Self, Vec<JournalEntry>)> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let entries = match File::open(path) {
+            Ok(file) => read_entries(file)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+            Err(err) => return Err(err.into()),
+        };
+
+        // Rewrite the journal, as its last entry may have been partially written
+        let mut journal = Self {
+            path: path.to_path_buf(),
+            writer: rewrite(path, &entries)?,
+            capacity,
+            num_entries: entries.len(),
+        };
+        if journal.num_entries > capacity {
+            journal.replace(entries.clone())?;
+        }
+        Ok((journal, entries))
+    }
+
+    /// Appends an entry to the journal, unless it's full. The entry is only durable once the
+    /// journal is synced.
+    pub fn append(&mut self, entry: &JournalEntry) -> Result<()> {
+        if self.num_entries >= self.capacity {
+            bail!("The mempool journal is full");
+        }
+        write_entry(&mut self.writer, entry)?;
+        self.num_entries += 1;
+        Ok(())
+    }
+
+    /// Flushes the appended entries and syncs them to disk
+    pub fn sync(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        Ok(())
+    }
+
+    /// Returns the number of entries in the journal
+    pub fn num_entries(&self) -> usize {
+        self.num_entries
+    }
+
+    /// Drops the entries of the transactions that aren't in Mempool anymore, given the keys of
+    /// those that are. A transaction that was replaced in Mempool keeps its last entry.
+    pub fn compact(&mut self, txn_keys: &HashSet<TxnKey>) -> Result<()> {
+        self.writer.flush()?;
+        let mut entries = HashMap::new();
+        for entry in read_entries(File::open(&self.path)?)? {
+            if txn_keys.contains(&entry.key()) {
+                entries.insert(entry.key(), entry);
+            }
+        }
+        self.replace(entries.into_values().collect())
+    }
+
+    /// Replaces the content of the journal with the given entries, keeping only the most recently
+    /// inserted ones if there are more entries than the capacity of the journal.
+    fn replace(&mut self, mut entries: Vec<JournalEntry>) -> Result<()> {
+        entries.sort_by_key(|entry| entry.insertion_time);
+        if entries.len() > self.capacity {
+            entries.drain(..entries.len() - self.capacity);
+        }
+        self.writer = rewrite(&self.path, &entries)?;
+        self.num_entries = entries.len();
+        Ok(())
+    }
+}
+
+/// A write to the journal, made by the journal writer thread
+enum JournalWrite {
+    Append(JournalEntry),
+    Compact(HashSet<TxnKey>),
+}
+
+/// Handle to the journal, written by a background thread.
+///
+/// Writes never block: they are dropped if the writer thread falls behind, or if the journal is
+/// full. Dropping the handle waits for the pending writes to be done.
+pub struct JournalWriter {
+    sender: Option<SyncSender<JournalWrite>>,
+    thread: Option<JoinHandle<()>>,
+    capacity: usize,
+    // Upper bound of the number of entries in the journal, once the pending writes are done
+    num_entries: usize,
+}
+
+impl JournalWriter {
+    /// Opens the journal at `path` and starts its writer thread. Returns the entries the journal
+    /// holds.
+    pub fn open(path: &Path, capacity: usize) -> Result<(Self, Vec<JournalEntry>)> {
+        let (journal, entries) = MempoolJournal::open(path, capacity)?;
+        let num_entries = journal.num_entries();
+        let (sender, receiver) = mpsc::sync_channel(JOURNAL_CHANNEL_SIZE);
+        let thread = thread::Builder::new()
+            .name("mempool-journal".into())
+            .spawn(move || write_journal(journal, receiver))?;
+        let writer = Self {
+            sender: Some(sender),
+            thread: Some(thread),
+            capacity,
+            num_entries,
+        };
+        Ok((writer, entries))
+    }
+
+    /// Appends an entry to the journal, unless it's full
+    pub fn append(&mut self, entry: JournalEntry) {
+        if self.num_entries >= self.capacity {
+            counters::CORE_MEMPOOL_JOURNAL_DROPPED_WRITES.inc();
+            sample!(
+                SampleRate::Duration(Duration::from_secs(10)),
+                warn!(
+                    LogSchema::new(LogEntry::Journal),
+                    "The mempool journal is full, dropping writes until it's compacted"
+                )
+            );
+            return;
+        }
+        if self.send(JournalWrite::Append(entry)) {
+            self.num_entries += 1;
+        }
+    }
+
+    /// Returns true if the journal should be compacted, given the number of transactions in
+    /// Mempool. This is the case once the journal is half full and at least half of its entries
+    /// are no longer in Mempool, so that compactions are amortized over as many appends as entries
+    /// they keep, or once the journal is full and some of its entries are no longer in Mempool.
+    pub fn needs_compaction(&self, num_transactions: usize) -> bool {
+        (self.num_entries >= self.capacity / 2 && self.num_entries >= 2 * num_transactions)
+            || (self.num_entries >= self.capacity && self.num_entries > num_transactions)
+    }
+
+    /// Compacts the journal down to the given transactions, which must be those in Mempool. The
+    /// journal is reread and rewritten by the writer thread.
+    pub fn compact(&mut self, txn_keys: HashSet<TxnKey>) {
+        let num_entries = txn_keys.len().min(self.capacity);
+        if self.send(JournalWrite::Compact(txn_keys)) {
+            self.num_entries = num_entries;
+        }
+    }
+
+    fn send(&self, write: JournalWrite) -> bool {
+        let sender = self.sender.as_ref().expect("The journal writer is running");
+        match sender.try_send(write) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                counters::CORE_MEMPOOL_JOURNAL_DROPPED_WRITES.inc();
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(10)),
+                    warn!(
+                        LogSchema::new(LogEntry::Journal),
+                        "The mempool journal writer is behind, dropping writes"
+                    )
+                );
+                false
+            },
+            Err(TrySendError::Disconnected(_)) => {
+                counters::CORE_MEMPOOL_JOURNAL_DROPPED_WRITES.inc();
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(10)),
+                    error!(
+                        LogSchema::new(LogEntry::Journal),
+                        "The mempool journal writer has stopped"
+                    )
+                );
+                false
+            },
+        }
+    }
+}
+
+impl Drop for JournalWriter {
+    fn drop(&mut self) {
+        // Closing the channel stops the writer thread once it has done the pending writes
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                error!(
+                    LogSchema::new(LogEntry::Journal),
+                    "The mempool journal writer panicked"
+                );
+            }
+        }
+    }
+}
+
+/// Runs the journal writer thread, until the channel is closed
+fn write_journal(mut journal: MempoolJournal, receiver: Receiver<JournalWrite>) {
+    while let Ok(write) = receiver.recv() {
+        let batch = iter::once(write).chain(receiver.try_iter().take(MAX_BATCH_SIZE - 1));
+        for write in batch {
+            let result = match write {
+                JournalWrite::Append(entry) => journal.append(&entry),
+                JournalWrite::Compact(txn_keys) => journal.compact(&txn_keys),
+            };
+            if let Err(e) = result {
+                error!(
+                    LogSchema::new(LogEntry::Journal).error(&e),
+                    "Failed to write to the mempool journal"
+                );
+            }
+        }
+        if let Err(e) = journal.sync() {
+            error!(
+                LogSchema::new(LogEntry::Journal).error(&e),
+                "Failed to sync the mempool journal"
+            );
+        }
+    }
+}
+
+/// Replaces the content of the file at `path` with the given entries, and returns a writer
+/// appending to it
+fn rewrite(path: &Path, entries: &[JournalEntry]) -> Result<BufWriter<File>> {
+    // Write the entries to a temporary file first, so that a crash can't lose the journal
+    let tmp_path = path.with_extension("tmp");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    for entry in entries {
+        write_entry(&mut writer, entry)?;
+    }
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(BufWriter::new(OpenOptions::new().append(true).open(path)?))
+}
+
+fn write_entry(writer: &mut impl Write, entry: &JournalEntry) -> Result<()> {
+    let bytes = bcs::to_bytes(entry)?;
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Reads the entries of a journal, ignoring a partially written last entry
+fn read_entries(file: File) -> Result<Vec<JournalEntry>> {
+    let mut reader = BufReader::new(file);
+    let mut entries = vec![];
+    loop {
+        let mut len = [0u8; 4];
+        if let Err(err) = reader.read_exact(&mut len) {
+            return match err.kind() {
+                ErrorKind::UnexpectedEof => Ok(entries),
+                _ => Err(err.into()),
+            };
+        }
+        let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+        if let Err(err) = reader.read_exact(&mut bytes) {
+            return match err.kind() {
+                ErrorKind::UnexpectedEof => Ok(entries),
+                _ => Err(err.into()),
+            };
+        }
+        let entry = bcs::from_bytes(&bytes)
+            .map_err(|err| format_err!("Invalid mempool journal entry: {}", err))?;
+        entries.push(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script, TransactionPayload},
+    };
+
+    fn create_entry(sequence_number: u64, insertion_time: u64) -> JournalEntry {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = RawTransaction::new(
+            AccountAddress::random(),
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            1,
+            u64::MAX,
+            ChainId::new(10),
+        );
+        let signature = private_key.sign(&raw_txn).unwrap();
+        JournalEntry {
+            txn: SignedTransaction::new(raw_txn, private_key.public_key(), signature),
+            ranking_score: 1,
+            expiration_time: Duration::from_secs(u64::MAX),
+            insertion_time: Duration::from_secs(insertion_time),
+            timeline_state: TimelineState::NotReady,
+            client_submitted: true,
+        }
+    }
+
+    #[test]
+    fn test_reopen_journal() {
+        let path = TempPath::new();
+        let entries = vec![create_entry(0, 0), create_entry(1, 1)];
+        {
+            let (mut journal, recovered) = MempoolJournal::open(path.path(), 10).unwrap();
+            assert!(recovered.is_empty());
+            for entry in &entries {
+                journal.append(entry).unwrap();
+            }
+        }
+
+        let (_, recovered) = MempoolJournal::open(path.path(), 10).unwrap();
+        assert_eq!(recovered, entries);
+    }
+
+    #[test]
+    fn test_ignore_partially_written_entry() {
+        let path = TempPath::new();
+        let entry = create_entry(0, 0);
+        {
+            let (mut journal, _) = MempoolJournal::open(path.path(), 10).unwrap();
+            journal.append(&entry).unwrap();
+        }
+
+        // Simulate a crash in the middle of writing an entry
+        let mut file = OpenOptions::new().append(true).open(path.path()).unwrap();
+        file.write_all(&100u32.to_le_bytes()).unwrap();
+        file.write_all(&[0u8; 10]).unwrap();
+
+        let (mut journal, recovered) = MempoolJournal::open(path.path(), 10).unwrap();
+        assert_eq!(recovered, vec![entry.clone()]);
+
+        // New entries are appended after the last complete entry
+        let new_entry = create_entry(1, 1);
+        journal.append(&new_entry).unwrap();
+        let (_, recovered) = MempoolJournal::open(path.path(), 10).unwrap();
+        assert_eq!(recovered, vec![entry, new_entry]);
+    }
+
+    #[test]
+    fn test_compact() {
+        let path = TempPath::new();
+        let (mut journal, _) = MempoolJournal::open(path.path(), 3).unwrap();
+        let entries = vec![create_entry(0, 0), create_entry(1, 1), create_entry(2, 2)];
+        for entry in &entries {
+            journal.append(entry).unwrap();
+        }
+
+        // The journal is full
+        assert!(journal.append(&create_entry(3, 3)).is_err());
+
+        // Only the entries of the given transactions are kept
+        let txn_keys = [entries[0].key(), entries[2].key()].into_iter().collect();
+        journal.compact(&txn_keys).unwrap();
+        assert_eq!(journal.num_entries(), 2);
+        let (_, recovered) = MempoolJournal::open(path.path(), 3).unwrap();
+        assert_eq!(recovered, vec![entries[0].clone(), entries[2].clone()]);
+    }
+
+    #[test]
+    fn test_compact_keeps_most_recent_entries() {
+        let path = TempPath::new();
+        let entries = vec![create_entry(0, 2), create_entry(1, 0), create_entry(2, 1)];
+        {
+            let (mut journal, _) = MempoolJournal::open(path.path(), 3).unwrap();
+            for entry in &entries {
+                journal.append(entry).unwrap();
+            }
+        }
+
+        // Reopening the journal with a smaller capacity compacts it
+        let (journal, recovered) = MempoolJournal::open(path.path(), 2).unwrap();
+        assert_eq!(recovered, entries);
+        assert_eq!(journal.num_entries(), 2);
+        let (_, recovered) = MempoolJournal::open(path.path(), 2).unwrap();
+        assert_eq!(recovered, vec![entries[2].clone(), entries[0].clone()]);
+    }
+
+    #[test]
+    fn test_journal_writer() {
+        let path = TempPath::new();
+        let entries = vec![create_entry(0, 0), create_entry(1, 1), create_entry(2, 2)];
+        {
+            let (mut writer, recovered) = JournalWriter::open(path.path(), 2).unwrap();
+            assert!(recovered.is_empty());
+            writer.append(entries[0].clone());
+            // Not compacted while most entries are still in Mempool
+            assert!(!writer.needs_compaction(1));
+            assert!(writer.needs_compaction(0));
+            writer.append(entries[1].clone());
+            assert!(!writer.needs_compaction(2));
+
+            // The journal is full, so the entry is dropped
+            writer.append(entries[2].clone());
+            assert!(writer.needs_compaction(1));
+
+            writer.compact([entries[1].key()].into_iter().collect());
+            assert!(!writer.needs_compaction(1));
+            writer.append(entries[2].clone());
+        }
+
+        // Dropping the writer waits for the pending writes
+        let (_, recovered) = MempoolJournal::open(path.path(), 2).unwrap();
+        assert_eq!(recovered, entries[1..].to_vec());
+    }
+}
@@ -7,12 +7,13 @@
 use crate::{
     core_mempool::{
         index::TxnPointer,
+        journal::{JournalEntry, JournalWriter},
         transaction::{InsertionInfo, MempoolTransaction, TimelineState},
         transaction_store::TransactionStore,
     },
     counters,
     logging::{LogEntry, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{MempoolStats, MultiBucketTimelineIndexIds, PendingTransactionInfo},
 };
 use aptos_config::config::NodeConfig;
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
@@ -26,20 +27,46 @@ use aptos_types::{
 };
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    time::{Duration, Instant, SystemTime},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 pub struct Mempool {
     // Stores the metadata of all transactions in mempool (of all states).
     transactions: TransactionStore,
+    // Journal of the accepted transactions, if enabled.
+    journal: Option<JournalWriter>,
+    // Transactions recovered from the journal on startup, waiting to be re-validated.
+    recovered_transactions: Vec<JournalEntry>,
+    // Whether the recovered transactions have been restored. The journal isn't compacted until
+    // then, so that it keeps the entries of the transactions being restored.
+    journal_restored: bool,
 
     pub system_transaction_timeout: Duration,
 }
 
 impl Mempool {
     pub fn new(config: &NodeConfig) -> Self {
+        let (journal, recovered_transactions) = if config.mempool.journal_enabled {
+            let path = config.mempool.journal_path(&config.base.data_dir);
+            match JournalWriter::open(&path, config.mempool.journal_capacity) {
+                Ok((journal, recovered_transactions)) => (Some(journal), recovered_transactions),
+                Err(e) => {
+                    error!(
+                        LogSchema::new(LogEntry::Journal).error(&e),
+                        "Failed to open the mempool journal at {:?}", path
+                    );
+                    (None, vec![])
+                },
+            }
+        } else {
+            (None, vec![])
+        };
+
         Mempool {
             transactions: TransactionStore::new(&config.mempool),
+            journal,
+            journal_restored: recovered_transactions.is_empty(),
+            recovered_transactions,
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
@@ -171,6 +198,17 @@ impl Mempool {
         self.transactions.get_by_hash(hash)
     }
 
+    pub(crate) fn get_pending_transactions(
+        &self,
+        address: &AccountAddress,
+    ) -> Vec<PendingTransactionInfo> {
+        self.transactions.get_pending_transactions(address)
+    }
+
+    pub(crate) fn get_stats(&self) -> MempoolStats {
+        self.transactions.get_stats()
+    }
+
     /// Used to add a transaction to the Mempool.
     /// Performs basic validation: checks account's sequence number.
     pub(crate) fn add_txn(
@@ -210,7 +248,15 @@ impl Mempool {
             client_submitted,
         );
 
+        let journal_entry = self.journal.as_ref().map(|_| JournalEntry::from(&txn_info));
         let status = self.transactions.insert(txn_info);
+        if let Some(journal_entry) = journal_entry {
+            if status.code == MempoolStatusCode::Accepted {
+                if let Some(journal) = &mut self.journal {
+                    journal.append(journal_entry);
+                }
+            }
+        }
         counters::core_mempool_txn_ranking_score(
             counters::INSERT_LABEL,
             status.code.to_string().as_str(),
@@ -220,6 +266,61 @@ impl Mempool {
         status
     }
 
+    /// Takes the transactions recovered from the journal on startup. They must be re-validated
+    /// before being restored with `restore_txn`.
+    pub(crate) fn take_recovered_transactions(&mut self) -> Vec<JournalEntry> {
+        std::mem::take(&mut self.recovered_transactions)
+    }
+
+    /// Restores a transaction recovered from the journal, keeping its original insertion info,
+    /// ranking score and system expiration time.
+    pub(crate) fn restore_txn(
+        &mut self,
+        entry: JournalEntry,
+        db_sequence_number: u64,
+    ) -> MempoolStatus {
+        // don't restore committed transactions
+        if entry.txn.sequence_number() < db_sequence_number {
+            return MempoolStatus::new(MempoolStatusCode::InvalidSeqNumber).with_message(format!(
+                "transaction sequence number is {}, current sequence number is  {}",
+                entry.txn.sequence_number(),
+                db_sequence_number,
+            ));
+        }
+
+        let txn_info = MempoolTransaction::new(
+            entry.txn,
+            entry.expiration_time,
+            entry.ranking_score,
+            entry.timeline_state,
+            db_sequence_number,
+            UNIX_EPOCH + entry.insertion_time,
+            entry.client_submitted,
+        );
+        self.transactions.insert(txn_info)
+    }
+
+    /// Marks the recovered transactions as restored, and compacts the journal down to the
+    /// transactions currently in Mempool, dropping those that weren't restored.
+    pub(crate) fn finish_journal_restore(&mut self) {
+        self.journal_restored = true;
+        if let Some(journal) = &mut self.journal {
+            journal.compact(self.transactions.get_txn_keys());
+        }
+    }
+
+    /// Compacts the journal down to the transactions currently in Mempool, if it needs to be.
+    fn maybe_compact_journal(&mut self) {
+        if !self.journal_restored {
+            return;
+        }
+        if let Some(journal) = &mut self.journal {
+            if journal.needs_compaction(self.transactions.get_stats().num_transactions) {
+                journal.compact(self.transactions.get_txn_keys());
+            }
+        }
+    }
+
     fn was_seen(
         txn_pointer: &TransactionSummary,
         seen: &HashMap<TransactionSummary, u64>,
@@ -405,6 +506,7 @@ impl Mempool {
     pub(crate) fn gc(&mut self) {
         let now = aptos_infallible::duration_since_epoch();
         self.transactions.gc_by_system_ttl(now);
+        self.maybe_compact_journal();
     }
 
     /// Garbage collection based on client-specified expiration time.
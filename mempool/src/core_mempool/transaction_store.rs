@@ -8,6 +8,7 @@ use crate::{
             AccountTransactions, MultiBucketTimelineIndex, ParkingLotIndex, PriorityIndex,
             PriorityQueueIter, TTLIndex,
         },
+        journal::TxnKey,
         mempool::Mempool,
         transaction::{InsertionInfo, MempoolTransaction, TimelineState},
         TxnPointer,
@@ -15,7 +16,7 @@ use crate::{
     counters,
     counters::{BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL},
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{MempoolStats, MultiBucketTimelineIndexIds, PendingTransactionInfo},
 };
 use aptos_config::config::MempoolConfig;
 use aptos_crypto::HashValue;
@@ -27,7 +28,7 @@ use aptos_types::{
 };
 use std::{
     cmp::max,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     mem::size_of,
     ops::Bound,
     time::{Duration, SystemTime},
@@ -759,6 +760,46 @@ impl TransactionStore {
         self.track_indices();
     }
 
+    /// Returns the keys of all the transactions in the store
+    pub(crate) fn get_txn_keys(&self) -> HashSet<TxnKey> {
+        self.transactions
+            .iter()
+            .flat_map(|(address, txns)| txns.keys().map(|seq_num| (*address, *seq_num)))
+            .collect()
+    }
+
+    /// Returns the transactions of an account, ordered by sequence number
+    pub(crate) fn get_pending_transactions(
+        &self,
+        address: &AccountAddress,
+    ) -> Vec<PendingTransactionInfo> {
+        self.transactions
+            .get(address)
+            .map(|txns| {
+                txns.iter()
+                    .map(|(seq_num, txn)| PendingTransactionInfo {
+                        txn: txn.txn.clone(),
+                        parked: self.parking_lot_index.contains(address, seq_num),
+                        ranking_score: txn.ranking_score,
+                        bucket: self.get_bucket(txn.ranking_score).to_string(),
+                        insertion_time: txn.insertion_info.insertion_time,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get_stats(&self) -> MempoolStats {
+        MempoolStats {
+            num_transactions: self.system_ttl_index.size(),
+            num_parked_transactions: self.parking_lot_index.size(),
+            num_accounts: self.transactions.len(),
+            size_bytes: self.size_bytes,
+            capacity: self.capacity,
+            capacity_bytes: self.capacity_bytes,
+        }
+    }
+
     pub(crate) fn iter_queue(&self) -> PriorityQueueIter {
         self.priority_index.iter()
     }
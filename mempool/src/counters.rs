@@ -64,6 +64,8 @@ pub const SUCCESS_LABEL: &str = "success";
 // Bounded executor task labels
 pub const CLIENT_EVENT_LABEL: &str = "client_event";
 pub const CLIENT_EVENT_GET_TXN_LABEL: &str = "client_event_get_txn";
+pub const CLIENT_EVENT_GET_PENDING_TXNS_LABEL: &str = "client_event_get_pending_txns";
+pub const CLIENT_EVENT_GET_STATS_LABEL: &str = "client_event_get_stats";
 pub const RECONFIG_EVENT_LABEL: &str = "reconfig";
 pub const PEER_BROADCAST_EVENT_LABEL: &str = "peer_broadcast";
 
@@ -183,6 +185,15 @@ pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of journal writes dropped because the journal writer fell behind
+pub static CORE_MEMPOOL_JOURNAL_DROPPED_WRITES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_core_mempool_journal_dropped_writes_count",
+        "Number of journal writes dropped because the journal writer fell behind"
+    )
+    .unwrap()
+});
+
 pub fn core_mempool_txn_commit_latency(
     stage: &'static str,
     submitted_by: &'static str,
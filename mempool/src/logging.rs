@@ -156,6 +156,8 @@ pub enum LogEntry {
     ReconfigUpdate,
     JsonRpc,
     GetTransaction,
+    GetPendingTransactions,
+    GetStats,
     GetBlock,
     QuorumStore,
     StateSyncCommit,
@@ -173,6 +175,7 @@ pub enum LogEntry {
     DBError,
     UnexpectedNetworkMsg,
     MempoolSnapshot,
+    Journal,
 }
 
 #[derive(Clone, Copy, Serialize)]
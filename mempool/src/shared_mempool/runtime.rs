@@ -7,6 +7,7 @@ use crate::{
     network::MempoolSyncMsg,
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
+        tasks::restore_journal_transactions,
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
     QuorumStoreRequest,
@@ -32,6 +33,7 @@ use tokio::runtime::{Handle, Runtime};
 ///   - outbound_sync_task (task that periodically broadcasts transactions to peers).
 ///   - inbound_network_task (task that handles inbound mempool messages and network events).
 ///   - gc_task (task that performs GC of all expired transactions by SystemTTL).
+///   - restore task (task that restores the transactions of the journal on startup).
 pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     executor: &Handle,
     config: &NodeConfig,
@@ -54,6 +56,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
         SharedMempool::new(
             mempool.clone(),
             config.mempool.clone(),
+            config.execution.transaction_filter.clone(),
             network_client,
             db,
             validator,
@@ -61,6 +64,10 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
             config.base.role,
         );
 
+    // Restoring the transactions of the journal takes a while, so it's done in the background
+    let restore_smp = smp.clone();
+    executor.spawn_blocking(move || restore_journal_transactions(&restore_smp));
+
     executor.spawn(coordinator(
         smp,
         executor.clone(),
@@ -9,8 +9,8 @@ use crate::{
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
     shared_mempool::types::{
-        notify_subscribers, MultiBatchId, ScheduledBroadcast, SharedMempool,
-        SharedMempoolNotification, SubmissionStatusBundle,
+        notify_subscribers, MempoolStats, MultiBatchId, PendingTransactionInfo, ScheduledBroadcast,
+        SharedMempool, SharedMempoolNotification, SubmissionStatusBundle,
     },
     thread_pool::IO_POOL,
     QuorumStoreRequest, QuorumStoreResponse, SubmissionStatus,
@@ -25,6 +25,7 @@ use aptos_metrics_core::HistogramTimer;
 use aptos_network::application::interface::NetworkClientInterface;
 use aptos_storage_interface::state_view::LatestDbStateCheckpointView;
 use aptos_types::{
+    account_address::AccountAddress,
     mempool_status::{MempoolStatus, MempoolStatusCode},
     on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig},
     transaction::SignedTransaction,
@@ -40,6 +41,9 @@ use std::{
 };
 use tokio::runtime::Handle;
 
+/// Number of transactions recovered from the Mempool journal that are restored at once
+const RESTORE_BATCH_SIZE: usize = 1_000;
+
 // ============================== //
 //  broadcast_coordinator tasks  //
 // ============================== //
@@ -163,6 +167,49 @@ pub(crate) async fn process_client_get_transaction<NetworkClient, TransactionVal
     }
 }
 
+/// Processes get pending transactions by sender request by client.
+pub(crate) async fn process_client_get_pending_transactions<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    address: AccountAddress,
+    callback: oneshot::Sender<Vec<PendingTransactionInfo>>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    let txns = smp.mempool.lock().get_pending_transactions(&address);
+
+    if callback.send(txns).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::GetPendingTransactions,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
+/// Processes get mempool stats request by client.
+pub(crate) async fn process_client_get_stats<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    callback: oneshot::Sender<MempoolStats>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    let stats = smp.mempool.lock().get_stats();
+
+    if callback.send(stats).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::GetStats,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes transactions from other nodes.
 pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
@@ -263,6 +310,33 @@ where
 {
     let mut statuses = vec![];
 
+    // Reject the transactions denied by the transaction filter before any further processing
+    let transaction_filter = &smp.transaction_filter;
+    let transactions: Vec<_> = if transaction_filter.is_empty() {
+        transactions
+    } else {
+        transactions
+            .into_iter()
+            .filter_map(|t| {
+                if transaction_filter.allows_transaction(&t) {
+                    return Some(t);
+                }
+                statuses.push((
+                    t,
+                    (
+                        MempoolStatus::new(MempoolStatusCode::RejectedByFilter)
+                            .with_message("Transaction is denied by the transaction filter".into()),
+                        None,
+                    ),
+                ));
+                None
+            })
+            .collect()
+    };
+    if transactions.is_empty() {
+        return statuses;
+    }
+
     let start_storage_read = Instant::now();
     let state_view = smp
         .db
@@ -329,6 +403,73 @@ where
     statuses
 }
 
+/// Re-validates the transactions recovered from the Mempool journal on startup, and restores
+/// those that are still valid and allowed by the transaction filter. Expired and already committed
+/// transactions are dropped. The transactions are validated in parallel, and restored in batches
+/// so that Mempool isn't locked for long.
+pub(crate) fn restore_journal_transactions<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    let recovered_transactions = smp.mempool.lock().take_recovered_transactions();
+    if recovered_transactions.is_empty() {
+        return;
+    }
+    let num_recovered = recovered_transactions.len();
+
+    let state_view = match smp.db.latest_state_checkpoint_view() {
+        Ok(state_view) => state_view,
+        Err(e) => {
+            error!(LogSchema::new(LogEntry::DBError).error(&e));
+            counters::DB_ERROR.inc();
+            smp.mempool.lock().finish_journal_restore();
+            return;
+        },
+    };
+    let now = aptos_infallible::duration_since_epoch();
+    let mut num_restored = 0;
+    for batch in recovered_transactions.chunks(RESTORE_BATCH_SIZE) {
+        let validated_transactions: Vec<_> = batch
+            .par_iter()
+            .filter(|entry| {
+                entry.expiration_time > now
+                    && Duration::from_secs(entry.txn.expiration_timestamp_secs()) > now
+                    && smp.transaction_filter.allows_transaction(&entry.txn)
+            })
+            .filter_map(|entry| {
+                let sequence_number =
+                    get_account_sequence_number(&state_view, entry.txn.sender()).ok()?;
+                if entry.txn.sequence_number() < sequence_number {
+                    return None;
+                }
+                match smp.validator.read().validate_transaction(entry.txn.clone()) {
+                    Ok(validation_result) if validation_result.status().is_none() => {
+                        Some((entry.clone(), sequence_number))
+                    },
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let mut mempool = smp.mempool.lock();
+        num_restored += validated_transactions
+            .into_iter()
+            .map(|(entry, sequence_number)| mempool.restore_txn(entry, sequence_number))
+            .filter(|status| status.code == MempoolStatusCode::Accepted)
+            .count();
+    }
+    // Drop the transactions that weren't restored from the journal
+    smp.mempool.lock().finish_journal_restore();
+
+    info!(
+        LogSchema::new(LogEntry::Journal).num_txns(num_restored),
+        num_recovered = num_recovered,
+        "Restored transactions from the mempool journal"
+    );
+}
+
 /// Perfoms VM validation on the transactions and inserts those that passes
 /// validation into the mempool.
 #[cfg(not(feature = "consensus-only-perf-test"))]
@@ -9,7 +9,7 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_config::{
-    config::{MempoolConfig, RoleType},
+    config::{transaction_filter_type::Filter, MempoolConfig, RoleType},
     network_id::PeerNetworkId,
 };
 use aptos_consensus_types::common::{
@@ -22,7 +22,8 @@ use aptos_network::{
 };
 use aptos_storage_interface::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -47,6 +48,8 @@ use tokio::runtime::Handle;
 pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub mempool: Arc<Mutex<CoreMempool>>,
     pub config: MempoolConfig,
+    /// The transaction filter of consensus, also applied to the transactions entering the Mempool
+    pub transaction_filter: Filter,
     pub network_interface: MempoolNetworkInterface<NetworkClient>,
     pub db: Arc<dyn DbReader>,
     pub validator: Arc<RwLock<TransactionValidator>>,
@@ -62,6 +65,7 @@ impl<
     pub fn new(
         mempool: Arc<Mutex<CoreMempool>>,
         config: MempoolConfig,
+        transaction_filter: Filter,
         network_client: NetworkClient,
         db: Arc<dyn DbReader>,
         validator: Arc<RwLock<TransactionValidator>>,
@@ -72,6 +76,7 @@ impl<
         SharedMempool {
             mempool,
             config,
+            transaction_filter,
             network_interface,
             db,
             validator,
@@ -232,6 +237,32 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    GetPendingTransactionsBySender(AccountAddress, oneshot::Sender<Vec<PendingTransactionInfo>>),
+    GetStats(oneshot::Sender<MempoolStats>),
+}
+
+/// A transaction waiting in mempool, along with its status in mempool
+#[derive(Clone, Debug)]
+pub struct PendingTransactionInfo {
+    pub txn: SignedTransaction,
+    /// Whether the transaction is in the parking lot, i.e. it can't be included in the next block
+    /// because a transaction with a lower sequence number is missing
+    pub parked: bool,
+    pub ranking_score: u64,
+    /// Broadcast bucket of the transaction, derived from its ranking score
+    pub bucket: String,
+    pub insertion_time: SystemTime,
+}
+
+/// Aggregate statistics of the transactions in mempool
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MempoolStats {
+    pub num_transactions: usize,
+    pub num_parked_transactions: usize,
+    pub num_accounts: usize,
+    pub size_bytes: usize,
+    pub capacity: usize,
+    pub capacity_bytes: usize,
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
@@ -12,6 +12,7 @@ use crate::{
 use aptos_config::config::NodeConfig;
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
+use aptos_temppath::TempPath;
 use aptos_types::{
     mempool_status::MempoolStatusCode, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
 };
@@ -914,3 +915,85 @@ fn test_include_gas_upgraded() {
     });
     assert_eq!(batch.len(), 0);
 }
+
+#[test]
+fn test_restore_from_journal() {
+    let data_dir = TempPath::new();
+    data_dir.create_as_dir().unwrap();
+    let mut config = NodeConfig::generate_random_config();
+    config.set_data_dir(data_dir.path().to_path_buf());
+    config.mempool.journal_enabled = true;
+
+    let mut mempool = CoreMempool::new(&config);
+    add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 1),
+        TestTransaction::new(1, 0, 1),
+    ]);
+    drop(mempool);
+
+    // The accepted transactions are recovered after a restart
+    let mut mempool = CoreMempool::new(&config);
+    let recovered_transactions = mempool.take_recovered_transactions();
+    assert_eq!(recovered_transactions.len(), 3);
+
+    // Restore the transactions, the first transaction of account 0 having been committed
+    let committed_account = TestTransaction::get_address(0);
+    let num_restored = recovered_transactions
+        .into_iter()
+        .map(|entry| {
+            let db_sequence_number = if entry.txn.sender() == committed_account {
+                1
+            } else {
+                0
+            };
+            mempool.restore_txn(entry, db_sequence_number)
+        })
+        .filter(|status| status.code == MempoolStatusCode::Accepted)
+        .count();
+    assert_eq!(num_restored, 2);
+    mempool.finish_journal_restore();
+    drop(mempool);
+
+    // The committed transaction is dropped from the journal
+    let mut mempool = CoreMempool::new(&config);
+    assert_eq!(mempool.take_recovered_transactions().len(), 2);
+}
+
+#[test]
+fn test_get_pending_transactions() {
+    let mut pool = setup_mempool_with_broadcast_buckets(vec![0, 101, 201]).0;
+    add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 150),
+        TestTransaction::new(0, 3, 250),
+        TestTransaction::new(1, 0, 1),
+    ]);
+
+    let pending_txns = pool.get_pending_transactions(&TestTransaction::get_address(0));
+    let summaries: Vec<_> = pending_txns
+        .iter()
+        .map(|info| {
+            (
+                info.txn.sequence_number(),
+                info.parked,
+                info.bucket.as_str(),
+            )
+        })
+        .collect();
+    // The transaction with sequence number 3 is parked, as sequence number 2 is missing
+    assert_eq!(summaries, vec![
+        (0, false, "0"),
+        (1, false, "101"),
+        (3, true, "201")
+    ]);
+    assert!(pool
+        .get_pending_transactions(&TestTransaction::get_address(2))
+        .is_empty());
+
+    let stats = pool.get_stats();
+    assert_eq!(stats.num_transactions, 4);
+    assert_eq!(stats.num_parked_transactions, 1);
+    assert_eq!(stats.num_accounts, 2);
+    assert!(stats.size_bytes > 0);
+}
@@ -4,9 +4,14 @@
 
 use crate::tests::{
     common::TestTransaction,
-    test_framework::{test_transaction, MempoolNode, MempoolTestFrameworkBuilder},
+    test_framework::{
+        test_transaction, MempoolNode, MempoolTestFramework, MempoolTestFrameworkBuilder,
+    },
+};
+use aptos_config::{
+    config::{transaction_filter_type::Filter, NodeConfig},
+    network_id::PeerNetworkId,
 };
-use aptos_config::network_id::PeerNetworkId;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_network::{
     testutils::{
@@ -19,6 +24,7 @@ use aptos_network::{
     transport::ConnectionMetadata,
     ProtocolId,
 };
+use aptos_types::mempool_status::MempoolStatusCode;
 use std::time::Duration;
 
 const ALL_PROTOCOLS: [ProtocolId; 1] = [ProtocolId::MempoolDirectSend];
@@ -380,3 +386,19 @@ async fn fn_to_val_test() {
         val.assert_only_txns_in_mempool(ALL_TXNS);
     }
 }
+
+/// Transactions denied by the transaction filter are rejected at admission
+#[tokio::test]
+async fn test_transaction_filter() {
+    let denied_txn = TestTransaction::new(2, 0, 1);
+    let mut config = NodeConfig::get_default_validator_config();
+    config.execution.transaction_filter =
+        Filter::empty().add_deny_sender(TestTransaction::get_address(denied_txn.address));
+    let mut node = MempoolTestFramework::single_validator_with_config(config);
+
+    assert_eq!(node.submit_txns_via_client(&[denied_txn]).await, vec![
+        MempoolStatusCode::RejectedByFilter
+    ]);
+    node.add_txns_via_client(TXN_1).await;
+    node.assert_only_txns_in_mempool(TXN_1);
+}
@@ -47,6 +47,7 @@ use aptos_types::{
     mempool_status::MempoolStatusCode,
     on_chain_config::{InMemoryOnChainConfig, OnChainConfigPayload},
     transaction::SignedTransaction,
+    PeerId,
 };
 use aptos_vm_validator::mocks::mock_vm_validator::MockVMValidator;
 use futures::{channel::oneshot, SinkExt};
@@ -145,6 +146,17 @@ impl ApplicationNode for MempoolNode {
 impl MempoolNode {
     /// Queues transactions for sending on a node, uses client
     pub async fn add_txns_via_client(&mut self, txns: &[TestTransaction]) {
+        for status in self.submit_txns_via_client(txns).await {
+            assert_eq!(status, MempoolStatusCode::Accepted)
+        }
+    }
+
+    /// Submits transactions to a node through the client, and returns their statuses
+    pub async fn submit_txns_via_client(
+        &mut self,
+        txns: &[TestTransaction],
+    ) -> Vec<MempoolStatusCode> {
+        let mut statuses = vec![];
         for txn in sign_transactions(txns) {
             let (sender, receiver) = oneshot::channel();
 
@@ -153,8 +165,9 @@ impl MempoolNode {
                 .await
                 .unwrap();
             let status = receiver.await.unwrap().unwrap();
-            assert_eq!(status.0.code, MempoolStatusCode::Accepted)
+            statuses.push(status.0.code);
         }
+        statuses
     }
 
     pub async fn commit_txns(&mut self, txns: &[TestTransaction]) {
@@ -425,6 +438,17 @@ pub struct MempoolTestFramework {
     pub nodes: HashMap<NodeId, MempoolNode>,
 }
 
+impl MempoolTestFramework {
+    /// Builds a single validator [`MempoolNode`] with the given config
+    pub fn single_validator_with_config(config: NodeConfig) -> MempoolNode {
+        let peer_id = PeerId::random();
+        Self::build_node(NodeId::validator(0), config, &[
+            PeerNetworkId::new(NetworkId::Validator, peer_id),
+            PeerNetworkId::new(NetworkId::Vfn, peer_id),
+        ])
+    }
+}
+
 impl TestFramework<MempoolNode> for MempoolTestFramework {
     fn new(nodes: HashMap<NodeId, MempoolNode>) -> Self {
         Self { nodes }
@@ -871,6 +871,7 @@ pub mod any_public_key {
         Unspecified = 0,
         Ed25519 = 1,
         Secp256k1Ecdsa = 2,
+        Secp256r1Ecdsa = 3,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -882,6 +883,7 @@ pub mod any_public_key {
                 Type::Unspecified => "TYPE_UNSPECIFIED",
                 Type::Ed25519 => "TYPE_ED25519",
                 Type::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+                Type::Secp256r1Ecdsa => "TYPE_SECP256R1_ECDSA",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -890,6 +892,7 @@ pub mod any_public_key {
                 "TYPE_UNSPECIFIED" => Some(Self::Unspecified),
                 "TYPE_ED25519" => Some(Self::Ed25519),
                 "TYPE_SECP256K1_ECDSA" => Some(Self::Secp256k1Ecdsa),
+                "TYPE_SECP256R1_ECDSA" => Some(Self::Secp256r1Ecdsa),
                 _ => None,
             }
         }
@@ -911,6 +914,7 @@ pub mod any_signature {
         Unspecified = 0,
         Ed25519 = 1,
         Secp256k1Ecdsa = 2,
+        WebAuthn = 3,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -922,6 +926,7 @@ pub mod any_signature {
                 Type::Unspecified => "TYPE_UNSPECIFIED",
                 Type::Ed25519 => "TYPE_ED25519",
                 Type::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
+                Type::WebAuthn => "TYPE_WEBAUTHN",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -930,6 +935,7 @@ pub mod any_signature {
                 "TYPE_UNSPECIFIED" => Some(Self::Unspecified),
                 "TYPE_ED25519" => Some(Self::Ed25519),
                 "TYPE_SECP256K1_ECDSA" => Some(Self::Secp256k1Ecdsa),
+                "TYPE_WEBAUTHN" => Some(Self::WebAuthn),
                 _ => None,
             }
         }
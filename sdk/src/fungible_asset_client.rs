@@ -0,0 +1,326 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bcs,
+    coin_client::TransferOptions,
+    move_types::{
+        ident_str,
+        identifier::Identifier,
+        language_storage::{ModuleId, StructTag, TypeTag},
+    },
+    rest_client::{aptos_api_types::ViewFunction, Client as ApiClient, PendingTransaction},
+    transaction_builder::TransactionBuilder,
+    types::{
+        account_address::{create_object_address, AccountAddress},
+        account_config::CORE_CODE_ADDRESS,
+        chain_id::ChainId,
+        transaction::{EntryFunction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Client for fungible assets (`0x1::fungible_asset`) held in primary stores
+/// (`0x1::primary_fungible_store`). A fungible asset is identified by the address of its
+/// metadata object. Transactions take the same [`TransferOptions`] as the coin client, except
+/// that their `coin_type` is ignored.
+#[derive(Clone, Debug)]
+pub struct FungibleAssetClient<'a> {
+    api_client: &'a ApiClient,
+}
+
+/// The `0x1::fungible_asset::Metadata` resource of a fungible asset.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct FungibleAssetMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub icon_uri: String,
+    pub project_uri: String,
+}
+
+impl<'a> FungibleAssetClient<'a> {
+    pub fn new(api_client: &'a ApiClient) -> Self {
+        Self { api_client }
+    }
+
+    /// The address of the metadata object of a fungible asset created as a named object by
+    /// `creator`, with `seed` as its name (`0x1::object::create_named_object`).
+    pub fn named_metadata_address(creator: AccountAddress, seed: &[u8]) -> AccountAddress {
+        create_object_address(creator, seed)
+    }
+
+    /// Reads the metadata of the fungible asset whose metadata object is at `metadata`, which
+    /// fails if there is no fungible asset metadata at that address.
+    pub async fn get_metadata(&self, metadata: AccountAddress) -> Result<FungibleAssetMetadata> {
+        let resource = self
+            .api_client
+            .get_account_resource(metadata, "0x1::fungible_asset::Metadata")
+            .await
+            .context("Failed to get fungible asset metadata")?
+            .into_inner()
+            .ok_or_else(|| anyhow!("No fungible asset metadata at {}", metadata))?;
+        Ok(serde_json::from_value(resource.data)?)
+    }
+
+    /// Gets the balance of the primary store of `account`, which is 0 if the account never
+    /// held the fungible asset.
+    pub async fn get_balance(
+        &self,
+        account: AccountAddress,
+        metadata: AccountAddress,
+    ) -> Result<u64> {
+        let response = self
+            .api_client
+            .get_account_fa_balance(account, metadata)
+            .await
+            .context("Failed to get fungible asset balance")?;
+        Ok(response.into_inner())
+    }
+
+    /// Gets the total balance of an asset which exists both as a coin of type `coin_type` and as
+    /// a fungible asset with metadata object at `metadata`, i.e. the sum of the balance of the
+    /// account's `CoinStore` (if it is registered for the coin) and of its primary store.
+    pub async fn get_paired_balance(
+        &self,
+        account: AccountAddress,
+        coin_type: &str,
+        metadata: AccountAddress,
+    ) -> Result<u64> {
+        let is_registered = Self::coin_registered_view(account, TypeTag::from_str(coin_type)?);
+        let is_registered: Vec<bool> = self
+            .api_client
+            .view_bcs(&is_registered, None)
+            .await
+            .context("Failed to check coin registration")?
+            .into_inner();
+        let coin_balance = if is_registered.first().copied().unwrap_or(false) {
+            self.api_client
+                .get_account_balance_bcs(account, coin_type)
+                .await
+                .context("Failed to get coin balance")?
+                .into_inner()
+        } else {
+            0
+        };
+        let fa_balance = self.get_balance(account, metadata).await?;
+        coin_balance
+            .checked_add(fa_balance)
+            .ok_or_else(|| anyhow!("Balance of {} overflows", account))
+    }
+
+    /// Transfers `amount` from the primary store of `from_account` to the primary store of
+    /// `to_account`, which is created if it doesn't exist yet.
+    pub async fn transfer(
+        &self,
+        from_account: &mut LocalAccount,
+        metadata: AccountAddress,
+        to_account: AccountAddress,
+        amount: u64,
+        options: Option<TransferOptions<'_>>,
+    ) -> Result<PendingTransaction> {
+        self.submit(
+            from_account,
+            Self::transfer_payload(metadata, to_account, amount),
+            options,
+        )
+        .await
+        .context("Failed to submit transfer transaction")
+    }
+
+    /// Mints `amount` into the primary store of `to_account`, see [`Self::mint_payload`].
+    pub async fn mint(
+        &self,
+        admin_account: &mut LocalAccount,
+        module: ModuleId,
+        to_account: AccountAddress,
+        amount: u64,
+        options: Option<TransferOptions<'_>>,
+    ) -> Result<PendingTransaction> {
+        self.submit(
+            admin_account,
+            Self::mint_payload(module, to_account, amount),
+            options,
+        )
+        .await
+        .context("Failed to submit mint transaction")
+    }
+
+    /// Burns `amount` from the primary store of `from_account`, see [`Self::burn_payload`].
+    pub async fn burn(
+        &self,
+        admin_account: &mut LocalAccount,
+        module: ModuleId,
+        from_account: AccountAddress,
+        amount: u64,
+        options: Option<TransferOptions<'_>>,
+    ) -> Result<PendingTransaction> {
+        self.submit(
+            admin_account,
+            Self::burn_payload(module, from_account, amount),
+            options,
+        )
+        .await
+        .context("Failed to submit burn transaction")
+    }
+
+    /// Payload of `0x1::primary_fungible_store::transfer`.
+    pub fn transfer_payload(
+        metadata: AccountAddress,
+        to_account: AccountAddress,
+        amount: u64,
+    ) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            ModuleId::new(
+                CORE_CODE_ADDRESS,
+                Identifier::new("primary_fungible_store").unwrap(),
+            ),
+            Identifier::new("transfer").unwrap(),
+            vec![TypeTag::Struct(Box::new(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new("fungible_asset").unwrap(),
+                name: Identifier::new("Metadata").unwrap(),
+                type_params: vec![],
+            }))],
+            vec![
+                bcs::to_bytes(&metadata).unwrap(),
+                bcs::to_bytes(&to_account).unwrap(),
+                bcs::to_bytes(&amount).unwrap(),
+            ],
+        ))
+    }
+
+    /// View function checking whether `account` is registered for the coin of type
+    /// `coin_type`, i.e. whether it has a `CoinStore` holding a coin balance.
+    pub fn coin_registered_view(account: AccountAddress, coin_type: TypeTag) -> ViewFunction {
+        ViewFunction {
+            module: ModuleId::new(CORE_CODE_ADDRESS, ident_str!("coin").to_owned()),
+            function: ident_str!("is_account_registered").to_owned(),
+            ty_args: vec![coin_type],
+            args: vec![bcs::to_bytes(&account).unwrap()],
+        }
+    }
+
+    /// Payload minting `amount` into the primary store of `to_account`. The framework doesn't
+    /// provide entry functions to mint or burn, as only the holder of the mint and burn refs of
+    /// a fungible asset can do so, so this calls `mint(admin: &signer, to: address, amount: u64)`
+    /// in the module of the issuer, which is the convention of the fungible asset examples.
+    pub fn mint_payload(
+        module: ModuleId,
+        to_account: AccountAddress,
+        amount: u64,
+    ) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            module,
+            Identifier::new("mint").unwrap(),
+            vec![],
+            vec![
+                bcs::to_bytes(&to_account).unwrap(),
+                bcs::to_bytes(&amount).unwrap(),
+            ],
+        ))
+    }
+
+    /// Payload burning `amount` from the primary store of `from_account`, by calling
+    /// `burn(admin: &signer, from: address, amount: u64)` in the module of the issuer (see
+    /// [`Self::mint_payload`]).
+    pub fn burn_payload(
+        module: ModuleId,
+        from_account: AccountAddress,
+        amount: u64,
+    ) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            module,
+            Identifier::new("burn").unwrap(),
+            vec![],
+            vec![
+                bcs::to_bytes(&from_account).unwrap(),
+                bcs::to_bytes(&amount).unwrap(),
+            ],
+        ))
+    }
+
+    async fn submit(
+        &self,
+        account: &mut LocalAccount,
+        payload: TransactionPayload,
+        options: Option<TransferOptions<'_>>,
+    ) -> Result<PendingTransaction> {
+        let options = options.unwrap_or_default();
+
+        let chain_id = self
+            .api_client
+            .get_index()
+            .await
+            .context("Failed to get chain ID")?
+            .inner()
+            .chain_id;
+        let transaction_builder = TransactionBuilder::new(
+            payload,
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + options.timeout_secs,
+            ChainId::new(chain_id),
+        )
+        .sender(account.address())
+        .sequence_number(account.sequence_number())
+        .max_gas_amount(options.max_gas_amount)
+        .gas_unit_price(options.gas_unit_price);
+        let signed_txn = account.sign_with_transaction_builder(transaction_builder);
+        Ok(self.api_client.submit(&signed_txn).await?.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_payload() {
+        let metadata = AccountAddress::from_hex_literal("0xa").unwrap();
+        let to_account = AccountAddress::from_hex_literal("0xb").unwrap();
+        let entry_function = match FungibleAssetClient::transfer_payload(metadata, to_account, 7) {
+            TransactionPayload::EntryFunction(entry_function) => entry_function,
+            payload => panic!("Unexpected payload {:?}", payload),
+        };
+        assert_eq!(
+            entry_function.module(),
+            &ModuleId::new(
+                CORE_CODE_ADDRESS,
+                ident_str!("primary_fungible_store").to_owned()
+            )
+        );
+        assert_eq!(entry_function.function().as_str(), "transfer");
+        assert_eq!(entry_function.ty_args(), &[TypeTag::from_str(
+            "0x1::fungible_asset::Metadata"
+        )
+        .unwrap()]);
+        assert_eq!(entry_function.args(), &[
+            bcs::to_bytes(&metadata).unwrap(),
+            bcs::to_bytes(&to_account).unwrap(),
+            bcs::to_bytes(&7u64).unwrap(),
+        ]);
+    }
+
+    #[test]
+    fn test_coin_registered_view() {
+        let account = AccountAddress::from_hex_literal("0xb").unwrap();
+        let coin_type = TypeTag::from_str("0x1::aptos_coin::AptosCoin").unwrap();
+        let view = FungibleAssetClient::coin_registered_view(account, coin_type.clone());
+        assert_eq!(
+            view.module,
+            ModuleId::new(CORE_CODE_ADDRESS, ident_str!("coin").to_owned())
+        );
+        assert_eq!(view.function.as_str(), "is_account_registered");
+        assert_eq!(view.ty_args, vec![coin_type]);
+        assert_eq!(view.args, vec![bcs::to_bytes(&account).unwrap()]);
+    }
+}
@@ -24,6 +24,8 @@ pub use bcs;
 
 pub mod coin_client;
 
+pub mod fungible_asset_client;
+
 pub mod crypto {
     pub use aptos_crypto::*;
 }
@@ -38,4 +40,6 @@ pub mod rest_client {
 
 pub mod transaction_builder;
 
+pub mod transaction_submitter;
+
 pub mod types;
@@ -6,9 +6,13 @@ use crate::{
     move_types::account_address::AccountAddress,
     types::{
         chain_id::ChainId,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, TransactionPayload},
+        transaction::{
+            authenticator::AuthenticationKey, RawTransaction, SignedTransaction, TransactionPayload,
+        },
+        TransactionSigner,
     },
 };
+use anyhow::{ensure, Result};
 pub use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{ed25519::Ed25519PublicKey, HashValue};
 use aptos_global_constants::{GAS_UNIT_PRICE, MAX_GAS_AMOUNT};
@@ -84,6 +88,43 @@ impl TransactionBuilder {
             self.chain_id,
         )
     }
+
+    /// Builds the transaction with `signer` as its sender, failing if the sequence number isn't
+    /// set.
+    fn build_for(self, signer: &dyn TransactionSigner) -> Result<RawTransaction> {
+        ensure!(
+            self.sequence_number.is_some(),
+            "sequence number must have been set"
+        );
+        Ok(self.sender(signer.address()).build())
+    }
+
+    /// Builds the transaction with `signer` as its sender and signs it. The sequence number must
+    /// have been set.
+    pub fn sign(self, signer: &dyn TransactionSigner) -> Result<SignedTransaction> {
+        signer.sign_transaction(self.build_for(signer)?)
+    }
+
+    /// Builds the transaction with `signer` as its sender and signs it along with the secondary
+    /// signers. The sequence number must have been set.
+    pub fn sign_multi_agent(
+        self,
+        signer: &dyn TransactionSigner,
+        secondary_signers: &[&dyn TransactionSigner],
+    ) -> Result<SignedTransaction> {
+        signer.sign_multi_agent_transaction(self.build_for(signer)?, secondary_signers)
+    }
+
+    /// Builds the transaction with `signer` as its sender and signs it along with the secondary
+    /// signers and the fee payer. The sequence number must have been set.
+    pub fn sign_fee_payer(
+        self,
+        signer: &dyn TransactionSigner,
+        secondary_signers: &[&dyn TransactionSigner],
+        fee_payer: &dyn TransactionSigner,
+    ) -> Result<SignedTransaction> {
+        signer.sign_fee_payer_transaction(self.build_for(signer)?, secondary_signers, fee_payer)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,655 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    move_types::vm_status::StatusCode,
+    rest_client::{
+        aptos_api_types::{AptosErrorCode, TransactionData, TransactionOnChainData},
+        error::RestError,
+        Client as ApiClient,
+    },
+    transaction_builder::TransactionFactory,
+    types::{
+        account_address::AccountAddress,
+        transaction::{SignedTransaction, TransactionPayload},
+        TransactionSigner,
+    },
+};
+use anyhow::{anyhow, bail, Result};
+use aptos_crypto::HashValue;
+use async_trait::async_trait;
+use futures::future::join_all;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Semaphore;
+
+/// Manages the sequence numbers of an account whose transactions are submitted concurrently.
+/// Sequence numbers are reserved for transactions before they are signed, and tracked until
+/// their transactions are committed. Numbers of transactions which were rejected or expired
+/// are reused by the next transactions, so that no gap blocks the transactions after them.
+#[derive(Debug)]
+pub struct SequenceNumberManager {
+    address: AccountAddress,
+    state: Mutex<SequenceNumbers>,
+}
+
+#[derive(Debug)]
+struct SequenceNumbers {
+    /// Sequence number of the account on chain, i.e. of its next transaction to be committed
+    committed: u64,
+    /// Next sequence number to reserve, if there are no gaps
+    next: u64,
+    /// Reserved sequence numbers, along with the expiration time of their transaction once it
+    /// was submitted
+    in_flight: BTreeMap<u64, Option<u64>>,
+    /// Sequence numbers below `next` which are not used by any transaction
+    gaps: BTreeSet<u64>,
+}
+
+impl SequenceNumberManager {
+    pub fn new(address: AccountAddress, sequence_number: u64) -> Self {
+        Self {
+            address,
+            state: Mutex::new(SequenceNumbers {
+                committed: sequence_number,
+                next: sequence_number,
+                in_flight: BTreeMap::new(),
+                gaps: BTreeSet::new(),
+            }),
+        }
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    /// Number of transactions whose sequence number is reserved but not known to be committed
+    pub fn num_in_flight(&self) -> usize {
+        self.state.lock().unwrap().in_flight.len()
+    }
+
+    /// Reserves a sequence number for a new transaction, filling gaps first.
+    pub fn reserve(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        let sequence_number = match state.gaps.pop_first() {
+            Some(gap) => gap,
+            None => {
+                state.next += 1;
+                state.next - 1
+            },
+        };
+        state.in_flight.insert(sequence_number, None);
+        sequence_number
+    }
+
+    /// Records that the transaction with a reserved sequence number was accepted, and expires at
+    /// `expiration_timestamp_secs`.
+    pub fn submitted(&self, sequence_number: u64, expiration_timestamp_secs: u64) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(expiration) = state.in_flight.get_mut(&sequence_number) {
+            *expiration = Some(expiration_timestamp_secs);
+        }
+    }
+
+    /// Gives back a reserved sequence number whose transaction wasn't accepted, or will never be
+    /// committed.
+    pub fn release(&self, sequence_number: u64) {
+        let mut state = self.state.lock().unwrap();
+        if state.in_flight.remove(&sequence_number).is_some() {
+            state.gaps.insert(sequence_number);
+            state.trim();
+        }
+    }
+
+    /// Records that the transaction with the given sequence number was committed, which means
+    /// all the transactions before it were committed as well.
+    pub fn committed(&self, sequence_number: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.update_committed(sequence_number + 1);
+    }
+
+    /// Catches up with the sequence number of the account on chain, and frees the sequence
+    /// numbers of the transactions which expired without being committed. Returns the number of
+    /// such transactions.
+    pub fn sync(&self, sequence_number: u64) -> usize {
+        self.sync_at(sequence_number, now_secs())
+    }
+
+    fn sync_at(&self, sequence_number: u64, now_secs: u64) -> usize {
+        let mut state = self.state.lock().unwrap();
+        state.update_committed(sequence_number);
+
+        let expired: Vec<u64> = state
+            .in_flight
+            .iter()
+            .filter(
+                |(_, expiration)| matches!(expiration, Some(expiration) if *expiration < now_secs),
+            )
+            .map(|(sequence_number, _)| *sequence_number)
+            .collect();
+        for sequence_number in &expired {
+            state.in_flight.remove(sequence_number);
+            state.gaps.insert(*sequence_number);
+        }
+        state.trim();
+        expired.len()
+    }
+}
+
+impl SequenceNumbers {
+    fn update_committed(&mut self, sequence_number: u64) {
+        self.committed = self.committed.max(sequence_number);
+        self.next = self.next.max(self.committed);
+        let committed = self.committed;
+        self.in_flight
+            .retain(|sequence_number, _| *sequence_number >= committed);
+        self.gaps
+            .retain(|sequence_number| *sequence_number >= committed);
+        self.trim();
+    }
+
+    /// Gives back the gaps at the end of the reserved sequence numbers, so that the numbers to
+    /// reserve next follow the ones in flight.
+    fn trim(&mut self) {
+        while self.next > self.committed && self.gaps.remove(&(self.next - 1)) {
+            self.next -= 1;
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Requests to the node made by the [`TransactionSubmitter`].
+#[async_trait]
+trait Node: Send + Sync {
+    async fn get_sequence_number(&self, address: AccountAddress) -> Result<u64, RestError>;
+
+    async fn submit(&self, txn: &SignedTransaction) -> Result<(), RestError>;
+
+    async fn wait(&self, txn: &SignedTransaction) -> Result<TransactionOnChainData, RestError>;
+
+    async fn get_transaction(&self, hash: HashValue) -> Result<TransactionData, RestError>;
+}
+
+#[async_trait]
+impl Node for ApiClient {
+    async fn get_sequence_number(&self, address: AccountAddress) -> Result<u64, RestError> {
+        match self.get_account_bcs(address).await {
+            Ok(account) => Ok(account.into_inner().sequence_number()),
+            // The account is created by its first transaction
+            Err(RestError::Api(response))
+                if response.error.error_code == AptosErrorCode::AccountNotFound =>
+            {
+                Ok(0)
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn submit(&self, txn: &SignedTransaction) -> Result<(), RestError> {
+        self.submit_bcs(txn).await.map(|_| ())
+    }
+
+    async fn wait(&self, txn: &SignedTransaction) -> Result<TransactionOnChainData, RestError> {
+        self.wait_for_signed_transaction_bcs(txn)
+            .await
+            .map(|response| response.into_inner())
+    }
+
+    async fn get_transaction(&self, hash: HashValue) -> Result<TransactionData, RestError> {
+        self.get_transaction_by_hash_bcs(hash)
+            .await
+            .map(|response| response.into_inner())
+    }
+}
+
+/// Submits transactions of an account concurrently, without its callers managing sequence
+/// numbers. The sequence numbers are managed by a [`SequenceNumberManager`], which resyncs with
+/// the chain when a transaction is rejected because of its sequence number or expires, and the
+/// transactions are then resubmitted. The number of transactions in flight is bounded, so that
+/// callers wait for earlier transactions to commit instead of overflowing mempool.
+pub struct TransactionSubmitter {
+    node: Box<dyn Node>,
+    signer: Arc<dyn TransactionSigner + Send + Sync>,
+    transaction_factory: TransactionFactory,
+    sequence_numbers: SequenceNumberManager,
+    in_flight: Semaphore,
+    max_attempts: usize,
+}
+
+impl TransactionSubmitter {
+    /// Number of times a transaction is submitted before giving up
+    const DEFAULT_MAX_ATTEMPTS: usize = 5;
+    /// Delay before resubmitting a transaction rejected because mempool is full
+    const MEMPOOL_FULL_DELAY: Duration = Duration::from_millis(500);
+
+    /// Creates a submitter for the account of `signer`, starting from its sequence number on
+    /// chain, which lets up to `max_in_flight` transactions wait to be committed at a time.
+    pub async fn new(
+        api_client: ApiClient,
+        signer: Arc<dyn TransactionSigner + Send + Sync>,
+        transaction_factory: TransactionFactory,
+        max_in_flight: usize,
+    ) -> Result<Self> {
+        Self::with_node(
+            Box::new(api_client),
+            signer,
+            transaction_factory,
+            max_in_flight,
+        )
+        .await
+    }
+
+    async fn with_node(
+        node: Box<dyn Node>,
+        signer: Arc<dyn TransactionSigner + Send + Sync>,
+        transaction_factory: TransactionFactory,
+        max_in_flight: usize,
+    ) -> Result<Self> {
+        let sequence_number = node.get_sequence_number(signer.address()).await?;
+        Ok(Self {
+            node,
+            sequence_numbers: SequenceNumberManager::new(signer.address(), sequence_number),
+            signer,
+            transaction_factory,
+            in_flight: Semaphore::new(max_in_flight),
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+        })
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn sequence_numbers(&self) -> &SequenceNumberManager {
+        &self.sequence_numbers
+    }
+
+    /// Resyncs the sequence numbers with the chain.
+    pub async fn resync(&self) -> Result<usize> {
+        let sequence_number = self.node.get_sequence_number(self.signer.address()).await?;
+        Ok(self.sequence_numbers.sync(sequence_number))
+    }
+
+    /// Submits a transaction with the given payload and waits for it to be committed, which
+    /// fails if the transaction is committed but its execution failed, e.g. aborted. Can be
+    /// called concurrently, in which case transactions are submitted in parallel.
+    pub async fn submit_and_wait(
+        &self,
+        payload: TransactionPayload,
+    ) -> Result<TransactionOnChainData> {
+        let _permit = self.in_flight.acquire().await?;
+
+        let mut last_error = None;
+        for _ in 0..self.max_attempts {
+            let sequence_number = self.sequence_numbers.reserve();
+            let txn = match self
+                .transaction_factory
+                .payload(payload.clone())
+                .sequence_number(sequence_number)
+                .sign(self.signer.as_ref())
+            {
+                Ok(txn) => txn,
+                Err(err) => {
+                    self.sequence_numbers.release(sequence_number);
+                    return Err(err);
+                },
+            };
+
+            if let Err(err) = self.node.submit(&txn).await {
+                match SubmissionFailure::from(&err) {
+                    SubmissionFailure::SequenceNumber => {
+                        self.sequence_numbers.release(sequence_number);
+                        self.resync().await?;
+                        last_error = Some(err.into());
+                        continue;
+                    },
+                    SubmissionFailure::MempoolFull => {
+                        self.sequence_numbers.release(sequence_number);
+                        tokio::time::sleep(Self::MEMPOOL_FULL_DELAY).await;
+                        last_error = Some(err.into());
+                        continue;
+                    },
+                    SubmissionFailure::Rejected => {
+                        self.sequence_numbers.release(sequence_number);
+                        return Err(err.into());
+                    },
+                    // The transaction may have been accepted, so it is waited for like the
+                    // accepted ones, and its sequence number is kept until it expires
+                    SubmissionFailure::Unknown => (),
+                }
+            }
+            self.sequence_numbers
+                .submitted(sequence_number, txn.expiration_timestamp_secs());
+
+            match self.wait(&txn).await {
+                Ok(Some(committed)) => {
+                    if !committed.info.status().is_success() {
+                        bail!(
+                            "Transaction {} of {} failed: {:?}",
+                            sequence_number,
+                            self.signer.address(),
+                            committed.info.status()
+                        );
+                    }
+                    return Ok(committed);
+                },
+                Ok(None) => {
+                    last_error = Some(anyhow!(
+                        "Transaction {} of {} expired",
+                        sequence_number,
+                        self.signer.address()
+                    ));
+                },
+                Err(err) => return Err(err),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow!("No attempt to submit the transaction")))
+    }
+
+    /// Submits transactions with the given payloads in parallel, and waits for all of them to
+    /// be committed.
+    pub async fn submit_and_wait_all(
+        &self,
+        payloads: Vec<TransactionPayload>,
+    ) -> Vec<Result<TransactionOnChainData>> {
+        join_all(
+            payloads
+                .into_iter()
+                .map(|payload| self.submit_and_wait(payload)),
+        )
+        .await
+    }
+
+    /// Waits for a transaction to be committed. Returns `None` if it expired or another
+    /// transaction used its sequence number, in which case its sequence number is freed.
+    async fn wait(&self, txn: &SignedTransaction) -> Result<Option<TransactionOnChainData>> {
+        match self.node.wait(txn).await {
+            Ok(committed) => {
+                self.sequence_numbers.committed(txn.sequence_number());
+                Ok(Some(committed))
+            },
+            Err(err) => {
+                // The transaction may have been committed, expired or be still pending, which
+                // the chain tells apart
+                self.resync().await?;
+                if txn.sequence_number() < self.committed_sequence_number() {
+                    match self
+                        .node
+                        .get_transaction(txn.clone().committed_hash())
+                        .await
+                    {
+                        Ok(TransactionData::OnChain(committed)) => Ok(Some(committed)),
+                        // Another transaction used the sequence number
+                        Ok(TransactionData::Pending(_)) => Ok(None),
+                        Err(RestError::Api(response))
+                            if response.error.error_code == AptosErrorCode::TransactionNotFound =>
+                        {
+                            Ok(None)
+                        },
+                        Err(err) => Err(err.into()),
+                    }
+                } else if txn.expiration_timestamp_secs() < now_secs() {
+                    Ok(None)
+                } else {
+                    Err(err.into())
+                }
+            },
+        }
+    }
+
+    fn committed_sequence_number(&self) -> u64 {
+        self.sequence_numbers.state.lock().unwrap().committed
+    }
+}
+
+/// Reasons for which the submission of a transaction can fail
+#[derive(Debug, PartialEq, Eq)]
+enum SubmissionFailure {
+    /// The sequence number was already used, or is too far ahead of the chain, so the
+    /// transaction is resubmitted
+    SequenceNumber,
+    /// Mempool has too many transactions, in general or of the account, so the transaction is
+    /// resubmitted
+    MempoolFull,
+    /// The node rejected the transaction for another reason
+    Rejected,
+    /// The node didn't answer, so the transaction may have been accepted
+    Unknown,
+}
+
+impl From<&RestError> for SubmissionFailure {
+    fn from(err: &RestError) -> Self {
+        let error = match err {
+            RestError::Api(response) => &response.error,
+            _ => return Self::Unknown,
+        };
+        match error.error_code {
+            AptosErrorCode::SequenceNumberTooOld | AptosErrorCode::InvalidTransactionUpdate => {
+                Self::SequenceNumber
+            },
+            AptosErrorCode::MempoolIsFull => Self::MempoolFull,
+            _ => match error
+                .vm_error_code
+                .and_then(|code| StatusCode::try_from(code).ok())
+            {
+                Some(StatusCode::SEQUENCE_NUMBER_TOO_OLD | StatusCode::SEQUENCE_NUMBER_TOO_NEW) => {
+                    Self::SequenceNumber
+                },
+                _ => Self::Rejected,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        move_types::vm_status::AbortLocation,
+        rest_client::{aptos_api_types::AptosError, error::AptosErrorResponse},
+        transaction_builder::aptos_stdlib,
+        types::{
+            chain_id::ChainId,
+            transaction::{ExecutionStatus, Transaction, TransactionInfo},
+            write_set::WriteSet,
+            LocalAccount,
+        },
+    };
+    use std::collections::VecDeque;
+
+    /// Node answering the requests of a submitter with queued responses
+    #[derive(Default)]
+    struct MockNode {
+        sequence_number: Mutex<u64>,
+        submit_results: Mutex<VecDeque<Result<(), RestError>>>,
+        /// Execution statuses of the transactions waited for, which aren't committed when
+        /// there are none left
+        wait_results: Mutex<VecDeque<ExecutionStatus>>,
+        /// Sequence numbers of the submitted transactions
+        submitted: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl Node for Arc<MockNode> {
+        async fn get_sequence_number(&self, _address: AccountAddress) -> Result<u64, RestError> {
+            Ok(*self.sequence_number.lock().unwrap())
+        }
+
+        async fn submit(&self, txn: &SignedTransaction) -> Result<(), RestError> {
+            self.submitted.lock().unwrap().push(txn.sequence_number());
+            self.submit_results
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(Ok(()))
+        }
+
+        async fn wait(&self, txn: &SignedTransaction) -> Result<TransactionOnChainData, RestError> {
+            let status = self
+                .wait_results
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or(RestError::Timeout("transaction not committed"))?;
+            *self.sequence_number.lock().unwrap() = txn.sequence_number() + 1;
+            Ok(TransactionOnChainData {
+                version: 0,
+                transaction: Transaction::UserTransaction(txn.clone()),
+                info: TransactionInfo::new(
+                    HashValue::zero(),
+                    HashValue::zero(),
+                    HashValue::zero(),
+                    None,
+                    0,
+                    status,
+                ),
+                events: vec![],
+                accumulator_root_hash: HashValue::zero(),
+                changes: WriteSet::default(),
+            })
+        }
+
+        async fn get_transaction(&self, _hash: HashValue) -> Result<TransactionData, RestError> {
+            Err(api_error(AptosErrorCode::TransactionNotFound))
+        }
+    }
+
+    fn api_error(error_code: AptosErrorCode) -> RestError {
+        RestError::Api(AptosErrorResponse {
+            error: AptosError::new_with_error_code("rejected", error_code),
+            state: None,
+            status_code: reqwest::StatusCode::BAD_REQUEST,
+        })
+    }
+
+    async fn submitter(node: &Arc<MockNode>) -> TransactionSubmitter {
+        TransactionSubmitter::with_node(
+            Box::new(node.clone()),
+            Arc::new(LocalAccount::generate(&mut rand::rngs::OsRng)),
+            TransactionFactory::new(ChainId::test()),
+            10,
+        )
+        .await
+        .unwrap()
+    }
+
+    fn payload() -> TransactionPayload {
+        aptos_stdlib::aptos_coin_transfer(AccountAddress::ONE, 1)
+    }
+
+    #[test]
+    fn test_sequence_number_manager() {
+        let manager = SequenceNumberManager::new(AccountAddress::ONE, 10);
+        assert_eq!((0..4).map(|_| manager.reserve()).collect::<Vec<_>>(), vec![
+            10, 11, 12, 13
+        ]);
+        for sequence_number in 10..13 {
+            manager.submitted(sequence_number, 100);
+        }
+
+        // A rejected transaction leaves a gap which is filled first
+        manager.release(11);
+        assert_eq!(manager.reserve(), 11);
+        manager.submitted(11, 200);
+
+        // Trailing gaps are given back
+        manager.release(13);
+        assert_eq!(manager.reserve(), 13);
+        manager.release(13);
+
+        // Commits advance the sequence number
+        manager.committed(10);
+        assert_eq!(manager.num_in_flight(), 2);
+
+        // Expired transactions are freed on sync, and the ones after them are still in flight
+        assert_eq!(manager.sync_at(11, 150), 1);
+        assert_eq!(manager.num_in_flight(), 1);
+        assert_eq!(manager.reserve(), 12);
+        assert_eq!(manager.reserve(), 13);
+
+        // Sequence numbers used by others on chain are skipped
+        assert_eq!(manager.sync_at(20, 150), 0);
+        assert_eq!(manager.num_in_flight(), 0);
+        assert_eq!(manager.reserve(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_resubmit_after_sequence_number_error() {
+        let node = Arc::new(MockNode::default());
+        let submitter = submitter(&node).await;
+
+        // Other transactions of the account were committed in the meantime
+        *node.sequence_number.lock().unwrap() = 3;
+        node.submit_results
+            .lock()
+            .unwrap()
+            .push_back(Err(api_error(AptosErrorCode::SequenceNumberTooOld)));
+        node.wait_results
+            .lock()
+            .unwrap()
+            .push_back(ExecutionStatus::Success);
+
+        submitter.submit_and_wait(payload()).await.unwrap();
+        assert_eq!(*node.submitted.lock().unwrap(), vec![0, 3]);
+        assert_eq!(submitter.sequence_numbers().num_in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_aborted_transaction() {
+        let node = Arc::new(MockNode::default());
+        let submitter = submitter(&node).await;
+
+        node.wait_results
+            .lock()
+            .unwrap()
+            .push_back(ExecutionStatus::MoveAbort {
+                location: AbortLocation::Script,
+                code: 1,
+                info: None,
+            });
+        assert!(submitter.submit_and_wait(payload()).await.is_err());
+
+        // The transaction was committed, so it isn't resubmitted and its sequence number is used
+        assert_eq!(*node.submitted.lock().unwrap(), vec![0]);
+        assert_eq!(submitter.sequence_numbers().reserve(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submission_errors() {
+        let node = Arc::new(MockNode::default());
+        let submitter = submitter(&node).await;
+
+        // Without an answer of the node, the transaction may have been accepted, so it is waited
+        // for instead of reusing its sequence number
+        node.submit_results
+            .lock()
+            .unwrap()
+            .push_back(Err(RestError::Timeout("no answer")));
+        node.wait_results
+            .lock()
+            .unwrap()
+            .push_back(ExecutionStatus::Success);
+        submitter.submit_and_wait(payload()).await.unwrap();
+        assert_eq!(*node.submitted.lock().unwrap(), vec![0]);
+
+        // A rejected transaction gives back its sequence number
+        node.submit_results
+            .lock()
+            .unwrap()
+            .push_back(Err(api_error(AptosErrorCode::InvalidInput)));
+        assert!(submitter.submit_and_wait(payload()).await.is_err());
+        assert_eq!(*node.submitted.lock().unwrap(), vec![0, 1]);
+        assert_eq!(submitter.sequence_numbers().num_in_flight(), 0);
+        assert_eq!(submitter.sequence_numbers().reserve(), 1);
+    }
+}
@@ -5,16 +5,23 @@
 use crate::{
     crypto::{
         ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-        signing_message,
-        traits::Uniform,
+        multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+        secp256k1_ecdsa, signing_message,
+        traits::{PrivateKey, SigningKey, Uniform},
     },
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{
+                AccountAuthenticator, AnyPublicKey, AnySignature, AuthenticationKey, MultiKey,
+                MultiKeyAuthenticator, SingleKeyAuthenticator,
+            },
+            RawTransaction, RawTransactionWithData, SignedTransaction,
+        },
     },
 };
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use aptos_crypto::ed25519::Ed25519Signature;
 use aptos_ledger::AptosLedgerError;
 use aptos_types::event::EventKey;
@@ -212,13 +219,157 @@ pub enum HardwareWalletType {
     Ledger,
 }
 
+/// A message signed by the signers of a transaction.
+#[derive(Clone, Copy, Debug)]
+pub enum SigningMessage<'a> {
+    /// A transaction signed by its sender only
+    RawTransaction(&'a RawTransaction),
+    /// A multi-agent or fee payer transaction, signed by each of its signers
+    RawTransactionWithData(&'a RawTransactionWithData),
+}
+
+impl SigningMessage<'_> {
+    /// The bytes to sign, for signers which sign arbitrary bytes (e.g. hardware wallets or KMSs).
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(match self {
+            Self::RawTransaction(txn) => signing_message(*txn)?,
+            Self::RawTransactionWithData(txn) => signing_message(*txn)?,
+        })
+    }
+
+    fn sign<K: SigningKey>(&self, key: &K) -> Result<K::SignatureMaterial> {
+        Ok(match self {
+            Self::RawTransaction(txn) => key.sign(*txn)?,
+            Self::RawTransactionWithData(txn) => key.sign(*txn)?,
+        })
+    }
+}
+
+/// Signs transactions on behalf of an account, whatever the scheme of the key(s) of the account.
+/// Other signers, e.g. backed by a KMS, can be plugged in by implementing `sign_message`.
 pub trait TransactionSigner {
-    fn sign_transaction(&self, txn: RawTransaction) -> Result<SignedTransaction>;
+    /// Address of the account the signer signs for.
+    fn address(&self) -> AccountAddress;
+
+    /// Authentication key of the key(s) of the signer.
+    fn authentication_key(&self) -> AuthenticationKey;
+
+    /// Signs a message, returning the authenticator of the account for it.
+    fn sign_message(&self, message: SigningMessage) -> Result<AccountAuthenticator>;
 
+    /// Signs a transaction whose sender is the only signer.
+    fn sign_transaction(&self, txn: RawTransaction) -> Result<SignedTransaction> {
+        let authenticator = self.sign_message(SigningMessage::RawTransaction(&txn))?;
+        Ok(match authenticator {
+            // Ed25519 and multi-ed25519 senders keep using their dedicated authenticators
+            AccountAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            } => SignedTransaction::new(txn, public_key, signature),
+            AccountAuthenticator::MultiEd25519 {
+                public_key,
+                signature,
+            } => SignedTransaction::new_multisig(txn, public_key, signature),
+            authenticator => SignedTransaction::new_single_sender(txn, authenticator),
+        })
+    }
+
+    /// Builds the transaction with the signer as its sender and signs it. The sequence number must
+    /// have been set, unless the signer keeps track of it.
     fn sign_with_transaction_builder(
         &mut self,
         builder: TransactionBuilder,
-    ) -> Result<SignedTransaction>;
+    ) -> Result<SignedTransaction>
+    where
+        Self: Sized,
+    {
+        builder.sign(self)
+    }
+
+    /// Signs a transaction with secondary signers, along with the secondary signers.
+    fn sign_multi_agent_transaction(
+        &self,
+        txn: RawTransaction,
+        secondary_signers: &[&dyn TransactionSigner],
+    ) -> Result<SignedTransaction> {
+        let secondary_signer_addresses: Vec<_> = secondary_signers
+            .iter()
+            .map(|signer| signer.address())
+            .collect();
+        let message = RawTransactionWithData::new_multi_agent(
+            txn.clone(),
+            secondary_signer_addresses.clone(),
+        );
+        let message = SigningMessage::RawTransactionWithData(&message);
+        let sender = self.sign_message(message)?;
+        let secondary_authenticators = secondary_signers
+            .iter()
+            .map(|signer| signer.sign_message(message))
+            .collect::<Result<_>>()?;
+        Ok(SignedTransaction::new_multi_agent(
+            txn,
+            sender,
+            secondary_signer_addresses,
+            secondary_authenticators,
+        ))
+    }
+
+    /// Signs a transaction with secondary signers and whose gas is paid by a fee payer, along with
+    /// the secondary signers and the fee payer.
+    fn sign_fee_payer_transaction(
+        &self,
+        txn: RawTransaction,
+        secondary_signers: &[&dyn TransactionSigner],
+        fee_payer: &dyn TransactionSigner,
+    ) -> Result<SignedTransaction> {
+        let secondary_signer_addresses: Vec<_> = secondary_signers
+            .iter()
+            .map(|signer| signer.address())
+            .collect();
+        let message = RawTransactionWithData::new_fee_payer(
+            txn.clone(),
+            secondary_signer_addresses.clone(),
+            fee_payer.address(),
+        );
+        let message = SigningMessage::RawTransactionWithData(&message);
+        let sender = self.sign_message(message)?;
+        let secondary_authenticators = secondary_signers
+            .iter()
+            .map(|signer| signer.sign_message(message))
+            .collect::<Result<_>>()?;
+        Ok(SignedTransaction::new_fee_payer(
+            txn,
+            sender,
+            secondary_signer_addresses,
+            secondary_authenticators,
+            fee_payer.address(),
+            fee_payer.sign_message(message)?,
+        ))
+    }
+}
+
+impl TransactionSigner for LocalAccount {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn authentication_key(&self) -> AuthenticationKey {
+        self.key.authentication_key()
+    }
+
+    fn sign_message(&self, message: SigningMessage) -> Result<AccountAuthenticator> {
+        Ok(AccountAuthenticator::ed25519(
+            self.public_key().clone(),
+            message.sign(self.private_key())?,
+        ))
+    }
+
+    fn sign_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> Result<SignedTransaction> {
+        Ok(LocalAccount::sign_with_transaction_builder(self, builder))
+    }
 }
 
 /// Similar to LocalAccount, but for hardware wallets.
@@ -235,14 +386,17 @@ pub struct HardwareWalletAccount {
 }
 
 impl TransactionSigner for HardwareWalletAccount {
-    fn sign_transaction(&self, txn: RawTransaction) -> Result<SignedTransaction> {
-        let signature = self.sign_arbitrary_message(
-            signing_message(&txn)
-                .expect("Unable to convert txn to signing message.")
-                .as_ref(),
-        )?;
-        Ok(SignedTransaction::new(
-            txn,
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::ed25519(&self.public_key)
+    }
+
+    fn sign_message(&self, message: SigningMessage) -> Result<AccountAuthenticator> {
+        let signature = self.sign_arbitrary_message(&message.to_bytes()?)?;
+        Ok(AccountAuthenticator::ed25519(
             self.public_key().clone(),
             signature,
         ))
@@ -334,6 +488,257 @@ impl HardwareWalletAccount {
     }
 }
 
+/// Private key of an account using the single key scheme, which supports other keys than
+/// Ed25519.
+#[derive(Debug)]
+pub enum SingleKeyPrivateKey {
+    Ed25519(Ed25519PrivateKey),
+    Secp256k1Ecdsa(secp256k1_ecdsa::PrivateKey),
+}
+
+impl SingleKeyPrivateKey {
+    pub fn public_key(&self) -> AnyPublicKey {
+        match self {
+            Self::Ed25519(private_key) => AnyPublicKey::ed25519(private_key.public_key()),
+            Self::Secp256k1Ecdsa(private_key) => {
+                AnyPublicKey::secp256k1_ecdsa(private_key.public_key())
+            },
+        }
+    }
+
+    fn sign(&self, message: SigningMessage) -> Result<AnySignature> {
+        Ok(match self {
+            Self::Ed25519(private_key) => AnySignature::ed25519(message.sign(private_key)?),
+            Self::Secp256k1Ecdsa(private_key) => {
+                AnySignature::secp256k1_ecdsa(message.sign(private_key)?)
+            },
+        })
+    }
+}
+
+impl From<Ed25519PrivateKey> for SingleKeyPrivateKey {
+    fn from(private_key: Ed25519PrivateKey) -> Self {
+        Self::Ed25519(private_key)
+    }
+}
+
+impl From<secp256k1_ecdsa::PrivateKey> for SingleKeyPrivateKey {
+    fn from(private_key: secp256k1_ecdsa::PrivateKey) -> Self {
+        Self::Secp256k1Ecdsa(private_key)
+    }
+}
+
+/// Signer of an account using the single key scheme (`SingleKey` authenticators).
+#[derive(Debug)]
+pub struct SingleKeyAccount {
+    address: AccountAddress,
+    private_key: SingleKeyPrivateKey,
+    public_key: AnyPublicKey,
+}
+
+impl SingleKeyAccount {
+    pub fn new<T: Into<SingleKeyPrivateKey>>(address: AccountAddress, private_key: T) -> Self {
+        let private_key = private_key.into();
+        let public_key = private_key.public_key();
+        Self {
+            address,
+            private_key,
+            public_key,
+        }
+    }
+
+    /// Signer of the account whose address is derived from the key, i.e. whose key was never
+    /// rotated.
+    pub fn from_private_key<T: Into<SingleKeyPrivateKey>>(private_key: T) -> Self {
+        let private_key = private_key.into();
+        let address = AuthenticationKey::any_key(private_key.public_key()).account_address();
+        Self::new(address, private_key)
+    }
+
+    pub fn public_key(&self) -> &AnyPublicKey {
+        &self.public_key
+    }
+}
+
+impl TransactionSigner for SingleKeyAccount {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::any_key(self.public_key.clone())
+    }
+
+    fn sign_message(&self, message: SigningMessage) -> Result<AccountAuthenticator> {
+        Ok(AccountAuthenticator::single_key(
+            SingleKeyAuthenticator::new(self.public_key.clone(), self.private_key.sign(message)?),
+        ))
+    }
+}
+
+/// Signer of a k-of-n account using the multi key scheme (`MultiKey` authenticators), which holds
+/// the private keys of (at least k of) the n public keys.
+#[derive(Debug)]
+pub struct MultiKeyAccount {
+    address: AccountAddress,
+    public_keys: MultiKey,
+    /// Private keys along with the index of their public key
+    private_keys: Vec<(u8, SingleKeyPrivateKey)>,
+}
+
+impl MultiKeyAccount {
+    /// The private keys can be given in any order, they are sorted by the index of their public
+    /// key, which is the order `MultiKeyAuthenticator` expects the signatures in.
+    pub fn new(
+        address: AccountAddress,
+        public_keys: MultiKey,
+        mut private_keys: Vec<(u8, SingleKeyPrivateKey)>,
+    ) -> Result<Self> {
+        private_keys.sort_by_key(|(index, _)| *index);
+        ensure!(
+            private_keys
+                .windows(2)
+                .all(|window| window[0].0 != window[1].0),
+            "Duplicate private keys"
+        );
+        ensure!(
+            private_keys.len() >= public_keys.signatures_required() as usize,
+            "Not enough private keys to sign, {} < {}",
+            private_keys.len(),
+            public_keys.signatures_required()
+        );
+        for (index, private_key) in &private_keys {
+            ensure!(
+                public_keys.public_keys().get(*index as usize) == Some(&private_key.public_key()),
+                "Private key doesn't match public key {}",
+                index
+            );
+        }
+        Ok(Self {
+            address,
+            public_keys,
+            private_keys,
+        })
+    }
+
+    /// Signer of the account whose address is derived from the keys, i.e. whose keys were never
+    /// rotated.
+    pub fn from_private_keys(
+        public_keys: MultiKey,
+        private_keys: Vec<(u8, SingleKeyPrivateKey)>,
+    ) -> Result<Self> {
+        let address = AuthenticationKey::multi_key(public_keys.clone()).account_address();
+        Self::new(address, public_keys, private_keys)
+    }
+
+    pub fn public_keys(&self) -> &MultiKey {
+        &self.public_keys
+    }
+}
+
+impl TransactionSigner for MultiKeyAccount {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::multi_key(self.public_keys.clone())
+    }
+
+    fn sign_message(&self, message: SigningMessage) -> Result<AccountAuthenticator> {
+        let mut signatures = vec![];
+        for (index, private_key) in self
+            .private_keys
+            .iter()
+            .take(self.public_keys.signatures_required() as usize)
+        {
+            signatures.push((*index, private_key.sign(message)?));
+        }
+        // Signatures have to be ordered by the index of their public key
+        signatures.sort_by_key(|(index, _)| *index);
+        Ok(AccountAuthenticator::multi_key(MultiKeyAuthenticator::new(
+            self.public_keys.clone(),
+            signatures,
+        )?))
+    }
+}
+
+/// Signer of a k-of-n account using the multi-ed25519 scheme, which holds the private keys of
+/// (at least k of) the n public keys.
+#[derive(Debug)]
+pub struct MultiEd25519Account {
+    address: AccountAddress,
+    public_key: MultiEd25519PublicKey,
+    /// Private keys along with the index of their public key
+    private_keys: Vec<(u8, Ed25519PrivateKey)>,
+}
+
+impl MultiEd25519Account {
+    pub fn new(
+        address: AccountAddress,
+        public_key: MultiEd25519PublicKey,
+        private_keys: Vec<(u8, Ed25519PrivateKey)>,
+    ) -> Result<Self> {
+        ensure!(
+            private_keys.len() >= *public_key.threshold() as usize,
+            "Not enough private keys to sign, {} < {}",
+            private_keys.len(),
+            public_key.threshold()
+        );
+        for (index, private_key) in &private_keys {
+            ensure!(
+                public_key.public_keys().get(*index as usize) == Some(&private_key.public_key()),
+                "Private key doesn't match public key {}",
+                index
+            );
+        }
+        Ok(Self {
+            address,
+            public_key,
+            private_keys,
+        })
+    }
+
+    /// Signer of the account whose address is derived from the keys, i.e. whose keys were never
+    /// rotated.
+    pub fn from_private_keys(
+        public_key: MultiEd25519PublicKey,
+        private_keys: Vec<(u8, Ed25519PrivateKey)>,
+    ) -> Result<Self> {
+        let address = AuthenticationKey::multi_ed25519(&public_key).account_address();
+        Self::new(address, public_key, private_keys)
+    }
+
+    pub fn public_key(&self) -> &MultiEd25519PublicKey {
+        &self.public_key
+    }
+}
+
+impl TransactionSigner for MultiEd25519Account {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::multi_ed25519(&self.public_key)
+    }
+
+    fn sign_message(&self, message: SigningMessage) -> Result<AccountAuthenticator> {
+        let mut signatures = vec![];
+        for (index, private_key) in self
+            .private_keys
+            .iter()
+            .take(*self.public_key.threshold() as usize)
+        {
+            signatures.push((message.sign(private_key)?, *index));
+        }
+        Ok(AccountAuthenticator::multi_ed25519(
+            self.public_key.clone(),
+            MultiEd25519Signature::new(signatures)?,
+        ))
+    }
+}
+
 #[derive(Debug)]
 pub struct AccountKey {
     private_key: Ed25519PrivateKey,
@@ -383,6 +788,8 @@ impl From<Ed25519PrivateKey> for AccountKey {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transaction_builder::TransactionFactory;
+    use aptos_types::chain_id::ChainId;
 
     #[test]
     fn test_recover_account_from_derive_path() {
@@ -403,4 +810,131 @@ mod tests {
         // Return an error for empty mnemonic phrase.
         assert!(LocalAccount::from_derive_path(derive_path, "", 0).is_err());
     }
+
+    #[test]
+    fn test_transaction_signers() {
+        let mut rng = rand::rngs::OsRng;
+        let ed25519 = LocalAccount::generate(&mut rng);
+        let secp256k1 =
+            SingleKeyAccount::from_private_key(secp256k1_ecdsa::PrivateKey::generate(&mut rng));
+
+        let private_keys: Vec<SingleKeyPrivateKey> = vec![
+            Ed25519PrivateKey::generate(&mut rng).into(),
+            secp256k1_ecdsa::PrivateKey::generate(&mut rng).into(),
+            Ed25519PrivateKey::generate(&mut rng).into(),
+        ];
+        let public_keys =
+            MultiKey::new(private_keys.iter().map(|key| key.public_key()).collect(), 2).unwrap();
+        let multi_key = MultiKeyAccount::from_private_keys(
+            public_keys,
+            private_keys
+                .into_iter()
+                .enumerate()
+                .skip(1)
+                .map(|(index, key)| (index as u8, key))
+                .collect(),
+        )
+        .unwrap();
+
+        let private_keys: Vec<_> = (0..3)
+            .map(|_| Ed25519PrivateKey::generate(&mut rng))
+            .collect();
+        let public_key = MultiEd25519PublicKey::new(
+            private_keys.iter().map(|key| key.public_key()).collect(),
+            2,
+        )
+        .unwrap();
+        let multi_ed25519 = MultiEd25519Account::from_private_keys(
+            public_key.clone(),
+            private_keys
+                .into_iter()
+                .enumerate()
+                .take(2)
+                .map(|(index, key)| (index as u8, key))
+                .collect(),
+        )
+        .unwrap();
+
+        let factory = TransactionFactory::new(ChainId::test());
+        let signers: Vec<&dyn TransactionSigner> =
+            vec![&ed25519, &secp256k1, &multi_key, &multi_ed25519];
+        for signer in signers {
+            let txn = factory
+                .transfer(AccountAddress::ONE, 1)
+                .sequence_number(0)
+                .sign(signer)
+                .unwrap();
+            assert_eq!(txn.sender(), signer.address());
+            txn.verify_signature().unwrap();
+        }
+
+        let txn = factory
+            .transfer(AccountAddress::ONE, 1)
+            .sequence_number(0)
+            .sign_multi_agent(&secp256k1, &[&multi_key, &ed25519])
+            .unwrap();
+        txn.verify_signature().unwrap();
+
+        let txn = factory
+            .transfer(AccountAddress::ONE, 1)
+            .sequence_number(0)
+            .sign_fee_payer(&multi_ed25519, &[&ed25519], &multi_key)
+            .unwrap();
+        txn.verify_signature().unwrap();
+
+        // The sequence number has to be set, unless the signer keeps track of it
+        assert!(factory
+            .transfer(AccountAddress::ONE, 1)
+            .sign(&ed25519)
+            .is_err());
+        let mut single_key =
+            SingleKeyAccount::from_private_key(Ed25519PrivateKey::generate(&mut rng));
+        let txn = single_key
+            .sign_with_transaction_builder(
+                factory.transfer(AccountAddress::ONE, 1).sequence_number(0),
+            )
+            .unwrap();
+        txn.verify_signature().unwrap();
+        let mut local = LocalAccount::generate(&mut rng);
+        let txn = TransactionSigner::sign_with_transaction_builder(
+            &mut local,
+            factory.transfer(AccountAddress::ONE, 1),
+        )
+        .unwrap();
+        txn.verify_signature().unwrap();
+        assert_eq!(local.sequence_number(), 1);
+
+        // Private keys can be given in any order
+        let private_keys: Vec<SingleKeyPrivateKey> = (0..3)
+            .map(|_| Ed25519PrivateKey::generate(&mut rng).into())
+            .collect();
+        let public_keys =
+            MultiKey::new(private_keys.iter().map(|key| key.public_key()).collect(), 3).unwrap();
+        let unsorted = MultiKeyAccount::from_private_keys(
+            public_keys,
+            private_keys
+                .into_iter()
+                .enumerate()
+                .rev()
+                .map(|(index, key)| (index as u8, key))
+                .collect(),
+        )
+        .unwrap();
+        factory
+            .transfer(AccountAddress::ONE, 1)
+            .sequence_number(0)
+            .sign(&unsorted)
+            .unwrap()
+            .verify_signature()
+            .unwrap();
+
+        // Private keys have to match the public keys
+        assert!(
+            MultiEd25519Account::new(multi_ed25519.address(), public_key, vec![
+                (0, Ed25519PrivateKey::generate(&mut rng)),
+                (1, Ed25519PrivateKey::generate(&mut rng))
+            ],)
+            .is_err()
+        );
+    }
 }
@@ -3,29 +3,40 @@
 
 use crate::{
     get_first_seq_num_and_limit,
-    pruner::{LedgerPrunerManager, StateMerklePrunerManager},
+    pruner::{LedgerPrunerManager, RetainedAccounts, StateMerklePrunerManager},
     test_helper,
     test_helper::{arb_blocks_to_commit, put_as_state_root, put_transaction_info},
     AptosDB, PrunerManager, StaleNodeIndexSchema,
 };
 use aptos_config::config::{
-    EpochSnapshotPrunerConfig, LedgerPrunerConfig, PrunerConfig, RocksdbConfigs,
+    EpochSnapshotPrunerConfig, LedgerPrunerConfig, PrunerConfig, RocksdbConfig, RocksdbConfigs,
     StateMerklePrunerConfig, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
     DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
 };
-use aptos_crypto::{hash::CryptoHash, HashValue};
-use aptos_storage_interface::{DbReader, ExecutedTrees, Order};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::CryptoHash, HashValue, PrivateKey, Uniform};
+use aptos_db_indexer::Indexer;
+use aptos_proptest_helpers::Index;
+use aptos_schemadb::SchemaBatch;
+use aptos_storage_interface::{DbReader, DbWriter, Error as StorageError, ExecutedTrees, Order};
 use aptos_temppath::TempPath;
 use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    contract_event::ContractEvent,
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     proof::SparseMerkleLeafNode,
+    proptest_types::{AccountInfoUniverse, ContractEventGen},
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
-    transaction::{ExecutionStatus, TransactionInfo, TransactionToCommit, Version},
+    test_helpers::transaction_test_helpers::get_test_signed_txn,
+    transaction::{ExecutionStatus, Transaction, TransactionInfo, TransactionToCommit, Version},
+    write_set::{WriteOp, WriteSet, WriteSetMut},
 };
-use proptest::prelude::*;
-use std::{collections::HashSet, sync::Arc};
+use move_core_types::language_storage::TypeTag;
+use proptest::{collection::vec, prelude::*};
+use std::{cell::RefCell, collections::HashSet, sync::Arc};
 use test_helper::{test_save_blocks_impl, test_sync_transactions_impl};
 
 proptest! {
@@ -40,6 +51,28 @@ proptest! {
     fn test_sync_transactions(input in arb_blocks_to_commit(), threshold in 10..20usize) {
         test_sync_transactions_impl(input, threshold);
     }
+
+    #[test]
+    fn test_create_online_checkpoint(input in arb_blocks_to_commit()) {
+        test_create_online_checkpoint_impl(input);
+    }
+
+    #[test]
+    fn test_prune_events_by_type(
+        mut universe in any_with::<AccountInfoUniverse>(3),
+        gen_batches in vec(vec((any::<Index>(), any::<ContractEventGen>()), 0..=2), 0..20),
+    ) {
+        let event_batches = gen_batches
+            .into_iter()
+            .map(|gens| {
+                gens.into_iter()
+                    .map(|(index, gen)| gen.materialize(*index, &mut universe))
+                    .collect()
+            })
+            .collect();
+
+        test_prune_events_by_type_impl(event_batches);
+    }
 }
 
 #[test]
@@ -100,13 +133,17 @@ fn test_pruner_config() {
         assert_eq!(state_merkle_pruner.is_pruner_enabled(), enable);
         assert_eq!(state_merkle_pruner.get_prune_window(), 20);
 
-        let ledger_pruner =
-            LedgerPrunerManager::new(Arc::clone(&aptos_db.ledger_db), LedgerPrunerConfig {
+        let ledger_pruner = LedgerPrunerManager::new(
+            Arc::clone(&aptos_db.ledger_db),
+            LedgerPrunerConfig {
                 enable,
                 prune_window: 100,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
-            });
+                retained_accounts: vec![],
+            },
+            None,
+        );
         assert_eq!(ledger_pruner.is_pruner_enabled(), enable);
         assert_eq!(ledger_pruner.get_prune_window(), 100);
     }
@@ -135,6 +172,12 @@ fn test_error_if_version_pruned() {
             .to_string(),
         "Transaction at version 9 is pruned, min available version is 10."
     );
+    assert!(matches!(
+        db.error_if_ledger_pruned("Transaction", 9)
+            .unwrap_err()
+            .downcast_ref::<StorageError>(),
+        Some(StorageError::Pruned { .. })
+    ));
     assert!(db.error_if_ledger_pruned("Transaction", 10).is_ok());
 }
 
@@ -187,6 +230,7 @@ pub fn test_state_merkle_pruning_impl(
                 prune_window: 10,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
+                retained_accounts: vec![],
             },
             state_merkle_pruner_config: StateMerklePrunerConfig {
                 enable: true,
@@ -289,3 +333,271 @@ proptest! {
         test_state_merkle_pruning_impl(input);
     }
 }
+
+fn test_create_online_checkpoint_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut cur_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in &input {
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            cur_ver,                /* first_version */
+            cur_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            false, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        cur_ver += txns_to_commit.len() as u64;
+    }
+
+    // The checkpoint is taken while the db is open, and holds everything committed.
+    let cp_dir = TempPath::new();
+    let progress = RefCell::new(vec![]);
+    let ledger_info_with_sigs = db
+        .create_online_checkpoint(cp_dir.path(), &|msg| {
+            progress.borrow_mut().push(msg.to_string())
+        })
+        .unwrap();
+    assert_eq!(progress.into_inner(), vec![
+        "Made ledger db checkpoint.",
+        "Made state merkle db checkpoint.",
+    ]);
+    let (last_txns_to_commit, last_ledger_info_with_sigs) = input.last().unwrap();
+    assert_eq!(&ledger_info_with_sigs, last_ledger_info_with_sigs);
+
+    let cp_db = AptosDB::new_for_test(&cp_dir);
+    assert_eq!(
+        cp_db.get_latest_ledger_info().unwrap(),
+        ledger_info_with_sigs
+    );
+    test_helper::verify_committed_transactions(
+        &cp_db,
+        last_txns_to_commit,
+        cur_ver - last_txns_to_commit.len() as u64,
+        &ledger_info_with_sigs,
+        true, /* is_latest */
+    );
+}
+
+fn test_prune_events_by_type_impl(events: Vec<Vec<ContractEvent>>) {
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test(&tmp_dir);
+    let num_versions = events.len() as Version;
+
+    // Write the events, and index them by type
+    let batch = SchemaBatch::new();
+    for (version, events_for_version) in events.iter().enumerate() {
+        aptos_db
+            .event_store
+            .put_events(
+                version as Version,
+                events_for_version,
+                /*skip_index=*/ false,
+                &batch,
+            )
+            .unwrap();
+    }
+    aptos_db.ledger_db.event_db().write_schemas(batch).unwrap();
+    let indexer_dir = TempPath::new();
+    let indexer = Arc::new(Indexer::open(&indexer_dir, RocksdbConfig::default()).unwrap());
+    let write_sets = vec![WriteSet::default(); events.len()];
+    indexer
+        .index(
+            aptos_db.state_store.clone(),
+            0,
+            &write_sets.iter().collect::<Vec<_>>(),
+            &events.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        )
+        .unwrap();
+
+    let pruner = LedgerPrunerManager::new(
+        Arc::clone(&aptos_db.ledger_db),
+        LedgerPrunerConfig {
+            enable: true,
+            prune_window: 0,
+            batch_size: 1,
+            user_pruning_window_offset: 0,
+            retained_accounts: vec![],
+        },
+        Some(Arc::clone(&indexer)),
+    );
+    pruner.wake_and_wait_pruner(num_versions).unwrap();
+
+    // The pruned events are removed from the events by type index
+    for event in events.iter().flatten() {
+        assert!(indexer
+            .get_event_indices_by_type(event.type_tag(), (0, 0), 100, num_versions)
+            .unwrap()
+            .is_empty());
+    }
+}
+
+#[test]
+fn test_ledger_pruner_with_retained_accounts() {
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test(&tmp_dir);
+    let retained_account = AccountAddress::random();
+    let other_account = AccountAddress::random();
+
+    // The account is retained from the next version on, i.e. from the genesis of the empty db
+    let pruner = LedgerPrunerManager::new(
+        Arc::clone(&aptos_db.ledger_db),
+        LedgerPrunerConfig {
+            enable: true,
+            prune_window: 0,
+            batch_size: 1,
+            user_pruning_window_offset: 0,
+            retained_accounts: vec![retained_account],
+        },
+        None,
+    );
+
+    // Version 0 is sent by the retained account, version 2 writes its state and version 3 emits
+    // an event to its event handle, while versions 1 and 4 don't involve it
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let write_set_of = |address| {
+        WriteSetMut::new(vec![(
+            StateKey::access_path(AccessPath::new(address, b"resource".to_vec())),
+            WriteOp::Deletion,
+        )])
+        .freeze()
+        .unwrap()
+    };
+    let event_of =
+        |address| ContractEvent::new_v1(EventKey::new(0, address), 0, TypeTag::Bool, vec![]);
+    let versions = [
+        (retained_account, other_account, other_account),
+        (other_account, other_account, other_account),
+        (other_account, retained_account, other_account),
+        (other_account, other_account, retained_account),
+        (other_account, other_account, other_account),
+    ];
+    let transaction_batch = SchemaBatch::new();
+    let write_set_batch = SchemaBatch::new();
+    let event_batch = SchemaBatch::new();
+    for (version, (sender, written_account, event_account)) in versions.iter().enumerate() {
+        let version = version as Version;
+        let txn = Transaction::UserTransaction(get_test_signed_txn(
+            *sender,
+            version,
+            &private_key,
+            private_key.public_key(),
+            None,
+        ));
+        aptos_db
+            .transaction_store
+            .put_transaction(
+                version,
+                &txn,
+                /*skip_index=*/ false,
+                &transaction_batch,
+            )
+            .unwrap();
+        aptos_db
+            .transaction_store
+            .put_write_set(version, &write_set_of(*written_account), &write_set_batch)
+            .unwrap();
+        aptos_db
+            .event_store
+            .put_events(
+                version,
+                &[event_of(*event_account)],
+                /*skip_index=*/ false,
+                &event_batch,
+            )
+            .unwrap();
+        put_transaction_info(
+            &aptos_db,
+            version,
+            &TransactionInfo::new(
+                HashValue::random(),
+                HashValue::random(),
+                HashValue::random(),
+                None,
+                0,
+                ExecutionStatus::Success,
+            ),
+        );
+    }
+    let ledger_db = &aptos_db.ledger_db;
+    ledger_db
+        .transaction_db()
+        .write_schemas(transaction_batch)
+        .unwrap();
+    ledger_db
+        .write_set_db()
+        .write_schemas(write_set_batch)
+        .unwrap();
+    ledger_db.event_db().write_schemas(event_batch).unwrap();
+
+    pruner
+        .wake_and_wait_pruner(versions.len() as Version)
+        .unwrap();
+
+    for version in 0..versions.len() as Version {
+        let retained = matches!(version, 0 | 2 | 3);
+        assert_eq!(
+            aptos_db.transaction_store.get_transaction(version).is_ok(),
+            retained
+        );
+        assert_eq!(
+            aptos_db.transaction_store.get_write_set(version).is_ok(),
+            retained
+        );
+        assert_eq!(
+            aptos_db.ledger_store.get_transaction_info(version).is_ok(),
+            retained
+        );
+        assert_eq!(
+            aptos_db
+                .event_store
+                .get_events_by_version(version)
+                .unwrap()
+                .len(),
+            retained as usize
+        );
+    }
+}
+
+#[test]
+fn test_retained_account_start_version() {
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test(&tmp_dir);
+    let metadata_db = aptos_db.ledger_db.metadata_db();
+    let account = AccountAddress::random();
+    let new_account = AccountAddress::random();
+
+    // Nothing is retained before the account is initialized
+    let retained_accounts = RetainedAccounts::load(metadata_db, &[account]).unwrap();
+    assert!(retained_accounts.is_empty());
+
+    // The account is retained from the given version on
+    let retained_accounts =
+        RetainedAccounts::load_or_initialize(metadata_db, &[account], 10).unwrap();
+    assert!(!retained_accounts.retains(&account, 9));
+    assert!(retained_accounts.retains(&account, 10));
+
+    // The start version is kept once the account is retained, and a new account starts later
+    let retained_accounts =
+        RetainedAccounts::load_or_initialize(metadata_db, &[account, new_account], 20).unwrap();
+    assert!(retained_accounts.retains(&account, 10));
+    assert!(!retained_accounts.retains(&new_account, 19));
+    assert!(retained_accounts.retains(&new_account, 20));
+
+    // An account which is no longer retained starts over if it's retained again
+    RetainedAccounts::load_or_initialize(metadata_db, &[new_account], 30).unwrap();
+    let retained_accounts = RetainedAccounts::load(metadata_db, &[account, new_account]).unwrap();
+    assert!(!retained_accounts.retains(&account, 39));
+    assert!(retained_accounts.retains(&new_account, 20));
+}
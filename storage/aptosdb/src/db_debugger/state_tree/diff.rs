@@ -0,0 +1,101 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::db_debugger::common::DbDir;
+use anyhow::Result;
+use aptos_types::transaction::Version;
+use clap::Parser;
+use owo_colors::OwoColorize;
+
+const BATCH_SIZE: usize = 1000;
+
+#[derive(Parser)]
+#[clap(about = "Print state keys whose values differ between two state snapshots.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(long)]
+    old_version: Version,
+
+    #[clap(long)]
+    new_version: Version,
+
+    /// Stop after printing this many state keys.
+    #[clap(long)]
+    limit: Option<usize>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        println!(
+            "{}",
+            format!(
+                "* Diffing state snapshots at version {} and version {}. \n",
+                self.old_version, self.new_version,
+            )
+            .yellow()
+        );
+
+        let db = self.db_dir.open_state_merkle_db()?;
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let (mut added, mut modified, mut deleted) = (0, 0, 0);
+        let mut start_key = None;
+        let mut num_printed = 0;
+        loop {
+            let batch_size = BATCH_SIZE.min(limit - num_printed);
+            // One more diff is fetched to know where the next batch starts.
+            let mut diffs = db.get_diff(
+                self.old_version,
+                self.new_version,
+                start_key,
+                batch_size + 1,
+            )?;
+            start_key = if diffs.len() > batch_size {
+                diffs.pop().map(|diff| diff.key())
+            } else {
+                None
+            };
+
+            for diff in &diffs {
+                let (change, state_key) = match (&diff.old, &diff.new) {
+                    (None, Some(new)) => {
+                        added += 1;
+                        ("Added".green().to_string(), &new.value_index().0)
+                    },
+                    (Some(old), None) => {
+                        deleted += 1;
+                        ("Deleted".red().to_string(), &old.value_index().0)
+                    },
+                    (Some(_), Some(new)) => {
+                        modified += 1;
+                        ("Modified".yellow().to_string(), &new.value_index().0)
+                    },
+                    (None, None) => unreachable!("Either leaf must exist."),
+                };
+                println!(
+                    "{:>8} {} old ver: {:<20} new ver: {:<20} state key: {:?}",
+                    change,
+                    diff.key(),
+                    diff.old
+                        .as_ref()
+                        .map_or("-".to_string(), |leaf| leaf.value_index().1.to_string()),
+                    diff.new
+                        .as_ref()
+                        .map_or("-".to_string(), |leaf| leaf.value_index().1.to_string()),
+                    state_key,
+                );
+            }
+            num_printed += diffs.len();
+            if start_key.is_none() || num_printed >= limit {
+                break;
+            }
+        }
+
+        println!(
+            "\n{} added, {} modified, {} deleted.",
+            added, modified, deleted
+        );
+        Ok(())
+    }
+}
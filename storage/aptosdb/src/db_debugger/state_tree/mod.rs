@@ -1,16 +1,19 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod diff;
 mod get_path;
 mod get_snapshots;
 
 use anyhow::Result;
 
-/// Tool supports listing snapshots before version and printing node in merkel tree with version and nibble path
+/// Tool supports listing snapshots before version, printing node in merkel tree with version and nibble path
+/// and printing state keys that differ between two snapshots
 #[derive(clap::Subcommand)]
 pub enum Cmd {
     GetSnapshots(get_snapshots::Cmd),
     GetPath(get_path::Cmd),
+    Diff(diff::Cmd),
 }
 
 impl Cmd {
@@ -18,6 +21,7 @@ impl Cmd {
         match self {
             Self::GetSnapshots(cmd) => cmd.run(),
             Self::GetPath(cmd) => cmd.run(),
+            Self::Diff(cmd) => cmd.run(),
         }
     }
 }
@@ -0,0 +1,110 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::event_store::EventStore;
+use anyhow::Result;
+use aptos_db_indexer::Indexer;
+use aptos_infallible::Mutex;
+use aptos_logger::{info, warn};
+use aptos_types::transaction::{AtomicVersion, Version};
+use std::{
+    sync::{atomic::Ordering, mpsc, Arc},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+const BATCH_SIZE: Version = 10000;
+const RETRY_INTERVAL_MS: u64 = 10000;
+
+/// Indexes by type, in a background thread, the events of the transactions committed before the
+/// events by type index existed. The events are backfilled from the newest to the oldest, down to
+/// the oldest version that isn't pruned. The progress is persisted in the index db with each batch,
+/// so the backfill resumes where it stopped if the node is restarted in the meantime.
+#[derive(Debug)]
+pub(crate) struct EventByTypeBackfiller {
+    sender: Mutex<mpsc::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl EventByTypeBackfiller {
+    pub fn new(
+        indexer: Arc<Indexer>,
+        event_store: Arc<EventStore>,
+        min_readable_version: Arc<AtomicVersion>,
+    ) -> Self {
+        let (send, recv) = mpsc::channel();
+        let join_handle = std::thread::Builder::new()
+            .name("event_by_type_backfiller".into())
+            .spawn(move || loop {
+                let wait_ms = match backfill_batch(&indexer, &event_store, &min_readable_version) {
+                    Ok(true) => {
+                        info!(
+                            start_version = indexer.event_by_type_start_version(),
+                            "AptosDB Indexer events by type backfilled."
+                        );
+                        indexer.set_event_by_type_backfilled();
+                        break;
+                    },
+                    Ok(false) => 0,
+                    Err(e) => {
+                        warn!(error = ?e, "Backfilling events by type failed, will retry.");
+                        RETRY_INTERVAL_MS
+                    },
+                };
+
+                match recv.recv_timeout(Duration::from_millis(wait_ms)) {
+                    Ok(_) => break,
+                    Err(mpsc::RecvTimeoutError::Timeout) => (),
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            })
+            .expect("Creating event by type backfiller thread should succeed.");
+
+        Self {
+            sender: Mutex::new(send),
+            join_handle: Some(join_handle),
+        }
+    }
+}
+
+/// Backfills the batch of events right before the ones already indexed by type, and returns
+/// whether the backfill is done.
+fn backfill_batch(
+    indexer: &Indexer,
+    event_store: &EventStore,
+    min_readable_version: &AtomicVersion,
+) -> Result<bool> {
+    let min_readable_version = min_readable_version.load(Ordering::SeqCst);
+    let start_version = indexer.event_by_type_start_version();
+    if start_version <= min_readable_version {
+        return Ok(true);
+    }
+
+    let first_version = std::cmp::max(
+        min_readable_version,
+        start_version.saturating_sub(BATCH_SIZE),
+    );
+    info!(
+        first_version = first_version,
+        "AptosDB Indexer backfilling events by type."
+    );
+    let events = event_store
+        .get_events_by_version_iter(first_version, (start_version - first_version) as usize)?
+        .collect::<Result<Vec<_>>>()?;
+    let events_ref: Vec<_> = events.iter().map(Vec::as_slice).collect();
+    indexer.backfill_events_by_type(first_version, &events_ref)?;
+
+    Ok(false)
+}
+
+impl Drop for EventByTypeBackfiller {
+    fn drop(&mut self) {
+        // Notify the backfill thread to exit, unless it's already done
+        let _ = self.sender.lock().send(());
+        self.join_handle
+            .take()
+            .expect("Event by type backfiller thread must exist.")
+            .join()
+            .expect("Event by type backfiller thread should join peacefully.");
+    }
+}
@@ -30,6 +30,7 @@ use aptos_types::{
     proof::position::Position,
     transaction::Version,
 };
+use move_core_types::language_storage::TypeTag;
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     convert::{TryFrom, TryInto},
@@ -447,6 +448,7 @@ impl EventStore {
         &self,
         begin: Version,
         end: Version,
+        retained_versions: &HashSet<Version>,
         db_batch: &SchemaBatch,
     ) -> anyhow::Result<()> {
         let mut iter = self
@@ -457,6 +459,9 @@ impl EventStore {
             if version >= end {
                 return Ok(());
             }
+            if retained_versions.contains(&version) {
+                continue;
+            }
             db_batch.delete::<EventAccumulatorSchema>(&(version, position))?;
         }
         Ok(())
@@ -472,17 +477,25 @@ impl EventStore {
         }
     }
 
-    /// Prune a set of candidate events in the range of version in [begin, end) and all related indices
+    /// Prune a set of candidate events in the range of version in [begin, end) and all related
+    /// indices, except for the events of the `retained_versions`. Returns the type and position
+    /// of each pruned event, so that they can be removed from the indexer.
     pub fn prune_events(
         &self,
         start: Version,
         end: Version,
+        retained_versions: &HashSet<Version>,
         db_batch: &SchemaBatch,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Vec<(TypeTag, Version, u64)>> {
+        let mut pruned_events = Vec::new();
         let mut current_version = start;
         for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
+            if retained_versions.contains(&current_version) {
+                current_version += 1;
+                continue;
+            }
             for (idx, event) in (events?).into_iter().enumerate() {
-                if let ContractEvent::V1(v1) = event {
+                if let ContractEvent::V1(v1) = &event {
                     db_batch.delete::<EventByVersionSchema>(&(
                         *v1.key(),
                         current_version,
@@ -491,11 +504,12 @@ impl EventStore {
                     db_batch.delete::<EventByKeySchema>(&(*v1.key(), v1.sequence_number()))?;
                 }
                 db_batch.delete::<EventSchema>(&(current_version, idx as u64))?;
+                pruned_events.push((event.type_tag().clone(), current_version, idx as u64));
             }
             current_version += 1;
         }
-        self.prune_event_accumulator(start, end, db_batch)?;
-        Ok(())
+        self.prune_event_accumulator(start, end, retained_versions, db_batch)?;
+        Ok(pruned_events)
     }
 }
 
@@ -28,12 +28,13 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     proof::{
         accumulator::InMemoryAccumulator, position::Position, AccumulatorConsistencyProof,
-        AccumulatorRangeProof, SparseMerkleProofExt, TransactionAccumulatorProof,
-        TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
-        TransactionInfoListWithProof, TransactionInfoWithProof,
+        AccumulatorRangeProof, SparseMerkleMultiProof, SparseMerkleProofExt,
+        TransactionAccumulatorProof, TransactionAccumulatorRangeProof,
+        TransactionAccumulatorSummary, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_proof::StateProof,
     state_store::{
+        state_diff::StateKeyDiff,
         state_key::StateKey,
         state_key_prefix::StateKeyPrefix,
         state_storage_usage::StateStorageUsage,
@@ -48,11 +49,12 @@ use aptos_types::{
 };
 use dashmap::DashMap;
 use itertools::zip_eq;
-use move_core_types::move_resource::MoveStructType;
+use move_core_types::{language_storage::TypeTag, move_resource::MoveStructType};
 use std::{
     borrow::Borrow,
     collections::HashMap,
     mem::swap,
+    path::Path,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
@@ -399,6 +401,14 @@ impl DbWriter for FakeAptosDB {
             latest_in_memory_state,
         )
     }
+
+    fn create_online_checkpoint(
+        &self,
+        cp_path: &Path,
+        progress: &dyn Fn(&str),
+    ) -> Result<LedgerInfoWithSignatures> {
+        self.inner.create_online_checkpoint(cp_path, progress)
+    }
 }
 
 impl DbReader for FakeAptosDB {
@@ -657,6 +667,14 @@ impl DbReader for FakeAptosDB {
         self.inner.get_state_snapshot_before(next_version)
     }
 
+    fn is_state_merkle_pruned(&self, version: Version) -> Result<bool> {
+        self.inner.is_state_merkle_pruned(version)
+    }
+
+    fn is_account_state_retained(&self, address: AccountAddress, version: Version) -> Result<bool> {
+        self.inner.is_account_state_retained(address, version)
+    }
+
     fn get_account_transaction(
         &self,
         address: aptos_types::PeerId,
@@ -750,6 +768,26 @@ impl DbReader for FakeAptosDB {
             .get_state_value_with_proof_by_version_ext(state_key, version)
     }
 
+    fn get_state_values_with_multiproof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        self.inner
+            .get_state_values_with_multiproof_by_version(state_keys, version)
+    }
+
+    fn get_state_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+        start: Option<&StateKey>,
+        limit: usize,
+    ) -> Result<(Vec<StateKeyDiff>, Option<StateKey>)> {
+        self.inner
+            .get_state_diff(old_version, new_version, start, limit)
+    }
+
     fn get_latest_executed_trees(&self) -> Result<ExecutedTrees> {
         // If the genesis is not executed yet, we need to get the executed trees from the inner AptosDB
         // This is because when we call save_transactions for the genesis block, we call [AptosDB::save_transactions]
@@ -841,6 +879,17 @@ impl DbReader for FakeAptosDB {
         self.inner.get_table_info(handle)
     }
 
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start: (Version, u64),
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<(Vec<EventWithVersion>, Option<(Version, u64)>)> {
+        self.inner
+            .get_events_by_type(type_tag, start, limit, ledger_version)
+    }
+
     fn indexer_enabled(&self) -> bool {
         self.inner.indexer_enabled()
     }
@@ -17,7 +17,7 @@ use aptos_types::{
     transaction::{TransactionOutputListWithProof, TransactionToCommit, Version},
 };
 use either::Either;
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 pub const SECONDARY_DB_DIR: &str = "fast_sync_secondary";
 
@@ -45,7 +45,7 @@ impl FastSyncStorageWrapper {
         let db_main = AptosDB::open(
             config.storage.get_dir_paths(),
             /*readonly=*/ false,
-            config.storage.storage_pruner_config,
+            config.storage.storage_pruner_config.clone(),
             config.storage.rocksdb_configs,
             config.storage.enable_indexer,
             config.storage.buffered_state_target_items,
@@ -66,7 +66,7 @@ impl FastSyncStorageWrapper {
             let secondary_db = AptosDB::open(
                 StorageDirPaths::from_path(db_dir.as_path()),
                 /*readonly=*/ false,
-                config.storage.storage_pruner_config,
+                config.storage.storage_pruner_config.clone(),
                 config.storage.rocksdb_configs,
                 config.storage.enable_indexer,
                 config.storage.buffered_state_target_items,
@@ -176,6 +176,16 @@ impl DbWriter for FastSyncStorageWrapper {
             sharded_state_cache,
         )
     }
+
+    fn create_online_checkpoint(
+        &self,
+        cp_path: &Path,
+        progress: &dyn Fn(&str),
+    ) -> Result<LedgerInfoWithSignatures> {
+        // Checkpoints the db being served, which doesn't have a snapshot being restored into.
+        self.get_aptos_db_read_ref()
+            .create_online_checkpoint(cp_path, progress)
+    }
 }
 
 impl DbReader for FastSyncStorageWrapper {
@@ -169,6 +169,16 @@ impl LedgerDb {
             ..Default::default()
         };
         let ledger_db = Self::new(db_root_path, rocksdb_configs, /*readonly=*/ false)?;
+        ledger_db.create_checkpoint_at(cp_root_path, sharding)
+    }
+
+    /// Creates a checkpoint of the (already opened) ledger db, which can happen while the db is
+    /// being written to. The ledger metadata db is checkpointed before the other sub dbs.
+    pub(crate) fn create_checkpoint_at(
+        &self,
+        cp_root_path: impl AsRef<Path>,
+        sharding: bool,
+    ) -> Result<()> {
         let cp_ledger_db_folder = cp_root_path.as_ref().join(LEDGER_DB_FOLDER_NAME);
 
         info!(
@@ -181,25 +191,19 @@ impl LedgerDb {
             std::fs::create_dir_all(&cp_ledger_db_folder).unwrap_or(());
         }
 
-        ledger_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref(), sharding))?;
 
         if sharding {
-            ledger_db
-                .event_db()
+            self.event_db()
                 .create_checkpoint(cp_ledger_db_folder.join(EVENT_DB_NAME))?;
-            ledger_db
-                .transaction_accumulator_db()
+            self.transaction_accumulator_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_ACCUMULATOR_DB_NAME))?;
-            ledger_db
-                .transaction_db()
+            self.transaction_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_DB_NAME))?;
-            ledger_db
-                .transaction_info_db()
+            self.transaction_info_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_INFO_DB_NAME))?;
-            ledger_db
-                .write_set_db()
+            self.write_set_db()
                 .create_checkpoint(cp_ledger_db_folder.join(WRITE_SET_DB_NAME))?;
         }
 
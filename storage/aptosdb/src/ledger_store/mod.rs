@@ -218,6 +218,14 @@ impl LedgerStore {
         Ok(EpochEndingLedgerInfoIter::new(iter, start_epoch, end_epoch))
     }
 
+    pub fn is_epoch_ending(&self, version: Version) -> Result<bool> {
+        Ok(self
+            .ledger_db
+            .metadata_db()
+            .get::<EpochByVersionSchema>(&version)?
+            .is_some())
+    }
+
     pub fn ensure_epoch_ending(&self, version: Version) -> Result<()> {
         self.ledger_db
             .metadata_db()
@@ -24,6 +24,7 @@ pub mod state_restore;
 pub mod utils;
 
 mod db_options;
+mod event_by_type_backfiller;
 mod event_store;
 mod ledger_db;
 mod ledger_store;
@@ -55,6 +56,7 @@ use crate::{
         transaction_info_db_column_families, write_set_db_column_families,
     },
     errors::AptosDbError,
+    event_by_type_backfiller::EventByTypeBackfiller,
     event_store::EventStore,
     ledger_db::{LedgerDb, LedgerDbSchemaBatches},
     ledger_store::LedgerStore,
@@ -73,7 +75,7 @@ use crate::{
 };
 use anyhow::{anyhow, bail, ensure, Result};
 use aptos_config::config::{
-    PrunerConfig, RocksdbConfig, RocksdbConfigs, StorageDirPaths, NO_OP_STORAGE_PRUNER_CONFIG,
+    PrunerConfig, RocksdbConfigs, StorageDirPaths, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 #[cfg(any(test, feature = "fuzzing"))]
 use aptos_config::config::{
@@ -89,7 +91,8 @@ use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_scratchpad::SparseMerkleTree;
 use aptos_storage_interface::{
     cached_state_view::ShardedStateCache, state_delta::StateDelta, state_view::DbStateView,
-    DbReader, DbWriter, ExecutedTrees, Order, StateSnapshotReceiver, MAX_REQUEST_LIMIT,
+    DbReader, DbWriter, Error as StorageError, ExecutedTrees, Order, StateSnapshotReceiver,
+    MAX_REQUEST_LIMIT,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -101,12 +104,13 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::{CurrentTimeMicroseconds, OnChainConfig},
     proof::{
-        accumulator::InMemoryAccumulator, AccumulatorConsistencyProof, SparseMerkleProofExt,
-        TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
+        accumulator::InMemoryAccumulator, AccumulatorConsistencyProof, SparseMerkleMultiProof,
+        SparseMerkleProofExt, TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
         TransactionInfoListWithProof,
     },
     state_proof::StateProof,
     state_store::{
+        state_diff::StateKeyDiff,
         state_key::StateKey,
         state_key_prefix::StateKeyPrefix,
         state_storage_usage::StateStorageUsage,
@@ -123,6 +127,7 @@ use aptos_types::{
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use arr_macro::arr;
+use move_core_types::language_storage::TypeTag;
 use move_resource_viewer::MoveValueAnnotator;
 use once_cell::sync::Lazy;
 use rayon::prelude::*;
@@ -341,7 +346,8 @@ pub struct AptosDB {
     ledger_pruner: LedgerPrunerManager,
     _rocksdb_property_reporter: RocksdbPropertyReporter,
     ledger_commit_lock: std::sync::Mutex<()>,
-    indexer: Option<Indexer>,
+    indexer: Option<Arc<Indexer>>,
+    _event_by_type_backfiller: Option<EventByTypeBackfiller>,
     skip_index_and_usage: bool,
 }
 
@@ -355,6 +361,7 @@ impl AptosDB {
         hack_for_tests: bool,
         empty_buffered_state_for_restore: bool,
         skip_index_and_usage: bool,
+        indexer: Option<Arc<Indexer>>,
     ) -> Self {
         let ledger_db = Arc::new(ledger_db);
         let state_merkle_db = Arc::new(state_merkle_db);
@@ -367,8 +374,10 @@ impl AptosDB {
             Arc::clone(&state_merkle_db),
             pruner_config.epoch_snapshot_pruner_config.into(),
         );
-        let state_kv_pruner =
-            StateKvPrunerManager::new(Arc::clone(&state_kv_db), pruner_config.ledger_pruner_config);
+        let state_kv_pruner = StateKvPrunerManager::new(
+            Arc::clone(&state_kv_db),
+            pruner_config.ledger_pruner_config.clone(),
+        );
         let state_store = Arc::new(StateStore::new(
             Arc::clone(&ledger_db),
             Arc::clone(&state_merkle_db),
@@ -382,8 +391,11 @@ impl AptosDB {
             skip_index_and_usage,
         ));
 
-        let ledger_pruner =
-            LedgerPrunerManager::new(Arc::clone(&ledger_db), pruner_config.ledger_pruner_config);
+        let ledger_pruner = LedgerPrunerManager::new(
+            Arc::clone(&ledger_db),
+            pruner_config.ledger_pruner_config,
+            indexer.clone(),
+        );
 
         AptosDB {
             ledger_db: Arc::clone(&ledger_db),
@@ -399,7 +411,8 @@ impl AptosDB {
                 state_kv_db,
             ),
             ledger_commit_lock: std::sync::Mutex::new(()),
-            indexer: None,
+            indexer,
+            _event_by_type_backfiller: None,
             skip_index_and_usage,
         }
     }
@@ -426,6 +439,16 @@ impl AptosDB {
             max_num_nodes_per_lru_cache_shard,
         )?;
 
+        // The indexer is opened before the pruner, which removes pruned events from its index.
+        let indexer = if !readonly && enable_indexer {
+            Some(Arc::new(Indexer::open(
+                db_paths.default_root_path(),
+                rocksdb_configs.index_db_config,
+            )?))
+        } else {
+            None
+        };
+
         let mut myself = Self::new_with_dbs(
             ledger_db,
             state_merkle_db,
@@ -435,13 +458,12 @@ impl AptosDB {
             readonly,
             empty_buffered_state_for_restore,
             rocksdb_configs.enable_storage_sharding,
+            indexer,
         );
 
-        if !readonly && enable_indexer {
-            myself.open_indexer(
-                db_paths.default_root_path(),
-                rocksdb_configs.index_db_config,
-            )?;
+        if let Some(indexer) = myself.indexer.clone() {
+            myself.catch_up_indexer(&indexer)?;
+            myself._event_by_type_backfiller = myself.backfill_events_by_type(indexer);
         }
 
         Ok(myself)
@@ -512,12 +534,7 @@ impl AptosDB {
         Ok((ledger_db, state_merkle_db, state_kv_db))
     }
 
-    fn open_indexer(
-        &mut self,
-        db_root_path: impl AsRef<Path>,
-        rocksdb_config: RocksdbConfig,
-    ) -> Result<()> {
-        let indexer = Indexer::open(&db_root_path, rocksdb_config)?;
+    fn catch_up_indexer(&self, indexer: &Indexer) -> Result<()> {
         let ledger_next_version = self.get_latest_version().map_or(0, |v| v + 1);
         info!(
             indexer_next_version = indexer.next_version(),
@@ -542,17 +559,44 @@ impl AptosDB {
                     .transaction_store
                     .get_write_sets(next_version, end_version)?;
                 let write_sets_ref: Vec<_> = write_sets.iter().collect();
-                indexer.index_with_annotator(&annotator, next_version, &write_sets_ref)?;
+                let events = self
+                    .event_store
+                    .get_events_by_version_iter(
+                        next_version,
+                        (end_version - next_version) as usize,
+                    )?
+                    .collect::<Result<Vec<_>>>()?;
+                let events_ref: Vec<_> = events.iter().map(Vec::as_slice).collect();
+                indexer.index_with_annotator(
+                    &annotator,
+                    next_version,
+                    &write_sets_ref,
+                    &events_ref,
+                )?;
 
                 next_version = end_version;
             }
         }
         info!("AptosDB Indexer caught up.");
 
-        self.indexer = Some(indexer);
         Ok(())
     }
 
+    /// Starts backfilling the events by type index in the background, unless there's nothing
+    /// left to backfill. Until it's done, looking up events by type fails.
+    fn backfill_events_by_type(&self, indexer: Arc<Indexer>) -> Option<EventByTypeBackfiller> {
+        if indexer.event_by_type_start_version() <= self.ledger_pruner.get_min_readable_version() {
+            indexer.set_event_by_type_backfilled();
+            return None;
+        }
+
+        Some(EventByTypeBackfiller::new(
+            indexer,
+            Arc::clone(&self.event_store),
+            self.ledger_pruner.min_readable_version_arc(),
+        ))
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     fn new_without_pruner<P: AsRef<Path> + Clone>(
         db_root_path: P,
@@ -735,7 +779,7 @@ impl AptosDB {
         ledger_version: Version,
         fetch_events: bool,
     ) -> Result<TransactionWithProof> {
-        self.error_if_ledger_pruned("Transaction", version)?;
+        self.error_if_transaction_pruned("Transaction", version)?;
 
         let proof = self
             .ledger_store
@@ -874,6 +918,23 @@ impl AptosDB {
         }
     }
 
+    fn get_events_by_type_indices(
+        &self,
+        type_tag: &TypeTag,
+        start: (Version, u64),
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, u64)>> {
+        match &self.indexer {
+            Some(indexer) => {
+                indexer.get_event_indices_by_type(type_tag, start, limit, ledger_version)
+            },
+            None => {
+                bail!("Indexer not enabled.");
+            },
+        }
+    }
+
     fn save_transactions_validation(
         &self,
         txns_to_commit: &[TransactionToCommit],
@@ -1324,7 +1385,13 @@ impl AptosDB {
                 .with_label_values(&["indexer_index"])
                 .start_timer();
             let write_sets: Vec<_> = txns_to_commit.iter().map(|txn| txn.write_set()).collect();
-            indexer.index(self.state_store.clone(), first_version, &write_sets)?;
+            let events: Vec<_> = txns_to_commit.iter().map(|txn| txn.events()).collect();
+            indexer.index(
+                self.state_store.clone(),
+                first_version,
+                &write_sets,
+                &events,
+            )?;
         }
 
         // Once everything is successfully persisted, update the latest in-memory ledger info.
@@ -1340,16 +1407,35 @@ impl AptosDB {
 
     fn error_if_ledger_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self.ledger_pruner.get_min_readable_version();
-        ensure!(
-            version >= min_readable_version,
-            "{} at version {} is pruned, min available version is {}.",
-            data_type,
-            version,
-            min_readable_version
-        );
+        if version < min_readable_version {
+            return Err(StorageError::Pruned {
+                data_type: data_type.to_string(),
+                version,
+                min_readable_version,
+            }
+            .into());
+        }
         Ok(())
     }
 
+    /// Like `error_if_ledger_pruned`, but the transactions retained for the retained accounts of
+    /// the ledger pruner are readable beyond the prune window.
+    fn error_if_transaction_pruned(&self, data_type: &str, version: Version) -> Result<()> {
+        if version < self.ledger_pruner.get_min_readable_version()
+            && self
+                .transaction_store
+                .get_retained_versions(
+                    version,
+                    version + 1,
+                    self.ledger_pruner.retained_accounts(),
+                )?
+                .contains(&version)
+        {
+            return Ok(());
+        }
+        self.error_if_ledger_pruned(data_type, version)
+    }
+
     fn error_if_state_merkle_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self
             .state_store
@@ -1389,6 +1475,20 @@ impl AptosDB {
         );
         Ok(())
     }
+
+    /// Like `error_if_state_kv_pruned`, but the state of the retained accounts of the state kv
+    /// pruner is readable beyond the prune window.
+    fn error_if_state_value_pruned(&self, state_key: &StateKey, version: Version) -> Result<()> {
+        if self
+            .state_store
+            .state_kv_pruner
+            .retained_accounts()
+            .retains_state_key(state_key, version)
+        {
+            return Ok(());
+        }
+        self.error_if_state_kv_pruned("StateValue", version)
+    }
 }
 
 impl DbReader for AptosDB {
@@ -1411,7 +1511,14 @@ impl DbReader for AptosDB {
         version: Version,
     ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + '_>> {
         gauged_api("get_prefixed_state_value_iterator", || {
-            self.error_if_state_kv_pruned("StateValue", version)?;
+            if !self
+                .state_store
+                .state_kv_pruner
+                .retained_accounts()
+                .retains_state_key_prefix(key_prefix, version)
+            {
+                self.error_if_state_kv_pruned("StateValue", version)?;
+            }
 
             Ok(Box::new(
                 self.state_store
@@ -1604,9 +1711,14 @@ impl DbReader for AptosDB {
                 return Ok(TransactionOutputListWithProof::new_empty());
             }
 
-            self.error_if_ledger_pruned("Transaction", start_version)?;
-
             let limit = std::cmp::min(limit, ledger_version - start_version + 1);
+            // Only a single output can be read beyond the prune window, as the ones around a
+            // retained transaction are pruned.
+            if limit == 1 {
+                self.error_if_transaction_pruned("Transaction", start_version)?;
+            } else {
+                self.error_if_ledger_pruned("Transaction", start_version)?;
+            }
 
             let (txn_infos, txns_and_outputs) = (start_version..start_version + limit)
                 .map(|version| {
@@ -1786,7 +1898,7 @@ impl DbReader for AptosDB {
         version: Version,
     ) -> Result<Option<StateValue>> {
         gauged_api("get_state_value_by_version", || {
-            self.error_if_state_kv_pruned("StateValue", version)?;
+            self.error_if_state_value_pruned(state_store_key, version)?;
 
             self.state_store
                 .get_state_value_by_version(state_store_key, version)
@@ -1799,7 +1911,7 @@ impl DbReader for AptosDB {
         version: Version,
     ) -> Result<Option<(Version, StateValue)>> {
         gauged_api("get_state_value_with_version_by_version", || {
-            self.error_if_state_kv_pruned("StateValue", version)?;
+            self.error_if_state_value_pruned(state_key, version)?;
 
             self.state_store
                 .get_state_value_with_version_by_version(state_key, version)
@@ -1833,6 +1945,37 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_values_with_multiproof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        gauged_api("get_state_values_with_multiproof_by_version", || {
+            error_if_too_many_requested(state_keys.len() as u64, MAX_REQUEST_LIMIT)?;
+            self.error_if_state_merkle_pruned("State merkle", version)?;
+
+            self.state_store
+                .get_state_values_with_multiproof_by_version(state_keys, version)
+        })
+    }
+
+    fn get_state_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+        start: Option<&StateKey>,
+        limit: usize,
+    ) -> Result<(Vec<StateKeyDiff>, Option<StateKey>)> {
+        gauged_api("get_state_diff", || {
+            error_if_too_many_requested(limit as u64, MAX_REQUEST_LIMIT)?;
+            self.error_if_state_merkle_pruned("State merkle", old_version)?;
+            self.error_if_state_merkle_pruned("State merkle", new_version)?;
+
+            self.state_store
+                .get_state_diff(old_version, new_version, start, limit)
+        })
+    }
+
     fn get_latest_epoch_state(&self) -> Result<EpochState> {
         gauged_api("get_latest_epoch_state", || {
             let latest_ledger_info = self.ledger_store.get_latest_ledger_info()?;
@@ -2036,9 +2179,32 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn is_state_merkle_pruned(&self, version: Version) -> Result<bool> {
+        gauged_api("is_state_merkle_pruned", || {
+            let state_db = &self.state_store.state_db;
+            if version >= state_db.state_merkle_pruner.get_min_readable_version() {
+                Ok(false)
+            } else if version >= state_db.epoch_snapshot_pruner.get_min_readable_version() {
+                Ok(!self.ledger_store.is_epoch_ending(version)?)
+            } else {
+                Ok(true)
+            }
+        })
+    }
+
+    fn is_account_state_retained(&self, address: AccountAddress, version: Version) -> Result<bool> {
+        gauged_api("is_account_state_retained", || {
+            Ok(self
+                .state_store
+                .state_kv_pruner
+                .retained_accounts()
+                .retains(&address, version))
+        })
+    }
+
     fn get_accumulator_root_hash(&self, version: Version) -> Result<HashValue> {
         gauged_api("get_accumulator_root_hash", || {
-            self.error_if_ledger_pruned("Transaction accumulator", version)?;
+            self.error_if_transaction_pruned("Transaction accumulator", version)?;
             self.ledger_store.get_root_hash(version)
         })
     }
@@ -2126,6 +2292,45 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start: (Version, u64),
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<(Vec<EventWithVersion>, Option<(Version, u64)>)> {
+        gauged_api("get_events_by_type", || {
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            self.error_if_ledger_pruned("Event", start.0)?;
+
+            // Look up one more event than requested, its position is where the next page starts.
+            let mut event_indices =
+                self.get_events_by_type_indices(type_tag, start, limit + 1, ledger_version)?;
+            let next = if event_indices.len() as u64 > limit {
+                event_indices.pop()
+            } else {
+                None
+            };
+
+            let events = event_indices
+                .into_iter()
+                .map(|(version, index)| {
+                    let event = self
+                        .event_store
+                        .get_event_by_version_and_index(version, index)?;
+                    ensure!(
+                        event.type_tag() == type_tag,
+                        "Index broken, expected type: {}, actual: {}",
+                        type_tag,
+                        event.type_tag()
+                    );
+                    Ok(EventWithVersion::new(version, event))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok((events, next))
+        })
+    }
+
     /// Returns whether the indexer DB has been enabled or not
     fn indexer_enabled(&self) -> bool {
         self.indexer.is_some()
@@ -2142,6 +2347,79 @@ impl DbReader for AptosDB {
 }
 
 impl DbWriter for AptosDB {
+    fn create_online_checkpoint(
+        &self,
+        cp_path: &Path,
+        progress: &dyn Fn(&str),
+    ) -> Result<LedgerInfoWithSignatures> {
+        gauged_api("create_online_checkpoint", || {
+            let start = Instant::now();
+            let sharding = self.state_kv_db.enabled_sharding();
+
+            info!(
+                sharding = sharding,
+                cp_path = cp_path,
+                "Creating online checkpoint for AptosDB."
+            );
+            std::fs::create_dir_all(cp_path)?;
+
+            // The indexer catches up with the ledger when the checkpoint is opened, so it's
+            // checkpointed before anything newer than it can make it into the ledger db.
+            if let Some(indexer) = &self.indexer {
+                indexer.create_checkpoint(cp_path)?;
+                info!(
+                    time_ms = %start.elapsed().as_millis(),
+                    "Made indexer db checkpoint."
+                );
+                progress("Made indexer db checkpoint.");
+            }
+
+            // The ledger metadata db (which holds the overall commit progress) is checkpointed
+            // before any other sub db, so the others can only be ahead of it in the checkpoint,
+            // and what's ahead is truncated when the checkpoint is opened.
+            self.ledger_db.create_checkpoint_at(cp_path, sharding)?;
+            info!(
+                time_ms = %start.elapsed().as_millis(),
+                "Made ledger db checkpoint."
+            );
+            progress("Made ledger db checkpoint.");
+            if sharding {
+                self.state_kv_db.create_checkpoint_at(cp_path)?;
+                info!(
+                    time_ms = %start.elapsed().as_millis(),
+                    "Made state kv db checkpoint."
+                );
+                progress("Made state kv db checkpoint.");
+            }
+            self.state_store
+                .state_merkle_db
+                .create_checkpoint_at(cp_path)?;
+            info!(
+                time_ms = %start.elapsed().as_millis(),
+                "Made state merkle db checkpoint."
+            );
+            progress("Made state merkle db checkpoint.");
+
+            // Commits can happen while checkpointing, so the ledger info is read from the
+            // checkpoint itself rather than from memory.
+            let rocksdb_configs = RocksdbConfigs {
+                enable_storage_sharding: sharding,
+                ..Default::default()
+            };
+            let cp_ledger_db = LedgerDb::new(cp_path, rocksdb_configs, /*readonly=*/ true)?;
+            let ledger_info = LedgerStore::new(Arc::new(cp_ledger_db)).get_latest_ledger_info()?;
+
+            info!(
+                cp_path = cp_path,
+                version = ledger_info.ledger_info().version(),
+                epoch = ledger_info.ledger_info().epoch(),
+                time_ms = %start.elapsed().as_millis(),
+                "Made online AptosDB checkpoint."
+            );
+            Ok(ledger_info)
+        })
+    }
+
     /// `first_version` is the version of the first transaction in `txns_to_commit`.
     /// When `ledger_info_with_sigs` is provided, verify that the transaction accumulator root hash
     /// it carries is generated after the `txns_to_commit` are applied.
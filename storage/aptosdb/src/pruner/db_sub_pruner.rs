@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_types::transaction::Version;
+use std::collections::HashSet;
 
 /// Defines the trait for sub-pruner of a parent DB pruner
 pub trait DBSubPruner {
@@ -9,6 +10,11 @@ pub trait DBSubPruner {
     fn name(&self) -> &str;
 
     /// Performs the actual pruning, a target version is passed, which is the target the pruner
-    /// tries to prune.
-    fn prune(&self, current_progress: Version, target_version: Version) -> anyhow::Result<()>;
+    /// tries to prune. The data of the `retained_versions` is kept, see `RetainedAccounts`.
+    fn prune(
+        &self,
+        current_progress: Version,
+        target_version: Version,
+        retained_versions: &HashSet<Version>,
+    ) -> anyhow::Result<()>;
 }
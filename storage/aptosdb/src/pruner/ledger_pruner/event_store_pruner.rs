@@ -2,20 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress,
+        RetainedAccounts,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
-    EventStore,
+    EventStore, TransactionStore,
 };
 use anyhow::Result;
+use aptos_db_indexer::Indexer;
 use aptos_logger::info;
 use aptos_schemadb::{SchemaBatch, DB};
 use aptos_types::transaction::Version;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 #[derive(Debug)]
 pub struct EventStorePruner {
     event_store: Arc<EventStore>,
     event_db: Arc<DB>,
+    /// Events are also removed from its events by type index.
+    indexer: Option<Arc<Indexer>>,
 }
 
 impl DBSubPruner for EventStorePruner {
@@ -23,10 +29,24 @@ impl DBSubPruner for EventStorePruner {
         "EventStorePruner"
     }
 
-    fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
+    fn prune(
+        &self,
+        current_progress: Version,
+        target_version: Version,
+        retained_versions: &HashSet<Version>,
+    ) -> Result<()> {
         let batch = SchemaBatch::new();
-        self.event_store
-            .prune_events(current_progress, target_version, &batch)?;
+        let pruned_events = self.event_store.prune_events(
+            current_progress,
+            target_version,
+            retained_versions,
+            &batch,
+        )?;
+        // The index entries are removed first, so that they never point to pruned events. If
+        // the event db write fails, they are removed again when the range is pruned again.
+        if let Some(indexer) = &self.indexer {
+            indexer.prune_events_by_type(&pruned_events)?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::EventPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -38,7 +58,10 @@ impl DBSubPruner for EventStorePruner {
 impl EventStorePruner {
     pub(in crate::pruner) fn new(
         event_store: Arc<EventStore>,
+        transaction_store: Arc<TransactionStore>,
         event_db: Arc<DB>,
+        retained_accounts: &RetainedAccounts,
+        indexer: Option<Arc<Indexer>>,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -47,9 +70,15 @@ impl EventStorePruner {
             metadata_progress,
         )?;
 
+        let retained_versions = transaction_store.get_retained_versions(
+            progress,
+            metadata_progress,
+            retained_accounts,
+        )?;
         let myself = EventStorePruner {
             event_store,
             event_db,
+            indexer,
         };
 
         info!(
@@ -57,7 +86,7 @@ impl EventStorePruner {
             metadata_progress = metadata_progress,
             "Catching up EventStorePruner."
         );
-        myself.prune(progress, metadata_progress)?;
+        myself.prune(progress, metadata_progress, &retained_versions)?;
 
         Ok(myself)
     }
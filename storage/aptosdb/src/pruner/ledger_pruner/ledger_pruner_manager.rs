@@ -6,11 +6,13 @@ use crate::{
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
         ledger_pruner::LedgerPruner, pruner_manager::PrunerManager, pruner_utils,
-        pruner_worker::PrunerWorker,
+        pruner_worker::PrunerWorker, RetainedAccounts,
     },
+    utils::truncation_helper::get_ledger_commit_progress,
 };
 use anyhow::Result;
 use aptos_config::config::LedgerPrunerConfig;
+use aptos_db_indexer::Indexer;
 use aptos_infallible::Mutex;
 use aptos_types::transaction::{AtomicVersion, Version};
 use std::sync::{atomic::Ordering, Arc};
@@ -30,7 +32,9 @@ pub(crate) struct LedgerPrunerManager {
     /// Offset for displaying to users
     user_pruning_window_offset: u64,
     /// The minimal readable version for the ledger data.
-    min_readable_version: AtomicVersion,
+    min_readable_version: Arc<AtomicVersion>,
+    /// Accounts whose transactions and events are kept beyond the prune window.
+    retained_accounts: RetainedAccounts,
 }
 
 impl PrunerManager for LedgerPrunerManager {
@@ -103,12 +107,26 @@ impl PrunerManager for LedgerPrunerManager {
 }
 
 impl LedgerPrunerManager {
-    /// Creates a worker thread that waits on a channel for pruning commands.
-    pub fn new(ledger_db: Arc<LedgerDb>, ledger_pruner_config: LedgerPrunerConfig) -> Self {
+    /// Creates a worker thread that waits on a channel for pruning commands. Pruned events are
+    /// also removed from the events by type index of the `indexer`, if there is one.
+    pub fn new(
+        ledger_db: Arc<LedgerDb>,
+        ledger_pruner_config: LedgerPrunerConfig,
+        indexer: Option<Arc<Indexer>>,
+    ) -> Self {
+        let retained_accounts = pruner_utils::load_retained_accounts(
+            ledger_db.metadata_db(),
+            &ledger_pruner_config.retained_accounts,
+            get_ledger_commit_progress(ledger_db.metadata_db()).expect("Must succeed."),
+            ledger_pruner_config.enable,
+        )
+        .expect("Failed to load retained accounts.");
         let pruner_worker = if ledger_pruner_config.enable {
             Some(Self::init_pruner(
                 Arc::clone(&ledger_db),
-                ledger_pruner_config,
+                &ledger_pruner_config,
+                retained_accounts.clone(),
+                indexer,
             ))
         } else {
             None
@@ -128,16 +146,30 @@ impl LedgerPrunerManager {
             pruning_batch_size: ledger_pruner_config.batch_size,
             latest_version: Arc::new(Mutex::new(min_readable_version)),
             user_pruning_window_offset: ledger_pruner_config.user_pruning_window_offset,
-            min_readable_version: AtomicVersion::new(min_readable_version),
+            min_readable_version: Arc::new(AtomicVersion::new(min_readable_version)),
+            retained_accounts,
         }
     }
 
+    pub fn retained_accounts(&self) -> &RetainedAccounts {
+        &self.retained_accounts
+    }
+
+    /// The minimal readable version, which can be followed by the readers in other threads.
+    pub fn min_readable_version_arc(&self) -> Arc<AtomicVersion> {
+        Arc::clone(&self.min_readable_version)
+    }
+
     fn init_pruner(
         ledger_db: Arc<LedgerDb>,
-        ledger_pruner_config: LedgerPrunerConfig,
+        ledger_pruner_config: &LedgerPrunerConfig,
+        retained_accounts: RetainedAccounts,
+        indexer: Option<Arc<Indexer>>,
     ) -> PrunerWorker {
-        let pruner =
-            Arc::new(LedgerPruner::new(ledger_db).expect("Failed to create ledger pruner."));
+        let pruner = Arc::new(
+            LedgerPruner::new(ledger_db, retained_accounts, indexer)
+                .expect("Failed to create ledger pruner."),
+        );
 
         PRUNER_WINDOW
             .with_label_values(&["ledger_pruner"])
@@ -21,10 +21,12 @@ use crate::{
             transaction_info_pruner::TransactionInfoPruner, transaction_pruner::TransactionPruner,
             write_set_pruner::WriteSetPruner,
         },
+        RetainedAccounts,
     },
     EventStore, TransactionStore,
 };
 use anyhow::{anyhow, Result};
+use aptos_db_indexer::Indexer;
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_logger::info;
 use aptos_types::transaction::{AtomicVersion, Version};
@@ -46,6 +48,10 @@ pub(crate) struct LedgerPruner {
     ledger_metadata_pruner: Box<LedgerMetadataPruner>,
 
     sub_pruners: Vec<Box<dyn DBSubPruner + Send + Sync>>,
+
+    transaction_store: Arc<TransactionStore>,
+
+    retained_accounts: RetainedAccounts,
 }
 
 impl DBPruner for LedgerPruner {
@@ -69,10 +75,17 @@ impl DBPruner for LedgerPruner {
             self.ledger_metadata_pruner
                 .prune(progress, current_batch_target_version)?;
 
+            // The retained versions are shared by all sub pruners, so that they keep the same
+            // transactions.
+            let retained_versions = self.transaction_store.get_retained_versions(
+                progress,
+                current_batch_target_version,
+                &self.retained_accounts,
+            )?;
             THREAD_MANAGER.get_background_pool().install(|| {
                 self.sub_pruners.par_iter().try_for_each(|sub_pruner| {
                     sub_pruner
-                        .prune(progress, current_batch_target_version)
+                        .prune(progress, current_batch_target_version, &retained_versions)
                         .map_err(|err| anyhow!("{} failed to prune: {err}", sub_pruner.name()))
                 })
             })?;
@@ -109,7 +122,11 @@ impl DBPruner for LedgerPruner {
 }
 
 impl LedgerPruner {
-    pub fn new(ledger_db: Arc<LedgerDb>) -> Result<Self> {
+    pub fn new(
+        ledger_db: Arc<LedgerDb>,
+        retained_accounts: RetainedAccounts,
+        indexer: Option<Arc<Indexer>>,
+    ) -> Result<Self> {
         info!(name = LEDGER_PRUNER_NAME, "Initializing...");
 
         let ledger_metadata_pruner = Box::new(
@@ -128,27 +145,34 @@ impl LedgerPruner {
 
         let event_store_pruner = Box::new(EventStorePruner::new(
             Arc::new(EventStore::new(ledger_db.event_db_arc())),
+            Arc::clone(&transaction_store),
             ledger_db.event_db_arc(),
+            &retained_accounts,
+            indexer,
             metadata_progress,
         )?);
         let transaction_accumulator_pruner = Box::new(TransactionAccumulatorPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.transaction_accumulator_db_arc(),
+            &retained_accounts,
             metadata_progress,
         )?);
         let transaction_info_pruner = Box::new(TransactionInfoPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.transaction_info_db_arc(),
+            &retained_accounts,
             metadata_progress,
         )?);
         let transaction_pruner = Box::new(TransactionPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.transaction_db_arc(),
+            &retained_accounts,
             metadata_progress,
         )?);
         let write_set_pruner = Box::new(WriteSetPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.write_set_db_arc(),
+            &retained_accounts,
             metadata_progress,
         )?);
 
@@ -163,6 +187,8 @@ impl LedgerPruner {
                 transaction_pruner,
                 write_set_pruner,
             ],
+            transaction_store,
+            retained_accounts,
         };
 
         info!(
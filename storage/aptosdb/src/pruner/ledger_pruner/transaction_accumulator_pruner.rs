@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress,
+        RetainedAccounts,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     TransactionStore,
 };
@@ -10,12 +13,13 @@ use anyhow::Result;
 use aptos_logger::info;
 use aptos_schemadb::{SchemaBatch, DB};
 use aptos_types::transaction::Version;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 #[derive(Debug)]
 pub struct TransactionAccumulatorPruner {
     transaction_store: Arc<TransactionStore>,
     transaction_accumulator_db: Arc<DB>,
+    retained_accounts: RetainedAccounts,
 }
 
 impl DBSubPruner for TransactionAccumulatorPruner {
@@ -23,13 +27,21 @@ impl DBSubPruner for TransactionAccumulatorPruner {
         "TransactionAccumulatorPruner"
     }
 
-    fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
+    fn prune(
+        &self,
+        current_progress: Version,
+        target_version: Version,
+        _retained_versions: &HashSet<Version>,
+    ) -> Result<()> {
         let batch = SchemaBatch::new();
-        self.transaction_store.prune_transaction_accumulator(
-            current_progress,
-            target_version,
-            &batch,
-        )?;
+        // The proofs of the retained transactions need the accumulator nodes of the pruned ones.
+        if self.retained_accounts.is_empty() {
+            self.transaction_store.prune_transaction_accumulator(
+                current_progress,
+                target_version,
+                &batch,
+            )?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::TransactionAccumulatorPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -42,6 +54,7 @@ impl TransactionAccumulatorPruner {
     pub(in crate::pruner) fn new(
         transaction_store: Arc<TransactionStore>,
         transaction_accumulator_db: Arc<DB>,
+        retained_accounts: &RetainedAccounts,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -53,6 +66,7 @@ impl TransactionAccumulatorPruner {
         let myself = TransactionAccumulatorPruner {
             transaction_store,
             transaction_accumulator_db,
+            retained_accounts: retained_accounts.clone(),
         };
 
         info!(
@@ -60,7 +74,7 @@ impl TransactionAccumulatorPruner {
             metadata_progress = metadata_progress,
             "Catching up TransactionAccumulatorPruner."
         );
-        myself.prune(progress, metadata_progress)?;
+        myself.prune(progress, metadata_progress, &HashSet::new())?;
 
         Ok(myself)
     }
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress,
+        RetainedAccounts,
+    },
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         transaction::TransactionSchema,
@@ -13,7 +16,7 @@ use anyhow::{ensure, Result};
 use aptos_logger::info;
 use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_types::transaction::{Transaction, Version};
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 #[derive(Debug)]
 pub struct TransactionPruner {
@@ -26,10 +29,19 @@ impl DBSubPruner for TransactionPruner {
         "TransactionPruner"
     }
 
-    fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
+    fn prune(
+        &self,
+        current_progress: Version,
+        target_version: Version,
+        retained_versions: &HashSet<Version>,
+    ) -> Result<()> {
         let batch = SchemaBatch::new();
-        let candidate_transactions =
-            self.get_pruning_candidate_transactions(current_progress, target_version)?;
+        let candidate_transactions: Vec<_> = self
+            .get_pruning_candidate_transactions(current_progress, target_version)?
+            .into_iter()
+            .filter(|(version, _)| !retained_versions.contains(version))
+            .map(|(_, txn)| txn)
+            .collect();
         self.transaction_store
             .prune_transaction_by_hash(&candidate_transactions, &batch)?;
         self.transaction_store
@@ -37,6 +49,7 @@ impl DBSubPruner for TransactionPruner {
         self.transaction_store.prune_transaction_schema(
             current_progress,
             target_version,
+            retained_versions,
             &batch,
         )?;
         batch.put::<DbMetadataSchema>(
@@ -51,6 +64,7 @@ impl TransactionPruner {
     pub(in crate::pruner) fn new(
         transaction_store: Arc<TransactionStore>,
         transaction_db: Arc<DB>,
+        retained_accounts: &RetainedAccounts,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -59,6 +73,11 @@ impl TransactionPruner {
             metadata_progress,
         )?;
 
+        let retained_versions = transaction_store.get_retained_versions(
+            progress,
+            metadata_progress,
+            retained_accounts,
+        )?;
         let myself = TransactionPruner {
             transaction_store,
             transaction_db,
@@ -69,7 +88,7 @@ impl TransactionPruner {
             metadata_progress = metadata_progress,
             "Catching up TransactionPruner."
         );
-        myself.prune(progress, metadata_progress)?;
+        myself.prune(progress, metadata_progress, &retained_versions)?;
 
         Ok(myself)
     }
@@ -78,7 +97,7 @@ impl TransactionPruner {
         &self,
         start: Version,
         end: Version,
-    ) -> Result<Vec<Transaction>> {
+    ) -> Result<Vec<(Version, Transaction)>> {
         ensure!(end >= start);
 
         let mut iter = self
@@ -94,7 +113,7 @@ impl TransactionPruner {
             if version >= end {
                 break;
             }
-            txns.push(txn);
+            txns.push((version, txn));
         }
 
         Ok(txns)
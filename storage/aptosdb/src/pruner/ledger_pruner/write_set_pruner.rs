@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress,
+        RetainedAccounts,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     TransactionStore,
 };
@@ -10,7 +13,7 @@ use anyhow::Result;
 use aptos_logger::info;
 use aptos_schemadb::{SchemaBatch, DB};
 use aptos_types::transaction::Version;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 #[derive(Debug)]
 pub struct WriteSetPruner {
@@ -23,10 +26,19 @@ impl DBSubPruner for WriteSetPruner {
         "WriteSetPruner"
     }
 
-    fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
+    fn prune(
+        &self,
+        current_progress: Version,
+        target_version: Version,
+        retained_versions: &HashSet<Version>,
+    ) -> Result<()> {
         let batch = SchemaBatch::new();
-        self.transaction_store
-            .prune_write_set(current_progress, target_version, &batch)?;
+        self.transaction_store.prune_write_set(
+            current_progress,
+            target_version,
+            retained_versions,
+            &batch,
+        )?;
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::WriteSetPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -39,6 +51,7 @@ impl WriteSetPruner {
     pub(in crate::pruner) fn new(
         transaction_store: Arc<TransactionStore>,
         write_set_db: Arc<DB>,
+        retained_accounts: &RetainedAccounts,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -47,6 +60,11 @@ impl WriteSetPruner {
             metadata_progress,
         )?;
 
+        let retained_versions = transaction_store.get_retained_versions(
+            progress,
+            metadata_progress,
+            retained_accounts,
+        )?;
         let myself = WriteSetPruner {
             transaction_store,
             write_set_db,
@@ -57,7 +75,7 @@ impl WriteSetPruner {
             metadata_progress = metadata_progress,
             "Catching up WriteSetPruner."
         );
-        myself.prune(progress, metadata_progress)?;
+        myself.prune(progress, metadata_progress, &retained_versions)?;
 
         Ok(myself)
     }
@@ -5,7 +5,7 @@
 
 use crate::{
     ledger_db::LedgerDb,
-    pruner::state_merkle_pruner::generics::StaleNodeIndexSchemaTrait,
+    pruner::{state_merkle_pruner::generics::StaleNodeIndexSchemaTrait, RetainedAccounts},
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         transaction_info::TransactionInfoSchema,
@@ -17,7 +17,7 @@ use crate::{
 use anyhow::Result;
 use aptos_jellyfish_merkle::StaleNodeIndex;
 use aptos_schemadb::{schema::KeyCodec, ReadOptions, DB};
-use aptos_types::transaction::Version;
+use aptos_types::{account_address::AccountAddress, transaction::Version};
 
 pub(crate) fn get_ledger_pruner_progress(ledger_db: &LedgerDb) -> Result<Version> {
     Ok(
@@ -77,3 +77,23 @@ pub(crate) fn get_or_initialize_subpruner_progress(
         },
     )
 }
+
+/// Loads the retained accounts of a pruned db from its `metadata_db`. The accounts new to the
+/// config are only retained from the version after the `commit_progress` of the db on, and only
+/// while the pruner is enabled, as nothing is pruned otherwise.
+pub(crate) fn load_retained_accounts(
+    metadata_db: &DB,
+    accounts: &[AccountAddress],
+    commit_progress: Option<Version>,
+    pruner_enabled: bool,
+) -> Result<RetainedAccounts> {
+    if pruner_enabled {
+        RetainedAccounts::load_or_initialize(
+            metadata_db,
+            accounts,
+            commit_progress.map_or(0, |version| version + 1),
+        )
+    } else {
+        RetainedAccounts::load(metadata_db, accounts)
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+    utils::get_progress,
+};
+use anyhow::Result;
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
+use aptos_types::{
+    account_address::AccountAddress,
+    contract_event::ContractEvent,
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_key_prefix::StateKeyPrefix,
+        state_value::StaleStateValueIndex,
+    },
+    transaction::{Transaction, Version},
+    write_set::WriteSet,
+};
+use std::{collections::HashMap, sync::Arc};
+
+/// Accounts whose history is kept by the ledger and state kv pruners regardless of the prune
+/// window, see `LedgerPrunerConfig::retained_accounts`.
+///
+/// The history of an account is only kept from its start version on, which is persisted in the
+/// metadata db of the pruned db when the account is first retained, as the history before it may
+/// have been pruned already.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RetainedAccounts {
+    start_versions: Arc<HashMap<AccountAddress, Version>>,
+}
+
+impl RetainedAccounts {
+    /// Loads the start versions of the `accounts` from `metadata_db`. The accounts without one
+    /// aren't retained.
+    pub fn load(metadata_db: &DB, accounts: &[AccountAddress]) -> Result<Self> {
+        let mut start_versions = HashMap::new();
+        for address in accounts {
+            if let Some(version) = get_progress(
+                metadata_db,
+                &DbMetadataKey::RetainedAccountStartVersion(*address),
+            )? {
+                start_versions.insert(*address, version);
+            }
+        }
+        Ok(Self {
+            start_versions: Arc::new(start_versions),
+        })
+    }
+
+    /// Like `load`, but the accounts without a start version are retained from `next_version` on.
+    /// The start versions of the accounts which are no longer retained are removed, so that they
+    /// start over if they are retained again.
+    pub fn load_or_initialize(
+        metadata_db: &DB,
+        accounts: &[AccountAddress],
+        next_version: Version,
+    ) -> Result<Self> {
+        let batch = SchemaBatch::new();
+        let mut iter = metadata_db.iter::<DbMetadataSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (key, _) = item?;
+            if let DbMetadataKey::RetainedAccountStartVersion(address) = &key {
+                if !accounts.contains(address) {
+                    batch.delete::<DbMetadataSchema>(&key)?;
+                }
+            }
+        }
+        for address in accounts {
+            let key = DbMetadataKey::RetainedAccountStartVersion(*address);
+            if metadata_db.get::<DbMetadataSchema>(&key)?.is_none() {
+                batch.put::<DbMetadataSchema>(&key, &DbMetadataValue::Version(next_version))?;
+            }
+        }
+        metadata_db.write_schemas(batch)?;
+
+        Self::load(metadata_db, accounts)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start_versions.is_empty()
+    }
+
+    /// The earliest version the history of any account is kept from.
+    pub fn min_start_version(&self) -> Option<Version> {
+        self.start_versions.values().min().copied()
+    }
+
+    /// Whether the history of the account at `address` is kept at `version`.
+    pub fn retains(&self, address: &AccountAddress, version: Version) -> bool {
+        self.start_versions
+            .get(address)
+            .map_or(false, |start_version| version >= *start_version)
+    }
+
+    /// A transaction is retained if it's sent by a retained account, writes the state under its
+    /// address, or emits an event to an event handle it created, e.g. a transfer to it. The info,
+    /// write set and events of the transaction are retained as well, so the table items and
+    /// objects it writes are kept in its write set.
+    pub fn retains_transaction(
+        &self,
+        version: Version,
+        txn: &Transaction,
+        write_set: &WriteSet,
+        events: &[ContractEvent],
+    ) -> bool {
+        !self.is_empty()
+            && (txn
+                .try_as_signed_user_txn()
+                .map_or(false, |txn| self.retains(&txn.sender(), version))
+                || write_set
+                    .iter()
+                    .any(|(state_key, _)| self.retains_state_key(state_key, version))
+                || events.iter().any(|event| match event {
+                    ContractEvent::V1(event) => {
+                        self.retains(&event.key().get_creator_address(), version)
+                    },
+                    ContractEvent::V2(_) => false,
+                }))
+    }
+
+    /// A state value is readable at `version` beyond the prune window if it's under the address
+    /// of an account retained at `version`.
+    pub fn retains_state_key(&self, state_key: &StateKey, version: Version) -> bool {
+        match state_key.inner() {
+            StateKeyInner::AccessPath(access_path) => self.retains(&access_path.address, version),
+            StateKeyInner::TableItem { .. } | StateKeyInner::Raw(_) => false,
+        }
+    }
+
+    pub fn retains_state_key_prefix(&self, key_prefix: &StateKeyPrefix, version: Version) -> bool {
+        key_prefix
+            .account_address()
+            .map_or(false, |address| self.retains(&address, version))
+    }
+
+    /// A stale state value is retained if it's still readable at some version the state key is
+    /// retained at, i.e. it's not stale yet at the start version of the account.
+    pub fn retains_stale_state_value(&self, index: &StaleStateValueIndex) -> bool {
+        index.stale_since_version > 0
+            && self.retains_state_key(&index.state_key, index.stale_since_version - 1)
+    }
+}
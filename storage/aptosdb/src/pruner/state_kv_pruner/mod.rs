@@ -13,6 +13,7 @@ use crate::{
             state_kv_metadata_pruner::StateKvMetadataPruner,
             state_kv_shard_pruner::StateKvShardPruner,
         },
+        RetainedAccounts,
     },
     state_kv_db::StateKvDb,
     OTHER_TIMERS_SECONDS,
@@ -110,10 +111,11 @@ impl DBPruner for StateKvPruner {
 }
 
 impl StateKvPruner {
-    pub fn new(state_kv_db: Arc<StateKvDb>) -> Result<Self> {
+    pub fn new(state_kv_db: Arc<StateKvDb>, retained_accounts: RetainedAccounts) -> Result<Self> {
         info!(name = STATE_KV_PRUNER_NAME, "Initializing...");
 
-        let metadata_pruner = StateKvMetadataPruner::new(Arc::clone(&state_kv_db));
+        let metadata_pruner =
+            StateKvMetadataPruner::new(Arc::clone(&state_kv_db), retained_accounts.clone());
 
         let metadata_progress = metadata_pruner.progress()?;
 
@@ -129,6 +131,7 @@ impl StateKvPruner {
                 shard_pruners.push(StateKvShardPruner::new(
                     shard_id,
                     state_kv_db.db_shard_arc(shard_id),
+                    retained_accounts.clone(),
                     metadata_progress,
                 )?);
             }
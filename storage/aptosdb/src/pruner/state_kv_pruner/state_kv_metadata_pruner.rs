@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    pruner::RetainedAccounts,
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         stale_state_value_index::StaleStateValueIndexSchema,
@@ -18,11 +19,18 @@ use std::sync::Arc;
 
 pub(in crate::pruner) struct StateKvMetadataPruner {
     state_kv_db: Arc<StateKvDb>,
+    retained_accounts: RetainedAccounts,
 }
 
 impl StateKvMetadataPruner {
-    pub(in crate::pruner) fn new(state_kv_db: Arc<StateKvDb>) -> Self {
-        Self { state_kv_db }
+    pub(in crate::pruner) fn new(
+        state_kv_db: Arc<StateKvDb>,
+        retained_accounts: RetainedAccounts,
+    ) -> Self {
+        Self {
+            state_kv_db,
+            retained_accounts,
+        }
     }
 
     pub(in crate::pruner) fn prune(
@@ -46,6 +54,9 @@ impl StateKvMetadataPruner {
                     if index.stale_since_version > target_version {
                         break;
                     }
+                    if self.retained_accounts.retains_stale_state_value(&index) {
+                        continue;
+                    }
                     batch.delete::<StateValueIndexSchema>(&(index.state_key, index.version))?;
                 }
             }
@@ -60,6 +71,9 @@ impl StateKvMetadataPruner {
                 if index.stale_since_version > target_version {
                     break;
                 }
+                if self.retained_accounts.retains_stale_state_value(&index) {
+                    continue;
+                }
                 batch.delete::<StaleStateValueIndexSchema>(&index)?;
                 batch.delete::<StateValueSchema>(&(index.state_key, index.version))?;
             }
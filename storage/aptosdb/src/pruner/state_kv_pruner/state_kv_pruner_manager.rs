@@ -5,9 +5,10 @@ use crate::{
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
         pruner_manager::PrunerManager, pruner_utils, pruner_worker::PrunerWorker,
-        state_kv_pruner::StateKvPruner,
+        state_kv_pruner::StateKvPruner, RetainedAccounts,
     },
     state_kv_db::StateKvDb,
+    utils::truncation_helper::get_state_kv_commit_progress,
 };
 use anyhow::Result;
 use aptos_config::config::LedgerPrunerConfig;
@@ -25,6 +26,8 @@ pub(crate) struct StateKvPrunerManager {
     pruning_batch_size: usize,
     /// The minimal readable version for the ledger data.
     min_readable_version: AtomicVersion,
+    /// Accounts whose state values are kept beyond the prune window.
+    retained_accounts: RetainedAccounts,
 }
 
 impl PrunerManager for StateKvPrunerManager {
@@ -82,10 +85,18 @@ impl PrunerManager for StateKvPrunerManager {
 
 impl StateKvPrunerManager {
     pub fn new(state_kv_db: Arc<StateKvDb>, state_kv_pruner_config: LedgerPrunerConfig) -> Self {
+        let retained_accounts = pruner_utils::load_retained_accounts(
+            state_kv_db.metadata_db(),
+            &state_kv_pruner_config.retained_accounts,
+            get_state_kv_commit_progress(&state_kv_db).expect("Must succeed."),
+            state_kv_pruner_config.enable,
+        )
+        .expect("Failed to load retained accounts.");
         let pruner_worker = if state_kv_pruner_config.enable {
             Some(Self::init_pruner(
                 Arc::clone(&state_kv_db),
-                state_kv_pruner_config,
+                &state_kv_pruner_config,
+                retained_accounts.clone(),
             ))
         } else {
             None
@@ -104,15 +115,23 @@ impl StateKvPrunerManager {
             pruner_worker,
             pruning_batch_size: state_kv_pruner_config.batch_size,
             min_readable_version: AtomicVersion::new(min_readable_version),
+            retained_accounts,
         }
     }
 
+    pub fn retained_accounts(&self) -> &RetainedAccounts {
+        &self.retained_accounts
+    }
+
     fn init_pruner(
         state_kv_db: Arc<StateKvDb>,
-        state_kv_pruner_config: LedgerPrunerConfig,
+        state_kv_pruner_config: &LedgerPrunerConfig,
+        retained_accounts: RetainedAccounts,
     ) -> PrunerWorker {
-        let pruner =
-            Arc::new(StateKvPruner::new(state_kv_db).expect("Failed to create state kv pruner."));
+        let pruner = Arc::new(
+            StateKvPruner::new(state_kv_db, retained_accounts)
+                .expect("Failed to create state kv pruner."),
+        );
 
         PRUNER_WINDOW
             .with_label_values(&["state_kv_pruner"])
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::pruner_utils::get_or_initialize_subpruner_progress,
+    pruner::{pruner_utils::get_or_initialize_subpruner_progress, RetainedAccounts},
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         stale_state_value_index::StaleStateValueIndexSchema,
@@ -18,12 +18,14 @@ use std::sync::Arc;
 pub(in crate::pruner) struct StateKvShardPruner {
     shard_id: u8,
     db_shard: Arc<DB>,
+    retained_accounts: RetainedAccounts,
 }
 
 impl StateKvShardPruner {
     pub(in crate::pruner) fn new(
         shard_id: u8,
         db_shard: Arc<DB>,
+        retained_accounts: RetainedAccounts,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -31,7 +33,11 @@ impl StateKvShardPruner {
             &DbMetadataKey::StateKvShardPrunerProgress(shard_id as usize),
             metadata_progress,
         )?;
-        let myself = Self { shard_id, db_shard };
+        let myself = Self {
+            shard_id,
+            db_shard,
+            retained_accounts,
+        };
 
         info!(
             progress = progress,
@@ -59,6 +65,9 @@ impl StateKvShardPruner {
             if index.stale_since_version > target_version {
                 break;
             }
+            if self.retained_accounts.retains_stale_state_value(&index) {
+                continue;
+            }
             batch.delete::<StaleStateValueIndexSchema>(&index)?;
             batch.delete::<StateValueSchema>(&(index.state_key, index.version))?;
         }
@@ -16,6 +16,8 @@ use aptos_schemadb::{ReadOptions, SchemaBatch};
 use aptos_storage_interface::{jmt_update_refs, jmt_updates, DbReader};
 use aptos_temppath::TempPath;
 use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
     state_store::{
         state_key::StateKey,
         state_storage_usage::StateStorageUsage,
@@ -365,6 +367,7 @@ fn verify_state_value_pruner(inputs: Vec<Vec<(StateKey, Option<StateValue>)>>) {
         prune_window: 0,
         batch_size: 1,
         user_pruning_window_offset: 0,
+        retained_accounts: vec![],
     });
     for batch in inputs {
         update_store(store, batch.clone().into_iter(), version);
@@ -390,6 +393,85 @@ fn verify_state_value_pruner(inputs: Vec<Vec<(StateKey, Option<StateValue>)>>) {
     }
 }
 
+#[test]
+fn test_state_value_pruner_with_retained_accounts() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+
+    let retained_account = AccountAddress::random();
+    let late_account = AccountAddress::random();
+    let retained_key =
+        StateKey::access_path(AccessPath::new(retained_account, b"retained".to_vec()));
+    let late_key = StateKey::access_path(AccessPath::new(late_account, b"late".to_vec()));
+    let other_key =
+        StateKey::access_path(AccessPath::new(AccountAddress::random(), b"other".to_vec()));
+    let value = |v: u8| Some(StateValue::from(vec![v]));
+    let config = |retained_accounts| LedgerPrunerConfig {
+        enable: true,
+        prune_window: 0,
+        batch_size: 1,
+        user_pruning_window_offset: 0,
+        retained_accounts,
+    };
+
+    // The account is retained from the genesis of the empty db on
+    drop(StateKvPrunerManager::new(
+        Arc::clone(&db.state_kv_db),
+        config(vec![retained_account]),
+    ));
+
+    // Each key is written at two of versions 0 to 5.
+    update_store(
+        store,
+        vec![
+            (retained_key.clone(), value(0)),
+            (other_key.clone(), value(1)),
+            (late_key.clone(), value(2)),
+            (retained_key.clone(), value(3)),
+            (other_key.clone(), value(4)),
+            (late_key.clone(), value(5)),
+        ]
+        .into_iter(),
+        0,
+    );
+
+    // The late account is only retained from version 6 on, after its first value is stale.
+    let pruner = StateKvPrunerManager::new(
+        Arc::clone(&db.state_kv_db),
+        config(vec![retained_account, late_account]),
+    );
+    assert!(pruner.retained_accounts().retains(&retained_account, 0));
+    assert!(!pruner.retained_accounts().retains(&late_account, 5));
+    assert!(pruner.retained_accounts().retains(&late_account, 6));
+    pruner.wake_and_wait_pruner(6 /* latest_version */).unwrap();
+
+    assert_eq!(
+        store.get_state_value_by_version(&retained_key, 2).unwrap(),
+        value(0)
+    );
+    assert_eq!(
+        store.get_state_value_by_version(&other_key, 3).unwrap(),
+        None
+    );
+    assert_eq!(
+        store.get_state_value_by_version(&late_key, 4).unwrap(),
+        None
+    );
+    assert_eq!(
+        store.get_state_value_by_version(&retained_key, 5).unwrap(),
+        value(3)
+    );
+    assert_eq!(
+        store.get_state_value_by_version(&other_key, 5).unwrap(),
+        value(4)
+    );
+    assert_eq!(
+        store.get_state_value_by_version(&late_key, 5).unwrap(),
+        value(5)
+    );
+}
+
 fn verify_state_value<'a, I: Iterator<Item = (&'a StateKey, &'a (Version, Option<StateValue>))>>(
     kvs: I,
     version: Version,
@@ -15,7 +15,7 @@ use aptos_schemadb::{
     define_schema,
     schema::{KeyCodec, ValueCodec},
 };
-use aptos_types::transaction::Version;
+use aptos_types::{account_address::AccountAddress, transaction::Version};
 use serde::{Deserialize, Serialize};
 
 type ShardId = usize;
@@ -66,6 +66,7 @@ pub enum DbMetadataKey {
     EpochEndingStateMerkleShardPrunerProgress(ShardId),
     StateKvShardPrunerProgress(ShardId),
     StateMerkleShardRestoreProgress(ShardId, Version),
+    RetainedAccountStartVersion(AccountAddress),
 }
 
 define_schema!(
@@ -156,6 +156,12 @@ impl StateKvDb {
             RocksdbConfig::default(),
             false,
         )?;
+        state_kv_db.create_checkpoint_at(cp_root_path)
+    }
+
+    /// Creates a checkpoint of the (already opened) state kv db, which can happen while the db is
+    /// being written to.
+    pub(crate) fn create_checkpoint_at(&self, cp_root_path: impl AsRef<Path>) -> Result<()> {
         let cp_state_kv_db_path = cp_root_path.as_ref().join(STATE_KV_DB_FOLDER_NAME);
 
         info!("Creating state_kv_db checkpoint at: {cp_state_kv_db_path:?}");
@@ -163,13 +169,11 @@ impl StateKvDb {
         std::fs::remove_dir_all(&cp_state_kv_db_path).unwrap_or(());
         std::fs::create_dir_all(&cp_state_kv_db_path).unwrap_or(());
 
-        state_kv_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref()))?;
 
         for shard_id in 0..NUM_STATE_SHARDS {
-            state_kv_db
-                .db_shard(shard_id as u8)
+            self.db_shard(shard_id as u8)
                 .create_checkpoint(Self::db_shard_path(cp_root_path.as_ref(), shard_id as u8))?;
         }
 
@@ -19,7 +19,7 @@ use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_experimental_runtimes::thread_manager::{optimal_min_len, THREAD_MANAGER};
 use aptos_jellyfish_merkle::{
     node_type::{NodeKey, NodeType},
-    JellyfishMerkleTree, TreeReader, TreeUpdateBatch, TreeWriter,
+    JellyfishMerkleTree, LeafDiff, TreeReader, TreeUpdateBatch, TreeWriter,
 };
 use aptos_logger::prelude::*;
 use aptos_rocksdb_options::gen_rocksdb_options;
@@ -28,7 +28,7 @@ use aptos_schemadb::{SchemaBatch, DB};
 use aptos_scratchpad::get_state_shard_id;
 use aptos_types::{
     nibble::{nibble_path::NibblePath, ROOT_NIBBLE_HEIGHT},
-    proof::{SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{SparseMerkleMultiProof, SparseMerkleProofExt, SparseMerkleRangeProof},
     state_store::state_key::StateKey,
     transaction::Version,
 };
@@ -171,6 +171,13 @@ impl StateMerkleDb {
             /*readonly=*/ false,
             /*max_nodes_per_lru_cache_shard=*/ 0,
         )?;
+        state_merkle_db.create_checkpoint_at(cp_root_path)
+    }
+
+    /// Creates a checkpoint of the (already opened) state merkle db, which can happen while the db
+    /// is being written to.
+    pub(crate) fn create_checkpoint_at(&self, cp_root_path: impl AsRef<Path>) -> Result<()> {
+        let sharding = self.enable_sharding;
         let cp_state_merkle_db_path = cp_root_path.as_ref().join(STATE_MERKLE_DB_FOLDER_NAME);
 
         info!("Creating state_merkle_db checkpoint at: {cp_state_merkle_db_path:?}");
@@ -180,14 +187,12 @@ impl StateMerkleDb {
             std::fs::create_dir_all(&cp_state_merkle_db_path).unwrap_or(());
         }
 
-        state_merkle_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref(), sharding))?;
 
         if sharding {
             for shard_id in 0..NUM_STATE_SHARDS {
-                state_merkle_db
-                    .db_shard(shard_id as u8)
+                self.db_shard(shard_id as u8)
                     .create_checkpoint(Self::db_shard_path(
                         cp_root_path.as_ref(),
                         shard_id as u8,
@@ -248,6 +253,27 @@ impl StateMerkleDb {
         JellyfishMerkleTree::new(self).get_with_proof_ext(state_key.hash(), version)
     }
 
+    pub fn get_with_multiproof(
+        &self,
+        keys: &[HashValue],
+        version: Version,
+    ) -> Result<(
+        Vec<Option<(HashValue, (StateKey, Version))>>,
+        SparseMerkleMultiProof,
+    )> {
+        JellyfishMerkleTree::new(self).get_with_multiproof(keys, version)
+    }
+
+    pub fn get_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+        start_key: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Vec<LeafDiff<StateKey>>> {
+        JellyfishMerkleTree::new(self).get_diff(old_version, new_version, start_key, limit)
+    }
+
     pub fn get_range_proof(
         &self,
         rightmost_key: HashValue,
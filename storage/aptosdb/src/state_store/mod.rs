@@ -47,9 +47,12 @@ use aptos_storage_interface::{
     DbReader, StateSnapshotReceiver,
 };
 use aptos_types::{
-    proof::{definition::LeafCount, SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{
+        definition::LeafCount, SparseMerkleMultiProof, SparseMerkleProofExt, SparseMerkleRangeProof,
+    },
     state_store::{
         create_empty_sharded_state_updates,
+        state_diff::StateKeyDiff,
         state_key::StateKey,
         state_key_prefix::StateKeyPrefix,
         state_storage_usage::StateStorageUsage,
@@ -187,6 +190,77 @@ impl DbReader for StateDb {
         ))
     }
 
+    /// Get the state values with a single proof given the state keys and version
+    fn get_state_values_with_multiproof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        let mut state_keys: Vec<_> = state_keys
+            .iter()
+            .map(|state_key| (state_key.hash(), state_key))
+            .collect();
+        state_keys.sort_by_key(|(key_hash, _)| *key_hash);
+        state_keys.dedup_by_key(|(key_hash, _)| *key_hash);
+        let key_hashes: Vec<_> = state_keys.iter().map(|(key_hash, _)| *key_hash).collect();
+
+        let (leaves_data, proof) = self
+            .state_merkle_db
+            .get_with_multiproof(&key_hashes, version)?;
+        let values = state_keys
+            .into_iter()
+            .zip(leaves_data)
+            .map(|((_, state_key), leaf_data)| {
+                Ok((state_key.clone(), match leaf_data {
+                    Some((_, (key, version))) => Some(self.expect_value_by_version(&key, version)?),
+                    None => None,
+                }))
+            })
+            .collect::<Result<_>>()?;
+        Ok((values, proof))
+    }
+
+    /// Get the state keys whose values differ between two state snapshots
+    fn get_state_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+        start: Option<&StateKey>,
+        limit: usize,
+    ) -> Result<(Vec<StateKeyDiff>, Option<StateKey>)> {
+        let mut diffs: Vec<_> = self
+            .state_merkle_db
+            .get_diff(
+                old_version,
+                new_version,
+                start.map(|state_key| state_key.hash()),
+                limit.saturating_add(1),
+            )?
+            .into_iter()
+            .map(|diff| {
+                let state_key = diff
+                    .old
+                    .as_ref()
+                    .or(diff.new.as_ref())
+                    .expect("Either leaf must exist.")
+                    .value_index()
+                    .0
+                    .clone();
+                StateKeyDiff::new(
+                    state_key,
+                    diff.old.map(|leaf| leaf.value_index().1),
+                    diff.new.map(|leaf| leaf.value_index().1),
+                )
+            })
+            .collect();
+        let next_state_key = if diffs.len() > limit {
+            diffs.pop().map(|diff| diff.state_key)
+        } else {
+            None
+        };
+        Ok((diffs, next_state_key))
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         version.map_or(Ok(StateStorageUsage::zero()), |version| {
             Ok(
@@ -279,6 +353,28 @@ impl DbReader for StateStore {
         self.deref()
             .get_state_value_with_proof_by_version_ext(state_key, version)
     }
+
+    /// Get the state values with a single proof given the state keys and version
+    fn get_state_values_with_multiproof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        self.deref()
+            .get_state_values_with_multiproof_by_version(state_keys, version)
+    }
+
+    /// Get the state keys whose values differ between two state snapshots
+    fn get_state_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+        start: Option<&StateKey>,
+        limit: usize,
+    ) -> Result<(Vec<StateKeyDiff>, Option<StateKey>)> {
+        self.deref()
+            .get_state_diff(old_version, new_version, start, limit)
+    }
 }
 
 impl StateDb {
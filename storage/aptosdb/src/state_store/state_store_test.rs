@@ -19,8 +19,10 @@ use aptos_storage_interface::{
 };
 use aptos_temppath::TempPath;
 use aptos_types::{
-    access_path::AccessPath, account_address::AccountAddress, nibble::nibble_path::NibblePath,
-    state_store::state_key::StateKeyTag,
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    nibble::nibble_path::NibblePath,
+    state_store::{state_diff::StateKeyChange, state_key::StateKeyTag},
 };
 use arr_macro::arr;
 use proptest::{collection::hash_map, prelude::*};
@@ -164,6 +166,102 @@ fn test_state_store_reader_writer() {
     verify_value_and_proof(store, key3, Some(&value3), 1, root);
 }
 
+#[test]
+fn test_state_store_multiproof() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let key1 = StateKey::raw(String::from("test_key1").into_bytes());
+    let key2 = StateKey::raw(String::from("test_key2").into_bytes());
+    let key3 = StateKey::raw(String::from("test_key3").into_bytes());
+    let value1 = StateValue::from(String::from("test_val1").into_bytes());
+    let value2 = StateValue::from(String::from("test_val2").into_bytes());
+
+    let root = put_value_set(
+        store,
+        vec![
+            (key1.clone(), value1.clone()),
+            (key2.clone(), value2.clone()),
+        ],
+        0, /* version */
+        None,
+    );
+
+    // Duplicated keys are only proven once.
+    let (values, proof) = store
+        .get_state_values_with_multiproof_by_version(
+            &[key3.clone(), key1.clone(), key2.clone(), key1.clone()],
+            0,
+        )
+        .unwrap();
+    let mut expected_values = vec![(key1, Some(value1)), (key2, Some(value2)), (key3, None)];
+    expected_values.sort_by_key(|(key, _)| key.hash());
+    assert_eq!(values, expected_values);
+
+    let elements: Vec<_> = values
+        .iter()
+        .map(|(key, value)| (key.hash(), value.as_ref()))
+        .collect();
+    proof.verify(root, &elements).unwrap();
+}
+
+#[test]
+fn test_state_store_diff() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let key1 = StateKey::raw(String::from("test_key1").into_bytes());
+    let key2 = StateKey::raw(String::from("test_key2").into_bytes());
+    let key3 = StateKey::raw(String::from("test_key3").into_bytes());
+    let value1 = StateValue::from(String::from("test_val1").into_bytes());
+    let value2 = StateValue::from(String::from("test_val2").into_bytes());
+    let value2_update = StateValue::from(String::from("test_val2_update").into_bytes());
+    let value3 = StateValue::from(String::from("test_val3").into_bytes());
+
+    put_value_set(
+        store,
+        vec![(key1, value1), (key2.clone(), value2)],
+        0, /* version */
+        None,
+    );
+    put_value_set(
+        store,
+        vec![(key2.clone(), value2_update), (key3.clone(), value3)],
+        1, /* version */
+        Some(0),
+    );
+
+    let mut expected_diffs = vec![
+        StateKeyDiff::new(key2, Some(0), Some(1)),
+        StateKeyDiff::new(key3, None, Some(1)),
+    ];
+    expected_diffs.sort_by_key(|diff| diff.state_key.hash());
+    let (diffs, next_state_key) = store.get_state_diff(0, 1, None, 10).unwrap();
+    assert_eq!(diffs, expected_diffs);
+    assert_eq!(next_state_key, None);
+
+    let (first_page, next_state_key) = store.get_state_diff(0, 1, None, 1).unwrap();
+    assert_eq!(first_page, expected_diffs[..1]);
+    assert_eq!(next_state_key.as_ref(), Some(&expected_diffs[1].state_key));
+    let (second_page, next_state_key) = store
+        .get_state_diff(0, 1, next_state_key.as_ref(), 1)
+        .unwrap();
+    assert_eq!(second_page, expected_diffs[1..]);
+    assert_eq!(next_state_key, None);
+
+    let (diffs, _) = store.get_state_diff(1, 0, None, 10).unwrap();
+    assert_eq!(
+        diffs.iter().map(|diff| diff.change()).collect::<Vec<_>>(),
+        expected_diffs
+            .iter()
+            .map(|diff| match diff.change() {
+                StateKeyChange::Added => StateKeyChange::Deleted,
+                change => change,
+            })
+            .collect::<Vec<_>>()
+    );
+}
+
 fn traverse_values(
     store: &StateStore,
     prefix: &StateKeyPrefix,
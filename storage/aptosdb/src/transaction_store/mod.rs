@@ -7,8 +7,10 @@
 use crate::{
     errors::AptosDbError,
     ledger_db::LedgerDb,
+    pruner::RetainedAccounts,
     schema::{
-        transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
+        event::EventSchema, transaction::TransactionSchema,
+        transaction_by_account::TransactionByAccountSchema,
         transaction_by_hash::TransactionByHashSchema, write_set::WriteSetSchema,
     },
     transaction_accumulator::TransactionAccumulatorSchema,
@@ -24,7 +26,7 @@ use aptos_types::{
     transaction::{Transaction, Version},
     write_set::WriteSet,
 };
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 #[cfg(test)]
 mod test;
@@ -252,28 +254,104 @@ impl TransactionStore {
         Ok(())
     }
 
-    /// Prune the transaction schema store between a range of version in [begin, end)
+    /// Gets the versions in [begin, end) of the transactions which are retained for the retained
+    /// accounts. Versions which are already pruned are skipped, as they weren't retained.
+    ///
+    /// The transactions, write sets and events are scanned side by side over the range, rather
+    /// than looked up version by version.
+    pub fn get_retained_versions(
+        &self,
+        begin: Version,
+        end: Version,
+        retained_accounts: &RetainedAccounts,
+    ) -> Result<HashSet<Version>> {
+        let mut versions = HashSet::new();
+        // Nothing before the earliest start version of the retained accounts is retained
+        let begin = match retained_accounts.min_start_version() {
+            Some(min_start_version) if min_start_version < end => begin.max(min_start_version),
+            _ => return Ok(versions),
+        };
+
+        let mut txn_iter = self
+            .ledger_db
+            .transaction_db()
+            .iter::<TransactionSchema>(ReadOptions::default())?;
+        txn_iter.seek(&begin)?;
+        let mut write_set_iter = self
+            .ledger_db
+            .write_set_db()
+            .iter::<WriteSetSchema>(ReadOptions::default())?;
+        write_set_iter.seek(&begin)?;
+        let mut write_set_iter = write_set_iter.peekable();
+        let mut event_iter = self
+            .ledger_db
+            .event_db()
+            .iter::<EventSchema>(ReadOptions::default())?;
+        event_iter.seek(&begin)?;
+        let mut event_iter = event_iter.peekable();
+
+        for item in txn_iter {
+            let (version, txn) = item?;
+            if version >= end {
+                break;
+            }
+            // The write sets and events of the versions skipped by the transaction iterator (if
+            // any) are skipped as well
+            let mut write_set = WriteSet::default();
+            while let Some(item) =
+                write_set_iter.next_if(|item| item.as_ref().map_or(true, |(v, _)| *v <= version))
+            {
+                let (v, ws) = item?;
+                if v == version {
+                    write_set = ws;
+                }
+            }
+            let mut events = vec![];
+            while let Some(item) =
+                event_iter.next_if(|item| item.as_ref().map_or(true, |((v, _), _)| *v <= version))
+            {
+                let ((v, _), event) = item?;
+                if v == version {
+                    events.push(event);
+                }
+            }
+            if retained_accounts.retains_transaction(version, &txn, &write_set, &events) {
+                versions.insert(version);
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Prune the transaction schema store between a range of version in [begin, end), except for
+    /// the `retained_versions`
     pub fn prune_transaction_schema(
         &self,
         begin: Version,
         end: Version,
+        retained_versions: &HashSet<Version>,
         db_batch: &SchemaBatch,
     ) -> Result<()> {
         for version in begin..end {
-            db_batch.delete::<TransactionSchema>(&version)?;
+            if !retained_versions.contains(&version) {
+                db_batch.delete::<TransactionSchema>(&version)?;
+            }
         }
         Ok(())
     }
 
-    /// Prune the transaction schema store between a range of version in [begin, end)
+    /// Prune the transaction info schema store between a range of version in [begin, end),
+    /// except for the `retained_versions`
     pub fn prune_transaction_info_schema(
         &self,
         begin: Version,
         end: Version,
+        retained_versions: &HashSet<Version>,
         db_batch: &SchemaBatch,
     ) -> Result<()> {
         for version in begin..end {
-            db_batch.delete::<TransactionInfoSchema>(&version)?;
+            if !retained_versions.contains(&version) {
+                db_batch.delete::<TransactionInfoSchema>(&version)?;
+            }
         }
         Ok(())
     }
@@ -337,15 +415,19 @@ impl TransactionStore {
         Position::from_level_and_pos(first_ancestor_that_is_a_left_child_level, index_in_level)
     }
 
-    /// Prune the transaction schema store between a range of version in [begin, end)
+    /// Prune the write set schema store between a range of version in [begin, end), except for the
+    /// `retained_versions`
     pub fn prune_write_set(
         &self,
         begin: Version,
         end: Version,
+        retained_versions: &HashSet<Version>,
         db_batch: &SchemaBatch,
     ) -> Result<()> {
         for version in begin..end {
-            db_batch.delete::<WriteSetSchema>(&version)?;
+            if !retained_versions.contains(&version) {
+                db_batch.delete::<WriteSetSchema>(&version)?;
+            }
         }
         Ok(())
     }
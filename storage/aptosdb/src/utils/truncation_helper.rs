@@ -33,6 +33,7 @@ use claims::{assert_ge, assert_lt};
 use rayon::prelude::*;
 use status_line::StatusLine;
 use std::{
+    collections::HashSet,
     fmt::{Display, Formatter},
     sync::{
         atomic::{AtomicU64, Ordering},
@@ -401,7 +402,7 @@ fn delete_event_data(
                 latest_version = latest_version,
                 "Truncate event data."
             );
-            event_store.prune_events(start_version, latest_version + 1, batch)?;
+            event_store.prune_events(start_version, latest_version + 1, &HashSet::new(), batch)?;
         }
     }
     Ok(())
@@ -9,18 +9,20 @@ use crate::{
     db::INDEX_DB_NAME,
     metadata::{MetadataKey, MetadataValue},
     schema::{
-        column_families, indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        column_families, event_by_type::EventByTypeSchema, indexer_metadata::IndexerMetadataSchema,
+        table_info::TableInfoSchema,
     },
 };
 use anyhow::{bail, ensure, Result};
 use aptos_config::config::RocksdbConfig;
 use aptos_logger::warn;
 use aptos_rocksdb_options::gen_rocksdb_options;
-use aptos_schemadb::{SchemaBatch, DB};
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{state_view::DbStateView, DbReader};
 use aptos_types::{
     access_path::Path,
     account_address::AccountAddress,
+    contract_event::ContractEvent,
     state_store::{
         state_key::{StateKey, StateKeyInner},
         table::{TableHandle, TableInfo},
@@ -39,12 +41,20 @@ use move_resource_viewer::{AnnotatedMoveValue, MoveValueAnnotator};
 use std::{
     collections::{BTreeMap, HashMap},
     convert::TryInto,
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 #[derive(Debug)]
 pub struct Indexer {
     db: DB,
     next_version: AtomicVersion,
+    /// Events of the versions before this one are not indexed by type (yet).
+    event_by_type_start_version: AtomicVersion,
+    /// Whether the events before `event_by_type_start_version` are all pruned, i.e. the events by
+    /// type index is complete.
+    event_by_type_backfilled: AtomicBool,
 }
 
 impl Indexer {
@@ -65,9 +75,25 @@ impl Indexer {
             .get::<IndexerMetadataSchema>(&MetadataKey::LatestVersion)?
             .map_or(0, |v| v.expect_version());
 
+        // An index db created before events were indexed by type only indexes the events of new
+        // transactions, older ones need to be backfilled with `backfill_events_by_type`.
+        let event_by_type_start_version =
+            match db.get::<IndexerMetadataSchema>(&MetadataKey::EventByTypeStartVersion)? {
+                Some(v) => v.expect_version(),
+                None => {
+                    db.put::<IndexerMetadataSchema>(
+                        &MetadataKey::EventByTypeStartVersion,
+                        &MetadataValue::Version(next_version),
+                    )?;
+                    next_version
+                },
+            };
+
         Ok(Self {
             db,
             next_version: AtomicVersion::new(next_version),
+            event_by_type_start_version: AtomicVersion::new(event_by_type_start_version),
+            event_by_type_backfilled: AtomicBool::new(event_by_type_start_version == 0),
         })
     }
 
@@ -76,6 +102,7 @@ impl Indexer {
         db_reader: Arc<dyn DbReader>,
         first_version: Version,
         write_sets: &[&WriteSet],
+        events: &[&[ContractEvent]],
     ) -> Result<()> {
         let last_version = first_version + write_sets.len() as Version;
         let state_view = DbStateView {
@@ -84,7 +111,7 @@ impl Indexer {
         };
         let resolver = state_view.as_move_resolver();
         let annotator = MoveValueAnnotator::new(&resolver);
-        self.index_with_annotator(&annotator, first_version, write_sets)
+        self.index_with_annotator(&annotator, first_version, write_sets, events)
     }
 
     pub fn index_with_annotator<R: MoveResolver>(
@@ -92,7 +119,14 @@ impl Indexer {
         annotator: &MoveValueAnnotator<R>,
         first_version: Version,
         write_sets: &[&WriteSet],
+        events: &[&[ContractEvent]],
     ) -> Result<()> {
+        ensure!(
+            write_sets.len() == events.len(),
+            "Indexer expects write sets and events of the same transactions. Got {} write sets and {} event lists.",
+            write_sets.len(),
+            events.len(),
+        );
         let next_version = self.next_version();
         ensure!(
             first_version <= next_version,
@@ -132,6 +166,7 @@ impl Indexer {
                 bail!(err);
             },
         };
+        Self::put_events_by_type(&batch, first_version, events)?;
         batch.put::<IndexerMetadataSchema>(
             &MetadataKey::LatestVersion,
             &MetadataValue::Version(end_version - 1),
@@ -142,13 +177,127 @@ impl Indexer {
         Ok(())
     }
 
+    /// Indexes by type the events of the transactions right before the first version whose events
+    /// are already indexed by type, starting at `first_version`.
+    pub fn backfill_events_by_type(
+        &self,
+        first_version: Version,
+        events: &[&[ContractEvent]],
+    ) -> Result<()> {
+        let start_version = self.event_by_type_start_version();
+        ensure!(
+            first_version + events.len() as Version == start_version,
+            "Indexer expects to backfill the events right before version {}, got {} transactions starting from version {}.",
+            start_version,
+            events.len(),
+            first_version,
+        );
+
+        let batch = SchemaBatch::new();
+        Self::put_events_by_type(&batch, first_version, events)?;
+        batch.put::<IndexerMetadataSchema>(
+            &MetadataKey::EventByTypeStartVersion,
+            &MetadataValue::Version(first_version),
+        )?;
+        self.db.write_schemas(batch)?;
+        self.event_by_type_start_version
+            .store(first_version, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Removes the given events, as (event type, transaction version, event index), from the
+    /// events by type index once they are pruned from the ledger.
+    pub fn prune_events_by_type(&self, events: &[(TypeTag, Version, u64)]) -> Result<()> {
+        let batch = SchemaBatch::new();
+        for event in events {
+            batch.delete::<EventByTypeSchema>(event)?;
+        }
+        self.db.write_schemas(batch)
+    }
+
+    fn put_events_by_type(
+        batch: &SchemaBatch,
+        first_version: Version,
+        events: &[&[ContractEvent]],
+    ) -> Result<()> {
+        for (i, txn_events) in events.iter().enumerate() {
+            let version = first_version + i as Version;
+            for (idx, event) in txn_events.iter().enumerate() {
+                batch.put::<EventByTypeSchema>(
+                    &(event.type_tag().clone(), version, idx as u64),
+                    &(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a checkpoint of the index db under `cp_root_path`, which can happen while the db
+    /// is being written to.
+    pub fn create_checkpoint(&self, cp_root_path: impl AsRef<std::path::Path>) -> Result<()> {
+        let cp_db_path = cp_root_path.as_ref().join(INDEX_DB_NAME);
+        std::fs::remove_dir_all(&cp_db_path).unwrap_or(());
+        self.db.create_checkpoint(cp_db_path)
+    }
+
     pub fn next_version(&self) -> Version {
         self.next_version.load(Ordering::Relaxed)
     }
 
+    pub fn event_by_type_start_version(&self) -> Version {
+        self.event_by_type_start_version.load(Ordering::Relaxed)
+    }
+
+    pub fn is_event_by_type_backfilled(&self) -> bool {
+        self.event_by_type_backfilled.load(Ordering::Relaxed)
+    }
+
+    /// Marks the events by type index as complete, once the events before its start version are
+    /// pruned.
+    pub fn set_event_by_type_backfilled(&self) {
+        self.event_by_type_backfilled.store(true, Ordering::Relaxed);
+    }
+
     pub fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         self.db.get::<TableInfoSchema>(&handle)
     }
+
+    /// Returns the positions, as (transaction version, event index) pairs, of up to `limit`
+    /// events of the given type, starting at the position `start` and not past `ledger_version`.
+    pub fn get_event_indices_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start: (Version, u64),
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, u64)>> {
+        let (start_version, start_index) = start;
+        let event_by_type_start_version = self.event_by_type_start_version();
+        ensure!(
+            self.is_event_by_type_backfilled(),
+            "Events are still being indexed by type, down from version {}.",
+            event_by_type_start_version,
+        );
+        ensure!(
+            start_version >= event_by_type_start_version,
+            "Events are only indexed by type from version {}, got version {}.",
+            event_by_type_start_version,
+            start_version,
+        );
+        let mut iter = self.db.iter::<EventByTypeSchema>(ReadOptions::default())?;
+        iter.seek(&(type_tag.clone(), start_version, start_index))?;
+
+        let mut result = Vec::new();
+        for res in iter.take(limit as usize) {
+            let ((event_type, version, index), _) = res?;
+            if &event_type != type_tag || version > ledger_version {
+                break;
+            }
+            result.push((version, index));
+        }
+        Ok(result)
+    }
 }
 
 struct TableInfoParser<'a, R> {
@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an event index via which a ContractEvent (
+//! represented by a <txn_version, event_idx> tuple so that it can be fetched from `EventSchema`)
+//! can be found by the type of the event.
+//!
+//! ```text
+//! |<-------------key------------->|
+//! | type_tag | txn_ver | event_idx |
+//! ```
+//!
+//! `txn_ver` and `event_idx` are serialized in big endian so that records of the same type are
+//! in order of their numeric value.
+
+use crate::schema::EVENT_BY_TYPE_CF_NAME;
+use anyhow::{ensure, Result};
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt};
+use move_core_types::language_storage::TypeTag;
+use std::mem::size_of;
+
+define_schema!(EventByTypeSchema, Key, (), EVENT_BY_TYPE_CF_NAME);
+
+type Index = u64;
+type Key = (TypeTag, Version, Index);
+
+impl KeyCodec<EventByTypeSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (type_tag, version, index) = self;
+
+        let mut encoded = bcs::to_bytes(type_tag)?;
+        encoded.extend_from_slice(&version.to_be_bytes());
+        encoded.extend_from_slice(&index.to_be_bytes());
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const SUFFIX_SIZE: usize = size_of::<Version>() + size_of::<Index>();
+
+        ensure!(
+            data.len() > SUFFIX_SIZE,
+            "Unexpected data len {}, expected more than {}.",
+            data.len(),
+            SUFFIX_SIZE,
+        );
+        let type_tag_size = data.len() - SUFFIX_SIZE;
+        let type_tag = bcs::from_bytes(&data[..type_tag_size])?;
+        let version = (&data[type_tag_size..]).read_u64::<BigEndian>()?;
+        let index = (&data[type_tag_size + size_of::<Version>()..]).read_u64::<BigEndian>()?;
+
+        Ok((type_tag, version, index))
+    }
+}
+
+impl ValueCodec<EventByTypeSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.is_empty(),
+            "Unexpected data len {}, expected 0.",
+            data.len()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -7,8 +7,9 @@ use crate::{
     node_type::NodeType,
     test_helper::{
         arb_existent_kvs_and_nonexistent_keys, arb_kv_pair_with_distinct_last_nibble,
-        arb_tree_with_index, gen_value, test_get_leaf_count, test_get_range_proof,
-        test_get_with_proof, test_get_with_proof_with_distinct_last_nibble, ValueBlob,
+        arb_tree_with_index, gen_value, plus_one, test_get_leaf_count, test_get_range_proof,
+        test_get_with_multiproof, test_get_with_proof,
+        test_get_with_proof_with_distinct_last_nibble, ValueBlob,
     },
 };
 use aptos_crypto::{hash::SPARSE_MERKLE_PLACEHOLDER_HASH, HashValue};
@@ -16,7 +17,7 @@ use aptos_types::nibble::Nibble;
 use mock_tree_store::MockTreeStore;
 use proptest::{collection::hash_set, prelude::*};
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 fn update_nibble(original_key: &HashValue, n: usize, nibble: u8) -> HashValue {
     assert!(nibble < 16);
@@ -706,6 +707,102 @@ fn many_keys_deletion(seed: &[u8], num_keys: usize) {
     }
 }
 
+fn many_keys_diff(seed: &[u8], num_keys: usize) {
+    assert!(seed.len() < 32);
+    let mut actual_seed = [0u8; 32];
+    actual_seed[..seed.len()].copy_from_slice(seed);
+    let mut rng: StdRng = StdRng::from_seed(actual_seed);
+
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+
+    let values: Vec<_> = (0..4 * num_keys).map(|_i| gen_value()).collect();
+    let keys: Vec<_> = (0..3 * num_keys)
+        .map(|_i| HashValue::random_with_rng(&mut rng))
+        .collect();
+
+    // The first half of the keys at version 0 is deleted or modified at version 1, while keys
+    // which didn't exist are added.
+    let first_batch: Vec<_> = keys[..2 * num_keys]
+        .iter()
+        .zip(values.iter())
+        .map(|(k, v)| (*k, Some(v)))
+        .collect();
+    let second_batch: Vec<_> = keys[..num_keys / 2]
+        .iter()
+        .map(|k| (*k, None))
+        .chain(
+            keys[num_keys / 2..num_keys]
+                .iter()
+                .chain(keys[2 * num_keys..].iter())
+                .zip(values[2 * num_keys..].iter())
+                .map(|(k, v)| (*k, Some(v))),
+        )
+        .collect();
+
+    let (_root, batch) = tree
+        .put_value_set_test(first_batch.clone(), 0 /* version */)
+        .unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+    let (_root, batch) = tree
+        .put_value_set_test(second_batch.clone(), 1 /* version */)
+        .unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+
+    let mut expected = BTreeMap::new();
+    for (k, v) in &first_batch {
+        expected.insert(*k, (v.map(|x| x.0), None));
+    }
+    for (k, v) in &second_batch {
+        expected.entry(*k).or_insert((None, None)).1 = v.map(|x| x.0);
+    }
+    expected.retain(|_k, (old, new)| old != new);
+
+    let diffs = tree.get_diff(0, 1, None, usize::MAX).unwrap();
+    let actual: BTreeMap<_, _> = diffs
+        .iter()
+        .map(|diff| {
+            (
+                diff.key(),
+                (
+                    diff.old.as_ref().map(|leaf| leaf.value_hash()),
+                    diff.new.as_ref().map(|leaf| leaf.value_hash()),
+                ),
+            )
+        })
+        .collect();
+    assert_eq!(actual, expected);
+    assert!(diffs.windows(2).all(|pair| pair[0].key() < pair[1].key()));
+
+    // Walking the diff page by page yields the same keys.
+    let mut paged_diffs = vec![];
+    let mut start_key = None;
+    loop {
+        let page = tree.get_diff(0, 1, start_key, 7).unwrap();
+        let is_last_page = page.len() < 7;
+        start_key = page.last().map(|diff| plus_one(diff.key()));
+        paged_diffs.extend(page);
+        if is_last_page {
+            break;
+        }
+    }
+    assert_eq!(paged_diffs, diffs);
+
+    // The diff the other way round swaps the leaves.
+    let reversed_diffs = tree.get_diff(1, 0, None, usize::MAX).unwrap();
+    assert_eq!(
+        reversed_diffs,
+        diffs
+            .into_iter()
+            .map(|diff| LeafDiff {
+                old: diff.new,
+                new: diff.old,
+            })
+            .collect::<Vec<_>>()
+    );
+    assert!(tree.get_diff(1, 1, None, usize::MAX).unwrap().is_empty());
+}
+
 #[test]
 fn test_1000_keys() {
     let seed: &[_] = &[1, 2, 3, 4];
@@ -718,6 +815,12 @@ fn test_2000_keys_deletion() {
     many_keys_deletion(seed, 2000);
 }
 
+#[test]
+fn test_1000_keys_diff() {
+    let seed: &[_] = &[1, 2, 3, 4];
+    many_keys_diff(seed, 1000);
+}
+
 fn many_versions_get_proof_and_verify_tree_root(seed: &[u8], num_versions: usize) {
     assert!(seed.len() < 32);
     let mut actual_seed = [0u8; 32];
@@ -792,6 +895,11 @@ proptest! {
         test_get_with_proof((existent_kvs, nonexistent_keys))
     }
 
+    #[test]
+    fn proptest_get_with_multiproof((existent_kvs, nonexistent_keys) in arb_existent_kvs_and_nonexistent_keys::<ValueBlob>(1000, 100)) {
+        test_get_with_multiproof((existent_kvs, nonexistent_keys))
+    }
+
     #[test]
     fn proptest_get_with_proof_with_distinct_last_nibble((kv1, kv2) in arb_kv_pair_with_distinct_last_nibble::<ValueBlob>()) {
         test_get_with_proof_with_distinct_last_nibble((kv1, kv2))
@@ -89,7 +89,9 @@ use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_types::{
     nibble::{nibble_path::NibblePath, Nibble, ROOT_NIBBLE_HEIGHT},
-    proof::{SparseMerkleProof, SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{
+        SparseMerkleMultiProof, SparseMerkleProof, SparseMerkleProofExt, SparseMerkleRangeProof,
+    },
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::Version,
 };
@@ -102,6 +104,7 @@ use proptest_derive::Arbitrary;
 use rayon::prelude::*;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
+    cmp::Ordering,
     collections::{BTreeMap, HashMap},
     hash::Hash,
     marker::PhantomData,
@@ -299,6 +302,69 @@ impl<'a, K> std::iter::Iterator for NibbleRangeIterator<'a, K> {
     }
 }
 
+/// A key whose leaf differs between two versions of the tree, as returned by
+/// [`JellyfishMerkleTree::get_diff`]. At least one of the two leaves is present.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeafDiff<K> {
+    /// The leaf at the old version, `None` if the key was added.
+    pub old: Option<LeafNode<K>>,
+    /// The leaf at the new version, `None` if the key was deleted.
+    pub new: Option<LeafNode<K>>,
+}
+
+impl<K> LeafDiff<K>
+where
+    K: Key,
+{
+    /// The hashed key of the leaves.
+    pub fn key(&self) -> HashValue {
+        self.old
+            .as_ref()
+            .or(self.new.as_ref())
+            .expect("Either leaf must exist.")
+            .account_key()
+    }
+}
+
+/// Collects the leaf diffs found while walking two trees, in ascending order of their keys.
+struct LeafDiffCollector<K> {
+    start_key: Option<HashValue>,
+    limit: usize,
+    diffs: Vec<LeafDiff<K>>,
+}
+
+impl<K> LeafDiffCollector<K>
+where
+    K: Key,
+{
+    fn is_full(&self) -> bool {
+        self.diffs.len() >= self.limit
+    }
+
+    /// Whether the subtree at `nibble_path` can hold keys not less than the start key.
+    fn reaches(&self, nibble_path: &NibblePath) -> bool {
+        self.start_key.map_or(true, |start_key| {
+            let start_path = NibblePath::new_even(start_key.to_vec());
+            nibble_path
+                .nibbles()
+                .zip(start_path.nibbles())
+                .find(|(nibble, start_nibble)| nibble != start_nibble)
+                .map_or(true, |(nibble, start_nibble)| nibble > start_nibble)
+        })
+    }
+
+    fn push(&mut self, old: Option<LeafNode<K>>, new: Option<LeafNode<K>>) {
+        let diff = LeafDiff { old, new };
+        if !self.is_full()
+            && self
+                .start_key
+                .map_or(true, |start_key| diff.key() >= start_key)
+        {
+            self.diffs.push(diff);
+        }
+    }
+}
+
 /// The Jellyfish Merkle tree data structure. See [`crate`] for description.
 pub struct JellyfishMerkleTree<'a, R, K> {
     reader: &'a R,
@@ -786,6 +852,83 @@ where
         bail!("Jellyfish Merkle tree has cyclic graph inside.");
     }
 
+    /// Returns the values (if applicable) of several keys, given in strictly ascending order, and
+    /// a single merkle proof of all of them, in which siblings shared by the routes of the keys
+    /// are only included once.
+    pub fn get_with_multiproof(
+        &self,
+        keys: &[HashValue],
+        version: Version,
+    ) -> Result<(
+        Vec<Option<(HashValue, (K, Version))>>,
+        SparseMerkleMultiProof,
+    )> {
+        ensure!(!keys.is_empty(), "No key to prove.");
+        ensure!(
+            keys.windows(2).all(|pair| pair[0] < pair[1]),
+            "Keys to prove must be in strictly ascending order.",
+        );
+
+        let mut values = Vec::with_capacity(keys.len());
+        let mut proofs = Vec::with_capacity(keys.len());
+        for key in keys {
+            let (value, proof) = self.get_with_proof(*key, version)?;
+            values.push(value);
+            proofs.push(proof);
+        }
+
+        let mut siblings = vec![];
+        Self::collect_multiproof_siblings(keys, &proofs, 0, &mut siblings);
+        let multiproof = SparseMerkleMultiProof::new(
+            proofs.iter().map(|proof| proof.leaf()).collect(),
+            proofs
+                .iter()
+                .map(|proof| proof.siblings().len() as u16)
+                .collect(),
+            siblings,
+        );
+        Ok((values, multiproof))
+    }
+
+    /// Collects the siblings below `depth` of the routes of `keys` which are not on the route of
+    /// another of these keys, in the order `SparseMerkleMultiProof` verification expects them.
+    fn collect_multiproof_siblings(
+        keys: &[HashValue],
+        proofs: &[SparseMerkleProof],
+        depth: usize,
+        siblings: &mut Vec<HashValue>,
+    ) {
+        // The keys share the first `depth` bits, so their routes all end here or all go further.
+        if proofs[0].siblings().len() == depth {
+            return;
+        }
+
+        // Siblings are ordered from the bottom level to the root level in a single key proof.
+        let sibling_at_depth =
+            |proof: &SparseMerkleProof| proof.siblings()[proof.siblings().len() - 1 - depth];
+        let split = keys.partition_point(|key| !key.bit(depth));
+        if split == 0 {
+            siblings.push(sibling_at_depth(&proofs[0]));
+        } else {
+            Self::collect_multiproof_siblings(
+                &keys[..split],
+                &proofs[..split],
+                depth + 1,
+                siblings,
+            );
+        }
+        if split == keys.len() {
+            siblings.push(sibling_at_depth(&proofs[0]));
+        } else {
+            Self::collect_multiproof_siblings(
+                &keys[split..],
+                &proofs[split..],
+                depth + 1,
+                siblings,
+            );
+        }
+    }
+
     /// Gets the proof that shows a list of keys up to `rightmost_key_to_prove` exist at `version`.
     pub fn get_range_proof(
         &self,
@@ -866,6 +1009,192 @@ where
         out_keys.push(key);
         Ok(())
     }
+
+    /// Returns the keys whose leaves differ between the trees at `old_version` and
+    /// `new_version`, in ascending order, starting from `start_key` (inclusive) if given and up
+    /// to `limit` of them. Subtrees with the same hash in both trees are not walked.
+    pub fn get_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+        start_key: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Vec<LeafDiff<K>>> {
+        let mut collector = LeafDiffCollector {
+            start_key,
+            limit,
+            diffs: vec![],
+        };
+        let old_root_key = NodeKey::new_empty_path(old_version);
+        let old_root = self.get_root_node(old_version)?;
+        let new_root_key = NodeKey::new_empty_path(new_version);
+        let new_root = self.get_root_node(new_version)?;
+        if old_root.hash() != new_root.hash() {
+            self.diff_nodes(
+                (old_root_key, old_root),
+                (new_root_key, new_root),
+                &mut collector,
+            )?;
+        }
+        Ok(collector.diffs)
+    }
+
+    /// Walks two subtrees at the same position but with different hashes. A missing subtree is
+    /// represented by `Node::Null`.
+    fn diff_nodes(
+        &self,
+        (old_key, old_node): (NodeKey, Node<K>),
+        (new_key, new_node): (NodeKey, Node<K>),
+        collector: &mut LeafDiffCollector<K>,
+    ) -> Result<()> {
+        match (old_node, new_node) {
+            (Node::Internal(old_internal), Node::Internal(new_internal)) => {
+                for n in 0..16 {
+                    if collector.is_full() {
+                        break;
+                    }
+                    let nibble = Nibble::from(n);
+                    let (old_child, new_child) =
+                        (old_internal.child(nibble), new_internal.child(nibble));
+                    match (old_child, new_child) {
+                        (None, None) => continue,
+                        (Some(old_child), Some(new_child)) if old_child.hash == new_child.hash => {
+                            continue
+                        },
+                        _ => {},
+                    }
+                    let mut child_nibble_path = old_key.nibble_path().clone();
+                    child_nibble_path.push(nibble);
+                    if !collector.reaches(&child_nibble_path) {
+                        continue;
+                    }
+                    let old = self.get_child_or_null(&old_key, nibble, old_child)?;
+                    let new = self.get_child_or_null(&new_key, nibble, new_child)?;
+                    self.diff_nodes(old, new, collector)?;
+                }
+            },
+            // At least one side holds at most a single leaf from here on.
+            (Node::Internal(old_internal), new_node) => {
+                let mut new_leaf = Self::into_leaf(new_node);
+                self.diff_subtree_against_leaf(
+                    &old_key,
+                    Node::Internal(old_internal),
+                    &mut new_leaf,
+                    true,
+                    collector,
+                )?;
+                if new_leaf.is_some() {
+                    collector.push(None, new_leaf);
+                }
+            },
+            (old_node, new_node) => {
+                let mut old_leaf = Self::into_leaf(old_node);
+                self.diff_subtree_against_leaf(
+                    &new_key,
+                    new_node,
+                    &mut old_leaf,
+                    false,
+                    collector,
+                )?;
+                if old_leaf.is_some() {
+                    collector.push(old_leaf, None);
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Walks the subtree at `node_key`, which is in the old tree if `subtree_is_old` and in the
+    /// new one otherwise, against `leaf`, the only leaf (if any) at the same position in the
+    /// other tree. `leaf` is taken once it has been compared, otherwise it is left to the caller.
+    fn diff_subtree_against_leaf(
+        &self,
+        node_key: &NodeKey,
+        node: Node<K>,
+        leaf: &mut Option<LeafNode<K>>,
+        subtree_is_old: bool,
+        collector: &mut LeafDiffCollector<K>,
+    ) -> Result<()> {
+        match node {
+            Node::Internal(internal_node) => {
+                for (nibble, child) in internal_node.children_sorted() {
+                    if collector.is_full() {
+                        break;
+                    }
+                    let child_key = node_key.gen_child_node_key(child.version, *nibble);
+                    if !collector.reaches(child_key.nibble_path()) {
+                        continue;
+                    }
+                    let child_node = self.reader.get_node(&child_key)?;
+                    self.diff_subtree_against_leaf(
+                        &child_key,
+                        child_node,
+                        leaf,
+                        subtree_is_old,
+                        collector,
+                    )?;
+                }
+            },
+            Node::Leaf(subtree_leaf) => {
+                let other_leaf = match leaf
+                    .as_ref()
+                    .map(|other| other.account_key().cmp(&subtree_leaf.account_key()))
+                {
+                    Some(Ordering::Less) => {
+                        // The other leaf comes first and has no counterpart in the subtree.
+                        let other = leaf.take();
+                        if subtree_is_old {
+                            collector.push(None, other);
+                        } else {
+                            collector.push(other, None);
+                        }
+                        None
+                    },
+                    Some(Ordering::Equal) => leaf.take(),
+                    Some(Ordering::Greater) | None => None,
+                };
+                if other_leaf.as_ref().map_or(false, |other| {
+                    other.value_hash() == subtree_leaf.value_hash()
+                }) {
+                    return Ok(());
+                }
+                if subtree_is_old {
+                    collector.push(Some(subtree_leaf), other_leaf);
+                } else {
+                    collector.push(other_leaf, Some(subtree_leaf));
+                }
+            },
+            Node::Null => {},
+        }
+        Ok(())
+    }
+
+    fn get_child_or_null(
+        &self,
+        node_key: &NodeKey,
+        nibble: Nibble,
+        child: Option<&Child>,
+    ) -> Result<(NodeKey, Node<K>)> {
+        Ok(match child {
+            Some(child) => {
+                let child_key = node_key.gen_child_node_key(child.version, nibble);
+                let child_node = self.reader.get_node(&child_key)?;
+                (child_key, child_node)
+            },
+            None => (
+                node_key.gen_child_node_key(node_key.version(), nibble),
+                Node::Null,
+            ),
+        })
+    }
+
+    fn into_leaf(node: Node<K>) -> Option<LeafNode<K>> {
+        match node {
+            Node::Leaf(leaf_node) => Some(leaf_node),
+            Node::Internal(_) => unreachable!("Expected a leaf or an empty subtree."),
+            Node::Null => None,
+        }
+    }
 }
 
 /// Get the node hash from the cache if cache is provided, otherwise (for test only) compute it.
@@ -20,7 +20,7 @@ use proptest::{
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     ops::Bound,
 };
 
@@ -137,6 +137,56 @@ pub fn test_get_with_proof<V: TestKey>(
     test_nonexistent_keys_impl(&tree, version, &nonexistent_keys);
 }
 
+pub fn test_get_with_multiproof<V: TestKey>(
+    (existent_kvs, nonexistent_keys): (HashMap<HashValue, (HashValue, V)>, Vec<HashValue>),
+) {
+    let (db, version) = init_mock_db(&existent_kvs);
+    let tree = JellyfishMerkleTree::new(&db);
+    let root_hash = tree.get_root_hash(version).unwrap();
+
+    let keys: Vec<_> = existent_kvs
+        .keys()
+        .chain(nonexistent_keys.iter())
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let (values, proof) = tree.get_with_multiproof(&keys, version).unwrap();
+    let elements: Vec<_> = keys
+        .iter()
+        .zip(values.iter())
+        .map(|(key, value)| (*key, value.as_ref().map(|v| v.0)))
+        .collect();
+    assert!(proof.verify_by_hash(root_hash, &elements).is_ok());
+    for (key, value) in keys.iter().zip(values) {
+        match existent_kvs.get(key) {
+            Some(expected) => {
+                let value = value.unwrap();
+                assert_eq!((value.0, value.1 .0), *expected);
+            },
+            None => assert!(value.is_none()),
+        }
+    }
+
+    // Siblings shared by several keys are only included once.
+    let num_single_proof_siblings: usize = keys
+        .iter()
+        .map(|key| {
+            tree.get_with_proof(*key, version)
+                .unwrap()
+                .1
+                .siblings()
+                .len()
+        })
+        .sum();
+    assert!(proof.siblings().len() <= num_single_proof_siblings);
+
+    // The proof doesn't hold for another value.
+    let mut tampered_elements = elements;
+    tampered_elements[0].1 = Some(HashValue::random());
+    assert!(proof.verify_by_hash(root_hash, &tampered_elements).is_err());
+}
+
 pub fn arb_kv_pair_with_distinct_last_nibble<V: TestKey>(
 ) -> impl Strategy<Value = ((HashValue, (HashValue, V)), (HashValue, (HashValue, V)))> {
     (
@@ -17,11 +17,13 @@ use aptos_types::{
     move_resource::MoveStorage,
     on_chain_config::{access_path_for_config, ConfigID},
     proof::{
-        AccumulatorConsistencyProof, SparseMerkleProof, SparseMerkleProofExt,
-        SparseMerkleRangeProof, TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
+        AccumulatorConsistencyProof, SparseMerkleMultiProof, SparseMerkleProof,
+        SparseMerkleProofExt, SparseMerkleRangeProof, TransactionAccumulatorRangeProof,
+        TransactionAccumulatorSummary,
     },
     state_proof::StateProof,
     state_store::{
+        state_diff::StateKeyDiff,
         state_key::StateKey,
         state_key_prefix::StateKeyPrefix,
         state_storage_usage::StateStorageUsage,
@@ -35,8 +37,9 @@ use aptos_types::{
     },
     write_set::WriteSet,
 };
+use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 use thiserror::Error;
 
 pub mod async_proof_fetcher;
@@ -71,6 +74,18 @@ pub enum Error {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error(
+        "{} at version {} is pruned, min available version is {}.",
+        data_type,
+        version,
+        min_readable_version
+    )]
+    Pruned {
+        data_type: String,
+        version: Version,
+        min_readable_version: Version,
+    },
 }
 
 impl From<anyhow::Error> for Error {
@@ -293,6 +308,17 @@ pub trait DbReader: Send + Sync {
             next_version: Version,
         ) -> Result<Option<(Version, HashValue)>>;
 
+        /// Returns whether the state Merkle tree at `version` has been pruned.
+        fn is_state_merkle_pruned(&self, version: Version) -> Result<bool>;
+
+        /// Returns whether the state under `address` at `version` is kept beyond the prune window,
+        /// as the account is retained by the storage pruners.
+        fn is_account_state_retained(
+            &self,
+            address: AccountAddress,
+            version: Version,
+        ) -> Result<bool>;
+
         /// Returns a transaction that is the `seq_num`-th one associated with the given account. If
         /// the transaction with given `seq_num` doesn't exist, returns `None`.
         fn get_account_transaction(
@@ -371,6 +397,28 @@ pub trait DbReader: Send + Sync {
             version: Version,
         ) -> Result<(Option<StateValue>, SparseMerkleProofExt)>;
 
+        /// Gets the state values of several state keys, with a single proof of all of them
+        /// against the state Merkle tree root at `version`, which must be a state snapshot
+        /// version. The keys are returned with their values in ascending order of their hashes,
+        /// which is the order in which the proof expects them, without duplicates.
+        fn get_state_values_with_multiproof_by_version(
+            &self,
+            state_keys: &[StateKey],
+            version: Version,
+        ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)>;
+
+        /// Gets the state keys whose values differ between the state Merkle trees at
+        /// `old_version` and `new_version`, which must both be state snapshot versions. The keys
+        /// are returned in ascending order of their hashes, starting from `start` (inclusive) if
+        /// given and up to `limit` of them, along with the key to start the next page from.
+        fn get_state_diff(
+            &self,
+            old_version: Version,
+            new_version: Version,
+            start: Option<&StateKey>,
+            limit: usize,
+        ) -> Result<(Vec<StateKeyDiff>, Option<StateKey>)>;
+
         /// Gets the latest ExecutedTrees no matter if db has been bootstrapped.
         /// Used by the Db-bootstrapper.
         fn get_latest_executed_trees(&self) -> Result<ExecutedTrees>;
@@ -443,6 +491,17 @@ pub trait DbReader: Send + Sync {
         /// Get table info from the internal indexer.
         fn get_table_info(&self, handle: TableHandle) -> Result<TableInfo>;
 
+        /// Returns up to `limit` events of the given type from the internal indexer, starting at
+        /// the event at position `start`, given as a (transaction version, event index) pair.
+        /// Also returns the position of the next event of that type, if there is one.
+        fn get_events_by_type(
+            &self,
+            type_tag: &TypeTag,
+            start: (Version, u64),
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<(Vec<EventWithVersion>, Option<(Version, u64)>)>;
+
         /// Returns whether the internal indexer DB has been enabled or not
         fn indexer_enabled(&self) -> bool;
 
@@ -558,6 +617,17 @@ pub trait DbWriter: Send + Sync {
     ) -> Result<()> {
         unimplemented!()
     }
+
+    /// Creates a consistent checkpoint of all the sub databases in `cp_path` while the DB is
+    /// being written to, and returns the latest ledger info contained in the checkpoint.
+    /// `progress` is called with a message whenever a sub database is checkpointed.
+    fn create_online_checkpoint(
+        &self,
+        cp_path: &Path,
+        progress: &dyn Fn(&str),
+    ) -> Result<LedgerInfoWithSignatures> {
+        unimplemented!()
+    }
 }
 
 #[derive(Clone)]
@@ -1372,9 +1372,9 @@ fn individual_workload_tests(test_name: String) -> ForgeConfig {
                 };
                 job.transaction_mix_per_phase(vec![
                     // warmup
+                    vec![(account_creation_type.clone(), 1)],
                     vec![(account_creation_type, 1)],
-                    vec![(account_creation_type, 1)],
-                    vec![(write_type, 1)],
+                    vec![(write_type.clone(), 1)],
                     // cooldown
                     vec![(write_type, 1)],
                 ])
@@ -5,7 +5,7 @@ use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     hash::{CryptoHasher as _, TestOnlyHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
-    secp256k1_ecdsa,
+    p256_ecdsa, secp256k1_ecdsa,
     traits::{SigningKey, Uniform},
 };
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
@@ -59,6 +59,13 @@ fn trace_crypto_values(tracer: &mut Tracer, samples: &mut Samples) -> Result<()>
     tracer.trace_value(samples, &secp256k1_public_key)?;
     tracer.trace_value(samples, &secp256k1_signature)?;
 
+    let p256_private_key = p256_ecdsa::PrivateKey::generate(&mut rng);
+    let p256_public_key = aptos_crypto::PrivateKey::public_key(&p256_private_key);
+    let p256_signature = p256_private_key.sign(&message).unwrap();
+    tracer.trace_value(samples, &p256_private_key)?;
+    tracer.trace_value(samples, &p256_public_key)?;
+    tracer.trace_value(samples, &p256_signature)?;
+
     Ok(())
 }
 
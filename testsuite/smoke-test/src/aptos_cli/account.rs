@@ -5,6 +5,7 @@ use crate::smoke_test_environment::SwarmBuilder;
 use aptos::{account::create::DEFAULT_FUNDED_COINS, common::types::GasOptions};
 use aptos_crypto::{PrivateKey, ValidCryptoMaterialStringExt};
 use aptos_keygen::KeyGen;
+use aptos_temppath::TempPath;
 
 #[tokio::test]
 async fn test_account_flow() {
@@ -136,3 +137,56 @@ async fn test_account_key_rotation() {
         .await
         .expect("New key should be able to transfer");
 }
+
+#[tokio::test]
+async fn test_offline_signed_transfer() {
+    let (swarm, cli, _faucet) = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .build_with_cli(3)
+        .await;
+    let dir = TempPath::new();
+    dir.create_as_dir().unwrap();
+    let txn_file = dir.path().join("txn.json");
+    let sender_signature_file = dir.path().join("sender_signature.json");
+    let fee_payer_signature_file = dir.path().join("fee_payer_signature.json");
+
+    // Account 1 pays the gas of a transfer from account 0 to account 2
+    let transfer_amount = 100;
+    cli.build_transaction(
+        0,
+        cli.transfer_entry_function_args(2, transfer_amount),
+        None,
+        0,
+        swarm.chain_id(),
+        Some(1),
+        txn_file.clone(),
+    )
+    .await
+    .unwrap();
+    cli.sign_transaction(0, txn_file.clone(), sender_signature_file.clone())
+        .await
+        .unwrap();
+
+    // The transaction can't be submitted until the fee payer signed it
+    cli.submit_transaction(0, txn_file.clone(), vec![sender_signature_file.clone()])
+        .await
+        .unwrap_err();
+
+    cli.sign_transaction(1, txn_file.clone(), fee_payer_signature_file.clone())
+        .await
+        .unwrap();
+    let summary = cli
+        .submit_transaction(0, txn_file, vec![
+            fee_payer_signature_file,
+            sender_signature_file,
+        ])
+        .await
+        .unwrap();
+    assert_eq!(summary.success, Some(true));
+
+    cli.assert_account_balance_now(0, DEFAULT_FUNDED_COINS - transfer_amount)
+        .await;
+    cli.assert_account_balance_now(2, DEFAULT_FUNDED_COINS + transfer_amount)
+        .await;
+    assert!(cli.account_balance_now(1).await.unwrap() < DEFAULT_FUNDED_COINS);
+}
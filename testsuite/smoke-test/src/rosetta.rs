@@ -27,9 +27,11 @@ use aptos_rosetta::{
     common::{native_coin, BlockHash, BLOCKCHAIN, Y2K_MS},
     types::{
         AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockIdentifier,
-        BlockRequest, BlockResponse, NetworkIdentifier, NetworkRequest, Operation,
-        OperationStatusType, OperationType, PartialBlockIdentifier, TransactionIdentifier,
-        TransactionType, STAKING_CONTRACT_MODULE, SWITCH_OPERATOR_WITH_SAME_COMMISSION_FUNCTION,
+        BlockRequest, BlockResponse, MempoolRequest, MempoolTransactionRequest, NetworkIdentifier,
+        NetworkRequest, Operation, OperationStatusType, OperationType, PartialBlockIdentifier,
+        SearchOperator, SearchTransactionsRequest, SearchTransactionsResponse,
+        TransactionIdentifier, TransactionType, STAKING_CONTRACT_MODULE,
+        SWITCH_OPERATOR_WITH_SAME_COMMISSION_FUNCTION,
     },
     ROSETTA_VERSION,
 };
@@ -683,6 +685,138 @@ async fn test_transfer() {
     );
 }
 
+#[tokio::test]
+async fn test_search_and_mempool() {
+    let (mut swarm, cli, _faucet, rosetta_client) = setup_simple_test(2).await;
+    let chain_id = swarm.chain_id();
+    let client = swarm.aptos_public_info().client().clone();
+    let network = NetworkIdentifier::from(chain_id);
+    let node_clients = NodeClients {
+        rosetta_client: &rosetta_client,
+        rest_client: &client,
+        network: &network,
+    };
+    wait_for_rosetta_block(&node_clients, 2).await;
+
+    let sender = cli.account_id(0);
+    let receiver = cli.account_id(1);
+    let mut transaction_identifiers = vec![];
+    for _ in 0..3 {
+        let txn = simple_transfer_and_wait(
+            &node_clients,
+            cli.private_key(0),
+            receiver,
+            DEFAULT_TRANSFER_AMOUNT,
+        )
+        .await
+        .unwrap();
+        transaction_identifiers.push(TransactionIdentifier::from(HashValue::from(txn.info.hash)));
+    }
+    // Newest first
+    transaction_identifiers.reverse();
+
+    // Committed transactions aren't in mempool anymore
+    let mempool = rosetta_client
+        .mempool(&MempoolRequest {
+            network_identifier: network.clone(),
+        })
+        .await
+        .unwrap();
+    for transaction_identifier in &transaction_identifiers {
+        assert!(!mempool
+            .transaction_identifiers
+            .contains(transaction_identifier));
+    }
+    rosetta_client
+        .mempool_transaction(&MempoolTransactionRequest {
+            network_identifier: network.clone(),
+            transaction_identifier: transaction_identifiers[0].clone(),
+        })
+        .await
+        .expect_err("Committed transactions aren't in mempool");
+
+    let search = |request: SearchTransactionsRequest| {
+        let rosetta_client = &rosetta_client;
+        async move { rosetta_client.search_transactions(&request).await.unwrap() }
+    };
+    let hashes = |response: &SearchTransactionsResponse| -> Vec<TransactionIdentifier> {
+        response
+            .transactions
+            .iter()
+            .map(|txn| txn.transaction.transaction_identifier.clone())
+            .collect()
+    };
+
+    // The transfers are the newest transactions of the sender, which also received coins when
+    // its account was funded
+    let response = search(SearchTransactionsRequest {
+        account_identifier: Some(AccountIdentifier::base_account(sender)),
+        limit: Some(2),
+        ..search_request(&network)
+    })
+    .await;
+    assert_eq!(hashes(&response), transaction_identifiers[..2]);
+    assert_eq!(response.next_offset, Some(2));
+
+    let response = search(SearchTransactionsRequest {
+        account_identifier: Some(AccountIdentifier::base_account(sender)),
+        offset: Some(2),
+        limit: Some(1),
+        ..search_request(&network)
+    })
+    .await;
+    assert_eq!(hashes(&response), transaction_identifiers[2..]);
+    assert_eq!(response.next_offset, Some(3));
+
+    // Conditions are intersected by default
+    let response = search(SearchTransactionsRequest {
+        transaction_identifier: Some(transaction_identifiers[1].clone()),
+        address: Some(receiver.to_hex_literal()),
+        ..search_request(&network)
+    })
+    .await;
+    assert_eq!(hashes(&response), transaction_identifiers[1..2]);
+    assert_eq!(response.total_count, 1);
+    assert_eq!(response.next_offset, None);
+
+    // Or combined
+    let response = search(SearchTransactionsRequest {
+        operator: Some(SearchOperator::Or),
+        account_identifier: Some(AccountIdentifier::base_account(sender)),
+        address: Some(receiver.to_hex_literal()),
+        limit: Some(3),
+        ..search_request(&network)
+    })
+    .await;
+    assert_eq!(hashes(&response), transaction_identifiers);
+
+    // Unsupported conditions are rejected
+    rosetta_client
+        .search_transactions(&SearchTransactionsRequest {
+            success: Some(true),
+            ..search_request(&network)
+        })
+        .await
+        .expect_err("Searching by success isn't supported");
+}
+
+fn search_request(network: &NetworkIdentifier) -> SearchTransactionsRequest {
+    SearchTransactionsRequest {
+        network_identifier: network.clone(),
+        operator: None,
+        max_block: None,
+        offset: None,
+        limit: None,
+        transaction_identifier: None,
+        account_identifier: None,
+        address: None,
+        currency: None,
+        status: None,
+        operation_type: None,
+        success: None,
+    }
+}
+
 /// This test tests all of Rosetta's functionality from the read side in one go.  Since
 /// it's block based and it needs time to run, we do all the checks in a single test.
 #[tokio::test]
@@ -8,13 +8,15 @@ use crossbeam::channel::{bounded, select};
 use lsp_server::{Connection, Message, Notification, Request, Response};
 use lsp_types::{
     notification::Notification as _, request::Request as _, CompletionOptions, Diagnostic,
-    HoverProviderCapability, OneOf, SaveOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
-    TextDocumentSyncOptions, TypeDefinitionProviderCapability, WorkDoneProgressOptions,
+    HoverProviderCapability, OneOf, SaveOptions, SemanticTokensFullOptions, SemanticTokensOptions,
+    SemanticTokensServerCapabilities, SignatureHelpOptions, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, TypeDefinitionProviderCapability,
+    WorkDoneProgressOptions,
 };
 use move_analyzer::{
     completion::on_completion_request,
     context::Context,
-    symbols,
+    formatting, symbols,
     vfs::{on_text_document_sync_notification, VirtualFileSystem},
 };
 use move_symbol_pool::Symbol;
@@ -72,7 +74,7 @@ fn main() {
                 // data be sent "over the wire." However, to do so, our language server would need
                 // to be capable of applying deltas to its view of the client's open files. See the
                 // 'move_analyzer::vfs' module for details.
-                change: Some(TextDocumentSyncKind::Full),
+                change: Some(TextDocumentSyncKind::FULL),
                 will_save: None,
                 will_save_wait_until: None,
                 save: Some(
@@ -99,6 +101,7 @@ fn main() {
             work_done_progress_options: WorkDoneProgressOptions {
                 work_done_progress: None,
             },
+            completion_item: None,
         }),
         definition_provider: Some(OneOf::Left(symbols::DEFS_AND_REFS_SUPPORT)),
         type_definition_provider: Some(TypeDefinitionProviderCapability::Simple(
@@ -106,6 +109,29 @@ fn main() {
         )),
         references_provider: Some(OneOf::Left(symbols::DEFS_AND_REFS_SUPPORT)),
         document_symbol_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Left(symbols::DEFS_AND_REFS_SUPPORT)),
+        // The server shows the signature of the called function when a user types the opening
+        // parenthesis of a call or moves on to its next argument.
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                work_done_progress_options: WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                legend: symbols::semantic_tokens_legend(),
+                range: None,
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+            },
+        )),
+        inlay_hint_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
         ..Default::default()
     })
     .expect("could not serialize server capabilities");
@@ -124,7 +150,9 @@ fn main() {
         // with diagnostics as they will be recomputed whenever the first source file is opened. The
         // main reason for this is to enable unit tests that rely on the symbolication information
         // to be available right after the client is initialized.
-        if let Some(uri) = initialize_params.root_uri {
+        #[allow(deprecated)]
+        let root_uri = initialize_params.root_uri;
+        if let Some(uri) = root_uri {
             if let Some(p) = symbols::SymbolicatorRunner::root_dir(&uri.to_file_path().unwrap()) {
                 // need to evaluate in a separate thread to allow for a larger stack size (needed on
                 // Windows)
@@ -175,7 +203,7 @@ fn main() {
                                 }
                             },
                             Err(err) => {
-                                let typ = lsp_types::MessageType::Error;
+                                let typ = lsp_types::MessageType::ERROR;
                                 let message = format!("{err}");
                                     // report missing manifest only once to avoid re-generating
                                     // user-visible error in cases when the developer decides to
@@ -240,6 +268,28 @@ fn on_request(context: &Context, request: &Request) {
         lsp_types::request::DocumentSymbolRequest::METHOD => {
             symbols::on_document_symbol_request(context, request, &context.symbols.lock().unwrap());
         },
+        lsp_types::request::Rename::METHOD => {
+            symbols::on_rename_request(context, request, &context.symbols.lock().unwrap());
+        },
+        lsp_types::request::SignatureHelpRequest::METHOD => {
+            symbols::on_signature_help_request(context, request, &context.symbols.lock().unwrap());
+        },
+        lsp_types::request::SemanticTokensFullRequest::METHOD => {
+            symbols::on_semantic_tokens_request(context, request, &context.symbols.lock().unwrap());
+        },
+        lsp_types::request::InlayHintRequest::METHOD => {
+            symbols::on_inlay_hint_request(context, request, &context.symbols.lock().unwrap());
+        },
+        lsp_types::request::WorkspaceSymbolRequest::METHOD => {
+            symbols::on_workspace_symbol_request(
+                context,
+                request,
+                &context.symbols.lock().unwrap(),
+            );
+        },
+        lsp_types::request::Formatting::METHOD => {
+            formatting::on_formatting_request(context, request);
+        },
         _ => eprintln!("handle request '{}' from client", request.method),
     }
 }
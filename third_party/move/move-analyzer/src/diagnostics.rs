@@ -91,10 +91,10 @@ pub fn lsp_empty_diagnostics(
 /// language server.
 fn severity(s: Severity) -> DiagnosticSeverity {
     match s {
-        Severity::Bug => DiagnosticSeverity::Error,
-        Severity::Error => DiagnosticSeverity::Error,
-        Severity::Warning => DiagnosticSeverity::Warning,
-        Severity::Note => DiagnosticSeverity::Information,
-        Severity::Help => DiagnosticSeverity::Hint,
+        Severity::Bug => DiagnosticSeverity::ERROR,
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Note => DiagnosticSeverity::INFORMATION,
+        Severity::Help => DiagnosticSeverity::HINT,
     }
 }
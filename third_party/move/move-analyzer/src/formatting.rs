@@ -0,0 +1,245 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    context::Context,
+    utils::{file_buffer, file_path, request_params, send_response},
+};
+use anyhow::{anyhow, Result};
+use lsp_server::Request;
+use lsp_types::{DocumentFormattingParams, Position, Range, TextEdit};
+use move_command_line_common::files::FileHash;
+use move_compiler::parser::lexer::{Lexer, Tok};
+
+/// Formats a Move source file. Each line is indented by one level per line with brackets left
+/// open before it (so that brackets opened on the same line only indent once), trailing
+/// whitespace is removed, and the file ends with a single newline. Lines starting within a
+/// comment or within a token spanning several lines are left as is.
+pub fn format(buffer: &str, indent_unit: &str) -> Result<String> {
+    // (start, end, token) of all tokens of the file
+    let mut tokens = vec![];
+    let mut lexer = Lexer::new(buffer, FileHash::new(buffer));
+    loop {
+        lexer
+            .advance()
+            .map_err(|_| anyhow!("The file could not be formatted as it can't be tokenized"))?;
+        if lexer.peek() == Tok::EOF {
+            break;
+        }
+        let start = lexer.start_loc();
+        tokens.push((start, start + lexer.content().len(), lexer.peek()));
+    }
+    Ok(format_tokens(buffer, &tokens, indent_unit))
+}
+
+/// Formats a file given its tokens (see `format`)
+fn format_tokens(buffer: &str, tokens: &[(usize, usize, Tok)], indent_unit: &str) -> String {
+    let newline = if buffer.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let verbatim_regions = verbatim_regions(buffer, tokens);
+
+    let mut lines = vec![];
+    let mut line_start = 0;
+    let mut tokens = tokens.iter().peekable();
+    // lines where the brackets that are still open were opened
+    let mut open_brackets: Vec<usize> = vec![];
+    for (line_number, line) in buffer.split('\n').enumerate() {
+        let line_end = line_start + line.len();
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        // brackets closed at the start of the line are indented like the line opening them
+        while tokens
+            .next_if(|(start, _, tok)| {
+                *start < line_end && matches!(tok, Tok::RBrace | Tok::RParen | Tok::RBracket)
+            })
+            .is_some()
+        {
+            open_brackets.pop();
+        }
+        let mut depth = 0;
+        let mut prev_line = None;
+        for open_line in &open_brackets {
+            if prev_line != Some(*open_line) {
+                depth += 1;
+                prev_line = Some(*open_line);
+            }
+        }
+
+        if verbatim_regions
+            .iter()
+            .any(|(start, end)| *start < line_start && line_start < *end)
+        {
+            lines.push(line.to_string());
+        } else if line.trim().is_empty() {
+            lines.push(String::new());
+        } else {
+            lines.push(format!("{}{}", indent_unit.repeat(depth), line.trim()));
+        }
+
+        while let Some((_, _, tok)) = tokens.next_if(|(start, _, _)| *start < line_end) {
+            match tok {
+                Tok::LBrace | Tok::LParen | Tok::LBracket => open_brackets.push(line_number),
+                Tok::RBrace | Tok::RParen | Tok::RBracket => {
+                    open_brackets.pop();
+                },
+                _ => (),
+            }
+        }
+        line_start = line_end + 1;
+    }
+
+    while lines.last().map_or(false, |line| line.is_empty()) {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut formatted = lines.join(newline);
+    formatted.push_str(newline);
+    formatted
+}
+
+/// Returns the regions of a file (as byte offsets) covered by block comments or by tokens, in
+/// which lines are not reformatted.
+fn verbatim_regions(buffer: &str, tokens: &[(usize, usize, Tok)]) -> Vec<(usize, usize)> {
+    let mut regions: Vec<_> = tokens
+        .iter()
+        .map(|(start, end, _)| (*start, *end))
+        .collect();
+
+    // comments are in the gaps between tokens
+    let mut gaps = vec![];
+    let mut prev_end = 0;
+    for (start, end, _) in tokens {
+        gaps.push((prev_end, *start));
+        prev_end = *end;
+    }
+    gaps.push((prev_end, buffer.len()));
+    for (gap_start, gap_end) in gaps {
+        let gap = &buffer[gap_start..gap_end];
+        let mut pos = 0;
+        while pos < gap.len() {
+            let rest = &gap[pos..];
+            if rest.starts_with("//") {
+                pos += rest.find('\n').unwrap_or(rest.len());
+            } else if rest.starts_with("/*") {
+                // block comments can be nested
+                let mut depth = 0;
+                let mut end = pos;
+                while end < gap.len() {
+                    if gap[end..].starts_with("/*") {
+                        depth += 1;
+                        end += 2;
+                    } else if gap[end..].starts_with("*/") {
+                        depth -= 1;
+                        end += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        end += gap[end..].chars().next().map_or(1, char::len_utf8);
+                    }
+                }
+                regions.push((gap_start + pos, gap_start + end));
+                pos = end;
+            } else {
+                pos += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    regions
+}
+
+/// Returns the position of the end of a file
+fn end_position(buffer: &str) -> Position {
+    let last_line = buffer.rsplit('\n').next().unwrap_or("");
+    Position {
+        line: buffer.matches('\n').count() as u32,
+        // positions are in UTF-16 code units
+        character: last_line.encode_utf16().count() as u32,
+    }
+}
+
+/// Handles formatting request of the language server
+pub fn on_formatting_request(context: &Context, request: &Request) {
+    let result = request_params::<DocumentFormattingParams>(request).and_then(|parameters| {
+        let fpath = file_path(&parameters.text_document.uri)?;
+        let buffer = file_buffer(context, &fpath)
+            .ok_or_else(|| anyhow!("Could not read '{}'", fpath.display()))?;
+        let indent_unit = if parameters.options.insert_spaces {
+            " ".repeat(parameters.options.tab_size as usize)
+        } else {
+            "\t".to_string()
+        };
+        let formatted = format(&buffer, &indent_unit)?;
+        let edits = if formatted == buffer {
+            vec![]
+        } else {
+            vec![TextEdit::new(
+                Range {
+                    start: Position {
+                        line: 0,
+                        character: 0,
+                    },
+                    end: end_position(&buffer),
+                },
+                formatted,
+            )]
+        };
+        Ok(serde_json::to_value(edits)?)
+    });
+    send_response(context, request.id.clone(), result);
+}
+
+#[test]
+/// Tests indentation of nested blocks, multi-line calls, comments and closing brackets.
+fn format_test() {
+    let buffer = "module 0x1::M {  \n\
+                  \n\
+                  \n\
+                  /* a block\n\
+                  \x20  comment */\n\
+                  fun f(\n\
+                  a: u64, b: u64,\n\
+                  ): u64 {\n\
+                  \x20   if (a > b) { foo(bar(\n\
+                  a), b) } else {\n\
+                  // comment with a brace {\n\
+                  b\n\
+                  }\n\
+                  \x20 }\n\
+                  }\n\
+                  \n";
+    let expected = "module 0x1::M {\n\
+                    \n\
+                    \n\
+                    \x20   /* a block\n\
+                    \x20  comment */\n\
+                    \x20   fun f(\n\
+                    \x20       a: u64, b: u64,\n\
+                    \x20   ): u64 {\n\
+                    \x20       if (a > b) { foo(bar(\n\
+                    \x20           a), b) } else {\n\
+                    \x20           // comment with a brace {\n\
+                    \x20           b\n\
+                    \x20       }\n\
+                    \x20   }\n\
+                    }\n";
+    assert_eq!(format(buffer, "    ").unwrap(), expected);
+    // formatting is idempotent
+    assert_eq!(format(expected, "    ").unwrap(), expected);
+    // CRLF line endings are kept
+    assert_eq!(
+        format(&buffer.replace('\n', "\r\n"), "    ").unwrap(),
+        expected.replace('\n', "\r\n")
+    );
+
+    assert!(format("module 0x1::M { /* unterminated", "    ").is_err());
+    assert_eq!(end_position("a\nbc"), Position {
+        line: 1,
+        character: 2
+    });
+}
@@ -49,7 +49,7 @@
 use crate::{
     context::Context,
     diagnostics::{lsp_diagnostics, lsp_empty_diagnostics},
-    utils::get_loc,
+    utils::{file_buffer, file_path, get_loc, request_params, send_response},
 };
 use anyhow::{anyhow, Result};
 use codespan_reporting::files::SimpleFiles;
@@ -59,14 +59,22 @@ use im::ordmap::OrdMap;
 use lsp_server::{Request, RequestId};
 use lsp_types::{
     request::GotoTypeDefinitionParams, Diagnostic, DocumentSymbol, DocumentSymbolParams,
-    GotoDefinitionParams, Hover, HoverContents, HoverParams, LanguageString, Location,
-    MarkedString, Position, Range, ReferenceParams, SymbolKind,
+    Documentation, GotoDefinitionParams, Hover, HoverContents, HoverParams, InlayHint,
+    InlayHintKind, InlayHintLabel, InlayHintParams, LanguageString, Location, MarkedString,
+    ParameterInformation, ParameterLabel, Position, Range, ReferenceParams, RenameParams,
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensLegend,
+    SemanticTokensParams, SemanticTokensResult, SignatureHelp, SignatureHelpParams,
+    SignatureInformation, SymbolInformation, SymbolKind, TextEdit, WorkspaceEdit,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse,
 };
 use move_command_line_common::files::FileHash;
 use move_compiler::{
     expansion::ast::{Address, Fields, ModuleIdent, ModuleIdent_},
     naming::ast::{StructDefinition, StructFields, TParam, Type, TypeName_, Type_},
-    parser::ast::StructName,
+    parser::{
+        ast::StructName,
+        lexer::{Lexer, Tok},
+    },
     shared::Identifier,
     typing::ast::{
         BuiltinFunction_, Exp, ExpListItem, Function, FunctionBody_, LValue, LValueList, LValue_,
@@ -208,6 +216,8 @@ pub struct Symbolicator {
     file_id_to_lines: HashMap<usize, Vec<String>>,
     /// Contains type params where relevant (e.g. when processing function definition)
     type_params: BTreeMap<Symbol, DefLoc>,
+    /// Definitions of all type params (used to classify identifiers)
+    type_param_defs: BTreeSet<DefLoc>,
     /// Current processed module (always set before module processing starts)
     current_mod: Option<ModuleIdent>,
 }
@@ -231,6 +241,10 @@ pub struct Symbols {
     file_name_mapping: BTreeMap<FileHash, Symbol>,
     /// A mapping from filePath to ModuleDefs
     file_mods: BTreeMap<PathBuf, BTreeSet<ModuleDefs>>,
+    /// Definitions of type params
+    type_param_defs: BTreeSet<DefLoc>,
+    /// Source files of the packages being worked on (as opposed to their dependencies)
+    workspace_files: BTreeSet<PathBuf>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -602,6 +616,8 @@ impl Symbols {
         self.file_use_defs.extend(other.file_use_defs);
         self.file_name_mapping.extend(other.file_name_mapping);
         self.file_mods.extend(other.file_mods);
+        self.type_param_defs.extend(other.type_param_defs);
+        self.workspace_files.extend(other.workspace_files);
     }
 
     pub fn file_mods(&self) -> &BTreeMap<PathBuf, BTreeSet<ModuleDefs>> {
@@ -645,6 +661,17 @@ impl Symbolicator {
             file_id_to_lines.insert(id, lines);
         }
 
+        // only the sources of the root package may be modified (e.g. renamed in)
+        let workspace_files = resolution_graph
+            .get_package(&resolution_graph.root_package.package.name)
+            .get_sources(&resolution_graph.build_options)?
+            .iter()
+            .map(|fname| {
+                dunce::canonicalize(fname.as_str())
+                    .unwrap_or_else(|_| PathBuf::from(fname.as_str()))
+            })
+            .collect();
+
         let build_plan = BuildPlan::create(resolution_graph)?;
         let mut typed_ast = None;
         let mut diagnostics = None;
@@ -745,6 +772,7 @@ impl Symbolicator {
             file_id_mapping,
             file_id_to_lines,
             type_params: BTreeMap::new(),
+            type_param_defs: BTreeSet::new(),
             current_mod: None,
         };
 
@@ -781,6 +809,8 @@ impl Symbolicator {
             file_use_defs,
             file_name_mapping,
             file_mods,
+            type_param_defs: symbolicator.type_param_defs,
+            workspace_files,
         };
 
         eprintln!("get_symbols load complete");
@@ -795,6 +825,8 @@ impl Symbolicator {
             references: BTreeMap::new(),
             file_name_mapping: BTreeMap::new(),
             file_mods: BTreeMap::new(),
+            type_param_defs: BTreeSet::new(),
+            workspace_files: BTreeSet::new(),
         }
     }
 
@@ -1642,6 +1674,7 @@ impl Symbolicator {
                 );
                 let exists = tp_scope.insert(tname, DefLoc { fhash, start });
                 debug_assert!(exists.is_none());
+                self.type_param_defs.insert(DefLoc { fhash, start });
             },
             None => {
                 debug_assert!(false);
@@ -2265,7 +2298,7 @@ pub fn on_document_symbol_request(context: &Context, request: &Request, symbols:
     for mod_def in mods {
         let name = mod_def.name.module.clone().to_string();
         let detail = Some(mod_def.name.clone().to_string());
-        let kind = SymbolKind::Module;
+        let kind = SymbolKind::MODULE;
         let range = Range {
             start: mod_def.start,
             end: mod_def.start,
@@ -2284,7 +2317,7 @@ pub fn on_document_symbol_request(context: &Context, request: &Request, symbols:
             children.push(DocumentSymbol {
                 name: sym.clone().to_string(),
                 detail: None,
-                kind: SymbolKind::Constant,
+                kind: SymbolKind::CONSTANT,
                 range: const_range,
                 selection_range: const_range,
                 children: None,
@@ -2307,7 +2340,7 @@ pub fn on_document_symbol_request(context: &Context, request: &Request, symbols:
             children.push(DocumentSymbol {
                 name: sym.clone().to_string(),
                 detail: None,
-                kind: SymbolKind::Struct,
+                kind: SymbolKind::STRUCT,
                 range: struct_range,
                 selection_range: struct_range,
                 children: Some(fields),
@@ -2332,7 +2365,7 @@ pub fn on_document_symbol_request(context: &Context, request: &Request, symbols:
             children.push(DocumentSymbol {
                 name: sym.clone().to_string(),
                 detail,
-                kind: SymbolKind::Function,
+                kind: SymbolKind::FUNCTION,
                 range: func_range,
                 selection_range: func_range,
                 children: None,
@@ -2378,7 +2411,7 @@ fn handle_struct_fields(struct_def: StructDef, fields: &mut Vec<DocumentSymbol>)
         fields.push(DocumentSymbol {
             name: field_def.name.clone().to_string(),
             detail: None,
-            kind: SymbolKind::Field,
+            kind: SymbolKind::FIELD,
             range: field_range,
             selection_range: field_range,
             children: None,
@@ -2388,6 +2421,566 @@ fn handle_struct_fields(struct_def: StructDef, fields: &mut Vec<DocumentSymbol>)
     }
 }
 
+/// Kind of a definition, used to classify identifiers and to validate new names on rename
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DefKind {
+    Struct,
+    Field,
+    Function,
+    Constant,
+    TypeParam,
+    /// Local variable or function parameter
+    Local,
+}
+
+/// Token types of semantic tokens, in the order of the legend
+const TOKEN_TYPE_STRUCT: u32 = 0;
+const TOKEN_TYPE_PROPERTY: u32 = 1;
+const TOKEN_TYPE_FUNCTION: u32 = 2;
+const TOKEN_TYPE_VARIABLE: u32 = 3;
+const TOKEN_TYPE_TYPE_PARAMETER: u32 = 4;
+/// Bits of token modifiers of semantic tokens, in the order of the legend
+const TOKEN_MODIFIER_DECLARATION: u32 = 1 << 0;
+const TOKEN_MODIFIER_READONLY: u32 = 1 << 1;
+
+/// Legend of the semantic tokens provided by the language server
+pub fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![
+            SemanticTokenType::STRUCT,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::FUNCTION,
+            SemanticTokenType::VARIABLE,
+            SemanticTokenType::TYPE_PARAMETER,
+        ],
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::READONLY,
+        ],
+    }
+}
+
+impl Symbols {
+    /// Returns the use of an identifier at the given position in a file
+    fn use_def_at(&self, fpath: &Path, line: u32, col: u32) -> Option<UseDef> {
+        self.file_use_defs
+            .get(fpath)?
+            .get(line)?
+            .into_iter()
+            .filter(|u| col >= u.col_start && col <= u.col_end)
+            .last()
+    }
+
+    /// Returns the hash of a file, if it contains modules
+    fn file_hash(&self, fpath: &Path) -> Option<FileHash> {
+        self.file_mods
+            .get(fpath)
+            .and_then(|mods| mods.iter().next())
+            .map(|mod_defs| mod_defs.fhash)
+    }
+
+    /// Returns the name of a file given its hash
+    fn file_name(&self, fhash: &FileHash) -> Result<Symbol> {
+        self.file_name_mapping
+            .get(fhash)
+            .copied()
+            .ok_or_else(|| anyhow!("Unknown file of hash {}", fhash))
+    }
+
+    /// Returns the kinds of all the definitions at the module level and of type params (all
+    /// other definitions are local)
+    fn def_kinds(&self) -> BTreeMap<DefLoc, DefKind> {
+        let mut kinds = BTreeMap::new();
+        for mod_defs in self.file_mods.values().flatten() {
+            let fhash = mod_defs.fhash;
+            for struct_def in mod_defs.structs.values() {
+                kinds.insert(
+                    DefLoc {
+                        fhash,
+                        start: struct_def.name_start,
+                    },
+                    DefKind::Struct,
+                );
+                for field_def in &struct_def.field_defs {
+                    kinds.insert(
+                        DefLoc {
+                            fhash,
+                            start: field_def.start,
+                        },
+                        DefKind::Field,
+                    );
+                }
+            }
+            for fun_def in mod_defs.functions.values() {
+                kinds.insert(
+                    DefLoc {
+                        fhash,
+                        start: fun_def.start,
+                    },
+                    DefKind::Function,
+                );
+            }
+            for start in mod_defs.constants.values() {
+                kinds.insert(
+                    DefLoc {
+                        fhash,
+                        start: *start,
+                    },
+                    DefKind::Constant,
+                );
+            }
+        }
+        for def_loc in &self.type_param_defs {
+            kinds.insert(*def_loc, DefKind::TypeParam);
+        }
+        kinds
+    }
+
+    /// Computes the edits renaming the identifier at the given position, along with its
+    /// definition and all its other uses. Returns `None` if there is no identifier at the
+    /// position, and an error if the identifier is defined outside of the package being worked
+    /// on.
+    pub fn rename(
+        &self,
+        fpath: &Path,
+        position: Position,
+        new_name: &str,
+    ) -> Result<Option<WorkspaceEdit>> {
+        let use_def = match self.use_def_at(fpath, position.line, position.character) {
+            Some(use_def) => use_def,
+            None => return Ok(None),
+        };
+        let kind = self
+            .def_kinds()
+            .get(&use_def.def_loc)
+            .copied()
+            .unwrap_or(DefKind::Local);
+        check_new_name(kind, new_name)?;
+        let def_name = self.file_name(&use_def.def_loc.fhash)?;
+        let def_path = dunce::canonicalize(def_name.as_str())
+            .unwrap_or_else(|_| PathBuf::from(def_name.as_str()));
+        if !self.workspace_files.contains(&def_path) {
+            return Err(anyhow!(
+                "Cannot rename a definition outside of the package, in '{}'",
+                def_path.display()
+            ));
+        }
+
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+        for ref_loc in self.references.get(&use_def.def_loc).into_iter().flatten() {
+            let path = self.file_name(&ref_loc.fhash)?;
+            let uri = Url::from_file_path(path.as_str())
+                .map_err(|_| anyhow!("'{}' can't be converted to a URI", path))?;
+            let range = Range {
+                start: ref_loc.start,
+                end: Position {
+                    line: ref_loc.start.line,
+                    character: ref_loc.col_end,
+                },
+            };
+            changes
+                .entry(uri)
+                .or_default()
+                .push(TextEdit::new(range, new_name.to_string()));
+        }
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    /// Computes the signature help for a call enclosing the given position, where `buffer` is
+    /// the current content of the file.
+    pub fn signature_help(
+        &self,
+        fpath: &Path,
+        buffer: &str,
+        position: Position,
+    ) -> Option<SignatureHelp> {
+        let (name_start, active_parameter) = enclosing_call(buffer, position)?;
+        let use_def = self.use_def_at(fpath, name_start.line, name_start.character)?;
+        let (arg_names, arg_types) = match &use_def.use_type {
+            IdentType::FunctionType(_, _, _, arg_names, arg_types, _, _) => (arg_names, arg_types),
+            IdentType::RegularType(_) => return None,
+        };
+        let parameters = arg_names
+            .iter()
+            .zip(arg_types)
+            .map(|(name, typ)| ParameterInformation {
+                label: ParameterLabel::Simple(format!("{}: {}", name, type_to_ide_string(typ))),
+                documentation: None,
+            })
+            .collect();
+        let documentation = if use_def.doc_string.is_empty() {
+            None
+        } else {
+            Some(Documentation::String(use_def.doc_string.clone()))
+        };
+        Some(SignatureHelp {
+            signatures: vec![SignatureInformation {
+                label: format!("{}", use_def.use_type),
+                documentation,
+                parameters: Some(parameters),
+                active_parameter: None,
+            }],
+            active_signature: Some(0),
+            active_parameter: Some(active_parameter),
+        })
+    }
+
+    /// Computes the semantic tokens of all identifiers in a file
+    pub fn semantic_tokens(&self, fpath: &Path) -> Vec<SemanticToken> {
+        let use_defs = match self.file_use_defs.get(fpath) {
+            Some(use_defs) => use_defs,
+            None => return vec![],
+        };
+        let fhash = self.file_hash(fpath);
+        let kinds = self.def_kinds();
+
+        let mut tokens = vec![];
+        let mut prev = Position {
+            line: 0,
+            character: 0,
+        };
+        for (line, uses) in &use_defs.0 {
+            for u in uses {
+                let kind = match &u.use_type {
+                    IdentType::FunctionType(..) => DefKind::Function,
+                    IdentType::RegularType(_) => {
+                        kinds.get(&u.def_loc).copied().unwrap_or(DefKind::Local)
+                    },
+                };
+                let (token_type, mut token_modifiers_bitset) = match kind {
+                    DefKind::Struct => (TOKEN_TYPE_STRUCT, 0),
+                    DefKind::Field => (TOKEN_TYPE_PROPERTY, 0),
+                    DefKind::Function => (TOKEN_TYPE_FUNCTION, 0),
+                    DefKind::Constant => (TOKEN_TYPE_VARIABLE, TOKEN_MODIFIER_READONLY),
+                    DefKind::TypeParam => (TOKEN_TYPE_TYPE_PARAMETER, 0),
+                    DefKind::Local => (TOKEN_TYPE_VARIABLE, 0),
+                };
+                if Some(u.def_loc.fhash) == fhash
+                    && u.def_loc.start.line == *line
+                    && u.def_loc.start.character == u.col_start
+                {
+                    token_modifiers_bitset |= TOKEN_MODIFIER_DECLARATION;
+                }
+
+                // positions of tokens are relative to the previous token
+                let delta_line = line - prev.line;
+                let delta_start = if delta_line == 0 {
+                    u.col_start - prev.character
+                } else {
+                    u.col_start
+                };
+                tokens.push(SemanticToken {
+                    delta_line,
+                    delta_start,
+                    length: u.col_end - u.col_start,
+                    token_type,
+                    token_modifiers_bitset,
+                });
+                prev = Position {
+                    line: *line,
+                    character: u.col_start,
+                };
+            }
+        }
+        tokens
+    }
+
+    /// Computes inlay hints with the inferred types of local variables declared in `let`
+    /// bindings without a type annotation, within a range of a file whose current content is
+    /// `buffer`.
+    pub fn inlay_hints(&self, fpath: &Path, buffer: &str, range: Range) -> Vec<InlayHint> {
+        let (use_defs, fhash) = match (self.file_use_defs.get(fpath), self.file_hash(fpath)) {
+            (Some(use_defs), Some(fhash)) => (use_defs, fhash),
+            _ => return vec![],
+        };
+        let lines: Vec<&str> = buffer.lines().collect();
+
+        let mut hints = vec![];
+        for (line, uses) in use_defs
+            .0
+            .iter()
+            .filter(|(line, _)| range.start.line <= **line && **line <= range.end.line)
+        {
+            let text = match lines.get(*line as usize) {
+                Some(text) => text,
+                None => continue,
+            };
+            for u in uses {
+                let is_def = u.def_loc.fhash == fhash
+                    && u.def_loc.start.line == *line
+                    && u.def_loc.start.character == u.col_start;
+                let typ = match &u.use_type {
+                    IdentType::RegularType(typ) if is_def => typ,
+                    _ => continue,
+                };
+                if matches!(
+                    typ.value,
+                    Type_::Anything | Type_::Var(_) | Type_::UnresolvedError
+                ) {
+                    continue;
+                }
+                let before: String = text.chars().take(u.col_start as usize).collect();
+                let after: String = text.chars().skip(u.col_end as usize).collect();
+                if !is_let_binding(before.trim_end()) || after.trim_start().starts_with(':') {
+                    continue;
+                }
+                hints.push(InlayHint {
+                    position: Position {
+                        line: *line,
+                        character: u.col_end,
+                    },
+                    label: InlayHintLabel::String(format!(": {}", type_to_ide_string(typ))),
+                    kind: Some(InlayHintKind::TYPE),
+                    text_edits: None,
+                    tooltip: None,
+                    padding_left: None,
+                    padding_right: None,
+                    data: None,
+                });
+            }
+        }
+        hints
+    }
+
+    /// Finds the modules, structs, functions and constants whose name contains `query`
+    /// (ignoring case)
+    #[allow(deprecated)]
+    pub fn workspace_symbols(&self, query: &str) -> Vec<SymbolInformation> {
+        let query = query.to_lowercase();
+        let mut symbols = vec![];
+        let mut add_symbol =
+            |name: String, kind: SymbolKind, fpath: &Path, start: Position, container: &str| {
+                if !name.to_lowercase().contains(&query) {
+                    return;
+                }
+                let uri = match Url::from_file_path(fpath) {
+                    Ok(uri) => uri,
+                    Err(_) => return,
+                };
+                symbols.push(SymbolInformation {
+                    name,
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    location: Location {
+                        uri,
+                        range: Range { start, end: start },
+                    },
+                    container_name: Some(container.to_string()),
+                });
+            };
+
+        for (fpath, mods) in &self.file_mods {
+            for mod_def in mods {
+                let mod_name = format!(
+                    "{}::{}",
+                    addr_to_ide_string(&mod_def.name.address),
+                    mod_def.name.module
+                );
+                add_symbol(
+                    mod_def.name.module.to_string(),
+                    SymbolKind::MODULE,
+                    fpath,
+                    mod_def.start,
+                    &addr_to_ide_string(&mod_def.name.address),
+                );
+                for (name, struct_def) in &mod_def.structs {
+                    add_symbol(
+                        name.to_string(),
+                        SymbolKind::STRUCT,
+                        fpath,
+                        struct_def.name_start,
+                        &mod_name,
+                    );
+                }
+                for (name, fun_def) in &mod_def.functions {
+                    add_symbol(
+                        name.to_string(),
+                        SymbolKind::FUNCTION,
+                        fpath,
+                        fun_def.start,
+                        &mod_name,
+                    );
+                }
+                for (name, start) in &mod_def.constants {
+                    add_symbol(
+                        name.to_string(),
+                        SymbolKind::CONSTANT,
+                        fpath,
+                        *start,
+                        &mod_name,
+                    );
+                }
+            }
+        }
+        symbols
+    }
+}
+
+/// Checks that a new name is a valid identifier for a definition of the given kind
+fn check_new_name(kind: DefKind, new_name: &str) -> Result<()> {
+    let mut lexer = Lexer::new(new_name, FileHash::new(new_name));
+    let is_identifier = lexer.advance().is_ok()
+        && lexer.peek() == Tok::Identifier
+        && lexer.content() == new_name
+        && lexer.advance().is_ok()
+        && lexer.peek() == Tok::EOF;
+    if !is_identifier {
+        return Err(anyhow!("'{}' is not a valid identifier", new_name));
+    }
+    let first = new_name.chars().next().unwrap();
+    match kind {
+        DefKind::Struct | DefKind::Constant if !first.is_ascii_uppercase() => Err(anyhow!(
+            "Names of structs and constants must start with 'A'..'Z'"
+        )),
+        DefKind::Local if !(first.is_ascii_lowercase() || first == '_') => Err(anyhow!(
+            "Names of local variables must start with 'a'..'z' or '_'"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Finds the call whose arguments enclose the given position, and returns the position where
+/// the name of the called function starts along with the index of the argument at the position
+fn enclosing_call(buffer: &str, position: Position) -> Option<(Position, u32)> {
+    // characters preceding the position, along with their positions
+    let mut chars = vec![];
+    for (line, text) in buffer.lines().enumerate().take(position.line as usize + 1) {
+        let len = if line == position.line as usize {
+            position.character as usize
+        } else {
+            usize::MAX
+        };
+        chars.extend(
+            text.chars()
+                .take(len)
+                .enumerate()
+                .map(|(col, c)| (line as u32, col as u32, c)),
+        );
+    }
+
+    // find the opening parenthesis of the call
+    let mut depth = 0;
+    let mut active_parameter = 0;
+    let mut open_paren = None;
+    for (idx, (_, _, c)) in chars.iter().enumerate().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' => {
+                open_paren = Some(idx);
+                break;
+            },
+            '[' | '{' | ';' if depth == 0 => return None,
+            ',' if depth == 0 => active_parameter += 1,
+            _ => (),
+        }
+    }
+
+    // skip type arguments preceding the parenthesis and find the start of the function name
+    let mut rest = chars[..open_paren?]
+        .iter()
+        .rev()
+        .skip_while(|(_, _, c)| c.is_whitespace())
+        .peekable();
+    if let Some((_, _, '>')) = rest.peek() {
+        let mut depth = 0;
+        for (_, _, c) in rest.by_ref() {
+            match c {
+                '>' => depth += 1,
+                '<' => depth -= 1,
+                _ => (),
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+    let name_start = rest
+        .skip_while(|(_, _, c)| c.is_whitespace())
+        .take_while(|(_, _, c)| c.is_ascii_alphanumeric() || *c == '_')
+        .last()?;
+    Some((
+        Position {
+            line: name_start.0,
+            character: name_start.1,
+        },
+        active_parameter,
+    ))
+}
+
+/// Checks whether an identifier preceded by `before` (on the same line) is bound by `let`
+fn is_let_binding(before: &str) -> bool {
+    if let Some(prefix) = before.strip_suffix("let") {
+        return prefix.is_empty() || prefix.ends_with(|c: char| c.is_whitespace() || c == '{');
+    }
+    // a variable in a tuple binding, e.g. `let (a, b) = ...`
+    (before.ends_with('(') || before.ends_with(','))
+        && (before.contains("let (") || before.contains("let("))
+}
+
+/// Handles rename request of the language server
+pub fn on_rename_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let result = request_params::<RenameParams>(request).and_then(|parameters| {
+        let fpath = file_path(&parameters.text_document_position.text_document.uri)?;
+        let edit = symbols.rename(
+            &fpath,
+            parameters.text_document_position.position,
+            &parameters.new_name,
+        )?;
+        Ok(serde_json::to_value(edit)?)
+    });
+    send_response(context, request.id.clone(), result);
+}
+
+/// Handles signature help request of the language server
+pub fn on_signature_help_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let result = request_params::<SignatureHelpParams>(request).and_then(|parameters| {
+        let fpath = file_path(&parameters.text_document_position_params.text_document.uri)?;
+        let position = parameters.text_document_position_params.position;
+        let signature_help = file_buffer(context, &fpath)
+            .and_then(|buffer| symbols.signature_help(&fpath, &buffer, position));
+        Ok(serde_json::to_value(signature_help)?)
+    });
+    send_response(context, request.id.clone(), result);
+}
+
+/// Handles semantic tokens request of the language server
+pub fn on_semantic_tokens_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let result = request_params::<SemanticTokensParams>(request).and_then(|parameters| {
+        let fpath = file_path(&parameters.text_document.uri)?;
+        let tokens = SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data: symbols.semantic_tokens(&fpath),
+        });
+        Ok(serde_json::to_value(tokens)?)
+    });
+    send_response(context, request.id.clone(), result);
+}
+
+/// Handles inlay hint request of the language server
+pub fn on_inlay_hint_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let result = request_params::<InlayHintParams>(request).and_then(|parameters| {
+        let fpath = file_path(&parameters.text_document.uri)?;
+        let hints = file_buffer(context, &fpath)
+            .map(|buffer| symbols.inlay_hints(&fpath, &buffer, parameters.range))
+            .unwrap_or_default();
+        Ok(serde_json::to_value(hints)?)
+    });
+    send_response(context, request.id.clone(), result);
+}
+
+/// Handles workspace symbol request of the language server
+pub fn on_workspace_symbol_request(context: &Context, request: &Request, symbols: &Symbols) {
+    let result = request_params::<WorkspaceSymbolParams>(request).and_then(|parameters| {
+        let symbols = symbols.workspace_symbols(&parameters.query);
+        Ok(serde_json::to_value(WorkspaceSymbolResponse::Flat(
+            symbols,
+        ))?)
+    });
+    send_response(context, request.id.clone(), result);
+}
+
 #[cfg(test)]
 fn assert_use_def_with_doc_string(
     mod_symbols: &UseDefMap,
@@ -3677,3 +4270,341 @@ fn symbols_test() {
         None,
     );
 }
+
+#[cfg(test)]
+/// Builds symbols of the test package, and returns them along with the path of one of its files
+fn test_symbols(file_name: &str) -> (Symbols, PathBuf) {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push("tests/symbols");
+
+    let (symbols_opt, _) = Symbolicator::get_symbols(path.as_path()).unwrap();
+    let symbols = symbols_opt.unwrap();
+
+    path.push("sources");
+    path.push(file_name);
+    let cpath = dunce::canonicalize(&path).unwrap();
+    (symbols, cpath)
+}
+
+#[test]
+/// Tests that renaming an identifier renames its definition and all its uses, and that invalid
+/// names are rejected.
+fn rename_test() {
+    let (symbols, cpath) = test_symbols("M1.move");
+
+    // const name (used across functions)
+    let edit = symbols
+        .rename(
+            &cpath,
+            Position {
+                line: 40,
+                character: 24,
+            },
+            "OTHER_CONST",
+        )
+        .unwrap()
+        .unwrap();
+    let changes = edit.changes.unwrap();
+    assert_eq!(changes.len(), 1);
+    let edits = changes.values().next().unwrap();
+    assert_eq!(edits.len(), 10);
+    assert!(edits.iter().all(|e| e.new_text == "OTHER_CONST"));
+    assert!(edits.contains(&TextEdit::new(
+        Range {
+            start: Position {
+                line: 6,
+                character: 10
+            },
+            end: Position {
+                line: 6,
+                character: 20
+            },
+        },
+        "OTHER_CONST".to_string()
+    )));
+
+    // local variable (cp function)
+    let edit = symbols
+        .rename(
+            &cpath,
+            Position {
+                line: 16,
+                character: 8,
+            },
+            "copied",
+        )
+        .unwrap()
+        .unwrap();
+    let mut ranges: Vec<_> = edit
+        .changes
+        .unwrap()
+        .values()
+        .next()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e.range.start.line,
+                e.range.start.character,
+                e.range.end.character,
+            )
+        })
+        .collect();
+    ranges.sort();
+    assert_eq!(ranges, vec![(15, 12, 15), (16, 8, 11)]);
+
+    // function defined in another file
+    let edit = symbols
+        .rename(
+            &cpath,
+            Position {
+                line: 40,
+                character: 12,
+            },
+            "two_args",
+        )
+        .unwrap()
+        .unwrap();
+    let changes = edit.changes.unwrap();
+    assert_eq!(changes.len(), 2);
+    assert!(changes.values().flatten().all(|e| e.new_text == "two_args"));
+
+    // no identifier at the position
+    assert!(symbols
+        .rename(
+            &cpath,
+            Position {
+                line: 7,
+                character: 0,
+            },
+            "x"
+        )
+        .unwrap()
+        .is_none());
+
+    // invalid names
+    let const_pos = Position {
+        line: 6,
+        character: 10,
+    };
+    assert!(symbols.rename(&cpath, const_pos, "OTHER CONST").is_err());
+    assert!(symbols.rename(&cpath, const_pos, "1CONST").is_err());
+    assert!(symbols.rename(&cpath, const_pos, "other_const").is_err());
+    let var_pos = Position {
+        line: 15,
+        character: 12,
+    };
+    assert!(symbols.rename(&cpath, var_pos, "let").is_err());
+    assert!(symbols.rename(&cpath, var_pos, "Copied").is_err());
+
+    // local variable in a dependency (`len` in `vector::reverse`)
+    let mut dep_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    dep_path.push("../move-stdlib/sources/vector.move");
+    let dep_cpath = dunce::canonicalize(&dep_path).unwrap();
+    let dep_pos = Position {
+        line: 67,
+        character: 12,
+    };
+    assert!(symbols.rename(&dep_cpath, dep_pos, "size").is_err());
+}
+
+#[test]
+/// Tests signature help for calls, including the argument being typed.
+fn signature_help_test() {
+    let (symbols, cpath) = test_symbols("M1.move");
+    let buffer = std::fs::read_to_string(&cpath).unwrap();
+
+    let help =
+        |line, character| symbols.signature_help(&cpath, &buffer, Position { line, character });
+
+    // first argument (multi_arg_call function)
+    let signature_help = help(40, 22).unwrap();
+    assert_eq!(signature_help.active_signature, Some(0));
+    assert_eq!(signature_help.active_parameter, Some(0));
+    let signature = &signature_help.signatures[0];
+    assert_eq!(
+        signature.label,
+        "fun Symbols::M2::multi_arg(p1: u64, p2: u64): u64"
+    );
+    let parameters: Vec<_> = signature
+        .parameters
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|p| p.label.clone())
+        .collect();
+    assert_eq!(parameters, vec![
+        ParameterLabel::Simple("p1: u64".to_string()),
+        ParameterLabel::Simple("p2: u64".to_string())
+    ]);
+
+    // second argument (multi_arg_call function)
+    assert_eq!(help(40, 36).unwrap().active_parameter, Some(1));
+
+    // not within a call
+    assert!(help(15, 18).is_none());
+    assert!(help(44, 30).is_none());
+}
+
+#[test]
+/// Tests classification of identifiers into semantic tokens.
+fn semantic_tokens_test() {
+    let (symbols, cpath) = test_symbols("M1.move");
+
+    // decode positions of tokens
+    let mut tokens = BTreeMap::new();
+    let (mut line, mut col) = (0, 0);
+    for token in symbols.semantic_tokens(&cpath) {
+        if token.delta_line > 0 {
+            col = 0;
+        }
+        line += token.delta_line;
+        col += token.delta_start;
+        tokens.insert(
+            (line, col),
+            (token.length, token.token_type, token.token_modifiers_bitset),
+        );
+    }
+
+    // struct def
+    assert_eq!(
+        tokens[&(2, 11)],
+        (10, TOKEN_TYPE_STRUCT, TOKEN_MODIFIER_DECLARATION)
+    );
+    // field def
+    assert_eq!(
+        tokens[&(3, 8)],
+        (10, TOKEN_TYPE_PROPERTY, TOKEN_MODIFIER_DECLARATION)
+    );
+    // const def
+    assert_eq!(
+        tokens[&(6, 10)],
+        (
+            10,
+            TOKEN_TYPE_VARIABLE,
+            TOKEN_MODIFIER_DECLARATION | TOKEN_MODIFIER_READONLY
+        )
+    );
+    // function def
+    assert_eq!(
+        tokens[&(9, 8)],
+        (6, TOKEN_TYPE_FUNCTION, TOKEN_MODIFIER_DECLARATION)
+    );
+    // param def
+    assert_eq!(
+        tokens[&(14, 11)],
+        (5, TOKEN_TYPE_VARIABLE, TOKEN_MODIFIER_DECLARATION)
+    );
+    // local var use
+    assert_eq!(tokens[&(16, 8)], (3, TOKEN_TYPE_VARIABLE, 0));
+    // call of a function in another module
+    assert_eq!(tokens[&(40, 12)], (9, TOKEN_TYPE_FUNCTION, 0));
+    // const use
+    assert_eq!(
+        tokens[&(40, 22)],
+        (10, TOKEN_TYPE_VARIABLE, TOKEN_MODIFIER_READONLY)
+    );
+
+    // type params
+    let (symbols, cpath) = test_symbols("M3.move");
+    let mut tokens = vec![];
+    let (mut line, mut col) = (0, 0);
+    for token in symbols.semantic_tokens(&cpath) {
+        if token.delta_line > 0 {
+            col = 0;
+        }
+        line += token.delta_line;
+        col += token.delta_start;
+        tokens.push((line, col, token.token_type, token.token_modifiers_bitset));
+    }
+    assert!(tokens.contains(&(2, 23, TOKEN_TYPE_TYPE_PARAMETER, TOKEN_MODIFIER_DECLARATION)));
+    assert!(tokens.contains(&(3, 20, TOKEN_TYPE_TYPE_PARAMETER, 0)));
+}
+
+#[test]
+/// Tests inlay hints with inferred types of `let` bindings.
+fn inlay_hints_test() {
+    let (symbols, cpath) = test_symbols("M1.move");
+    let buffer = std::fs::read_to_string(&cpath).unwrap();
+
+    let hints = |start, end| {
+        symbols
+            .inlay_hints(&cpath, &buffer, Range {
+                start: Position {
+                    line: start,
+                    character: 0,
+                },
+                end: Position {
+                    line: end,
+                    character: 0,
+                },
+            })
+            .into_iter()
+            .map(|h| {
+                let label = match h.label {
+                    InlayHintLabel::String(label) => label,
+                    InlayHintLabel::LabelParts(_) => panic!("inlay hint labels are strings"),
+                };
+                (h.position.line, h.position.character, label)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // inferred local var (cp function), but not the function param
+    assert_eq!(hints(14, 16), vec![(15, 15, ": u64".to_string())]);
+    // inferred struct type (pack function)
+    assert_eq!(hints(20, 20), vec![(
+        20,
+        15,
+        ": Symbols::M1::SomeStruct".to_string()
+    )]);
+    // annotated local var (unpack_no_assign function)
+    assert_eq!(hints(49, 49), vec![]);
+    // inferred reference type (mut function)
+    assert_eq!(hints(55, 56), vec![
+        (55, 15, ": u64".to_string()),
+        (56, 13, ": &mut u64".to_string())
+    ]);
+}
+
+#[test]
+/// Tests searching for module members across the workspace.
+fn workspace_symbols_test() {
+    let (symbols, _) = test_symbols("M1.move");
+
+    let mut found: Vec<_> = symbols
+        .workspace_symbols("MULTI_ARG")
+        .into_iter()
+        .map(|s| (s.name, s.kind, s.container_name.unwrap()))
+        .collect();
+    found.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(found, vec![
+        (
+            "multi_arg".to_string(),
+            SymbolKind::FUNCTION,
+            "Symbols::M2".to_string()
+        ),
+        (
+            "multi_arg_call".to_string(),
+            SymbolKind::FUNCTION,
+            "Symbols::M1".to_string()
+        ),
+    ]);
+
+    let found = symbols.workspace_symbols("anotherparam");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].kind, SymbolKind::STRUCT);
+    assert!(found[0].location.uri.path().ends_with("M3.move"));
+    assert_eq!(found[0].location.range.start, Position {
+        line: 22,
+        character: 11
+    });
+
+    let found = symbols.workspace_symbols("M1");
+    assert!(found
+        .iter()
+        .any(|s| s.name == "M1" && s.kind == SymbolKind::MODULE));
+
+    assert!(symbols.workspace_symbols("no_such_symbol").is_empty());
+}
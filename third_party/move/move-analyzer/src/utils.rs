@@ -1,12 +1,17 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::context::Context;
+use anyhow::{anyhow, Result};
 use codespan_reporting::files::{Files, SimpleFiles};
+use lsp_server::{Request, RequestId};
 use lsp_types::Position;
 use move_command_line_common::files::FileHash;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
-use std::collections::HashMap;
+use serde::de::DeserializeOwned;
+use std::{collections::HashMap, path::PathBuf};
+use url::Url;
 
 /// Converts a location from the byte index format to the line/character (Position) format, where
 /// line/character are 0-based.
@@ -29,3 +34,47 @@ pub fn get_loc(
         Err(_) => None,
     }
 }
+
+/// Deserializes the parameters of a request
+pub fn request_params<P: DeserializeOwned>(request: &Request) -> Result<P> {
+    serde_json::from_value(request.params.clone()).map_err(|err| {
+        anyhow!(
+            "Invalid parameters of '{}' request: {}",
+            request.method,
+            err
+        )
+    })
+}
+
+/// Converts the URI of a document to a file path
+pub fn file_path(uri: &Url) -> Result<PathBuf> {
+    uri.to_file_path()
+        .map_err(|_| anyhow!("'{}' is not a file path", uri))
+}
+
+/// Returns the current content of a file, which may not be saved yet
+pub fn file_buffer(context: &Context, fpath: &PathBuf) -> Option<String> {
+    match context.files.get(fpath) {
+        Some(buffer) => Some(buffer.to_string()),
+        None => std::fs::read_to_string(fpath).ok(),
+    }
+}
+
+/// Sends a response to a request, or an error response if `result` is an error
+pub fn send_response(context: &Context, id: RequestId, result: Result<serde_json::Value>) {
+    let response = match result {
+        Ok(value) => lsp_server::Response::new_ok(id, value),
+        Err(err) => lsp_server::Response::new_err(
+            id,
+            lsp_server::ErrorCode::InvalidParams as i32,
+            err.to_string(),
+        ),
+    };
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send response: {:?}", err);
+    }
+}
@@ -6,12 +6,18 @@ use crate::{
     interpreter::Interpreter,
     loader::{Function, Loader},
 };
-use move_binary_format::file_format::Bytecode;
+use move_binary_format::file_format::{Bytecode, FunctionDefinitionIndex};
+use move_core_types::language_storage::ModuleId;
 use move_vm_types::values::{self, Locals};
+use once_cell::sync::Lazy;
 use std::{
     collections::BTreeSet,
     io::{self, Write},
     str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
 };
 
 #[derive(Debug)]
@@ -194,3 +200,110 @@ impl DebugContext {
         }
     }
 }
+
+/// A frame of the call stack of the interpreter, as seen by a [`DebugHook`].
+#[derive(Clone, Debug)]
+pub struct DebugFrame {
+    /// Module of the function, `None` if the function is a script.
+    pub module_id: Option<ModuleId>,
+    pub function_name: String,
+    pub function_index: FunctionDefinitionIndex,
+    /// Offset of the instruction being executed by the frame.
+    pub pc: u16,
+    /// Values of the parameters and locals of the function, `-` for those holding no value.
+    pub locals: Vec<String>,
+}
+
+impl DebugFrame {
+    pub(crate) fn new(function: &Function, locals: &Locals, pc: u16) -> Self {
+        let locals = (0..function.local_count())
+            .map(|idx| {
+                let mut s = String::new();
+                values::debug::print_local(&mut s, locals, idx)
+                    .map(|_| s)
+                    .unwrap_or_else(|_| "?".to_string())
+            })
+            .collect();
+        Self {
+            module_id: function.module_id().cloned(),
+            function_name: function.name().to_string(),
+            function_index: function.index(),
+            pc,
+            locals,
+        }
+    }
+}
+
+/// The state of the interpreter before it executes an instruction.
+pub struct DebugState<'a> {
+    function: &'a Function,
+    locals: &'a Locals,
+    pc: u16,
+    interp: &'a Interpreter,
+}
+
+impl<'a> DebugState<'a> {
+    /// Module of the current function, `None` if the function is a script.
+    pub fn module_id(&self) -> Option<&ModuleId> {
+        self.function.module_id()
+    }
+
+    pub fn function_name(&self) -> &str {
+        self.function.name()
+    }
+
+    pub fn function_index(&self) -> FunctionDefinitionIndex {
+        self.function.index()
+    }
+
+    /// Offset of the instruction about to be executed.
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    /// Number of frames on the call stack, including the current one.
+    pub fn depth(&self) -> usize {
+        self.interp.call_stack_height() + 1
+    }
+
+    /// Frames of the call stack, starting with the current one.
+    pub fn call_stack(&self) -> Vec<DebugFrame> {
+        let mut frames = vec![DebugFrame::new(self.function, self.locals, self.pc)];
+        frames.extend(self.interp.debug_call_stack().into_iter().rev());
+        frames
+    }
+}
+
+/// A hook called by the interpreter before it executes each instruction, used to implement
+/// debuggers. The instruction isn't executed until the hook returns.
+pub trait DebugHook: Send {
+    fn on_instruction(&mut self, state: &DebugState);
+}
+
+static DEBUG_HOOK_ENABLED: AtomicBool = AtomicBool::new(false);
+
+static DEBUG_HOOK: Lazy<Mutex<Option<Box<dyn DebugHook>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Installs the hook called before every instruction, replacing the previous one, or removes it.
+///
+/// The hook is shared by all the threads executing Move code, which are serialized while it is
+/// installed, so it is meant to be used when a single thread executes Move code.
+pub fn set_debug_hook(hook: Option<Box<dyn DebugHook>>) {
+    let mut debug_hook = DEBUG_HOOK.lock().unwrap();
+    DEBUG_HOOK_ENABLED.store(hook.is_some(), Ordering::Release);
+    *debug_hook = hook;
+}
+
+pub(crate) fn call_debug_hook(function: &Function, locals: &Locals, pc: u16, interp: &Interpreter) {
+    if !DEBUG_HOOK_ENABLED.load(Ordering::Acquire) {
+        return;
+    }
+    if let Some(hook) = DEBUG_HOOK.lock().unwrap().as_mut() {
+        hook.on_instruction(&DebugState {
+            function,
+            locals,
+            pc,
+            interp,
+        });
+    }
+}
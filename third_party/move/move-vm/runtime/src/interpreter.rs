@@ -2,6 +2,8 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(any(debug_assertions, feature = "debugging"))]
+use crate::debug::DebugFrame;
 use crate::{
     data_cache::TransactionDataCache,
     loader::{Function, Loader, Resolver},
@@ -771,6 +773,21 @@ impl Interpreter {
         Ok(())
     }
 
+    #[allow(dead_code)]
+    pub(crate) fn call_stack_height(&self) -> usize {
+        self.call_stack.0.len()
+    }
+
+    /// Frames of the callers of the current function, starting with the outermost one.
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn debug_call_stack(&self) -> Vec<DebugFrame> {
+        self.call_stack
+            .0
+            .iter()
+            .map(|frame| DebugFrame::new(&frame.function, &frame.locals, frame.pc))
+            .collect()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn debug_print_stack_trace<B: Write>(
         &self,
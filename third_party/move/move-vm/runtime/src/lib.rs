@@ -25,7 +25,7 @@ pub mod config;
 
 // Only include debugging functionality in debug builds
 #[cfg(any(debug_assertions, feature = "debugging"))]
-mod debug;
+pub mod debug;
 
 #[cfg(test)]
 mod unit_tests;
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #[cfg(any(debug_assertions, feature = "debugging"))]
-use crate::debug::DebugContext;
+use crate::debug::{call_debug_hook, DebugContext};
 #[cfg(any(debug_assertions, feature = "debugging"))]
 use crate::{
     interpreter::Interpreter,
@@ -90,6 +90,7 @@ pub(crate) fn trace(
             .unwrap()
             .debug_loop(function_desc, locals, pc, instr, loader, interp);
     }
+    call_debug_hook(function_desc, locals, pc, interp);
 }
 
 #[macro_export]
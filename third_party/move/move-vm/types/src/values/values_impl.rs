@@ -2887,6 +2887,14 @@ pub mod debug {
         Ok(())
     }
 
+    /// Prints the local at index `idx`, or nothing if there is no such local.
+    pub fn print_local<B: Write>(buf: &mut B, locals: &Locals, idx: usize) -> PartialVMResult<()> {
+        match locals.0.borrow().get(idx) {
+            Some(val) => print_value_impl(buf, val),
+            None => Ok(()),
+        }
+    }
+
     pub fn print_value<B: Write>(buf: &mut B, val: &Value) -> PartialVMResult<()> {
         print_value_impl(buf, &val.0)
     }
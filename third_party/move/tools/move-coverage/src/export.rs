@@ -0,0 +1,290 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports source coverage in the formats read by coverage reporting tools: LCOV and Cobertura.
+
+#![forbid(unsafe_code)]
+
+use crate::source_coverage::FileCoverage;
+use std::{
+    io::{self, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Writes the coverage of source files as an LCOV tracefile.
+pub fn write_lcov<W: Write>(files: &[FileCoverage], writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "TN:")?;
+    for file in files {
+        writeln!(writer, "SF:{}", file.path.display())?;
+
+        for (name, function) in file.functions.iter() {
+            writeln!(writer, "FN:{},{}", function.line, name)?;
+        }
+        for (name, function) in file.functions.iter() {
+            writeln!(writer, "FNDA:{},{}", function.calls, name)?;
+        }
+        writeln!(writer, "FNF:{}", file.functions.len())?;
+        writeln!(
+            writer,
+            "FNH:{}",
+            file.functions
+                .values()
+                .filter(|function| function.calls > 0)
+                .count()
+        )?;
+
+        for (block, branch) in file.branches.iter().enumerate() {
+            for (idx, count) in [branch.counts.taken, branch.counts.not_taken]
+                .iter()
+                .enumerate()
+            {
+                if branch.executed {
+                    writeln!(writer, "BRDA:{},{},{},{}", branch.line, block, idx, count)?;
+                } else {
+                    writeln!(writer, "BRDA:{},{},{},-", branch.line, block, idx)?;
+                }
+            }
+        }
+        writeln!(writer, "BRF:{}", 2 * file.branches.len())?;
+        writeln!(writer, "BRH:{}", file.branches_covered())?;
+
+        for (line, hits) in file.lines.iter() {
+            writeln!(writer, "DA:{},{}", line, hits)?;
+        }
+        writeln!(writer, "LF:{}", file.lines.len())?;
+        writeln!(writer, "LH:{}", file.lines_covered())?;
+        writeln!(writer, "end_of_record")?;
+    }
+    Ok(())
+}
+
+/// Writes the coverage of the source files of a package as a Cobertura report. Each source file
+/// is reported as a class, named after the file, with a path relative to `source_root`.
+pub fn write_cobertura<W: Write>(
+    package_name: &str,
+    source_root: &Path,
+    files: &[FileCoverage],
+    writer: &mut W,
+) -> io::Result<()> {
+    let lines_valid: usize = files.iter().map(|file| file.lines.len()).sum();
+    let lines_covered: usize = files.iter().map(FileCoverage::lines_covered).sum();
+    let branches_valid: usize = files.iter().map(|file| 2 * file.branches.len()).sum();
+    let branches_covered: usize = files.iter().map(FileCoverage::branches_covered).sum();
+    let line_rate = rate(lines_covered, lines_valid);
+    let branch_rate = rate(branches_covered, branches_valid);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis());
+
+    writeln!(writer, r#"<?xml version="1.0" ?>"#)?;
+    writeln!(
+        writer,
+        r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="1.9" timestamp="{}">"#,
+        line_rate,
+        branch_rate,
+        lines_covered,
+        lines_valid,
+        branches_covered,
+        branches_valid,
+        timestamp
+    )?;
+    writeln!(writer, "  <sources>")?;
+    writeln!(
+        writer,
+        "    <source>{}</source>",
+        escape(&source_root.display().to_string())
+    )?;
+    writeln!(writer, "  </sources>")?;
+    writeln!(writer, "  <packages>")?;
+    writeln!(
+        writer,
+        r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+        escape(package_name),
+        line_rate,
+        branch_rate
+    )?;
+    writeln!(writer, "      <classes>")?;
+    for file in files {
+        let path = file.path.strip_prefix(source_root).unwrap_or(&file.path);
+        let name = path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+        writeln!(
+            writer,
+            r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            escape(&name),
+            escape(&path.display().to_string()),
+            rate(file.lines_covered(), file.lines.len()),
+            rate(file.branches_covered(), 2 * file.branches.len()),
+        )?;
+
+        writeln!(writer, "          <methods>")?;
+        for (name, function) in file.functions.iter() {
+            let hit = (function.calls > 0) as usize;
+            writeln!(
+                writer,
+                r#"            <method name="{}" signature="" line-rate="{}" branch-rate="{}" complexity="0">"#,
+                escape(name),
+                hit,
+                hit
+            )?;
+            writeln!(
+                writer,
+                r#"              <lines><line number="{}" hits="{}"/></lines>"#,
+                function.line, function.calls
+            )?;
+            writeln!(writer, "            </method>")?;
+        }
+        writeln!(writer, "          </methods>")?;
+
+        writeln!(writer, "          <lines>")?;
+        for (line, hits) in file.lines.iter() {
+            let branches = file
+                .branches
+                .iter()
+                .filter(|branch| branch.line == *line)
+                .collect::<Vec<_>>();
+            if branches.is_empty() {
+                writeln!(
+                    writer,
+                    r#"            <line number="{}" hits="{}" branch="false"/>"#,
+                    line, hits
+                )?;
+            } else {
+                let valid = 2 * branches.len();
+                let covered: usize = branches
+                    .iter()
+                    .map(|branch| {
+                        (branch.counts.taken > 0) as usize + (branch.counts.not_taken > 0) as usize
+                    })
+                    .sum();
+                writeln!(
+                    writer,
+                    r#"            <line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                    line,
+                    hits,
+                    covered * 100 / valid,
+                    covered,
+                    valid
+                )?;
+            }
+        }
+        writeln!(writer, "          </lines>")?;
+        writeln!(writer, "        </class>")?;
+    }
+    writeln!(writer, "      </classes>")?;
+    writeln!(writer, "    </package>")?;
+    writeln!(writer, "  </packages>")?;
+    writeln!(writer, "</coverage>")?;
+    Ok(())
+}
+
+fn rate(covered: usize, valid: usize) -> f64 {
+    if valid == 0 {
+        1.0
+    } else {
+        covered as f64 / valid as f64
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source_coverage::{BranchCounts, BranchLineCoverage, FunctionLineCoverage};
+    use std::path::PathBuf;
+
+    fn file_coverage() -> FileCoverage {
+        FileCoverage {
+            path: PathBuf::from("/pkg/sources/m.move"),
+            functions: [
+                ("m::f".to_string(), FunctionLineCoverage {
+                    line: 2,
+                    calls: 3,
+                }),
+                ("m::g".to_string(), FunctionLineCoverage {
+                    line: 8,
+                    calls: 0,
+                }),
+            ]
+            .into_iter()
+            .collect(),
+            lines: [(3, 3), (4, 1), (9, 0)].into_iter().collect(),
+            branches: vec![
+                BranchLineCoverage {
+                    line: 3,
+                    executed: true,
+                    counts: BranchCounts {
+                        taken: 1,
+                        not_taken: 0,
+                    },
+                },
+                BranchLineCoverage {
+                    line: 9,
+                    executed: false,
+                    counts: BranchCounts {
+                        taken: 0,
+                        not_taken: 0,
+                    },
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_write_lcov() {
+        let mut output = vec![];
+        write_lcov(&[file_coverage()], &mut output).unwrap();
+        let expected = "\
+TN:
+SF:/pkg/sources/m.move
+FN:2,m::f
+FN:8,m::g
+FNDA:3,m::f
+FNDA:0,m::g
+FNF:2
+FNH:1
+BRDA:3,0,0,1
+BRDA:3,0,1,0
+BRDA:9,1,0,-
+BRDA:9,1,1,-
+BRF:4
+BRH:1
+DA:3,3
+DA:4,1
+DA:9,0
+LF:3
+LH:2
+end_of_record
+";
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_write_cobertura() {
+        let mut output = vec![];
+        write_cobertura("P&Q", Path::new("/pkg"), &[file_coverage()], &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(
+            r#"lines-covered="2" lines-valid="3" branches-covered="1" branches-valid="4""#
+        ));
+        assert!(output.contains(r#"<package name="P&amp;Q""#));
+        assert!(output.contains(r#"<class name="m" filename="sources/m.move""#));
+        assert!(output.contains(
+            r#"<line number="3" hits="3" branch="true" condition-coverage="50% (1/2)"/>"#
+        ));
+        assert!(output.contains(r#"<line number="4" hits="1" branch="false"/>"#));
+    }
+}
@@ -9,7 +9,7 @@ use codespan::{Files, Span};
 use colored::*;
 use move_binary_format::{
     access::ModuleAccess,
-    file_format::{CodeOffset, FunctionDefinitionIndex},
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
     CompiledModule,
 };
 use move_bytecode_source_map::source_map::SourceMap;
@@ -20,7 +20,7 @@ use std::{
     collections::BTreeMap,
     fs,
     io::{self, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 #[derive(Clone, Debug, Serialize)]
@@ -29,12 +29,59 @@ pub struct FunctionSourceCoverage {
     pub uncovered_locations: Vec<Loc>,
 }
 
+/// Execution counts of the instructions of a function, located in the source
+#[derive(Clone, Debug, Serialize)]
+pub struct FunctionInstructionCoverage {
+    pub definition_location: Loc,
+    /// Number of times the function was called
+    pub calls: u64,
+    pub instructions: Vec<(Loc, u64)>,
+    pub branches: Vec<(Loc, BranchCounts)>,
+}
+
+/// Number of times each direction of a conditional branch was taken, `taken` being the jumps to
+/// the target of the branch. They are derived from the execution counts of the instructions, see
+/// [`EdgeCounts::derive`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct BranchCounts {
+    pub taken: u64,
+    pub not_taken: u64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct SourceCoverageBuilder<'a> {
+    module_name: Identifier,
     uncovered_locations: BTreeMap<Identifier, FunctionSourceCoverage>,
+    instruction_coverage: BTreeMap<Identifier, FunctionInstructionCoverage>,
     source_map: &'a SourceMap,
 }
 
+/// Line, function and branch coverage of a source file, in the terms of coverage reporting tools
+#[derive(Clone, Debug, Default)]
+pub struct FileCoverage {
+    pub path: PathBuf,
+    /// Functions by qualified name
+    pub functions: BTreeMap<String, FunctionLineCoverage>,
+    /// Execution count of each line holding code, by line number starting at 1
+    pub lines: BTreeMap<u32, u64>,
+    pub branches: Vec<BranchLineCoverage>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FunctionLineCoverage {
+    /// Line the function is defined on
+    pub line: u32,
+    pub calls: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct BranchLineCoverage {
+    pub line: u32,
+    /// Whether the line of the branch was executed
+    pub executed: bool,
+    pub counts: BranchCounts,
+}
+
 #[derive(Debug, Serialize, Eq, PartialEq, Ord, PartialOrd)]
 pub enum AbstractSegment {
     Bounded { start: u32, end: u32 },
@@ -126,12 +173,105 @@ impl<'a> SourceCoverageBuilder<'a> {
             })
             .collect();
 
+        let instruction_coverage = module
+            .function_defs()
+            .iter()
+            .enumerate()
+            .filter_map(|(function_def_idx, function_def)| {
+                let code_unit = function_def.code.as_ref()?;
+                let fn_handle = module.function_handle_at(function_def.function);
+                let fn_name = module.identifier_at(fn_handle.name).to_owned();
+                let function_map = source_map
+                    .get_function_source_map(FunctionDefinitionIndex(function_def_idx as u16))
+                    .ok()?;
+                let function_coverage =
+                    module_map.and_then(|fn_map| fn_map.function_maps.get(&fn_name));
+                let hits = |code_offset: usize| {
+                    function_coverage
+                        .and_then(|coverage| coverage.get(&(code_offset as u64)))
+                        .copied()
+                        .unwrap_or(0)
+                };
+
+                let instructions = (0..code_unit.code.len())
+                    .filter_map(|code_offset| {
+                        let loc = function_map.get_code_location(code_offset as CodeOffset)?;
+                        Some((loc, hits(code_offset)))
+                    })
+                    .collect();
+                let edge_counts = EdgeCounts::derive(&code_unit.code, hits);
+                let branches = edge_counts
+                    .branches
+                    .into_iter()
+                    .filter_map(|(code_offset, counts)| {
+                        let loc = function_map.get_code_location(code_offset as CodeOffset)?;
+                        Some((loc, counts))
+                    })
+                    .collect();
+                Some((fn_name, FunctionInstructionCoverage {
+                    definition_location: function_map.definition_location,
+                    calls: edge_counts.calls,
+                    instructions,
+                    branches,
+                }))
+            })
+            .collect();
+
         Self {
+            module_name: module_name.name().to_owned(),
             uncovered_locations,
+            instruction_coverage,
             source_map,
         }
     }
 
+    /// Computes the coverage of the lines, functions and branches of the module in its source
+    /// file.
+    pub fn compute_file_coverage(&self, file_path: &Path) -> FileCoverage {
+        let file_contents = fs::read_to_string(file_path).unwrap();
+        assert!(
+            self.source_map.check(&file_contents),
+            "File contents out of sync with source map"
+        );
+        let mut files = Files::new();
+        let file_id = files.add(file_path.as_os_str().to_os_string(), file_contents);
+        let line = |loc: &Loc| files.location(file_id, loc.start()).unwrap().line.0 + 1;
+
+        let mut file_coverage = FileCoverage {
+            path: file_path.to_path_buf(),
+            ..Default::default()
+        };
+        for (fn_name, fn_cov) in self.instruction_coverage.iter() {
+            file_coverage.functions.insert(
+                format!("{}::{}", self.module_name, fn_name),
+                FunctionLineCoverage {
+                    line: line(&fn_cov.definition_location),
+                    calls: fn_cov.calls,
+                },
+            );
+            // A line is executed as many times as its most executed instruction
+            for (loc, hits) in fn_cov.instructions.iter() {
+                let line_hits = file_coverage.lines.entry(line(loc)).or_insert(0);
+                *line_hits = (*line_hits).max(*hits);
+            }
+        }
+        for fn_cov in self.instruction_coverage.values() {
+            for (loc, counts) in fn_cov.branches.iter() {
+                let branch_line = line(loc);
+                file_coverage.branches.push(BranchLineCoverage {
+                    line: branch_line,
+                    executed: file_coverage
+                        .lines
+                        .get(&branch_line)
+                        .map_or(false, |hits| *hits > 0),
+                    counts: *counts,
+                });
+            }
+        }
+        file_coverage.branches.sort_by_key(|branch| branch.line);
+        file_coverage
+    }
+
     pub fn compute_source_coverage(&self, file_path: &Path) -> SourceCoverage {
         let file_contents = fs::read_to_string(file_path).unwrap();
         assert!(
@@ -225,6 +365,33 @@ impl<'a> SourceCoverageBuilder<'a> {
     }
 }
 
+impl FileCoverage {
+    /// Adds the coverage of another module defined in the same file.
+    pub fn merge(&mut self, other: FileCoverage) {
+        self.functions.extend(other.functions);
+        for (line, hits) in other.lines {
+            let line_hits = self.lines.entry(line).or_insert(0);
+            *line_hits = (*line_hits).max(hits);
+        }
+        self.branches.extend(other.branches);
+        self.branches.sort_by_key(|branch| branch.line);
+    }
+
+    pub fn lines_covered(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+
+    /// Returns the number of branch directions which were taken
+    pub fn branches_covered(&self) -> usize {
+        self.branches
+            .iter()
+            .map(|branch| {
+                (branch.counts.taken > 0) as usize + (branch.counts.not_taken > 0) as usize
+            })
+            .sum()
+    }
+}
+
 impl SourceCoverage {
     pub fn output_source_coverage<W: Write>(&self, output_writer: &mut W) -> io::Result<()> {
         for line in self.annotated_lines.iter() {
@@ -240,6 +407,133 @@ impl SourceCoverage {
     }
 }
 
+/// An execution count which is not recorded by the coverage map
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum UnknownCount {
+    Calls,
+    /// Number of jumps of the conditional branch at the code offset
+    Taken(usize),
+}
+
+/// The execution count of an instruction, as the sum of the counts of the edges flowing into it:
+/// `count = constant + sum of coefficient * unknown count`
+#[derive(Default)]
+struct FlowEquation {
+    count: i128,
+    constant: i128,
+    terms: BTreeMap<UnknownCount, i128>,
+}
+
+/// Execution counts of the edges of the control flow graph of a function
+struct EdgeCounts {
+    /// Number of times the function was called, i.e. the count of the entry edge
+    calls: u64,
+    /// Counts of the conditional branches, by code offset
+    branches: BTreeMap<usize, BranchCounts>,
+}
+
+impl EdgeCounts {
+    /// Derives the counts of the edges from the execution counts of the instructions.
+    ///
+    /// An instruction is executed as many times as control flows into it, from its predecessors
+    /// or, for the first instruction, from the callers of the function. An edge leaving an
+    /// instruction which is not a conditional branch is taken as many times as the instruction is
+    /// executed, and the two edges of a conditional branch add up to its count. These equations
+    /// are solved for the unknown counts, e.g. a branch jumping to a join point is counted from
+    /// the count of its fall through successor, and the calls of a function whose first
+    /// instruction is a loop header are counted by subtracting the back edges.
+    ///
+    /// Counts the equations do not determine are approximated by the count of the target of the
+    /// edge, capped by the count of its source.
+    fn derive(code: &[Bytecode], instruction_hits: impl Fn(usize) -> u64) -> Self {
+        let hits = |code_offset: usize| instruction_hits(code_offset) as i128;
+        let mut equations = (0..code.len())
+            .map(|code_offset| FlowEquation {
+                count: hits(code_offset),
+                ..FlowEquation::default()
+            })
+            .collect::<Vec<_>>();
+        if let Some(entry) = equations.first_mut() {
+            entry.terms.insert(UnknownCount::Calls, 1);
+        }
+        for (code_offset, instruction) in code.iter().enumerate() {
+            match instruction {
+                Bytecode::BrTrue(target) | Bytecode::BrFalse(target) => {
+                    let taken = UnknownCount::Taken(code_offset);
+                    *equations[*target as usize].terms.entry(taken).or_default() += 1;
+                    if let Some(fall_through) = equations.get_mut(code_offset + 1) {
+                        fall_through.constant += hits(code_offset);
+                        *fall_through.terms.entry(taken).or_default() -= 1;
+                    }
+                },
+                Bytecode::Branch(target) => {
+                    equations[*target as usize].constant += hits(code_offset);
+                },
+                Bytecode::Ret | Bytecode::Abort => (),
+                _ => {
+                    if let Some(next) = equations.get_mut(code_offset + 1) {
+                        next.constant += hits(code_offset);
+                    }
+                },
+            }
+        }
+
+        // Solve the equations with a single unknown count, until no more counts are determined
+        let mut solved: BTreeMap<UnknownCount, i128> = BTreeMap::new();
+        loop {
+            let mut progress = false;
+            for equation in equations.iter() {
+                let mut rest = equation.count - equation.constant;
+                let mut unknowns = vec![];
+                for (unknown, coefficient) in equation.terms.iter() {
+                    match solved.get(unknown) {
+                        _ if *coefficient == 0 => (),
+                        Some(count) => rest -= coefficient * count,
+                        None => unknowns.push((*unknown, *coefficient)),
+                    }
+                }
+                if let [(unknown, coefficient)] = unknowns[..] {
+                    solved.insert(unknown, rest / coefficient);
+                    progress = true;
+                }
+            }
+            if !progress {
+                break;
+            }
+        }
+
+        // Counts are inconsistent when instructions aborted, keep them within bounds
+        let count = |unknown: UnknownCount, approximation: i128, max: i128| {
+            solved
+                .get(&unknown)
+                .copied()
+                .unwrap_or(approximation)
+                .clamp(0, max) as u64
+        };
+        let calls = count(UnknownCount::Calls, hits(0), hits(0));
+        let branches = code
+            .iter()
+            .enumerate()
+            .filter_map(|(code_offset, instruction)| match instruction {
+                Bytecode::BrTrue(target) | Bytecode::BrFalse(target) => {
+                    let branch_count = hits(code_offset);
+                    let taken = count(
+                        UnknownCount::Taken(code_offset),
+                        hits(*target as usize),
+                        branch_count,
+                    );
+                    Some((code_offset, BranchCounts {
+                        taken,
+                        not_taken: branch_count as u64 - taken,
+                    }))
+                },
+                _ => None,
+            })
+            .collect();
+        Self { calls, branches }
+    }
+}
+
 fn merge_spans(cov: FunctionSourceCoverage) -> Vec<Span> {
     if cov.uncovered_locations.is_empty() {
         return vec![];
@@ -267,3 +561,90 @@ fn merge_spans(cov: FunctionSourceCoverage) -> Vec<Span> {
     unioned.push(curr);
     unioned
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn derive(code: &[Bytecode], hits: &[u64]) -> EdgeCounts {
+        assert_eq!(code.len(), hits.len());
+        EdgeCounts::derive(code, |code_offset| hits[code_offset])
+    }
+
+    #[test]
+    fn test_if_without_else() {
+        // if (x) { y = 1 }; called 3 times, with x true twice
+        let code = [
+            Bytecode::CopyLoc(0),
+            Bytecode::BrFalse(4),
+            Bytecode::LdU64(1),
+            Bytecode::StLoc(1),
+            Bytecode::Ret,
+        ];
+        // The target of the branch is a join point, executed on both directions
+        let counts = derive(&code, &[3, 3, 2, 2, 3]);
+        assert_eq!(counts.calls, 3);
+        assert_eq!(counts.branches[&1], BranchCounts {
+            taken: 1,
+            not_taken: 2,
+        });
+
+        // Never taken
+        let counts = derive(&code, &[2, 2, 2, 2, 2]);
+        assert_eq!(counts.branches[&1], BranchCounts {
+            taken: 0,
+            not_taken: 2,
+        });
+    }
+
+    #[test]
+    fn test_if_else() {
+        let code = [
+            Bytecode::CopyLoc(0),
+            Bytecode::BrFalse(4),
+            Bytecode::LdU64(1),
+            Bytecode::Branch(5),
+            Bytecode::LdU64(2),
+            Bytecode::Ret,
+        ];
+        let counts = derive(&code, &[4, 4, 3, 3, 1, 4]);
+        assert_eq!(counts.calls, 4);
+        assert_eq!(counts.branches[&1], BranchCounts {
+            taken: 1,
+            not_taken: 3,
+        });
+    }
+
+    #[test]
+    fn test_loop_at_entry() {
+        // while (x > 0) { x = x - 1 }; called once with x = 2
+        let code = [
+            Bytecode::CopyLoc(0),
+            Bytecode::BrFalse(4),
+            Bytecode::Nop,
+            Bytecode::Branch(0),
+            Bytecode::Ret,
+        ];
+        // The first instruction is also executed from the back edge
+        let counts = derive(&code, &[3, 3, 2, 2, 1]);
+        assert_eq!(counts.calls, 1);
+        assert_eq!(counts.branches[&1], BranchCounts {
+            taken: 1,
+            not_taken: 2,
+        });
+
+        // Loop exited by a conditional back edge: do { x = x - 1 } while (x > 0)
+        let code = [
+            Bytecode::Nop,
+            Bytecode::CopyLoc(0),
+            Bytecode::BrTrue(0),
+            Bytecode::Ret,
+        ];
+        let counts = derive(&code, &[4, 4, 4, 2]);
+        assert_eq!(counts.calls, 2);
+        assert_eq!(counts.branches[&2], BranchCounts {
+            taken: 2,
+            not_taken: 2,
+        });
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A Debug Adapter Protocol (DAP) server for the Move VM.
+//!
+//! The server listens for a DAP client, e.g. an editor, and lets it step through the Move code
+//! executed by the current process: it stops on source breakpoints, steps over, into and out of
+//! function calls, and shows the call stack and the locals of every frame. Source locations are
+//! derived from the source maps of the compiled packages.
+//!
+//! It relies on the debugging hook of the VM, which is only compiled in debug builds or with the
+//! `debugging` feature of `move-vm-runtime`.
+
+mod protocol;
+mod server;
+mod sources;
+
+pub use server::DebugServer;
+pub use sources::{SourceFile, SourceIndex, SourceLocation};
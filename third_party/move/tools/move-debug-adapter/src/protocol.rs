@@ -0,0 +1,144 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wire format of the Debug Adapter Protocol: JSON messages, each preceded by a `Content-Length`
+//! header. See https://microsoft.github.io/debug-adapter-protocol/specification
+
+use anyhow::{bail, format_err, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+/// A request sent by the client
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub seq: i64,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// Reads the next request sent by the client, `None` if the client closed the connection
+pub fn read_request(reader: &mut impl BufRead) -> Result<Option<Request>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(length) = line.strip_prefix("Content-Length:") {
+            content_length = Some(length.trim().parse::<usize>()?);
+        }
+    }
+    let Some(content_length) = content_length else {
+        bail!("Missing Content-Length header");
+    };
+
+    let mut content = vec![0u8; content_length];
+    reader.read_exact(&mut content)?;
+    let message: Value = serde_json::from_slice(&content)?;
+    if message["type"] != "request" {
+        bail!("Unexpected message: {}", message);
+    }
+    serde_json::from_value(message).map_err(|err| format_err!("Invalid request: {}", err))
+}
+
+/// Writes messages to the client, numbering them
+pub struct MessageWriter<W> {
+    writer: W,
+    seq: i64,
+}
+
+impl<W: Write> MessageWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, seq: 0 }
+    }
+
+    fn write(&mut self, mut message: Value) -> Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let content = serde_json::to_vec(&message)?;
+        write!(self.writer, "Content-Length: {}\r\n\r\n", content.len())?;
+        self.writer.write_all(&content)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Responds to a request with the given body
+    pub fn respond(&mut self, request: &Request, body: Value) -> Result<()> {
+        self.write(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": true,
+            "command": request.command,
+            "body": body,
+        }))
+    }
+
+    /// Responds to a request with an error
+    pub fn respond_error(&mut self, request: &Request, message: &str) -> Result<()> {
+        self.write(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": false,
+            "command": request.command,
+            "message": message,
+        }))
+    }
+
+    pub fn event(&mut self, event: &str, body: Value) -> Result<()> {
+        self.write(json!({
+            "type": "event",
+            "event": event,
+            "body": body,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_read_written_messages() {
+        let mut writer = MessageWriter::new(vec![]);
+        let request = Request {
+            seq: 3,
+            command: "threads".to_string(),
+            arguments: Value::Null,
+        };
+        writer.respond(&request, json!({ "threads": [] })).unwrap();
+        writer.event("initialized", json!({})).unwrap();
+
+        let output = String::from_utf8(writer.writer).unwrap();
+        let (header, rest) = output.split_once("\r\n\r\n").unwrap();
+        let length: usize = header
+            .strip_prefix("Content-Length: ")
+            .unwrap()
+            .parse()
+            .unwrap();
+        let response: Value = serde_json::from_str(&rest[..length]).unwrap();
+        assert_eq!(response["seq"], 1);
+        assert_eq!(response["request_seq"], 3);
+        assert_eq!(response["command"], "threads");
+        assert!(rest[length..].starts_with("Content-Length: "));
+    }
+
+    #[test]
+    fn test_read_request() {
+        let content =
+            r#"{"seq":1,"type":"request","command":"initialize","arguments":{"adapterID":"move"}}"#;
+        let input = format!("Content-Length: {}\r\n\r\n{}", content.len(), content);
+        let mut reader = BufReader::new(input.as_bytes());
+        let request = read_request(&mut reader).unwrap().unwrap();
+        assert_eq!(request.seq, 1);
+        assert_eq!(request.command, "initialize");
+        assert_eq!(request.arguments["adapterID"], "move");
+        assert!(read_request(&mut reader).unwrap().is_none());
+    }
+}
@@ -0,0 +1,668 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    protocol::{read_request, MessageWriter, Request},
+    sources::{SourceIndex, SourceLocation},
+};
+use anyhow::{bail, Result};
+use move_vm_runtime::debug::{set_debug_hook, DebugFrame, DebugHook, DebugState};
+use serde_json::{json, Value};
+use std::{
+    collections::HashSet,
+    io::BufReader,
+    net::{TcpListener, TcpStream},
+    path::Path,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// The VM executes on a single thread, which is the only thread reported to the client
+const THREAD_ID: i64 = 1;
+
+type Writer = Arc<Mutex<MessageWriter<TcpStream>>>;
+
+/// How to resume execution after it stopped
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Resume {
+    Continue,
+    StepIn,
+    StepOver,
+    StepOut,
+}
+
+/// A frame of the call stack at the point where execution stopped
+struct StoppedFrame {
+    frame: DebugFrame,
+    location: Option<SourceLocation>,
+}
+
+/// State shared between the client session and the VM
+#[derive(Default)]
+struct SharedState {
+    breakpoints: HashSet<SourceLocation>,
+    /// The call stack, innermost frame first, while execution is stopped
+    stopped: Option<Vec<StoppedFrame>>,
+    stop_on_entry: bool,
+    pause_requested: bool,
+    disconnected: bool,
+}
+
+/// A Debug Adapter Protocol server driving the Move VM of the current process.
+///
+/// While the server runs, the Move code executed by the process can be stepped through by the
+/// connected client, at the granularity of source lines. The server should only run while Move
+/// code is executed by a single thread.
+pub struct DebugServer {
+    writer: Writer,
+}
+
+impl DebugServer {
+    /// Waits for a client to connect to `listener` and to configure the debugging session, then
+    /// starts intercepting the instructions executed by the VM.
+    pub fn accept(listener: &TcpListener, sources: SourceIndex) -> Result<Self> {
+        let (stream, _) = listener.accept()?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let writer = Arc::new(Mutex::new(MessageWriter::new(stream)));
+        let sources = Arc::new(sources);
+        let state = Arc::new(Mutex::new(SharedState::default()));
+        let (resume_tx, resume_rx) = channel();
+
+        let mut session = Session {
+            reader,
+            writer: writer.clone(),
+            sources: sources.clone(),
+            state: state.clone(),
+            resume: resume_tx,
+        };
+        session.configure()?;
+        thread::spawn(move || session.run());
+
+        set_debug_hook(Some(Box::new(Debugger {
+            writer: writer.clone(),
+            sources,
+            state,
+            resume: resume_rx,
+            step: None,
+            lines: vec![],
+        })));
+        Ok(Self { writer })
+    }
+
+    /// Stops intercepting the instructions executed by the VM, and ends the debugging session.
+    pub fn finish(self) {
+        set_debug_hook(None);
+        // The client may already be gone
+        let _ = self.writer.lock().unwrap().event("terminated", json!({}));
+    }
+}
+
+/// Handles the requests of the client
+struct Session {
+    reader: BufReader<TcpStream>,
+    writer: Writer,
+    sources: Arc<SourceIndex>,
+    state: Arc<Mutex<SharedState>>,
+    resume: Sender<Resume>,
+}
+
+impl Session {
+    /// Handles the requests sent before execution starts, until the client is done configuring
+    /// the session
+    fn configure(&mut self) -> Result<()> {
+        loop {
+            let Some(request) = read_request(&mut self.reader)? else {
+                bail!("The debugger client disconnected before the session started");
+            };
+            self.handle(&request)?;
+            if request.command == "configurationDone" {
+                return Ok(());
+            }
+        }
+    }
+
+    fn run(mut self) {
+        while let Ok(Some(request)) = read_request(&mut self.reader) {
+            if self.handle(&request).is_err() || request.command == "disconnect" {
+                break;
+            }
+        }
+        self.disconnect();
+    }
+
+    fn disconnect(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.disconnected = true;
+        if state.stopped.take().is_some() {
+            let _ = self.resume.send(Resume::Continue);
+        }
+    }
+
+    fn handle(&mut self, request: &Request) -> Result<()> {
+        let args = &request.arguments;
+        let body = match request.command.as_str() {
+            "initialize" => {
+                self.respond(request, json!({ "supportsConfigurationDoneRequest": true }))?;
+                return self.writer.lock().unwrap().event("initialized", json!({}));
+            },
+            "launch" | "attach" => {
+                self.state.lock().unwrap().stop_on_entry =
+                    args["stopOnEntry"].as_bool().unwrap_or(false);
+                json!({})
+            },
+            "setBreakpoints" => self.set_breakpoints(args),
+            "setExceptionBreakpoints" | "configurationDone" => json!({}),
+            "threads" => json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+            "stackTrace" => self.stack_trace(),
+            "scopes" => {
+                let frame_id = args["frameId"].as_i64().unwrap_or_default();
+                json!({ "scopes": [{
+                    "name": "Locals",
+                    "variablesReference": frame_id + 1,
+                    "expensive": false,
+                }]})
+            },
+            "variables" => self.variables(args["variablesReference"].as_i64().unwrap_or_default()),
+            "continue" => {
+                self.resume(Resume::Continue);
+                json!({ "allThreadsContinued": true })
+            },
+            "next" => {
+                self.resume(Resume::StepOver);
+                json!({})
+            },
+            "stepIn" => {
+                self.resume(Resume::StepIn);
+                json!({})
+            },
+            "stepOut" => {
+                self.resume(Resume::StepOut);
+                json!({})
+            },
+            "pause" => {
+                self.state.lock().unwrap().pause_requested = true;
+                json!({})
+            },
+            "disconnect" | "terminate" => {
+                self.disconnect();
+                json!({})
+            },
+            command => {
+                return self
+                    .writer
+                    .lock()
+                    .unwrap()
+                    .respond_error(request, &format!("Unsupported request: {}", command));
+            },
+        };
+        self.respond(request, body)
+    }
+
+    fn respond(&self, request: &Request, body: Value) -> Result<()> {
+        self.writer.lock().unwrap().respond(request, body)
+    }
+
+    fn resume(&self, resume: Resume) {
+        if self.state.lock().unwrap().stopped.take().is_some() {
+            let _ = self.resume.send(resume);
+        }
+    }
+
+    fn set_breakpoints(&self, args: &Value) -> Value {
+        let file_idx = args["source"]["path"]
+            .as_str()
+            .and_then(|path| self.sources.find_file(Path::new(path)));
+        let breakable_lines = file_idx
+            .map(|idx| self.sources.breakable_lines(idx))
+            .unwrap_or_default();
+        let lines = args["breakpoints"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|breakpoint| breakpoint["line"].as_u64())
+            .map(|line| line as u32);
+
+        let mut state = self.state.lock().unwrap();
+        if let Some(file_idx) = file_idx {
+            state.breakpoints.retain(|(idx, _)| *idx != file_idx);
+        }
+        let breakpoints = lines
+            .map(|line| match file_idx {
+                Some(file_idx) if breakable_lines.contains(&line) => {
+                    state.breakpoints.insert((file_idx, line));
+                    json!({ "verified": true, "line": line })
+                },
+                Some(_) => json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "No code on this line",
+                }),
+                None => json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "Source file not in the debugged packages",
+                }),
+            })
+            .collect::<Vec<_>>();
+        json!({ "breakpoints": breakpoints })
+    }
+
+    fn stack_trace(&self) -> Value {
+        let state = self.state.lock().unwrap();
+        let frames = state
+            .stopped
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(id, stopped)| {
+                let frame = &stopped.frame;
+                let name = match &frame.module_id {
+                    Some(module_id) => format!(
+                        "{}::{}",
+                        module_id.short_str_lossless(),
+                        frame.function_name
+                    ),
+                    None => format!("script::{}", frame.function_name),
+                };
+                match stopped.location {
+                    Some((file_idx, line)) => {
+                        let path = &self.sources.file(file_idx).path;
+                        json!({
+                            "id": id,
+                            "name": name,
+                            "source": {
+                                "name": path.file_name().map(|name| name.to_string_lossy()),
+                                "path": path,
+                            },
+                            "line": line,
+                            "column": 1,
+                        })
+                    },
+                    None => json!({
+                        "id": id,
+                        "name": format!("{} [{}]", name, frame.pc),
+                        "line": 0,
+                        "column": 0,
+                        "presentationHint": "subtle",
+                    }),
+                }
+            })
+            .collect::<Vec<_>>();
+        json!({ "totalFrames": frames.len(), "stackFrames": frames })
+    }
+
+    fn variables(&self, reference: i64) -> Value {
+        let state = self.state.lock().unwrap();
+        let frame = state
+            .stopped
+            .as_ref()
+            .and_then(|frames| frames.get((reference - 1) as usize));
+        let variables = frame
+            .into_iter()
+            .flat_map(|stopped| {
+                let frame = &stopped.frame;
+                frame.locals.iter().enumerate().map(move |(idx, value)| {
+                    let name = frame
+                        .module_id
+                        .as_ref()
+                        .and_then(|module_id| {
+                            self.sources
+                                .local_name(module_id, frame.function_index, idx)
+                        })
+                        .map_or_else(|| format!("local{}", idx), str::to_string);
+                    json!({ "name": name, "value": value, "variablesReference": 0 })
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({ "variables": variables })
+    }
+}
+
+/// A step requested by the client
+struct Step {
+    resume: Resume,
+    /// Depth of the call stack when the step was requested
+    depth: usize,
+}
+
+/// The hook stopping the VM on breakpoints and steps
+struct Debugger {
+    writer: Writer,
+    sources: Arc<SourceIndex>,
+    state: Arc<Mutex<SharedState>>,
+    resume: Receiver<Resume>,
+    step: Option<Step>,
+    /// Source line last executed by each frame of the call stack, outermost frame first
+    lines: Vec<Option<SourceLocation>>,
+}
+
+impl Debugger {
+    fn stop_reason(&self, location: SourceLocation, depth: usize) -> Option<&'static str> {
+        let mut state = self.state.lock().unwrap();
+        if state.disconnected {
+            return None;
+        }
+        if state.stop_on_entry {
+            state.stop_on_entry = false;
+            return Some("entry");
+        }
+        if state.pause_requested {
+            state.pause_requested = false;
+            return Some("pause");
+        }
+        if state.breakpoints.contains(&location) {
+            return Some("breakpoint");
+        }
+        match &self.step {
+            Some(step) => match step.resume {
+                Resume::StepIn => Some("step"),
+                Resume::StepOver if depth <= step.depth => Some("step"),
+                Resume::StepOut if depth < step.depth => Some("step"),
+                _ => None,
+            },
+            None => None,
+        }
+    }
+}
+
+impl DebugHook for Debugger {
+    fn on_instruction(&mut self, state: &DebugState) {
+        let depth = state.depth();
+        let location = state.module_id().and_then(|module_id| {
+            self.sources
+                .location(module_id, state.function_index(), state.pc())
+        });
+
+        // Only stop when a frame reaches a new source line, rather than on every instruction
+        self.lines.resize(depth, None);
+        if location.is_none() || self.lines[depth - 1] == location {
+            return;
+        }
+        self.lines[depth - 1] = location;
+        let Some(reason) = self.stop_reason(location.unwrap(), depth) else {
+            return;
+        };
+
+        let frames = state
+            .call_stack()
+            .into_iter()
+            .map(|frame| StoppedFrame {
+                location: frame.module_id.as_ref().and_then(|module_id| {
+                    self.sources
+                        .location(module_id, frame.function_index, frame.pc)
+                }),
+                frame,
+            })
+            .collect();
+        self.state.lock().unwrap().stopped = Some(frames);
+        let stopped = self.writer.lock().unwrap().event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        );
+        if stopped.is_err() {
+            // The client is gone, let the execution complete
+            self.state.lock().unwrap().disconnected = true;
+            self.step = None;
+            return;
+        }
+
+        self.step = match self.resume.recv() {
+            Ok(Resume::Continue) | Err(_) => None,
+            Ok(resume) => Some(Step { resume, depth }),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::FunctionDefinitionIndex;
+    use move_core_types::{account_address::AccountAddress, ident_str, language_storage::ModuleId};
+    use std::io::{BufRead, Read, Write};
+
+    /// A client of the debug server, connected over the loopback interface
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+        seq: i64,
+    }
+
+    impl Client {
+        fn connect(listener: &TcpListener) -> Self {
+            let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+            Self {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+                seq: 0,
+            }
+        }
+
+        fn send(&mut self, command: &str, arguments: Value) {
+            self.seq += 1;
+            let content = serde_json::to_vec(&json!({
+                "seq": self.seq,
+                "type": "request",
+                "command": command,
+                "arguments": arguments,
+            }))
+            .unwrap();
+            write!(self.writer, "Content-Length: {}\r\n\r\n", content.len()).unwrap();
+            self.writer.write_all(&content).unwrap();
+        }
+
+        fn receive(&mut self) -> Value {
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                self.reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                content_length = line
+                    .strip_prefix("Content-Length: ")
+                    .unwrap()
+                    .parse()
+                    .unwrap();
+            }
+            let mut content = vec![0u8; content_length];
+            self.reader.read_exact(&mut content).unwrap();
+            serde_json::from_slice(&content).unwrap()
+        }
+
+        fn request(&mut self, command: &str, arguments: Value) -> Value {
+            self.send(command, arguments);
+            let response = self.receive();
+            assert_eq!(response["type"], "response");
+            assert_eq!(response["request_seq"], self.seq);
+            assert_eq!(response["command"], command);
+            response
+        }
+    }
+
+    /// Returns a session reading the requests of a connected client
+    fn session() -> (Session, Client, Receiver<Resume>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let client = Client::connect(&listener);
+        let (stream, _) = listener.accept().unwrap();
+        let (resume_tx, resume_rx) = channel();
+        let session = Session {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            writer: Arc::new(Mutex::new(MessageWriter::new(stream))),
+            sources: Arc::new(SourceIndex::new()),
+            state: Arc::new(Mutex::new(SharedState::default())),
+            resume: resume_tx,
+        };
+        (session, client, resume_rx)
+    }
+
+    fn request(command: &str, arguments: Value) -> Request {
+        Request {
+            seq: 1,
+            command: command.to_string(),
+            arguments,
+        }
+    }
+
+    fn frame(name: &str, locals: &[&str]) -> StoppedFrame {
+        StoppedFrame {
+            frame: DebugFrame {
+                module_id: Some(ModuleId::new(
+                    AccountAddress::ONE,
+                    ident_str!("m").to_owned(),
+                )),
+                function_name: name.to_string(),
+                function_index: FunctionDefinitionIndex(0),
+                pc: 3,
+                locals: locals.iter().map(|local| local.to_string()).collect(),
+            },
+            location: None,
+        }
+    }
+
+    #[test]
+    fn test_configure_session() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let mut client = Client::connect(&listener);
+        // Accepting returns once the client is done configuring the session
+        let server = thread::spawn(move || DebugServer::accept(&listener, SourceIndex::new()));
+
+        let response = client.request("initialize", json!({ "adapterID": "move" }));
+        assert_eq!(response["success"], true);
+        assert_eq!(response["body"]["supportsConfigurationDoneRequest"], true);
+        assert_eq!(client.receive()["event"], "initialized");
+
+        client.request("launch", json!({ "stopOnEntry": true }));
+        let response = client.request(
+            "setBreakpoints",
+            json!({
+                "source": { "path": "/unknown/sources/m.move" },
+                "breakpoints": [{ "line": 3 }, { "line": 5 }],
+            }),
+        );
+        let breakpoints = response["body"]["breakpoints"].as_array().unwrap();
+        assert_eq!(breakpoints.len(), 2);
+        assert_eq!(breakpoints[0]["verified"], false);
+        assert_eq!(breakpoints[1]["line"], 5);
+        let response = client.request("threads", json!({}));
+        assert_eq!(response["body"]["threads"][0]["id"], THREAD_ID);
+        client.request("configurationDone", json!({}));
+        let server = server.join().unwrap().unwrap();
+
+        // Requests are still handled once execution started
+        let response = client.request("evaluate", json!({ "expression": "x" }));
+        assert_eq!(response["success"], false);
+        assert_eq!(response["message"], "Unsupported request: evaluate");
+        client.request("disconnect", json!({}));
+
+        server.finish();
+        assert_eq!(client.receive()["event"], "terminated");
+    }
+
+    #[test]
+    fn test_session_ends_before_configuration() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let client = Client::connect(&listener);
+        drop(client);
+        assert!(DebugServer::accept(&listener, SourceIndex::new()).is_err());
+    }
+
+    #[test]
+    fn test_resume_stopped_execution() {
+        let (mut session, mut client, resume) = session();
+
+        // Resuming is ignored while execution runs
+        session.handle(&request("next", json!({}))).unwrap();
+        assert_eq!(client.receive()["success"], true);
+        assert!(resume.try_recv().is_err());
+
+        for (command, expected) in [
+            ("next", Resume::StepOver),
+            ("stepIn", Resume::StepIn),
+            ("stepOut", Resume::StepOut),
+            ("continue", Resume::Continue),
+        ] {
+            session.state.lock().unwrap().stopped = Some(vec![frame("f", &[])]);
+            session.handle(&request(command, json!({}))).unwrap();
+            assert_eq!(client.receive()["command"], command);
+            assert_eq!(resume.try_recv().unwrap(), expected);
+            assert!(session.state.lock().unwrap().stopped.is_none());
+        }
+
+        // Disconnecting lets a stopped execution complete
+        session.state.lock().unwrap().stopped = Some(vec![frame("f", &[])]);
+        session.handle(&request("disconnect", json!({}))).unwrap();
+        assert_eq!(resume.try_recv().unwrap(), Resume::Continue);
+        assert!(session.state.lock().unwrap().disconnected);
+    }
+
+    #[test]
+    fn test_inspect_stopped_execution() {
+        let (session, _client, _resume) = session();
+        session.state.lock().unwrap().stopped =
+            Some(vec![frame("inner", &["1", "-"]), frame("outer", &["true"])]);
+
+        let trace = session.stack_trace();
+        assert_eq!(trace["totalFrames"], 2);
+        assert_eq!(trace["stackFrames"][0]["id"], 0);
+        assert_eq!(trace["stackFrames"][0]["name"], "0x1::m::inner [3]");
+        assert_eq!(trace["stackFrames"][1]["name"], "0x1::m::outer [3]");
+
+        // The locals of frame `id` are referenced by `id + 1`, as 0 references no variables
+        let variables = session.variables(1);
+        assert_eq!(variables["variables"][0]["name"], "local0");
+        assert_eq!(variables["variables"][0]["value"], "1");
+        assert_eq!(variables["variables"][1]["value"], "-");
+        assert_eq!(session.variables(2)["variables"][0]["value"], "true");
+        assert_eq!(session.variables(3)["variables"], json!([]));
+
+        session.state.lock().unwrap().stopped = None;
+        assert_eq!(session.stack_trace()["totalFrames"], 0);
+    }
+
+    #[test]
+    fn test_stop_reason() {
+        let (session, _client, resume) = session();
+        let mut debugger = Debugger {
+            writer: session.writer.clone(),
+            sources: session.sources.clone(),
+            state: session.state.clone(),
+            resume,
+            step: None,
+            lines: vec![],
+        };
+
+        session.state.lock().unwrap().stop_on_entry = true;
+        assert_eq!(debugger.stop_reason((0, 1), 1), Some("entry"));
+        assert_eq!(debugger.stop_reason((0, 1), 1), None);
+
+        session.state.lock().unwrap().breakpoints.insert((0, 4));
+        assert_eq!(debugger.stop_reason((0, 4), 1), Some("breakpoint"));
+        assert_eq!(debugger.stop_reason((1, 4), 1), None);
+
+        debugger.step = Some(Step {
+            resume: Resume::StepOver,
+            depth: 2,
+        });
+        assert_eq!(debugger.stop_reason((0, 2), 3), None);
+        assert_eq!(debugger.stop_reason((0, 2), 2), Some("step"));
+        assert_eq!(debugger.stop_reason((0, 2), 1), Some("step"));
+
+        debugger.step = Some(Step {
+            resume: Resume::StepOut,
+            depth: 2,
+        });
+        assert_eq!(debugger.stop_reason((0, 2), 2), None);
+        assert_eq!(debugger.stop_reason((0, 2), 1), Some("step"));
+
+        session.state.lock().unwrap().pause_requested = true;
+        debugger.step = None;
+        assert_eq!(debugger.stop_reason((0, 2), 5), Some("pause"));
+
+        session.state.lock().unwrap().disconnected = true;
+        assert_eq!(debugger.stop_reason((0, 4), 1), None);
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mapping between the bytecode executed by the VM and the Move sources it was compiled from.
+
+use anyhow::{format_err, Result};
+use move_binary_format::{file_format::FunctionDefinitionIndex, CompiledModule};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::FileHash;
+use move_compiler::compiled_unit::CompiledUnitEnum;
+use move_core_types::language_storage::ModuleId;
+use move_package::compilation::compiled_package::CompiledPackage;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A position in a source file: the index of the file in the [`SourceIndex`] and a line number,
+/// starting at 1.
+pub type SourceLocation = (usize, u32);
+
+/// A Move source file
+pub struct SourceFile {
+    pub path: PathBuf,
+    /// Byte offsets at which each line starts
+    line_starts: Vec<u32>,
+}
+
+impl SourceFile {
+    fn new(path: PathBuf, contents: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(contents.match_indices('\n').map(|(i, _)| i as u32 + 1))
+            .collect();
+        Self { path, line_starts }
+    }
+
+    /// Returns the line, starting at 1, of the given byte offset
+    fn line(&self, offset: u32) -> u32 {
+        self.line_starts.partition_point(|start| *start <= offset) as u32
+    }
+}
+
+struct FunctionSources {
+    /// Location of every instruction of the function
+    locations: Vec<Option<SourceLocation>>,
+    /// Names of the parameters and locals of the function
+    local_names: Vec<String>,
+}
+
+/// Source files and source maps of the modules which can be debugged
+#[derive(Default)]
+pub struct SourceIndex {
+    files: Vec<SourceFile>,
+    file_hashes: HashMap<FileHash, usize>,
+    functions: HashMap<(ModuleId, FunctionDefinitionIndex), FunctionSources>,
+}
+
+impl SourceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes the modules of a compiled package and of its dependencies
+    pub fn from_compiled_package(package: &CompiledPackage) -> Result<Self> {
+        let mut index = Self::new();
+        index.add_compiled_package(package)?;
+        Ok(index)
+    }
+
+    /// Adds the modules of a compiled package and of its dependencies
+    pub fn add_compiled_package(&mut self, package: &CompiledPackage) -> Result<()> {
+        let units = package
+            .root_compiled_units
+            .iter()
+            .chain(package.deps_compiled_units.iter().map(|(_, unit)| unit));
+        for unit in units.clone() {
+            self.add_source_file(&unit.source_path)?;
+        }
+        // Scripts can't be debugged, as the VM only identifies them by their hash
+        for unit in units {
+            if let CompiledUnitEnum::Module(module) = &unit.unit {
+                self.add_module(&module.module, &module.source_map);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a source file, which must be added before the modules compiled from it.
+    pub fn add_source_file(&mut self, path: &Path) -> Result<()> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format_err!("Unable to read {}: {}", path.display(), err))?;
+        let file_hash = FileHash::new(&contents);
+        if !self.file_hashes.contains_key(&file_hash) {
+            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            self.file_hashes.insert(file_hash, self.files.len());
+            self.files.push(SourceFile::new(path, &contents));
+        }
+        Ok(())
+    }
+
+    /// Adds a module, locating its instructions with its source map.
+    pub fn add_module(&mut self, module: &CompiledModule, source_map: &SourceMap) {
+        let module_id = module.self_id();
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            let function_idx = FunctionDefinitionIndex(idx as u16);
+            let Ok(function_map) = source_map.get_function_source_map(function_idx) else {
+                continue;
+            };
+            let code_len = function_def.code.as_ref().map_or(0, |code| code.code.len());
+            let locations = (0..code_len)
+                .map(|offset| {
+                    let loc = function_map.get_code_location(offset as u16)?;
+                    let file_idx = *self.file_hashes.get(&loc.file_hash())?;
+                    Some((file_idx, self.files[file_idx].line(loc.start())))
+                })
+                .collect();
+            let local_names = function_map
+                .parameters
+                .iter()
+                .chain(function_map.locals.iter())
+                .map(|(name, _)| name.clone())
+                .collect();
+            self.functions
+                .insert((module_id.clone(), function_idx), FunctionSources {
+                    locations,
+                    local_names,
+                });
+        }
+    }
+
+    pub fn file(&self, idx: usize) -> &SourceFile {
+        &self.files[idx]
+    }
+
+    /// Returns the index of the source file at `path`, if it was added
+    pub fn find_file(&self, path: &Path) -> Option<usize> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.files.iter().position(|file| file.path == path)
+    }
+
+    /// Returns the source location of an instruction, if it is known
+    pub fn location(
+        &self,
+        module_id: &ModuleId,
+        function_idx: FunctionDefinitionIndex,
+        pc: u16,
+    ) -> Option<SourceLocation> {
+        self.functions
+            .get(&(module_id.clone(), function_idx))
+            .and_then(|function| function.locations.get(pc as usize).copied().flatten())
+    }
+
+    /// Returns the name of a parameter or local of a function, if it is known
+    pub fn local_name(
+        &self,
+        module_id: &ModuleId,
+        function_idx: FunctionDefinitionIndex,
+        local_idx: usize,
+    ) -> Option<&str> {
+        self.functions
+            .get(&(module_id.clone(), function_idx))
+            .and_then(|function| function.local_names.get(local_idx))
+            .map(String::as_str)
+    }
+
+    /// Returns the lines of a file which hold instructions, on which breakpoints can be set
+    pub fn breakable_lines(&self, file_idx: usize) -> BTreeSet<u32> {
+        self.functions
+            .values()
+            .flat_map(|function| function.locations.iter().flatten())
+            .filter(|(idx, _)| *idx == file_idx)
+            .map(|(_, line)| *line)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_of_offset() {
+        let file = SourceFile::new(
+            PathBuf::from("a.move"),
+            "module 0x1::a {\n\n    fun f() {}\n}",
+        );
+        assert_eq!(file.line(0), 1);
+        assert_eq!(file.line(15), 1);
+        assert_eq!(file.line(16), 2);
+        assert_eq!(file.line(17), 3);
+        assert_eq!(file.line(21), 3);
+        assert_eq!(file.line(32), 4);
+    }
+}
@@ -14,7 +14,10 @@ use crate::{
         build_plan::BuildPlan, compiled_package::CompiledPackage, model_builder::ModelBuilder,
     },
     package_lock::PackageLock,
-    resolution::resolution_graph::{ResolutionGraph, ResolvedGraph},
+    resolution::{
+        lock_file::LockFile,
+        resolution_graph::{ResolutionGraph, ResolvedGraph},
+    },
     source_package::manifest_parser,
 };
 use anyhow::{bail, Result};
@@ -254,11 +257,34 @@ impl BuildConfig {
         // This should be locked as it inspects the environment for `MOVE_HOME` which could
         // possibly be set by a different process in parallel.
         let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
-        ResolutionGraph::download_dependency_repos(&manifest, self, &path, writer)?;
+        let lock_file = LockFile::read(&path)?.unwrap_or_default();
+        ResolutionGraph::download_dependency_repos(&manifest, self, &lock_file, &path, writer)?;
         mutx.unlock();
         Ok(())
     }
 
+    /// Resolves the dependencies of the package at `path` or of the containing Move package to
+    /// their latest code, ignoring the resolution recorded in the `Move.lock` file of the package,
+    /// and records the new resolution in `Move.lock`.
+    pub fn update_lock_file_for_package<W: Write>(
+        mut self,
+        path: &Path,
+        writer: &mut W,
+    ) -> Result<()> {
+        // Record the dev dependencies too, so that the lock file applies to all build modes
+        self.dev_mode = true;
+        let path = SourcePackageLayout::try_find_root(path)?;
+        let toml_manifest =
+            self.parse_toml_manifest(path.join(SourcePackageLayout::Manifest.path()))?;
+        let mutx = PackageLock::lock();
+        // This should be locked as it inspects the environment for `MOVE_HOME` which could
+        // possibly be set by a different process in parallel.
+        let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
+        let ret = ResolutionGraph::update_lock_file(manifest, path, self, writer);
+        mutx.unlock();
+        ret.map(|_| ())
+    }
+
     pub fn resolution_graph_for_package<W: Write>(
         mut self,
         path: &Path,
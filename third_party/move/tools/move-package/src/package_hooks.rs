@@ -27,7 +27,30 @@ pub trait PackageHooks {
         dep_name: Symbol,
         info: &CustomDepInfo,
     ) -> anyhow::Result<()>;
+
+    /// Resolves a custom dependency like `resolve_custom_dependency`, but to the version recorded
+    /// in the lock file of the package, if any, rather than to the latest version. Returns the
+    /// version the dependency was resolved to, to record it in the lock file. By default, the
+    /// dependency is resolved to its latest version, and no version is recorded.
+    fn resolve_locked_custom_dependency(
+        &self,
+        dep_name: Symbol,
+        info: &CustomDepInfo,
+        _locked: Option<CustomDepVersion>,
+    ) -> anyhow::Result<Option<CustomDepVersion>> {
+        self.resolve_custom_dependency(dep_name, info)?;
+        Ok(None)
+    }
+}
+
+/// The version of the chain a custom dependency was resolved at.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct CustomDepVersion {
+    /// The upgrade number of the package at that version
+    pub upgrade_number: u64,
+    pub ledger_version: u64,
 }
+
 static HOOKS: Lazy<Mutex<Option<Box<dyn PackageHooks + Send + Sync>>>> =
     Lazy::new(|| Mutex::new(None));
 
@@ -36,13 +59,15 @@ pub fn register_package_hooks(hooks: Box<dyn PackageHooks + Send + Sync>) {
     *HOOKS.lock().unwrap() = Some(hooks)
 }
 
-/// Calls any registered hook to resolve a node dependency. Bails if none is registered.
+/// Calls any registered hook to resolve a node dependency, to its `locked` version if any.
+/// Returns the version it was resolved to, if known. Bails if no hook is registered.
 pub(crate) fn resolve_custom_dependency(
     dep_name: Symbol,
     info: &CustomDepInfo,
-) -> anyhow::Result<()> {
+    locked: Option<CustomDepVersion>,
+) -> anyhow::Result<Option<CustomDepVersion>> {
     if let Some(hooks) = &*HOOKS.lock().unwrap() {
-        hooks.resolve_custom_dependency(dep_name, info)
+        hooks.resolve_locked_custom_dependency(dep_name, info, locked)
     } else {
         bail!("use of unsupported custom dependency in package manifest")
     }
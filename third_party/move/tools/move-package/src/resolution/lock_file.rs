@@ -0,0 +1,185 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `Move.lock` file of a package, recording what its remote dependencies were resolved to,
+//! so that later builds resolve them to the same code even when e.g. a git branch moved.
+
+use crate::{
+    package_hooks::CustomDepVersion,
+    source_package::{
+        layout::SourcePackageLayout,
+        parsed_manifest::{Dependency, PackageDigest, PackageName},
+    },
+};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+const LOCK_FILE_VERSION: u64 = 1;
+
+const LOCK_FILE_HEADER: &str =
+    "# Generated when resolving the dependencies of the package. Do not edit it by hand.\n\n";
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LockFile {
+    pub version: u64,
+    /// The remote dependencies of the package, direct or transitive, by package name
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, LockedDependency>,
+}
+
+/// A remote dependency, as declared in the manifest, and what it was resolved to
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LockedDependency {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// The commit checked out for the git revision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// The on-chain upgrade number of the package, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade_number: Option<u64>,
+    /// The version of the chain the on-chain package was read at, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ledger_version: Option<u64>,
+    /// The digest of the manifest and sources of the package
+    pub digest: String,
+}
+
+impl Default for LockFile {
+    fn default() -> Self {
+        Self {
+            version: LOCK_FILE_VERSION,
+            dependencies: BTreeMap::new(),
+        }
+    }
+}
+
+impl LockFile {
+    /// Reads the lock file of the package at `package_path`, if it has one.
+    pub fn read(package_path: &Path) -> Result<Option<Self>> {
+        let path = package_path.join(SourcePackageLayout::LockFile.path());
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Unable to read {}", path.display()))?;
+        let lock_file: Self = toml::from_str(&contents)
+            .with_context(|| format!("Unable to parse {}", path.display()))?;
+        if lock_file.version > LOCK_FILE_VERSION {
+            bail!(
+                "Unsupported version {} of {}, the latest supported version is {}",
+                lock_file.version,
+                path.display(),
+                LOCK_FILE_VERSION
+            )
+        }
+        Ok(Some(lock_file))
+    }
+
+    /// Writes the lock file of the package at `package_path`.
+    pub fn write(&self, package_path: &Path) -> Result<()> {
+        let path = package_path.join(SourcePackageLayout::LockFile.path());
+        let contents = format!("{}{}", LOCK_FILE_HEADER, toml::to_string(self)?);
+        fs::write(&path, contents).with_context(|| format!("Unable to write {}", path.display()))
+    }
+
+    /// Returns the locked resolution of dependency `dep`, unless the dependency was declared
+    /// differently when it was locked.
+    pub fn get(&self, dep_name: PackageName, dep: &Dependency) -> Option<&LockedDependency> {
+        self.dependencies
+            .get(dep_name.as_str())
+            .filter(|locked| locked.is_declared_by(dep))
+    }
+
+    pub fn insert(&mut self, dep_name: PackageName, locked: LockedDependency) {
+        self.dependencies.insert(dep_name.to_string(), locked);
+    }
+}
+
+impl LockedDependency {
+    /// Returns a dependency as declared by `dep`, resolved to the given digest. Returns `None` if
+    /// `dep` is not a remote dependency.
+    pub fn new(dep: &Dependency, digest: PackageDigest) -> Option<Self> {
+        let mut locked = Self {
+            git: None,
+            rev: None,
+            subdir: None,
+            commit: None,
+            node: None,
+            address: None,
+            upgrade_number: None,
+            ledger_version: None,
+            digest: digest.to_string(),
+        };
+        if let Some(git_info) = &dep.git_info {
+            locked.git = Some(git_info.git_url.to_string());
+            locked.rev = Some(git_info.git_rev.to_string());
+            if !git_info.subdir.as_os_str().is_empty() {
+                locked.subdir = Some(git_info.subdir.to_string_lossy().to_string());
+            }
+        } else if let Some(node_info) = &dep.node_info {
+            locked.node = Some(node_info.node_url.to_string());
+            locked.address = Some(node_info.package_address.to_string());
+        } else {
+            return None;
+        }
+        Some(locked)
+    }
+
+    /// Returns the version of the chain an on-chain dependency was resolved at, if known.
+    pub fn custom_dep_version(&self) -> Option<CustomDepVersion> {
+        Some(CustomDepVersion {
+            upgrade_number: self.upgrade_number?,
+            ledger_version: self.ledger_version?,
+        })
+    }
+
+    /// Returns whether the dependency is declared as `dep` in the manifest.
+    fn is_declared_by(&self, dep: &Dependency) -> bool {
+        Self::new(dep, PackageDigest::from(self.digest.as_str())).map_or(false, |declared| {
+            (&declared.git, &declared.rev, &declared.subdir) == (&self.git, &self.rev, &self.subdir)
+                && (&declared.node, &declared.address) == (&self.node, &self.address)
+        })
+    }
+
+    /// Checks that the dependency resolved to the same code as when it was locked.
+    pub fn verify(&self, dep_name: PackageName, resolved: &LockedDependency) -> Result<()> {
+        if self.commit != resolved.commit {
+            bail!(
+                "Dependency '{}' is locked to commit '{}' in Move.lock but '{}' is checked out",
+                dep_name,
+                self.commit.as_deref().unwrap_or_default(),
+                resolved.commit.as_deref().unwrap_or_default(),
+            )
+        }
+        if self.upgrade_number.is_some() && self.upgrade_number != resolved.upgrade_number {
+            bail!(
+                "Dependency '{}' was upgraded on chain since it was locked in Move.lock. \
+                Expected upgrade number {} but got {}. Update Move.lock to use the upgraded package",
+                dep_name,
+                self.upgrade_number.unwrap_or_default(),
+                resolved
+                    .upgrade_number
+                    .map_or_else(|| "none".to_string(), |number| number.to_string()),
+            )
+        }
+        if self.digest != resolved.digest {
+            bail!(
+                "Source digest mismatch in dependency '{}'. Expected '{}' in Move.lock but got '{}'.",
+                dep_name,
+                self.digest,
+                resolved.digest
+            )
+        }
+        Ok(())
+    }
+}
@@ -3,8 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    package_hooks,
-    resolution::digest::compute_digest,
+    package_hooks::{self, CustomDepVersion},
+    resolution::{
+        digest::compute_digest,
+        lock_file::{LockFile, LockedDependency},
+    },
     source_package::{
         layout::SourcePackageLayout,
         manifest_parser::{parse_move_manifest_string, parse_source_manifest},
@@ -94,12 +97,70 @@ pub struct ResolutionPackage<T> {
 }
 
 impl ResolvingGraph {
+    /// Resolves the package graph, pinning the remote dependencies to the resolution recorded in
+    /// the `Move.lock` file of the root package. If the package has no `Move.lock` file yet, its
+    /// remote dependencies are resolved to their latest code, and recorded in a new one.
     pub fn new<W: Write>(
         root_package: SourceManifest,
         root_package_path: PathBuf,
-        mut build_options: BuildConfig,
+        build_options: BuildConfig,
         writer: &mut W,
     ) -> Result<ResolvingGraph> {
+        let current_lock_file = LockFile::read(&root_package_path)?;
+        let (resolution_graph, resolved_lock_file) = Self::new_with_lock_file(
+            root_package,
+            root_package_path.clone(),
+            build_options,
+            current_lock_file.clone().unwrap_or_default(),
+            writer,
+        )?;
+
+        match current_lock_file {
+            // Only packages with remote dependencies get a lock file
+            None if !resolved_lock_file.dependencies.is_empty() => {
+                resolved_lock_file.write(&root_package_path)?
+            },
+            Some(current_lock_file) if current_lock_file != resolved_lock_file => writeln!(
+                writer,
+                "{} Move.lock does not pin all the remote dependencies of the package, \
+                update it to pin them",
+                "WARNING".bold().yellow(),
+            )?,
+            _ => (),
+        }
+        Ok(resolution_graph)
+    }
+
+    /// Resolves the package graph, with the remote dependencies resolved to their latest code,
+    /// and records them in the `Move.lock` file of the root package.
+    pub fn update_lock_file<W: Write>(
+        root_package: SourceManifest,
+        root_package_path: PathBuf,
+        build_options: BuildConfig,
+        writer: &mut W,
+    ) -> Result<ResolvingGraph> {
+        let (resolution_graph, resolved_lock_file) = Self::new_with_lock_file(
+            root_package,
+            root_package_path.clone(),
+            build_options,
+            LockFile::default(),
+            writer,
+        )?;
+        resolved_lock_file.write(&root_package_path)?;
+        Ok(resolution_graph)
+    }
+
+    /// Resolves the package graph, pinning the remote dependencies to their resolution recorded
+    /// in `lock_file`, and checking that they still resolve to the same code. The remote
+    /// dependencies which are not pinned by `lock_file` are resolved to their latest code.
+    /// Returns the graph with the resolution of all the remote dependencies.
+    fn new_with_lock_file<W: Write>(
+        root_package: SourceManifest,
+        root_package_path: PathBuf,
+        mut build_options: BuildConfig,
+        lock_file: LockFile,
+        writer: &mut W,
+    ) -> Result<(ResolvingGraph, LockFile)> {
         if build_options.architecture.is_none() {
             if let Some(info) = &root_package.build {
                 build_options.architecture = info.architecture;
@@ -113,15 +174,22 @@ impl ResolvingGraph {
             package_table: BTreeMap::new(),
         };
 
+        let mut resolved_lock_file = lock_file;
         resolution_graph
-            .build_resolution_graph(root_package.clone(), root_package_path, true, writer)
+            .build_resolution_graph(
+                root_package.clone(),
+                root_package_path.clone(),
+                true,
+                &mut resolved_lock_file,
+                writer,
+            )
             .with_context(|| {
                 format!(
                     "Unable to resolve packages for package '{}'",
                     root_package.package.name
                 )
             })?;
-        Ok(resolution_graph)
+        Ok((resolution_graph, resolved_lock_file))
     }
 
     pub fn resolve(self) -> Result<ResolvedGraph> {
@@ -199,6 +267,7 @@ impl ResolvingGraph {
         package: SourceManifest,
         package_path: PathBuf,
         is_root_package: bool,
+        lock_file: &mut LockFile,
         writer: &mut W,
     ) -> Result<()> {
         let package_name = package.package.name;
@@ -252,7 +321,7 @@ impl ResolvingGraph {
             self.graph.add_edge(package_node_id, dep_node_id, ());
 
             let (dep_renaming, dep_resolution_table) = self
-                .process_dependency(dep_name, dep, package_path.clone(), writer)
+                .process_dependency(dep_name, dep, package_path.clone(), lock_file, writer)
                 .with_context(|| {
                     format!(
                         "While resolving dependency '{}' in package '{}'",
@@ -393,21 +462,27 @@ impl ResolvingGraph {
         dep_name_in_pkg: PackageName,
         dep: Dependency,
         root_path: PathBuf,
+        lock_file: &mut LockFile,
         writer: &mut W,
     ) -> Result<(Renaming, ResolvingTable)> {
-        Self::download_and_update_if_remote(
+        let custom_dep_version = Self::download_and_update_if_remote(
             dep_name_in_pkg,
             &dep,
             self.build_options.skip_fetch_latest_git_deps,
+            lock_file.get(dep_name_in_pkg, &dep),
             writer,
         )?;
         let (dep_package, dep_package_dir) =
             Self::parse_package_manifest(&dep, &dep_name_in_pkg, root_path)
                 .with_context(|| format!("While processing dependency '{}'", dep_name_in_pkg))?;
-        self.build_resolution_graph(dep_package.clone(), dep_package_dir, false, writer)
-            .with_context(|| {
-                format!("Unable to resolve package dependency '{}'", dep_name_in_pkg)
-            })?;
+        self.build_resolution_graph(
+            dep_package.clone(),
+            dep_package_dir.clone(),
+            false,
+            lock_file,
+            writer,
+        )
+        .with_context(|| format!("Unable to resolve package dependency '{}'", dep_name_in_pkg))?;
 
         if dep_name_in_pkg != dep_package.package.name {
             bail!("Name of dependency declared in package '{}' does not match dependency's package name '{}'",
@@ -434,6 +509,13 @@ impl ResolvingGraph {
             },
         }
 
+        if let Some(resolved) = Self::lock_dependency(&dep, &dep_package_dir, custom_dep_version)? {
+            if let Some(locked) = lock_file.get(dep_name_in_pkg, &dep) {
+                locked.verify(dep_name_in_pkg, &resolved)?;
+            }
+            lock_file.insert(dep_name_in_pkg, resolved);
+        }
+
         let resolving_dep = &self.package_table[&dep_name_in_pkg];
         let mut renaming = BTreeMap::new();
         let mut resolution_table = resolving_dep.resolution_table.clone();
@@ -526,6 +608,7 @@ impl ResolvingGraph {
     pub fn download_dependency_repos<W: Write>(
         manifest: &SourceManifest,
         build_options: &BuildConfig,
+        lock_file: &LockFile,
         root_path: &Path,
         writer: &mut W,
     ) -> Result<()> {
@@ -543,6 +626,7 @@ impl ResolvingGraph {
                 *dep_name,
                 dep,
                 build_options.skip_fetch_latest_git_deps,
+                lock_file.get(*dep_name, dep),
                 writer,
             )?;
 
@@ -550,7 +634,13 @@ impl ResolvingGraph {
                 Self::parse_package_manifest(dep, dep_name, root_path.to_path_buf())
                     .with_context(|| format!("While processing dependency '{}'", *dep_name))?;
             // download dependencies of dependencies
-            Self::download_dependency_repos(&dep_manifest, build_options, root_path, writer)?;
+            Self::download_dependency_repos(
+                &dep_manifest,
+                build_options,
+                lock_file,
+                root_path,
+                writer,
+            )?;
         }
         Ok(())
     }
@@ -559,11 +649,15 @@ impl ResolvingGraph {
         dep_name: PackageName,
         dep: &Dependency,
         skip_fetch_latest_git_deps: bool,
+        locked: Option<&LockedDependency>,
         writer: &mut W,
-    ) -> Result<()> {
+    ) -> Result<Option<CustomDepVersion>> {
         if let Some(git_info) = &dep.git_info {
             let git_url = git_info.git_url.as_str();
-            let git_rev = git_info.git_rev.as_str();
+            // Check out the commit recorded in the lock file rather than the latest commit of
+            // the revision, which may have moved since
+            let locked_commit = locked.and_then(|locked| locked.commit.as_deref());
+            let git_rev = locked_commit.unwrap_or_else(|| git_info.git_rev.as_str());
             let git_path = &git_info.download_to.display().to_string();
 
             // If there is no cached dependency, download it
@@ -595,6 +689,15 @@ impl ResolvingGraph {
                             dep_name
                         )
                     })?;
+            } else if let Some(commit) = locked_commit {
+                Self::checkout_locked_commit(
+                    dep_name,
+                    git_url,
+                    git_path,
+                    commit,
+                    skip_fetch_latest_git_deps,
+                    writer,
+                )?;
             } else if !skip_fetch_latest_git_deps {
                 // Confirm git is available.
                 confirm_git_available()?;
@@ -608,7 +711,7 @@ impl ResolvingGraph {
                     if let Ok(parsable_version) = String::from_utf8(rev.stdout) {
                         // If it's exactly the same, then it's a git rev
                         if parsable_version.trim().starts_with(git_rev) {
-                            return Ok(());
+                            return Ok(None);
                         }
                     }
                 }
@@ -623,7 +726,7 @@ impl ResolvingGraph {
                         // Tags don't easily update locally and you can't use reset --hard to cleanup
                         // any extra files
                         if parsable_version.trim().starts_with(git_rev) {
-                            return Ok(());
+                            return Ok(None);
                         }
                     }
                 }
@@ -690,10 +793,119 @@ impl ResolvingGraph {
             }
         }
         if let Some(node_info) = &dep.node_info {
-            package_hooks::resolve_custom_dependency(dep_name, node_info)?
+            // Fetch the version of the package recorded in the lock file rather than the latest
+            // version, which may have been upgraded since
+            let locked_version = locked.and_then(LockedDependency::custom_dep_version);
+            return package_hooks::resolve_custom_dependency(dep_name, node_info, locked_version);
+        }
+        Ok(None)
+    }
+
+    fn checkout_locked_commit<W: Write>(
+        dep_name: PackageName,
+        git_url: &str,
+        git_path: &str,
+        commit: &str,
+        skip_fetch_latest_git_deps: bool,
+        writer: &mut W,
+    ) -> Result<()> {
+        confirm_git_available()?;
+        if git_head_commit(git_path)? == commit {
+            return Ok(());
+        }
+
+        let has_commit = Command::new("git")
+            .args([
+                "-C",
+                git_path,
+                "cat-file",
+                "-e",
+                &format!("{}^{{commit}}", commit),
+            ])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_or(false, |status| status.success());
+        if !has_commit {
+            if skip_fetch_latest_git_deps {
+                bail!(
+                    "Commit '{}' of package '{}' locked in Move.lock is not available locally, \
+                    to fetch it unset --skip-fetch-latest-git-deps",
+                    commit,
+                    dep_name
+                )
+            }
+            writeln!(
+                writer,
+                "{} {}",
+                "UPDATING GIT DEPENDENCY".bold().green(),
+                git_url,
+            )?;
+            let status = Command::new("git")
+                .args(["-C", git_path, "fetch", "origin"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map_err(|_| {
+                    anyhow::anyhow!("Failed to fetch Git state for package '{}'", dep_name)
+                })?;
+            if !status.success() {
+                bail!(
+                    "Failed to fetch Git state for package '{}' | Exit status: {}",
+                    dep_name,
+                    status
+                )
+            }
+        }
+
+        let status = Command::new("git")
+            .args(["-C", git_path, "reset", "--hard", commit])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Failed to checkout commit '{}' for package '{}'",
+                    commit,
+                    dep_name
+                )
+            })?;
+        if !status.success() {
+            bail!(
+                "Failed to checkout commit '{}' for package '{}' | Exit status: {}",
+                commit,
+                dep_name,
+                status
+            )
         }
         Ok(())
     }
+
+    /// Returns what a remote dependency resolved to, `None` if the dependency is not remote.
+    fn lock_dependency(
+        dep: &Dependency,
+        dep_package_path: &Path,
+        custom_dep_version: Option<CustomDepVersion>,
+    ) -> Result<Option<LockedDependency>> {
+        // Digest the package as in a regular build, whatever the build mode
+        let digest = ResolvingPackage::get_package_digest_for_config(
+            dep_package_path,
+            &BuildConfig::default(),
+        )?;
+        let Some(mut locked) = LockedDependency::new(dep, digest) else {
+            return Ok(None);
+        };
+        if let Some(git_info) = &dep.git_info {
+            locked.commit = Some(git_head_commit(
+                &git_info.download_to.display().to_string(),
+            )?);
+        }
+        if let Some(version) = custom_dep_version {
+            locked.upgrade_number = Some(version.upgrade_number);
+            locked.ledger_version = Some(version.ledger_version);
+        }
+        Ok(Some(locked))
+    }
 }
 
 impl ResolvingPackage {
@@ -946,6 +1158,21 @@ impl ResolvedPackage {
     }
 }
 
+fn git_head_commit(git_path: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["-C", git_path, "rev-parse", "HEAD"])
+        .output()
+        .map_err(|_| anyhow::anyhow!("Failed to read the commit checked out in {}", git_path))?;
+    if !output.status.success() {
+        bail!(
+            "Failed to read the commit checked out in {} | Exit status: {}",
+            git_path,
+            output.status
+        )
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
 fn confirm_git_available() -> Result<()> {
     match Command::new("git").arg("--version").output() {
         Ok(_) => Ok(()),
@@ -16,6 +16,7 @@ pub enum SourcePackageLayout {
     Scripts,
     Examples,
     Manifest,
+    LockFile,
     DocTemplates,
 }
 
@@ -23,6 +24,7 @@ impl SourcePackageLayout {
     /// A Move source package is laid out on-disk as
     /// a_move_package
     /// ├── Move.toml      (required)
+    /// ├── Move.lock      (optional, written when resolving remote dependencies)
     /// ├── sources        (required)
     /// ├── examples       (optional, dev mode)
     /// ├── scripts        (optional)
@@ -52,6 +54,7 @@ impl SourcePackageLayout {
         match self {
             Self::Sources => "sources",
             Self::Manifest => "Move.toml",
+            Self::LockFile => "Move.lock",
             Self::Tests => "tests",
             Self::Scripts => "scripts",
             Self::Examples => "examples",
@@ -63,7 +66,8 @@ impl SourcePackageLayout {
     pub fn is_optional(&self) -> bool {
         match self {
             Self::Sources | Self::Manifest => false,
-            Self::Tests
+            Self::LockFile
+            | Self::Tests
             | Self::Scripts
             | Self::Examples
             | Self::Specifications
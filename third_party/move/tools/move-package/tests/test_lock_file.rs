@@ -0,0 +1,216 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{
+    package_hooks::{register_package_hooks, CustomDepVersion, PackageHooks},
+    resolution::{lock_file::LockFile, resolution_graph as RG},
+    source_package::{manifest_parser as MP, parsed_manifest::CustomDepInfo},
+    BuildConfig,
+};
+use move_symbol_pool::Symbol;
+use once_cell::sync::Lazy;
+use std::{fs, path::Path, process::Command, sync::Mutex};
+use tempfile::{tempdir, TempDir};
+
+/// The package system downloads remote dependencies to MOVE_HOME, which it reads once per process
+static MOVE_HOME: Lazy<TempDir> = Lazy::new(|| {
+    let dir = tempdir().unwrap();
+    std::env::set_var("MOVE_HOME", dir.path());
+    dir
+});
+
+/// The latest version of the on-chain packages
+static LATEST_VERSION: Mutex<CustomDepVersion> = Mutex::new(CustomDepVersion {
+    upgrade_number: 1,
+    ledger_version: 100,
+});
+
+/// Hooks downloading on-chain packages from a fake chain, where the code of a package is its
+/// upgrade number
+struct TestHooks;
+
+impl PackageHooks for TestHooks {
+    fn custom_package_info_fields(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn custom_dependency_key(&self) -> Option<String> {
+        Some("node".to_string())
+    }
+
+    fn resolve_custom_dependency(
+        &self,
+        _dep_name: Symbol,
+        _info: &CustomDepInfo,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("On-chain packages must be resolved with their locked version")
+    }
+
+    fn resolve_locked_custom_dependency(
+        &self,
+        _dep_name: Symbol,
+        info: &CustomDepInfo,
+        locked: Option<CustomDepVersion>,
+    ) -> anyhow::Result<Option<CustomDepVersion>> {
+        let version = locked.unwrap_or(*LATEST_VERSION.lock().unwrap());
+        fs::create_dir_all(info.download_to.join("sources"))?;
+        fs::write(
+            info.download_to.join("Move.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.0.0\"\n",
+                info.package_name
+            ),
+        )?;
+        fs::write(
+            info.download_to.join("sources/m.move"),
+            format!(
+                "module {}::m {{ public fun value(): u64 {{ {} }} }}",
+                info.package_address, version.upgrade_number
+            ),
+        )?;
+        Ok(Some(version))
+    }
+}
+
+fn git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@test"])
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn commit_dep_source(repo: &Path, value: u64) -> String {
+    fs::write(
+        repo.join("sources/dep.move"),
+        format!(
+            "module 0x1::dep {{ public fun value(): u64 {{ {} }} }}",
+            value
+        ),
+    )
+    .unwrap();
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-q", "-m", "update"]);
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+/// Resolves the dependencies of the package at `root`, and returns its lock file and the output
+fn resolve_with_output(root: &Path, update: bool) -> (LockFile, String) {
+    Lazy::force(&MOVE_HOME);
+    let config = BuildConfig {
+        install_dir: Some(root.join("build")),
+        ..Default::default()
+    };
+    let mut output = Vec::new();
+    if update {
+        config.update_lock_file_for_package(root, &mut output)
+    } else {
+        let manifest = MP::parse_move_manifest_from_file(root).unwrap();
+        RG::ResolutionGraph::new(manifest, root.to_path_buf(), config, &mut output)
+            .and_then(|graph| graph.resolve())
+            .map(|_| ())
+    }
+    .unwrap();
+    (
+        LockFile::read(root).unwrap().unwrap(),
+        String::from_utf8(output).unwrap(),
+    )
+}
+
+fn resolve(root: &Path, update: bool) -> LockFile {
+    resolve_with_output(root, update).0
+}
+
+fn write_root_package(root: &Path, dependencies: &str) {
+    fs::create_dir_all(root.join("sources")).unwrap();
+    fs::write(
+        root.join("Move.toml"),
+        format!(
+            "[package]\nname = \"Root\"\nversion = \"0.0.0\"\n\n[dependencies]\n{}",
+            dependencies
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_lock_file_pins_git_dependency() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("dep");
+    fs::create_dir_all(repo.join("sources")).unwrap();
+    fs::write(
+        repo.join("Move.toml"),
+        "[package]\nname = \"Dep\"\nversion = \"0.0.0\"\n",
+    )
+    .unwrap();
+    git(&repo, &["init", "-q"]);
+    git(&repo, &["checkout", "-q", "-b", "main"]);
+    let first_commit = commit_dep_source(&repo, 1);
+
+    let root = dir.path().join("root");
+    write_root_package(
+        &root,
+        &format!("Dep = {{ git = \"{}\", rev = \"main\" }}\n", repo.display()),
+    );
+
+    // The first build records the commit of the branch
+    let lock_file = resolve(&root, false);
+    let locked = &lock_file.dependencies["Dep"];
+    assert_eq!(locked.rev.as_deref(), Some("main"));
+    assert_eq!(locked.commit.as_deref(), Some(first_commit.as_str()));
+
+    // Later builds stay on the recorded commit when the branch moves
+    let second_commit = commit_dep_source(&repo, 2);
+    assert_eq!(resolve(&root, false), lock_file);
+
+    // Until the lock file is updated
+    let lock_file = resolve(&root, true);
+    let locked = &lock_file.dependencies["Dep"];
+    assert_eq!(locked.commit.as_deref(), Some(second_commit.as_str()));
+    assert_eq!(resolve(&root, false), lock_file);
+}
+
+#[test]
+fn test_lock_file_pins_onchain_dependency() {
+    register_package_hooks(Box::new(TestHooks));
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("root");
+    write_root_package(
+        &root,
+        "OnChain = { node = \"http://localhost:8080\", address = \"0xc0ffee\" }\n",
+    );
+
+    // The first build records the latest version of the package
+    let lock_file = resolve(&root, false);
+    let locked = &lock_file.dependencies["OnChain"];
+    assert_eq!(locked.node.as_deref(), Some("http://localhost:8080"));
+    assert_eq!(locked.upgrade_number, Some(1));
+    assert_eq!(locked.ledger_version, Some(100));
+
+    // Later builds fetch the recorded version when the package is upgraded
+    *LATEST_VERSION.lock().unwrap() = CustomDepVersion {
+        upgrade_number: 2,
+        ledger_version: 200,
+    };
+    assert_eq!(resolve(&root, false), lock_file);
+
+    // Dependencies added to the manifest are not recorded by builds
+    write_root_package(
+        &root,
+        "OnChain = { node = \"http://localhost:8080\", address = \"0xc0ffee\" }\n\
+        Other = { node = \"http://localhost:8080\", address = \"0xbeef\" }\n",
+    );
+    let (unchanged_lock_file, output) = resolve_with_output(&root, false);
+    assert_eq!(unchanged_lock_file, lock_file);
+    assert!(output.contains("Move.lock does not pin all the remote dependencies"));
+
+    // Until the lock file is updated
+    let lock_file = resolve(&root, true);
+    assert_eq!(lock_file.dependencies["OnChain"].upgrade_number, Some(2));
+    assert_eq!(lock_file.dependencies["Other"].ledger_version, Some(200));
+    assert_eq!(resolve(&root, false), lock_file);
+}
@@ -7,7 +7,7 @@ pub mod extensions;
 pub mod test_reporter;
 pub mod test_runner;
 
-use crate::test_runner::TestRunner;
+use crate::{test_reporter::ReportFormat, test_runner::TestRunner};
 use clap::*;
 use move_command_line_common::files::verify_and_create_named_address_mapping;
 use move_compiler::{
@@ -22,8 +22,10 @@ use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::{
     collections::BTreeMap,
-    io::{Result, Write},
+    fs::File,
+    io::{BufWriter, Result, Write},
     marker::Send,
+    path::PathBuf,
     sync::Mutex,
 };
 
@@ -107,6 +109,19 @@ pub struct UnitTestingConfig {
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
 
+    /// Write a machine-readable report of the test results in the given format
+    #[clap(
+        name = "report_format",
+        long = "report",
+        value_enum,
+        requires = "report_file"
+    )]
+    pub report_format: Option<ReportFormat>,
+
+    /// File to write the report of the test results to
+    #[clap(name = "report_file", long = "report-file", requires = "report_format")]
+    pub report_file: Option<PathBuf>,
+
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
     #[cfg(feature = "evm-backend")]
@@ -137,6 +152,8 @@ impl UnitTestingConfig {
             dep_files: vec![],
             check_stackless_vm: false,
             verbose: false,
+            report_format: None,
+            report_file: None,
             list: false,
             named_address_values: vec![],
 
@@ -262,6 +279,12 @@ impl UnitTestingConfig {
             test_results.report_goldens(&shared_writer)?;
         }
 
+        if let (Some(format), Some(report_file)) = (self.report_format, &self.report_file) {
+            let mut report_writer = BufWriter::new(File::create(report_file)?);
+            test_results.report(format, &mut report_writer)?;
+            report_writer.flush()?;
+        }
+
         let ok = test_results.summarize(&shared_writer)?;
 
         let writer = shared_writer.into_inner().unwrap();
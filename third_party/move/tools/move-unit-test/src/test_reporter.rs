@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::format_module_id;
+use clap::ValueEnum;
 use codespan_reporting::files::{Files, SimpleFiles};
 use colored::{control, Colorize};
 use move_binary_format::{
@@ -15,10 +16,16 @@ use move_compiler::{
     diagnostics::{self, Diagnostic, Diagnostics},
     unit_test::{ModuleTestPlan, TestName, TestPlan},
 };
-use move_core_types::{effects::ChangeSet, language_storage::ModuleId, vm_status::StatusType};
+use move_core_types::{
+    effects::ChangeSet,
+    language_storage::ModuleId,
+    vm_status::{StatusCode, StatusType},
+};
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
 use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::json;
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     io::{Result, Write},
@@ -81,6 +88,21 @@ pub struct TestResults {
     test_plan: TestPlan,
 }
 
+/// Machine-readable formats of the test results
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    /// JUnit XML, with a test suite per module
+    Junit,
+    /// JSON lines, with an object per test
+    Json,
+}
+
+/// The result of a test, as reported in machine-readable formats
+struct TestReport<'a> {
+    run_info: &'a TestRunInfo,
+    failure: Option<&'a TestFailure>,
+}
+
 impl TestRunInfo {
     pub fn new(function_ident: String, elapsed_time: Duration, instructions_executed: u64) -> Self {
         Self {
@@ -142,6 +164,43 @@ impl FailureReason {
     pub fn move_to_evm_error(diagnostics: String) -> Self {
         FailureReason::MoveToEVMError(diagnostics)
     }
+
+    /// Returns the kind of failure, as reported in machine-readable formats
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FailureReason::NoError(_) => "no_error",
+            FailureReason::WrongError(..) | FailureReason::WrongAbortDEPRECATED(..) => {
+                "wrong_error"
+            },
+            FailureReason::UnexpectedError(..) => "unexpected_error",
+            FailureReason::Timeout(_) => "timeout",
+            FailureReason::Mismatch { .. } => "mismatch",
+            FailureReason::Property(_) => "property",
+            #[cfg(feature = "evm-backend")]
+            FailureReason::MoveToEVMError(_) => "move_to_evm_error",
+        }
+    }
+
+    /// Returns a one line description of the failure
+    pub fn headline(&self) -> String {
+        match self {
+            FailureReason::NoError(message)
+            | FailureReason::WrongError(message, ..)
+            | FailureReason::WrongAbortDEPRECATED(message, ..)
+            | FailureReason::UnexpectedError(message, _)
+            | FailureReason::Timeout(message) => message.clone(),
+            FailureReason::Mismatch { .. } => {
+                "Executions via Move VM and stackless VM yield different results".to_string()
+            },
+            FailureReason::Property(message) => {
+                message.lines().next().unwrap_or_default().to_string()
+            },
+            #[cfg(feature = "evm-backend")]
+            FailureReason::MoveToEVMError(_) => {
+                "Failed to compile Move code into EVM bytecode".to_string()
+            },
+        }
+    }
 }
 
 impl TestFailure {
@@ -251,6 +310,46 @@ impl TestFailure {
         }
     }
 
+    /// Returns the abort code of the test, if it aborted
+    pub fn abort_code(&self) -> Option<u64> {
+        let vm_error = self.vm_error.as_ref()?;
+        if vm_error.major_status() == StatusCode::ABORTED {
+            vm_error.sub_status()
+        } else {
+            None
+        }
+    }
+
+    /// Returns where the test failed in the sources, as `<file>:<line>`, if known
+    pub fn error_location(&self, test_plan: &TestPlan) -> Option<String> {
+        let vm_error = self.vm_error.as_ref()?;
+        let Location::Module(module_id) = vm_error.location() else {
+            return None;
+        };
+        let (fdef_idx, offset) = vm_error.offsets().first()?;
+        let loc = test_plan
+            .module_info
+            .get(module_id)?
+            .source_map
+            .get_function_source_map(*fdef_idx)
+            .ok()?
+            .get_code_location(*offset)?;
+        let (file_name, source) = test_plan.files.get(&loc.file_hash())?;
+        let mut files = SimpleFiles::new();
+        let id = files.add(*file_name, source.as_str());
+        let line_index = files.line_index(id, loc.start() as usize).ok()?;
+        let line_number = files.line_number(id, line_index).ok()?;
+        Some(format!("{}:{}", file_name, line_number))
+    }
+
+    /// Renders the error without colors, for machine-readable formats
+    fn render_plain_error(&self, test_plan: &TestPlan) -> String {
+        static ANSI_ESCAPE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+        ANSI_ESCAPE
+            .replace_all(&self.render_error(test_plan), "")
+            .to_string()
+    }
+
     fn get_line_number(
         loc: &Loc,
         files: &SimpleFiles<Symbol, &str>,
@@ -557,6 +656,139 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// Writes the results of all tests in a machine-readable format
+    pub fn report<W: Write>(&self, format: ReportFormat, writer: &mut W) -> Result<()> {
+        match format {
+            ReportFormat::Junit => self.report_junit(writer),
+            ReportFormat::Json => self.report_json(writer),
+        }
+    }
+
+    /// Returns the results of the tests by module, ordered by test name
+    fn test_reports(&self) -> BTreeMap<&ModuleId, Vec<TestReport<'_>>> {
+        let mut reports: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (module_id, test_results) in self.final_statistics.passed.iter() {
+            reports
+                .entry(module_id)
+                .or_default()
+                .extend(test_results.iter().map(|run_info| TestReport {
+                    run_info,
+                    failure: None,
+                }));
+        }
+        for (module_id, test_failures) in self.final_statistics.failed.iter() {
+            reports
+                .entry(module_id)
+                .or_default()
+                .extend(test_failures.iter().map(|failure| TestReport {
+                    run_info: &failure.test_run_info,
+                    failure: Some(failure),
+                }));
+        }
+        for tests in reports.values_mut() {
+            tests.sort_by(|a, b| a.run_info.function_ident.cmp(&b.run_info.function_ident));
+        }
+        reports
+    }
+
+    fn report_json<W: Write>(&self, writer: &mut W) -> Result<()> {
+        for (module_id, tests) in self.test_reports() {
+            let module_name = format_module_id(module_id);
+            for test in tests {
+                let mut report = json!({
+                    "name": format!("{}::{}", module_name, test.run_info.function_ident),
+                    "module": module_name,
+                    "test": test.run_info.function_ident,
+                    "status": "passed",
+                    "duration_secs": test.run_info.elapsed_time.as_secs_f64(),
+                    "gas_used": test.run_info.instructions_executed,
+                });
+                if let Some(failure) = test.failure {
+                    report["status"] = json!(match failure.failure_reason {
+                        FailureReason::Timeout(_) => "timeout",
+                        _ => "failed",
+                    });
+                    report["failure"] = json!(failure.failure_reason.kind());
+                    report["abort_code"] = json!(failure.abort_code());
+                    report["location"] = json!(failure.error_location(&self.test_plan));
+                    report["message"] = json!(failure.render_plain_error(&self.test_plan));
+                }
+                writeln!(writer, "{}", report)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn report_junit<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let reports = self.test_reports();
+        let total_time = |tests: &[TestReport]| {
+            tests
+                .iter()
+                .map(|test| test.run_info.elapsed_time.as_secs_f64())
+                .sum::<f64>()
+        };
+        let failures = |tests: &[TestReport]| tests.iter().filter(|t| t.failure.is_some()).count();
+
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuites name="Move unit tests" tests="{}" failures="{}" time="{:.3}">"#,
+            reports.values().map(Vec::len).sum::<usize>(),
+            reports.values().map(|tests| failures(tests)).sum::<usize>(),
+            reports.values().map(|tests| total_time(tests)).sum::<f64>(),
+        )?;
+        for (module_id, tests) in reports.iter() {
+            let module_name = xml_escape(&format_module_id(module_id));
+            writeln!(
+                writer,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.3}">"#,
+                module_name,
+                tests.len(),
+                failures(tests),
+                total_time(tests),
+            )?;
+            for test in tests {
+                writeln!(
+                    writer,
+                    r#"    <testcase name="{}" classname="{}" time="{:.3}">"#,
+                    xml_escape(&test.run_info.function_ident),
+                    module_name,
+                    test.run_info.elapsed_time.as_secs_f64(),
+                )?;
+                writeln!(writer, "      <properties>")?;
+                writeln!(
+                    writer,
+                    r#"        <property name="gas_used" value="{}"/>"#,
+                    test.run_info.instructions_executed
+                )?;
+                if let Some(abort_code) = test.failure.and_then(TestFailure::abort_code) {
+                    writeln!(
+                        writer,
+                        r#"        <property name="abort_code" value="{}"/>"#,
+                        abort_code
+                    )?;
+                }
+                writeln!(writer, "      </properties>")?;
+                if let Some(failure) = test.failure {
+                    let mut message = failure.failure_reason.headline();
+                    if let Some(location) = failure.error_location(&self.test_plan) {
+                        message = format!("{} at {}", message, location);
+                    }
+                    writeln!(
+                        writer,
+                        r#"      <failure message="{}" type="{}">{}</failure>"#,
+                        xml_escape(&message),
+                        failure.failure_reason.kind(),
+                        xml_escape(&failure.render_plain_error(&self.test_plan)),
+                    )?;
+                }
+                writeln!(writer, "    </testcase>")?;
+            }
+            writeln!(writer, "  </testsuite>")?;
+        }
+        writeln!(writer, "</testsuites>")
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
     pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
@@ -610,3 +842,11 @@ impl TestResults {
         Ok(num_failed_tests == 0)
     }
 }
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
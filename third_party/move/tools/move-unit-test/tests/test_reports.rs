@@ -0,0 +1,69 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_unit_test::{test_reporter::ReportFormat, UnitTestingConfig};
+use std::{fs, path::PathBuf};
+use tempfile::tempdir;
+
+fn run_with_report(format: ReportFormat) -> String {
+    let dir = tempdir().unwrap();
+    let report_file = dir.path().join("report");
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let testing_config = UnitTestingConfig {
+        num_threads: 1,
+        source_files: vec![path
+            .join("tests/test_sources/cross_module_aborts.move")
+            .to_string_lossy()
+            .to_string()],
+        dep_files: move_stdlib::move_stdlib_files(),
+        report_format: Some(format),
+        report_file: Some(report_file.clone()),
+        ..UnitTestingConfig::default_with_bound(None)
+    }
+    .with_named_addresses(move_stdlib::move_stdlib_named_addresses());
+
+    let test_plan = testing_config.build_test_plan().unwrap();
+    let (_, ok) = testing_config
+        .run_and_report_unit_tests(test_plan, None, None, Vec::new())
+        .unwrap();
+    assert!(!ok);
+    fs::read_to_string(report_file).unwrap()
+}
+
+#[test]
+fn test_json_report() {
+    let report = run_with_report(ReportFormat::Json);
+    let tests = report
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(tests.len(), 2);
+
+    let failed = &tests[0];
+    assert_eq!(failed["name"], "0x1::B::failing_test");
+    assert_eq!(failed["status"], "failed");
+    assert_eq!(failed["failure"], "unexpected_error");
+    assert_eq!(failed["abort_code"], 0);
+    assert!(failed["location"]
+        .as_str()
+        .unwrap()
+        .ends_with("cross_module_aborts.move:5"));
+    assert!(!failed["message"].as_str().unwrap().contains('\x1b'));
+
+    let passed = &tests[1];
+    assert_eq!(passed["name"], "0x1::M::dummy_test");
+    assert_eq!(passed["status"], "passed");
+    assert!(passed["gas_used"].as_u64().is_some());
+    assert!(passed.get("abort_code").is_none());
+}
+
+#[test]
+fn test_junit_report() {
+    let report = run_with_report(ReportFormat::Junit);
+    assert!(report.contains(r#"<testsuites name="Move unit tests" tests="2" failures="1""#));
+    assert!(report.contains(r#"<testsuite name="0x1::B" tests="1" failures="1""#));
+    assert!(report.contains(r#"<testcase name="failing_test" classname="0x1::B""#));
+    assert!(report.contains(r#"<property name="abort_code" value="0"/>"#));
+    assert!(report.contains(r#"type="unexpected_error">"#));
+    assert!(report.contains(r#"<testsuite name="0x1::M" tests="1" failures="0""#));
+}
@@ -74,6 +74,12 @@ impl AccountResource {
     pub fn signer_capability_offer(&self) -> Option<AccountAddress> {
         self.signer_capability_offer
     }
+
+    /// Returns the resource with the authentication key replaced, as if the key was rotated
+    pub fn with_authentication_key(mut self, authentication_key: Vec<u8>) -> Self {
+        self.authentication_key = authentication_key;
+        self
+    }
 }
 
 impl MoveStructType for AccountResource {
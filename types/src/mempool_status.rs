@@ -64,6 +64,8 @@ pub enum MempoolStatusCode {
     // transaction didn't pass vm_validation
     VmError = 5,
     UnknownStatus = 6,
+    // Transaction was denied by the transaction filter of the node
+    RejectedByFilter = 7,
 }
 
 impl TryFrom<u64> for MempoolStatusCode {
@@ -78,6 +80,7 @@ impl TryFrom<u64> for MempoolStatusCode {
             4 => Ok(MempoolStatusCode::InvalidUpdate),
             5 => Ok(MempoolStatusCode::VmError),
             6 => Ok(MempoolStatusCode::UnknownStatus),
+            7 => Ok(MempoolStatusCode::RejectedByFilter),
             _ => Err("invalid StatusCode"),
         }
     }
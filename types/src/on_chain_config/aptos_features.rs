@@ -49,6 +49,8 @@ pub enum FeatureFlag {
     OPERATOR_BENEFICIARY_CHANGE = 39,
     VM_BINARY_FORMAT_V7 = 40,
     RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM = 41,
+    WEBAUTHN_SIGNATURE = 42,
+    BN254_STRUCTURES = 43,
 }
 
 /// Representation of features on chain as a bitset.
@@ -83,7 +85,7 @@ impl OnChainConfig for Features {
 }
 
 impl Features {
-    fn enable(&mut self, flag: FeatureFlag) {
+    pub fn enable(&mut self, flag: FeatureFlag) {
         let byte_index = (flag as u64 / 8) as usize;
         let bit_mask = 1 << (flag as u64 % 8);
         while self.features.len() <= byte_index {
@@ -142,4 +144,9 @@ impl Features {
     pub fn is_resource_group_charge_as_size_sum_enabled(&self) -> bool {
         self.is_enabled(FeatureFlag::RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM)
     }
+
+    /// Whether WebAuthn (passkey) signatures are accepted in transaction authenticators.
+    pub fn is_webauthn_signature_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::WEBAUTHN_SIGNATURE)
+    }
 }
@@ -374,6 +374,216 @@ impl SparseMerkleProof {
     }
 }
 
+/// A proof that can be used to authenticate several elements in a Sparse Merkle Tree at once given
+/// trusted root hash. It carries the same information as a `SparseMerkleProof` of each element,
+/// except that a sibling is included only once even if it is on the route of several elements, and
+/// not at all if it is on the route of another element and can therefore be computed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SparseMerkleMultiProof {
+    /// The leaf node at the end of the route of each element, in ascending order of element key,
+    /// with the same meaning as `SparseMerkleProof::leaf`. Elements whose routes end at the same
+    /// node have the same leaf.
+    leaves: Vec<Option<SparseMerkleLeafNode>>,
+
+    /// The depth at which the route of each element ends, in ascending order of element key, which
+    /// is the number of siblings in the `SparseMerkleProof` of the element.
+    depths: Vec<u16>,
+
+    /// The siblings which are not on the route of any element, in the order they are met by a
+    /// depth-first, left-to-right traversal of the routes of the elements.
+    siblings: Vec<HashValue>,
+}
+
+impl SparseMerkleMultiProof {
+    /// Constructs a new `SparseMerkleMultiProof` using the leaf and depth of each element and a
+    /// list of siblings.
+    pub fn new(
+        leaves: Vec<Option<SparseMerkleLeafNode>>,
+        depths: Vec<u16>,
+        siblings: Vec<HashValue>,
+    ) -> Self {
+        Self {
+            leaves,
+            depths,
+            siblings,
+        }
+    }
+
+    /// Returns the leaf node of each element in this proof.
+    pub fn leaves(&self) -> &[Option<SparseMerkleLeafNode>] {
+        &self.leaves
+    }
+
+    /// Returns the depth of the route of each element in this proof.
+    pub fn depths(&self) -> &[u16] {
+        &self.depths
+    }
+
+    /// Returns the list of siblings in this proof.
+    pub fn siblings(&self) -> &[HashValue] {
+        &self.siblings
+    }
+
+    pub fn verify<V: CryptoHash>(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<&V>)],
+    ) -> Result<()> {
+        let elements: Vec<_> = elements
+            .iter()
+            .map(|(key, value)| (*key, value.map(|v| v.hash())))
+            .collect();
+        self.verify_by_hash(expected_root_hash, &elements)
+    }
+
+    /// Verifies each element, given as a key and the hash of its value if it exists, the same way
+    /// `SparseMerkleProof::verify_by_hash` does, with all of them proven against the same root
+    /// hash. Elements must be given in strictly ascending order of key.
+    pub fn verify_by_hash(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<HashValue>)],
+    ) -> Result<()> {
+        ensure!(
+            !elements.is_empty(),
+            "Sparse Merkle Tree multiproof must prove at least one element."
+        );
+        ensure!(
+            elements.windows(2).all(|pair| pair[0].0 < pair[1].0),
+            "Elements must be in strictly ascending order of key."
+        );
+        ensure!(
+            self.leaves.len() == elements.len() && self.depths.len() == elements.len(),
+            "Sparse Merkle Tree multiproof has {} leaves and {} depths for {} elements.",
+            self.leaves.len(),
+            self.depths.len(),
+            elements.len(),
+        );
+
+        for ((element_key, element_hash), (leaf, depth)) in elements
+            .iter()
+            .zip(self.leaves.iter().zip(self.depths.iter()))
+        {
+            let depth = *depth as usize;
+            ensure!(
+                depth <= HashValue::LENGTH_IN_BITS,
+                "Sparse Merkle Tree multiproof has a route deeper than {} ({}).",
+                HashValue::LENGTH_IN_BITS,
+                depth,
+            );
+            match (element_hash, leaf) {
+                (Some(hash), Some(leaf)) => {
+                    ensure!(
+                        *element_key == leaf.key,
+                        "Keys do not match. Key in proof: {:x}. Expected key: {:x}.",
+                        leaf.key,
+                        element_key,
+                    );
+                    ensure!(
+                        *hash == leaf.value_hash,
+                        "Value hashes do not match for key {:x}. Value hash in proof: {:x}. \
+                         Expected value hash: {:x}.",
+                        element_key,
+                        leaf.value_hash,
+                        hash
+                    );
+                },
+                (Some(hash), None) => {
+                    bail!(
+                        "Expected inclusion proof of key {:x}, value hash: {:x}. Found \
+                         non-inclusion proof.",
+                        element_key,
+                        hash
+                    )
+                },
+                (None, Some(leaf)) => {
+                    ensure!(
+                        *element_key != leaf.key,
+                        "Expected non-inclusion proof, but key exists in proof. Key: {:x}.",
+                        element_key,
+                    );
+                    ensure!(
+                        element_key.common_prefix_bits_len(leaf.key) >= depth,
+                        "Key would not have ended up in the subtree where the provided key in \
+                         proof is the only existing key, if it existed. So this is not a valid \
+                         non-inclusion proof. Key: {:x}. Key in proof: {:x}.",
+                        element_key,
+                        leaf.key
+                    );
+                },
+                (None, None) => {},
+            }
+        }
+
+        let keys: Vec<_> = elements.iter().map(|(key, _)| *key).collect();
+        let mut siblings = self.siblings.iter();
+        let actual_root_hash = self.subtree_hash(&keys, 0, 0, &mut siblings)?;
+        ensure!(
+            siblings.next().is_none(),
+            "Sparse Merkle Tree multiproof has more siblings than needed.",
+        );
+        ensure!(
+            actual_root_hash == expected_root_hash,
+            "{}: Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            type_name::<Self>(),
+            actual_root_hash,
+            expected_root_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Computes the hash of the subtree at `depth` containing the routes of `keys`, which are the
+    /// elements of this proof starting at index `first`.
+    fn subtree_hash<'a>(
+        &self,
+        keys: &[HashValue],
+        first: usize,
+        depth: usize,
+        siblings: &mut impl Iterator<Item = &'a HashValue>,
+    ) -> Result<HashValue> {
+        let leaves = &self.leaves[first..first + keys.len()];
+        let depths = &self.depths[first..first + keys.len()];
+
+        if depths[0] as usize == depth {
+            // The routes of all these keys end at this node.
+            ensure!(
+                depths.iter().all(|d| *d as usize == depth)
+                    && leaves.iter().all(|l| *l == leaves[0]),
+                "Sparse Merkle Tree multiproof has routes ending at different nodes at depth {}.",
+                depth,
+            );
+            return Ok(leaves[0].map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash()));
+        }
+        ensure!(
+            depth < HashValue::LENGTH_IN_BITS && depths.iter().all(|d| *d as usize > depth),
+            "Sparse Merkle Tree multiproof has routes ending at different nodes at depth {}.",
+            depth,
+        );
+
+        // Either side of this node without any of the keys is a sibling.
+        let split = keys.partition_point(|key| !key.bit(depth));
+        let left_hash = if split == 0 {
+            siblings
+                .next()
+                .copied()
+                .ok_or_else(|| format_err!("Sparse Merkle Tree multiproof has too few siblings."))?
+        } else {
+            self.subtree_hash(&keys[..split], first, depth + 1, siblings)?
+        };
+        let right_hash = if split == keys.len() {
+            siblings
+                .next()
+                .copied()
+                .ok_or_else(|| format_err!("Sparse Merkle Tree multiproof has too few siblings."))?
+        } else {
+            self.subtree_hash(&keys[split..], first + split, depth + 1, siblings)?
+        };
+
+        Ok(SparseMerkleInternalNode::new(left_hash, right_hash).hash())
+    }
+}
+
 /// An in-memory accumulator for storing a summary of the core transaction info
 /// accumulator. It is a summary in the sense that it only stores maximally
 /// frozen subtree nodes rather than storing all leaves and internal nodes.
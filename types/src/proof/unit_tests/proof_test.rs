@@ -11,9 +11,9 @@ use crate::{
     ledger_info::LedgerInfo,
     proof::{
         definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccumulatorExtensionProof, AccumulatorRangeProof,
-        SparseMerkleInternalNode, SparseMerkleLeafNode, TestAccumulatorInternalNode,
-        TestAccumulatorProof, TransactionAccumulatorInternalNode, TransactionAccumulatorProof,
-        TransactionInfoListWithProof, TransactionInfoWithProof,
+        SparseMerkleInternalNode, SparseMerkleLeafNode, SparseMerkleMultiProof,
+        TestAccumulatorInternalNode, TestAccumulatorProof, TransactionAccumulatorInternalNode,
+        TransactionAccumulatorProof, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_store::state_value::StateValue,
     transaction::{
@@ -266,6 +266,106 @@ fn test_verify_three_element_sparse_merkle() {
     }
 }
 
+#[test]
+fn test_verify_three_element_sparse_merkle_multiproof() {
+    // Same tree as in `test_verify_three_element_sparse_merkle`.
+    //            root
+    //           /    \
+    //          a      default
+    //         / \
+    //     key1   b
+    //           / \
+    //       key2   key3
+    let key1 = b"hello".test_only_hash();
+    let key2 = b"world".test_only_hash();
+    let key3 = b"!".test_only_hash();
+    let non_existing_key1 = b"abc".test_only_hash();
+    let non_existing_key2 = b"def".test_only_hash();
+
+    let blob1 = StateValue::from(b"1".to_vec());
+    let blob2 = StateValue::from(b"2".to_vec());
+    let blob3 = StateValue::from(b"3".to_vec());
+
+    let leaf1 = SparseMerkleLeafNode::new(key1, blob1.hash());
+    let leaf2 = SparseMerkleLeafNode::new(key2, blob2.hash());
+    let leaf3_hash = SparseMerkleLeafNode::new(key3, blob3.hash()).hash();
+    let internal_b_hash = SparseMerkleInternalNode::new(leaf2.hash(), leaf3_hash).hash();
+    let internal_a_hash = SparseMerkleInternalNode::new(leaf1.hash(), internal_b_hash).hash();
+    let root_hash =
+        SparseMerkleInternalNode::new(internal_a_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH).hash();
+
+    // Proves key1, non_existing_key1 (ending at the leaf of key1), key2 and non_existing_key2
+    // (ending at the default node). Only the sibling of key2 is not on the route of another key.
+    let proof = SparseMerkleMultiProof::new(
+        vec![Some(leaf1), Some(leaf1), Some(leaf2), None],
+        vec![2, 2, 3, 1],
+        vec![leaf3_hash],
+    );
+    assert!(proof
+        .verify(root_hash, &[
+            (key1, Some(&blob1)),
+            (non_existing_key1, None),
+            (key2, Some(&blob2)),
+            (non_existing_key2, None),
+        ])
+        .is_ok());
+
+    // Trying to show that key2 has another value.
+    assert!(proof
+        .verify(root_hash, &[
+            (key1, Some(&blob1)),
+            (non_existing_key1, None),
+            (key2, Some(&blob1)),
+            (non_existing_key2, None),
+        ])
+        .is_err());
+    // Trying to show that non_existing_key2 exists.
+    assert!(proof
+        .verify(root_hash, &[
+            (key1, Some(&blob1)),
+            (non_existing_key1, None),
+            (key2, Some(&blob2)),
+            (non_existing_key2, Some(&blob3)),
+        ])
+        .is_err());
+    // The elements must be in ascending order of key.
+    assert!(proof
+        .verify(root_hash, &[
+            (non_existing_key1, None),
+            (key1, Some(&blob1)),
+            (key2, Some(&blob2)),
+            (non_existing_key2, None),
+        ])
+        .is_err());
+
+    // A proof of fewer elements needs the siblings on the routes of the others.
+    let proof =
+        SparseMerkleMultiProof::new(vec![Some(leaf1), None], vec![2, 1], vec![internal_b_hash]);
+    assert!(proof
+        .verify::<StateValue>(root_hash, &[
+            (key1, Some(&blob1)),
+            (non_existing_key2, None)
+        ])
+        .is_ok());
+    let proof = SparseMerkleMultiProof::new(vec![Some(leaf1), None], vec![2, 1], vec![
+        internal_b_hash,
+        leaf3_hash,
+    ]);
+    assert!(proof
+        .verify::<StateValue>(root_hash, &[
+            (key1, Some(&blob1)),
+            (non_existing_key2, None)
+        ])
+        .is_err());
+    let proof = SparseMerkleMultiProof::new(vec![Some(leaf1), None], vec![2, 1], vec![]);
+    assert!(proof
+        .verify::<StateValue>(root_hash, &[
+            (key1, Some(&blob1)),
+            (non_existing_key2, None)
+        ])
+        .is_err());
+}
+
 #[test]
 fn test_verify_transaction() {
     //            root
@@ -6,6 +6,7 @@ use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use arr_macro::arr;
 use std::collections::HashMap;
 
+pub mod state_diff;
 pub mod state_key;
 pub mod state_key_prefix;
 pub mod state_storage_usage;
@@ -0,0 +1,47 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{state_store::state_key::StateKey, transaction::Version};
+use serde::{Deserialize, Serialize};
+
+/// How the value of a state key changed between two versions of the state.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum StateKeyChange {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// A state key whose value differs between two versions of the state.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateKeyDiff {
+    pub state_key: StateKey,
+    /// The version at which the value at the old version was written, `None` if the key didn't
+    /// exist at the old version.
+    pub old_value_version: Option<Version>,
+    /// The version at which the value at the new version was written, `None` if the key doesn't
+    /// exist at the new version.
+    pub new_value_version: Option<Version>,
+}
+
+impl StateKeyDiff {
+    pub fn new(
+        state_key: StateKey,
+        old_value_version: Option<Version>,
+        new_value_version: Option<Version>,
+    ) -> Self {
+        Self {
+            state_key,
+            old_value_version,
+            new_value_version,
+        }
+    }
+
+    pub fn change(&self) -> StateKeyChange {
+        match (self.old_value_version, self.new_value_version) {
+            (None, _) => StateKeyChange::Added,
+            (_, None) => StateKeyChange::Deleted,
+            (Some(_), Some(_)) => StateKeyChange::Modified,
+        }
+    }
+}
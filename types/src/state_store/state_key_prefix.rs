@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::state_store::state_key::{StateKey, StateKeyTag};
+use crate::state_store::{
+    state_key::{StateKey, StateKeyTag},
+    table::TableHandle,
+};
 use move_core_types::account_address::AccountAddress;
 
 // Struct for defining prefix of a state key, which can be used for finding all the values with a
@@ -34,6 +37,16 @@ impl StateKeyPrefix {
         }
         Ok(encoded_prefix == encoded_key[..encoded_prefix.len()])
     }
+
+    /// Returns the account whose access paths start with this prefix, if any.
+    pub fn account_address(&self) -> Option<AccountAddress> {
+        match self.tag {
+            StateKeyTag::AccessPath => {
+                AccountAddress::from_bytes(self.bytes.get(..AccountAddress::LENGTH)?).ok()
+            },
+            _ => None,
+        }
+    }
 }
 
 impl From<AccountAddress> for StateKeyPrefix {
@@ -42,6 +55,12 @@ impl From<AccountAddress> for StateKeyPrefix {
     }
 }
 
+impl From<TableHandle> for StateKeyPrefix {
+    fn from(handle: TableHandle) -> Self {
+        Self::new(StateKeyTag::TableItem, handle.0.to_vec())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -49,6 +68,7 @@ mod tests {
         state_store::{
             state_key::{StateKey, StateKeyTag},
             state_key_prefix::StateKeyPrefix,
+            table::TableHandle,
         },
     };
     use move_core_types::account_address::AccountAddress;
@@ -69,4 +89,21 @@ mod tests {
         assert!(!account1_key_prefx.is_prefix(&key2).unwrap());
         assert!(!account2_key_prefx.is_prefix(&key1).unwrap());
     }
+
+    #[test]
+    fn test_table_item_key_prefix() {
+        let handle1 = TableHandle(AccountAddress::new([12u8; AccountAddress::LENGTH]));
+        let handle2 = TableHandle(AccountAddress::new([22u8; AccountAddress::LENGTH]));
+        let key1 = StateKey::table_item(handle1, b"key".to_vec());
+        let key2 = StateKey::table_item(handle2, b"key".to_vec());
+
+        let table1_key_prefix = StateKeyPrefix::from(handle1);
+        let table2_key_prefix = StateKeyPrefix::from(handle2);
+
+        assert!(table1_key_prefix.is_prefix(&key1).unwrap());
+        assert!(table2_key_prefix.is_prefix(&key2).unwrap());
+
+        assert!(!table1_key_prefix.is_prefix(&key2).unwrap());
+        assert!(!table2_key_prefix.is_prefix(&key1).unwrap());
+    }
 }
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    on_chain_config::CurrentTimeMicroseconds, proof::SparseMerkleRangeProof,
-    state_store::state_key::StateKey, transaction::Version,
+    on_chain_config::CurrentTimeMicroseconds,
+    proof::{SparseMerkleMultiProof, SparseMerkleRangeProof},
+    state_store::state_key::StateKey,
+    transaction::Version,
 };
 use aptos_crypto::{
     hash::{CryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
@@ -232,6 +234,35 @@ impl StateValueChunkWithProof {
     }
 }
 
+/// The state values of several state keys at a state snapshot version, with a single proof of
+/// all of them against the state Merkle tree root of that version.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValuesWithMultiProof {
+    pub version: Version,     // The state snapshot version of the values
+    pub root_hash: HashValue, // The root hash of the sparse merkle tree at this version
+    pub values: Vec<(StateKey, Option<StateValue>)>, // In ascending order of the hashes of the keys
+    pub proof: SparseMerkleMultiProof, // The proof of all the values against the root hash
+}
+
+impl StateValuesWithMultiProof {
+    /// Verifies the values against a trusted state root hash, e.g. the state checkpoint hash of
+    /// the transaction info at `version`.
+    pub fn verify(&self, expected_root_hash: HashValue) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.root_hash == expected_root_hash,
+            "Root hash {:x} is not the expected root hash {:x}.",
+            self.root_hash,
+            expected_root_hash,
+        );
+        let elements: Vec<_> = self
+            .values
+            .iter()
+            .map(|(key, value)| (key.hash(), value.as_ref()))
+            .collect();
+        self.proof.verify(expected_root_hash, &elements)
+    }
+}
+
 /// Indicates a state value becomes stale since `stale_since_version`.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
@@ -4,14 +4,16 @@
 
 use crate::{
     account_address::AccountAddress,
-    transaction::{RawTransaction, RawTransactionWithData},
+    transaction::{
+        webauthn::PartialAuthenticatorAssertionResponse, RawTransaction, RawTransactionWithData,
+    },
 };
 use anyhow::{bail, ensure, Error, Result};
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
-    secp256k1_ecdsa,
+    p256_ecdsa, secp256k1_ecdsa,
     traits::Signature,
     CryptoMaterialError, HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
@@ -230,6 +232,14 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Return the authenticators of all the signers of the transaction, starting with the sender
+    pub fn all_signers(&self) -> Vec<AccountAuthenticator> {
+        let mut signers = vec![self.sender()];
+        signers.extend(self.secondary_signers());
+        signers.extend(self.fee_payer_signer());
+        signers
+    }
+
     pub fn secondary_signer_addresses(&self) -> Vec<AccountAddress> {
         match self {
             Self::Ed25519 { .. } | Self::MultiEd25519 { .. } | Self::SingleSender { .. } => {
@@ -522,6 +532,18 @@ impl AccountAuthenticator {
         AuthenticationKey::from_preimage(self.public_key_bytes(), self.scheme())
     }
 
+    /// Return whether any of the signatures of this account authenticator is a WebAuthn assertion
+    pub fn has_webauthn_signature(&self) -> bool {
+        match self {
+            Self::Ed25519 { .. } | Self::MultiEd25519 { .. } => false,
+            Self::SingleKey { authenticator } => authenticator.signature().is_webauthn(),
+            Self::MultiKey { authenticator } => authenticator
+                .signatures
+                .iter()
+                .any(|signature| signature.is_webauthn()),
+        }
+    }
+
     /// Return the number of signatures included in this account authenticator.
     pub fn number_of_signatures(&self) -> usize {
         match self {
@@ -884,6 +906,9 @@ pub enum AnySignature {
     Secp256k1Ecdsa {
         signature: secp256k1_ecdsa::Signature,
     },
+    WebAuthn {
+        signature: PartialAuthenticatorAssertionResponse,
+    },
 }
 
 impl AnySignature {
@@ -895,6 +920,14 @@ impl AnySignature {
         Self::Secp256k1Ecdsa { signature }
     }
 
+    pub fn webauthn(signature: PartialAuthenticatorAssertionResponse) -> Self {
+        Self::WebAuthn { signature }
+    }
+
+    pub fn is_webauthn(&self) -> bool {
+        matches!(self, Self::WebAuthn { .. })
+    }
+
     pub fn verify<T: Serialize + CryptoHash>(
         &self,
         public_key: &AnyPublicKey,
@@ -907,6 +940,9 @@ impl AnySignature {
             (Self::Secp256k1Ecdsa { signature }, AnyPublicKey::Secp256k1Ecdsa { public_key }) => {
                 signature.verify(message, public_key)
             },
+            (Self::WebAuthn { signature }, AnyPublicKey::Secp256r1Ecdsa { public_key }) => {
+                signature.verify(message, public_key)
+            },
             _ => bail!("Invalid key, signature pairing"),
         }
     }
@@ -920,6 +956,9 @@ pub enum AnyPublicKey {
     Secp256k1Ecdsa {
         public_key: secp256k1_ecdsa::PublicKey,
     },
+    Secp256r1Ecdsa {
+        public_key: p256_ecdsa::PublicKey,
+    },
 }
 
 impl AnyPublicKey {
@@ -931,6 +970,10 @@ impl AnyPublicKey {
         Self::Secp256k1Ecdsa { public_key }
     }
 
+    pub fn secp256r1_ecdsa(public_key: p256_ecdsa::PublicKey) -> Self {
+        Self::Secp256r1Ecdsa { public_key }
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         bcs::to_bytes(self).expect("Only unhandleable errors happen here.")
     }
@@ -941,7 +984,7 @@ mod tests {
     use super::*;
     use crate::transaction::SignedTransaction;
     use aptos_crypto::{
-        ed25519::Ed25519PrivateKey, secp256k1_ecdsa, PrivateKey, SigningKey, Uniform,
+        ed25519::Ed25519PrivateKey, p256_ecdsa, secp256k1_ecdsa, PrivateKey, SigningKey, Uniform,
     };
 
     #[test]
@@ -1018,6 +1061,55 @@ mod tests {
         signed_txn.verify_signature().unwrap();
     }
 
+    #[test]
+    fn verify_webauthn_single_key_auth() {
+        let fake_sender = Ed25519PrivateKey::generate_for_testing();
+        let fake_sender_pub = fake_sender.public_key();
+
+        let sender = p256_ecdsa::PrivateKey::generate_for_testing();
+        let sender_pub = sender.public_key();
+
+        let single_sender_auth =
+            AuthenticationKey::any_key(AnyPublicKey::secp256r1_ecdsa(sender_pub.clone()));
+        let single_sender_addr = single_sender_auth.account_address();
+
+        let raw_txn = crate::test_helpers::transaction_test_helpers::get_test_signed_transaction(
+            single_sender_addr,
+            0,
+            &fake_sender,
+            fake_sender_pub.clone(),
+            None,
+            0,
+            0,
+            None,
+        )
+        .into_raw_transaction();
+
+        let assertion =
+            PartialAuthenticatorAssertionResponse::sign_for_testing(&sender, &raw_txn).unwrap();
+        let sk_auth = SingleKeyAuthenticator::new(
+            AnyPublicKey::secp256r1_ecdsa(sender_pub),
+            AnySignature::webauthn(assertion),
+        );
+        let account_auth = AccountAuthenticator::single_key(sk_auth);
+        assert!(account_auth.has_webauthn_signature());
+        let signed_txn = SignedTransaction::new_single_sender(raw_txn.clone(), account_auth);
+        signed_txn.verify_signature().unwrap();
+
+        // A WebAuthn assertion doesn't verify against a secp256k1 key
+        let assertion =
+            PartialAuthenticatorAssertionResponse::sign_for_testing(&sender, &raw_txn).unwrap();
+        let sk_auth = SingleKeyAuthenticator::new(
+            AnyPublicKey::secp256k1_ecdsa(
+                secp256k1_ecdsa::PrivateKey::generate_for_testing().public_key(),
+            ),
+            AnySignature::webauthn(assertion),
+        );
+        let account_auth = AccountAuthenticator::single_key(sk_auth);
+        let signed_txn = SignedTransaction::new_single_sender(raw_txn, account_auth);
+        signed_txn.verify_signature().unwrap_err();
+    }
+
     #[test]
     fn verify_multi_key_auth() {
         let sender0 = Ed25519PrivateKey::generate_for_testing();
@@ -0,0 +1,304 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! WebAuthn (passkey) assertions used to sign transactions.
+//!
+//! A passkey doesn't sign the transaction directly. The authenticator signs
+//! `authenticator_data || SHA-256(client_data_json)`, where `client_data_json` is a JSON document
+//! produced by the client whose `challenge` field carries the message to be signed. Here the
+//! challenge is the SHA3-256 hash of the signing message of the transaction, base64url encoded
+//! without padding.
+//!
+//! The signature must be the 64 bytes `r || s` encoding of the ECDSA signature with `s` in its
+//! low-S canonical form, i.e. `s <= n / 2`. Authenticators return DER encoded signatures with
+//! either value of `s`, so clients have to convert them and, when `s` is high, replace it by
+//! `n - s` (see `p256_ecdsa::Signature::make_canonical`). Otherwise about half of the assertions
+//! are rejected as malleable.
+//!
+//! See <https://www.w3.org/TR/webauthn-3/#sctn-verifying-assertion>.
+
+use anyhow::{ensure, Context, Result};
+use aptos_crypto::{hash::CryptoHash, p256_ecdsa, traits::signing_message, HashValue, Signature};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The `type` of the client data of an assertion, as opposed to a credential creation.
+const WEBAUTHN_GET_TYPE: &str = "webauthn.get";
+
+/// The authenticator data is made of the 32 bytes RP ID hash, a flags byte and a 4 bytes
+/// signature counter, optionally followed by extensions.
+const MIN_AUTHENTICATOR_DATA_LENGTH: usize = 37;
+
+/// The index of the flags byte in the authenticator data.
+const FLAGS_INDEX: usize = 32;
+
+/// The UP flag, set when the user was present when the assertion was made.
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+/// The signature of a WebAuthn assertion
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AssertionSignature {
+    Secp256r1Ecdsa { signature: p256_ecdsa::Signature },
+}
+
+/// The parts of the `AuthenticatorAssertionResponse` returned by `navigator.credentials.get()`
+/// that are needed to verify the assertion.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PartialAuthenticatorAssertionResponse {
+    signature: AssertionSignature,
+    authenticator_data: Vec<u8>,
+    client_data_json: Vec<u8>,
+}
+
+/// The fields of the client data of an assertion that are checked during verification.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+}
+
+impl PartialAuthenticatorAssertionResponse {
+    pub fn new(
+        signature: AssertionSignature,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            signature,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+
+    pub fn signature(&self) -> &AssertionSignature {
+        &self.signature
+    }
+
+    pub fn authenticator_data(&self) -> &[u8] {
+        &self.authenticator_data
+    }
+
+    pub fn client_data_json(&self) -> &[u8] {
+        &self.client_data_json
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Only unhandleable errors happen here.")
+    }
+
+    /// Returns the challenge a passkey must be asked to sign to authorize `message`.
+    pub fn challenge<T: Serialize + CryptoHash>(message: &T) -> Result<Vec<u8>> {
+        Ok(HashValue::sha3_256_of(&signing_message(message)?).to_vec())
+    }
+
+    /// Returns the data actually signed by the authenticator,
+    /// `authenticator_data || SHA-256(client_data_json)`.
+    pub fn verification_data(&self) -> Vec<u8> {
+        let mut data = self.authenticator_data.clone();
+        data.extend_from_slice(&Sha256::digest(&self.client_data_json));
+        data
+    }
+
+    /// Return Ok if this is a valid assertion of `message` by the passkey of `public_key`,
+    /// Err otherwise. The user must have been present, and the signature must be in low-S form.
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &p256_ecdsa::PublicKey,
+    ) -> Result<()> {
+        ensure!(
+            self.authenticator_data.len() >= MIN_AUTHENTICATOR_DATA_LENGTH,
+            "WebAuthn authenticator data is too short, {} < {}.",
+            self.authenticator_data.len(),
+            MIN_AUTHENTICATOR_DATA_LENGTH,
+        );
+        ensure!(
+            self.authenticator_data[FLAGS_INDEX] & USER_PRESENT_FLAG != 0,
+            "WebAuthn authenticator data doesn't have the user present flag set."
+        );
+
+        let client_data: CollectedClientData = serde_json::from_slice(&self.client_data_json)
+            .context("Failed to parse WebAuthn client data")?;
+        ensure!(
+            client_data.ty == WEBAUTHN_GET_TYPE,
+            "Unexpected WebAuthn client data type, {}.",
+            client_data.ty,
+        );
+        let challenge = base64::decode_config(&client_data.challenge, base64::URL_SAFE_NO_PAD)
+            .context("Failed to decode WebAuthn challenge")?;
+        ensure!(
+            challenge == Self::challenge(message)?,
+            "WebAuthn challenge doesn't match the signed message."
+        );
+
+        match &self.signature {
+            AssertionSignature::Secp256r1Ecdsa { signature } => {
+                signature.verify_arbitrary_msg(&self.verification_data(), public_key)
+            },
+        }
+    }
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+impl PartialAuthenticatorAssertionResponse {
+    /// Asserts `message` the way a passkey would, for testing only.
+    pub fn sign_for_testing<T: Serialize + CryptoHash>(
+        private_key: &p256_ecdsa::PrivateKey,
+        message: &T,
+    ) -> Result<Self> {
+        let client_data_json = serde_json::to_vec(&serde_json::json!({
+            "type": WEBAUTHN_GET_TYPE,
+            "challenge": base64::encode_config(Self::challenge(message)?, base64::URL_SAFE_NO_PAD),
+            "origin": "http://localhost",
+            "crossOrigin": false,
+        }))?;
+        Ok(Self::sign_data_for_testing(
+            private_key,
+            Self::authenticator_data_for_testing(),
+            client_data_json,
+        ))
+    }
+
+    fn authenticator_data_for_testing() -> Vec<u8> {
+        let mut authenticator_data = vec![0; MIN_AUTHENTICATOR_DATA_LENGTH];
+        authenticator_data[FLAGS_INDEX] = USER_PRESENT_FLAG;
+        authenticator_data
+    }
+
+    fn sign_data_for_testing(
+        private_key: &p256_ecdsa::PrivateKey,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        use aptos_crypto::SigningKey;
+
+        let mut signed_data = authenticator_data.clone();
+        signed_data.extend_from_slice(&Sha256::digest(&client_data_json));
+        Self::new(
+            AssertionSignature::Secp256r1Ecdsa {
+                signature: SigningKey::sign_arbitrary_message(private_key, &signed_data),
+            },
+            authenticator_data,
+            client_data_json,
+        )
+    }
+}
+
+impl TryFrom<&[u8]> for PartialAuthenticatorAssertionResponse {
+    type Error = anyhow::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        bcs::from_bytes(bytes).context("Failed to parse WebAuthn assertion")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script},
+    };
+    use aptos_crypto::{PrivateKey, Uniform};
+    use rand::rngs::OsRng;
+
+    fn raw_txn() -> RawTransaction {
+        RawTransaction::new_script(
+            AccountAddress::random(),
+            0,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        )
+    }
+
+    fn client_data_json(ty: &str, challenge: &[u8]) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "type": ty,
+            "challenge": base64::encode_config(challenge, base64::URL_SAFE_NO_PAD),
+            "origin": "https://wallet.example",
+            "crossOrigin": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_webauthn_assertion() {
+        let private_key = p256_ecdsa::PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let txn = raw_txn();
+
+        let assertion =
+            PartialAuthenticatorAssertionResponse::sign_for_testing(&private_key, &txn).unwrap();
+        assertion.verify(&txn, &public_key).unwrap();
+
+        // Round trips through its BCS encoding
+        let decoded =
+            PartialAuthenticatorAssertionResponse::try_from(assertion.to_bytes().as_slice())
+                .unwrap();
+        assert_eq!(decoded, assertion);
+
+        // Another transaction or key doesn't verify
+        assertion.verify(&raw_txn(), &public_key).unwrap_err();
+        let other_public_key = p256_ecdsa::PrivateKey::generate(&mut OsRng).public_key();
+        assertion.verify(&txn, &other_public_key).unwrap_err();
+    }
+
+    #[test]
+    fn reject_malformed_webauthn_assertion() {
+        let private_key = p256_ecdsa::PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let txn = raw_txn();
+        let challenge = PartialAuthenticatorAssertionResponse::challenge(&txn).unwrap();
+
+        // Credential creation instead of an assertion
+        PartialAuthenticatorAssertionResponse::sign_data_for_testing(
+            &private_key,
+            PartialAuthenticatorAssertionResponse::authenticator_data_for_testing(),
+            client_data_json("webauthn.create", &challenge),
+        )
+        .verify(&txn, &public_key)
+        .unwrap_err();
+
+        // Truncated authenticator data
+        PartialAuthenticatorAssertionResponse::sign_data_for_testing(
+            &private_key,
+            vec![USER_PRESENT_FLAG; MIN_AUTHENTICATOR_DATA_LENGTH - 1],
+            client_data_json(WEBAUTHN_GET_TYPE, &challenge),
+        )
+        .verify(&txn, &public_key)
+        .unwrap_err();
+
+        // User not present
+        PartialAuthenticatorAssertionResponse::sign_data_for_testing(
+            &private_key,
+            vec![0; MIN_AUTHENTICATOR_DATA_LENGTH],
+            client_data_json(WEBAUTHN_GET_TYPE, &challenge),
+        )
+        .verify(&txn, &public_key)
+        .unwrap_err();
+
+        // Challenge over the raw transaction bytes rather than its signing message hash
+        PartialAuthenticatorAssertionResponse::sign_data_for_testing(
+            &private_key,
+            PartialAuthenticatorAssertionResponse::authenticator_data_for_testing(),
+            client_data_json(WEBAUTHN_GET_TYPE, &bcs::to_bytes(&txn).unwrap()),
+        )
+        .verify(&txn, &public_key)
+        .unwrap_err();
+
+        // Client data that isn't JSON
+        PartialAuthenticatorAssertionResponse::sign_data_for_testing(
+            &private_key,
+            PartialAuthenticatorAssertionResponse::authenticator_data_for_testing(),
+            challenge,
+        )
+        .verify(&txn, &public_key)
+        .unwrap_err();
+    }
+}